@@ -6,7 +6,7 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
 use ink::env::Environment;
-use ink::prelude::vec::Vec;
+use ink::prelude::{collections::BTreeMap, vec::Vec};
 use scale::Compact;
 use sp_fragnova::{
 	Hash128,
@@ -15,6 +15,8 @@ use sp_fragnova::{
 		Proto
 	},
 	fragments::{
+		AttributeSchema,
+		AttributeValue,
 		FragmentDefinition,
 		FragmentInstance,
 		InstanceUnit
@@ -70,7 +72,7 @@ pub trait MyChainExtension {
 	fn get_proto_ids(owner: AccountId) -> Vec<Hash256>;
 
 	// Chain extension methods that access the fragments pallet are prefixied with 0x0c (this is the same number as the pallet's index)
-	/// Get the `FragmentDefinition` struct of the Fragment Definition which has the ID of `definition_hash`
+	/// Get the `FragmentDefinition` struct of the Fragment Definition which has the ID of `definition_hash` (including its current `owner`)
 	#[ink(extension = 0x0c00, handle_status = false)]
 	fn get_definition(definition_hash: Hash128) -> Option<FragmentDefinition<Vec<u8>, AssetId, AccountId, BlockNumber>>;
 	/// Get the `FragmentInstance` struct of the Fragment Instance whose Fragment Definition ID is `definition_hash`,
@@ -80,9 +82,22 @@ pub trait MyChainExtension {
 	/// Get the list of Fragment Instances of the Fragment Definition `definition_hash` that are owned by `owner`
 	#[ink(extension = 0x0c02, handle_status = false)]
 	fn get_instance_ids(definition_hash: Hash128, owner: AccountId) -> Vec<(Compact<InstanceUnit>, Compact<InstanceUnit>)>;
-	/// Give a Fragment Instance (that is owned by the smart contract) to `to`.
+	/// Give a Fragment Instance (that is owned by the smart contract, or that the smart contract is approved to transfer) to `to`.
 	#[ink(extension = 0x0c03)]
 	fn give_instance(definition_hash: Hash128, edition_id: InstanceUnit, copy_id: InstanceUnit, to: AccountId, new_permissions: Option<FragmentPerms>, expirations: Option<BlockNumber>) -> Result<(), MyChainExtensionError>;
+	/// Consume `amount` items of the stack of a Fragment Instance (that is owned by the smart contract).
+	#[ink(extension = 0x0c04)]
+	fn consume_instance(definition_hash: Hash128, edition_id: InstanceUnit, copy_id: InstanceUnit, amount: InstanceUnit) -> Result<(), MyChainExtensionError>;
+	/// Get the Account ID that has the use rights of a Fragment Instance (i.e its renter if it is rented out, otherwise its owner)
+	#[ink(extension = 0x0c05, handle_status = false)]
+	fn get_instance_user(definition_hash: Hash128, edition_id: InstanceUnit, copy_id: InstanceUnit) -> Option<AccountId>;
+	/// Get the attribute schema of the Fragment Definition `definition_hash`
+	#[ink(extension = 0x0c06, handle_status = false)]
+	fn get_attribute_schema(definition_hash: Hash128) -> Option<AttributeSchema>;
+	/// Get the attribute values of the Fragment Instance whose Fragment Definition ID is `definition_hash`,
+	/// whose Edition ID is `edition_id` and whose Copy ID is `copy_id`
+	#[ink(extension = 0x0c07, handle_status = false)]
+	fn get_instance_attributes(definition_hash: Hash128, edition_id: InstanceUnit, copy_id: InstanceUnit) -> BTreeMap<Vec<u8>, AttributeValue>;
 }
 
 /// The error codes of the chain extension `MyChainExtension`
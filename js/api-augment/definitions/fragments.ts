@@ -24,6 +24,30 @@ export default {
         { name: "at", type: "BlockHash", isOptional: true }
       ]
     },
+    getVaultBalance: {
+      description: "Query the balance of the vault of a Fragment Definition (i.e its sale proceeds). The return type is a JSON string",
+      type: "String",
+      params: [
+        { name: "definition_hash", type: "String" },
+        { name: "at", type: "BlockHash", isOptional: true }
+      ]
+    },
+    getSalePhase: {
+      description: "Query the current Sale Phase of a Fragment Definition that is on sale. The return type is a JSON string",
+      type: "String",
+      params: [
+        { name: "param", type: "GetSalePhaseParams" },
+        { name: "at", type: "BlockHash", isOptional: true }
+      ]
+    },
+    getRecipes: {
+      description: "Query the crafting recipes. The return type is a JSON string",
+      type: "String",
+      params: [
+        { name: "param", type: "GetRecipesParams" },
+        { name: "at", type: "BlockHash", isOptional: true }
+      ]
+    },
   },
   types: {
     DefinitionMetadata: {
@@ -42,6 +66,13 @@ export default {
       metadata_keys: "Vec<String>",
       owner: "Option<AccountId>",
       return_owners: "bool",
+      categories: "Vec<Categories>",
+      tags: "Vec<String>",
+      currency: "Option<String>",
+      published: "Option<bool>",
+      min_price: "Option<u128>",
+      max_price: "Option<u128>",
+      sold_out: "Option<bool>",
     },
     GetInstancesParams: {
       desc: "bool",
@@ -51,12 +82,24 @@ export default {
       metadata_keys: "Vec<String>",
       owner: "Option<AccountId>",
       only_return_first_copies: "bool",
+      return_nested: "bool",
     },
     GetInstanceOwnerParams: {
       definition_hash: 'String', // "[u8; 16]", // using `String` because Polkadot-JS has a problem fixed-sized arrays: https://github.com/encointer/pallets/pull/86
       edition_id: "InstanceUnit",
       copy_id: "InstanceUnit",
     },
+    GetSalePhaseParams: {
+      definition_hash: 'String', // "[u8; 16]", // using `String` because Polkadot-JS has a problem fixed-sized arrays: https://github.com/encointer/pallets/pull/86
+      account: "Option<AccountId>",
+      allowance: "Option<InstanceUnit>",
+    },
+    GetRecipesParams: {
+      from: "u64",
+      limit: "u64",
+      owner: "Option<AccountId>",
+      only_enabled: "bool",
+    },
     "InstanceUnit": "u64",
 
   }
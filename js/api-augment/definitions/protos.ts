@@ -8,6 +8,30 @@ export default {
         { name: "at", type: "BlockHash", isOptional: true }
       ]
     },
+    getChunkedData: {
+      description: "Query and Return the data of a Proto-Fragment that was uploaded in multiple parts, by reassembling its chunks. The **return type** is base64 encoded bytes.",
+      type: "String",
+      params: [
+        { name: "proto_hash", type: "BlockHash" },
+        { name: "at", type: "BlockHash", isOptional: true }
+      ]
+    },
+    getCid: {
+      description: "Query and Return the CID of the current data of a Proto-Fragment. The **return type** is the string representation of the CID.",
+      type: "String",
+      params: [
+        { name: "proto_hash", type: "BlockHash" },
+        { name: "at", type: "BlockHash", isOptional: true }
+      ]
+    },
+    getDataByCid: {
+      description: "Query and Return the data indexed on Fragnova based on its CID. The **return type** is base64 encoded bytes.",
+      type: "String",
+      params: [
+        { name: "cid", type: "String" },
+        { name: "at", type: "BlockHash", isOptional: true }
+      ]
+    },
     getProtos: {
       description: "Query and Return Proto-Fragment(s) based on `params`. The return type is a JSON string",
       type: "String",
@@ -17,7 +41,7 @@ export default {
       ]
     },
     getGenealogy: {
-      description: "Query the Genealogy of a Proto-Fragment based on `params`. The return type is a JSON string that represents an Adjacency List.",
+      description: "Query the Genealogy of a Proto-Fragment based on `params`. The return type is a string in the format `params.format` (a JSON Adjacency List, a DOT digraph or a GraphML document).",
       type: "String",
       params: [
         { name: "param", type: "GetGenealogyParams" },
@@ -104,6 +128,15 @@ export default {
       ]
     },
 
+    ProtoData: {
+      _enum: {
+        "Local": "Vec<u8>",
+        "Arweave": "[u8; 32]",
+        "Ipfs": "[u8; 64]",
+        "IpfsCid": "Vec<u8>",
+      }
+    },
+
     GetProtosParams: {
       desc: 'bool',
       from: 'u32',
@@ -115,10 +148,30 @@ export default {
       tags: 'Vec<String>',
       exclude_tags: 'Vec<String>',
       available: 'Option<bool>',
+      data_availability: 'Option<DataAvailability>',
+    },
+    DataAvailability: {
+      _enum: [
+        "Available",
+        "Missing",
+        "Invalid",
+      ]
     },
     GetGenealogyParams: {
       proto_hash: "String",
       get_ancestors: "bool",
+      max_depth: "Option<u32>",
+      max_nodes: "Option<u32>",
+      include_patches: "Option<bool>",
+      annotate_edges: "bool",
+      format: "GenealogyFormat",
+    },
+    GenealogyFormat: {
+      _enum: [
+        "Json",
+        "Dot",
+        "GraphMl",
+      ]
     },
 
   }
@@ -16,8 +16,8 @@
 // See for more info: https://docs.substrate.io/reference/how-to-guides/basics/configure-genesis-state/
 use fragnova_runtime::{
 	AccountId, AccountsConfig, AssetsConfig, AuraConfig, BalancesConfig, DetachConfig,
-	GenesisConfig, GrandpaConfig, IndicesConfig, OracleConfig, Signature, SudoConfig, SystemConfig,
-	WASM_BINARY,
+	GenesisConfig, GrandpaConfig, IndicesConfig, OracleConfig, ProtosConfig, Signature, SudoConfig,
+	SystemConfig, WASM_BINARY,
 };
 use sc_service::ChainType;
 use serde_json;
@@ -92,6 +92,12 @@ fn chain_spec_properties() -> serde_json::map::Map<String, serde_json::Value> {
 
 /// Returns the `ChainSpec` struct used when for starting/joining a Fragnova Development Network
 pub fn development_config() -> Result<ChainSpec, String> {
+	development_config_with_protos(Vec::new())
+}
+
+/// Returns the `ChainSpec` struct used when for starting/joining a Fragnova Development Network
+/// whose Genesis Block contains the Proto-Fragments `protos` (a list of SCALE-encoded `pallet_protos::GenesisProto` structs)
+pub fn development_config_with_protos(protos: Vec<Vec<u8>>) -> Result<ChainSpec, String> {
 	let wasm_binary = WASM_BINARY.ok_or_else(|| "Development wasm not available".to_string())?;
 
 	Ok(ChainSpec::from_genesis(
@@ -116,6 +122,7 @@ pub fn development_config() -> Result<ChainSpec, String> {
 					get_account_id_from_seed::<sr25519::Public>("Dave"),
 				],
 				true,
+				protos.clone(),
 			)
 		},
 		// Bootnodes
@@ -134,6 +141,12 @@ pub fn development_config() -> Result<ChainSpec, String> {
 
 /// Returns the `ChainSpec` struct used when for starting/joining a Fragnova Testnet Network
 pub fn local_testnet_config() -> Result<ChainSpec, String> {
+	local_testnet_config_with_protos(Vec::new())
+}
+
+/// Returns the `ChainSpec` struct used when for starting/joining a Fragnova Testnet Network
+/// whose Genesis Block contains the Proto-Fragments `protos` (a list of SCALE-encoded `pallet_protos::GenesisProto` structs)
+pub fn local_testnet_config_with_protos(protos: Vec<Vec<u8>>) -> Result<ChainSpec, String> {
 	let wasm_binary = WASM_BINARY.ok_or_else(|| "Development wasm not available".to_string())?;
 
 	Ok(ChainSpec::from_genesis(
@@ -165,6 +178,7 @@ pub fn local_testnet_config() -> Result<ChainSpec, String> {
 					get_account_id_from_seed::<sr25519::Public>("Ferdie//stash"),
 				],
 				true,
+				protos.clone(),
 			)
 		},
 		// Bootnodes
@@ -207,6 +221,7 @@ pub fn live_config() -> Result<ChainSpec, String> {
 				assets: AssetsConfig::default(),
 				accounts: AccountsConfig::default(),
 				oracle: OracleConfig::default(),
+				protos: ProtosConfig::default(),
 			}
 		},
 		// Bootnodes
@@ -230,6 +245,7 @@ fn testnet_genesis(
 	root_key: AccountId,
 	endowed_accounts: Vec<AccountId>,
 	_enable_println: bool,
+	protos: Vec<Vec<u8>>,
 ) -> GenesisConfig {
 	GenesisConfig {
 		system: SystemConfig {
@@ -265,5 +281,6 @@ fn testnet_genesis(
 			keys: initial_authorities.iter().map(|x| (x.4.clone())).collect(),
 		},
 		oracle: OracleConfig { keys: initial_authorities.iter().map(|x| (x.4.clone())).collect() },
+		protos: ProtosConfig { protos },
 	}
 }
@@ -11,6 +11,14 @@ pub struct Cli {
 
 	#[clap(short, long)]
 	pub geth_url: Option<String>,
+
+	/// URL of the Arweave Gateway used by the Offchain Worker to check the data of Proto-Fragments stored on Arweave (e.g `https://arweave.net`)
+	#[clap(long)]
+	pub arweave_gateway_url: Option<String>,
+
+	/// URL of the IPFS Gateway used by the Offchain Worker to check the data of Proto-Fragments stored on IPFS (e.g `https://ipfs.io/ipfs`)
+	#[clap(long)]
+	pub ipfs_gateway_url: Option<String>,
 }
 
 /// Possible subcommands of the main binary (i.e of the binary `/target/debug/fragnova`).
@@ -57,6 +65,12 @@ pub enum Subcommand {
 	/// Revert the chain to a previous state.
 	Revert(sc_cli::RevertCmd),
 
+	/// Export the Proto-Fragments (and their data) at a given block into a CAR archive.
+	ExportProtos(crate::protos_archive::ExportProtosCmd),
+
+	/// Build a chain specification whose genesis contains the Proto-Fragments of a CAR archive.
+	ImportProtos(crate::protos_archive::ImportProtosCmd),
+
 	/// Sub-commands concerned with benchmarking.
 	#[clap(subcommand)]
 	Benchmark(frame_benchmarking_cli::BenchmarkCmd),
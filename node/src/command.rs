@@ -111,6 +111,17 @@ pub fn run() -> sc_cli::Result<()> {
 				Ok((cmd.run(client, backend, Some(aux_revert)), task_manager))
 			})
 		},
+		Some(Subcommand::ExportProtos(cmd)) => {
+			let runner = cli.create_runner(cmd)?;
+			runner.sync_run(|config| {
+				let PartialComponents { client, .. } = new_partial(&config)?;
+				cmd.run(client)
+			})
+		},
+		Some(Subcommand::ImportProtos(cmd)) => {
+			let runner = cli.create_runner(cmd)?;
+			runner.sync_run(|config| cmd.run(config.chain_spec.id()))
+		},
 		Some(Subcommand::Benchmark(cmd)) => {
 			let runner = cli.create_runner(cmd)?;
 
@@ -163,7 +174,13 @@ pub fn run() -> sc_cli::Result<()> {
 		None => {
 			let runner = cli.create_runner(&cli.run)?;
 			runner.run_node_until_exit(|config| async move {
-				service::new_full(config, cli.geth_url).map_err(sc_cli::Error::Service)
+				service::new_full(
+					config,
+					cli.geth_url,
+					cli.arweave_gateway_url,
+					cli.ipfs_gateway_url,
+				)
+				.map_err(sc_cli::Error::Service)
 			})
 		},
 	}
@@ -6,6 +6,7 @@ mod chain_spec;
 mod service;
 mod cli;
 mod command;
+mod protos_archive;
 mod rpc;
 
 mod fragnova;
@@ -0,0 +1,459 @@
+//! Subcommands that **export** the **Proto-Fragments** of a Fragnova Blockchain into a **CARv1 archive** (https://ipld.io/specs/transport/car/carv1/)
+//! and that **import** such an archive into the **Genesis Block** of a new chain specification.
+//!
+//! The root of the archive is a **JSON manifest** that describes every Proto-Fragment (owner, tags, metadata, references...)
+//! and that also contains the **SCALE-encoded `pallet_protos::GenesisProto` struct** of every Proto-Fragment.
+//!
+//! The other blocks of the archive are the **data of the Proto-Fragments** (i.e the data of every version, every chunk and every Metadata Object)
+//! that was indexed on the Fragnova Blockchain. The CID of every block is the one with which the data can be fetched via bitswap (IPFS)
+//! (see `sp_fragnova::cid::encode_cid()`).
+
+use crate::chain_spec;
+use codec::{Decode, Encode};
+use fragnova_runtime::{opaque::Block, AccountId, BlockNumber, Runtime};
+use frame_support::storage::StoragePrefixedMap;
+use pallet_protos::{GenesisProto, Proto, ProtoData, ProtoOwner};
+use protos::categories::Categories;
+use sc_cli::{BlockNumberOrHash, CliConfiguration, DatabaseParams, PruningParams, SharedParams};
+use sc_client_api::{Backend, BlockBackend, StorageProvider};
+use sp_blockchain::HeaderBackend;
+use sp_core::{hashing::blake2_256, storage::StorageKey, H256};
+use sp_fragnova::{
+	cid::{decode_cid, encode_cid, read_varint},
+	Hash256, Hash64,
+};
+use std::{
+	collections::{BTreeMap, BTreeSet},
+	fs,
+	io::Write,
+	path::PathBuf,
+	sync::Arc,
+};
+
+/// The version of the manifest of the archives
+const MANIFEST_VERSION: u64 = 1;
+
+/// The `export-protos` subcommand
+#[derive(Debug, Clone, clap::Parser)]
+pub struct ExportProtosCmd {
+	/// Block hash or number at which the Proto-Fragments are exported (the best block is used if not provided)
+	#[arg(long, value_name = "HASH or NUMBER")]
+	pub at: Option<BlockNumberOrHash>,
+
+	/// Path of the CAR archive that will be written
+	#[arg(long, value_name = "FILE")]
+	pub out: PathBuf,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub shared_params: SharedParams,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub pruning_params: PruningParams,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub database_params: DatabaseParams,
+}
+
+impl ExportProtosCmd {
+	/// Run the `export-protos` subcommand
+	pub fn run<C, BA>(&self, client: Arc<C>) -> sc_cli::Result<()>
+	where
+		C: HeaderBackend<Block> + BlockBackend<Block> + StorageProvider<Block, BA>,
+		BA: Backend<Block>,
+	{
+		let at = match &self.at {
+			Some(at) => client.expect_block_hash_from_id(&at.parse::<Block>()?)?,
+			None => client.info().best_hash,
+		};
+		let block_number = client.number(at)?.ok_or("Block not found")?;
+
+		let tag_names: BTreeMap<u64, Vec<u8>> = read_map::<_, _, Vec<u8>, u64>(
+			&*client,
+			at,
+			pallet_protos::Tags::<Runtime>::final_prefix(),
+			8,
+		)?
+		.into_iter()
+		.map(|(name, index)| (index, name))
+		.collect();
+		let metadata_key_names: BTreeMap<u64, Vec<u8>> = read_map::<_, _, Vec<u8>, u64>(
+			&*client,
+			at,
+			pallet_protos::MetaKeys::<Runtime>::final_prefix(),
+			8,
+		)?
+		.into_iter()
+		.map(|(name, index)| (index, name))
+		.collect();
+		let trait_names: BTreeMap<Hash64, Vec<u8>> =
+			read_map(&*client, at, pallet_protos::Traits::<Runtime>::final_prefix(), 0)?
+				.into_iter()
+				.collect();
+		let chunks: BTreeMap<Hash256, Vec<Hash256>> =
+			read_map(&*client, at, pallet_protos::ProtoChunks::<Runtime>::final_prefix(), 0)?
+				.into_iter()
+				.collect();
+		let protos: Vec<(Hash256, Proto<AccountId, BlockNumber>)> =
+			read_map(&*client, at, pallet_protos::Protos::<Runtime>::final_prefix(), 0)?;
+
+		let mut entries = Vec::new();
+		let mut blocks = Vec::new();
+		let mut exported_hashes = BTreeSet::new();
+		for (proto_hash, proto) in protos {
+			let genesis_proto = GenesisProto {
+				proto_hash,
+				tags: proto
+					.tags
+					.iter()
+					.filter_map(|index| tag_names.get(&index.0).cloned())
+					.collect(),
+				metadata: proto
+					.metadata
+					.iter()
+					.filter_map(|(index, data_hash)| {
+						Some((metadata_key_names.get(&index.0)?.clone(), *data_hash))
+					})
+					.collect(),
+				trait_name: match &proto.category {
+					Categories::Trait(Some(trait_id)) => trait_names.get(trait_id).cloned(),
+					_ => None,
+				},
+				chunks: chunks.get(&proto_hash).cloned(),
+				proto,
+			};
+
+			for data_hash in get_indexed_data_hashes(&genesis_proto) {
+				if !exported_hashes.insert(data_hash) {
+					continue
+				}
+				match client.indexed_transaction(H256::from(data_hash))? {
+					Some(data) => blocks.push((encode_cid(&data_hash), data)),
+					None => log::warn!(
+						"The data 0x{} of the Proto-Fragment 0x{} was not found (was it pruned?)",
+						hex::encode(data_hash),
+						hex::encode(proto_hash)
+					),
+				}
+			}
+
+			entries.push(get_manifest_entry(&genesis_proto));
+		}
+
+		let protos_count = entries.len();
+		let manifest = serde_json::to_vec_pretty(&serde_json::json!({
+			"version": MANIFEST_VERSION,
+			"block_hash": format!("{:?}", at),
+			"block_number": block_number,
+			"protos": entries,
+		}))
+		.map_err(|e| e.to_string())?;
+		let root = encode_cid(&blake2_256(&manifest));
+
+		let mut archive = encode_car_header(&root);
+		push_car_block(&mut archive, &root, &manifest);
+		for (cid, data) in blocks.iter() {
+			push_car_block(&mut archive, cid, data);
+		}
+		fs::File::create(&self.out)?.write_all(&archive)?;
+
+		log::info!(
+			"Exported {} Proto-Fragments and {} data blocks at block #{} into {:?}",
+			protos_count,
+			blocks.len(),
+			block_number,
+			self.out
+		);
+
+		Ok(())
+	}
+}
+
+impl CliConfiguration for ExportProtosCmd {
+	fn shared_params(&self) -> &SharedParams {
+		&self.shared_params
+	}
+
+	fn pruning_params(&self) -> Option<&PruningParams> {
+		Some(&self.pruning_params)
+	}
+
+	fn database_params(&self) -> Option<&DatabaseParams> {
+		Some(&self.database_params)
+	}
+}
+
+/// The `import-protos` subcommand
+#[derive(Debug, Clone, clap::Parser)]
+pub struct ImportProtosCmd {
+	/// Path of the CAR archive (written by the subcommand `export-protos`) to import
+	#[arg(value_name = "FILE")]
+	pub input: PathBuf,
+
+	/// Path of the chain specification that will be written (it is printed to stdout if not provided)
+	#[arg(long, value_name = "FILE")]
+	pub out: Option<PathBuf>,
+
+	/// Force raw genesis storage output.
+	#[arg(long)]
+	pub raw: bool,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub shared_params: SharedParams,
+}
+
+impl ImportProtosCmd {
+	/// Run the `import-protos` subcommand.
+	///
+	/// The chain specification `chain_id` (either the development chain or the local testnet chain)
+	/// is written with the Proto-Fragments of the archive in its Genesis Block.
+	///
+	/// Note: The data of the Proto-Fragments is not indexed by the new chain (only extrinsics can index data),
+	/// but it can still be served from the archive itself (e.g using `ipfs dag import`).
+	pub fn run(&self, chain_id: &str) -> sc_cli::Result<()> {
+		let archive = fs::read(&self.input)?;
+		let (root, blocks) = read_car(&archive)?;
+
+		for (cid, data) in blocks.iter() {
+			let data_hash = decode_cid(cid)
+				.ok()
+				.and_then(|cid| cid.get_fragnova_hash())
+				.ok_or("The archive contains a block whose CID was not created by Fragnova")?;
+			if blake2_256(data) != data_hash {
+				return Err("The archive contains a block whose data does not match its CID".into())
+			}
+		}
+
+		let manifest = blocks
+			.iter()
+			.find(|(cid, _)| *cid == root)
+			.map(|(_, data)| data)
+			.ok_or("The manifest of the archive was not found")?;
+		let manifest: serde_json::Value =
+			serde_json::from_slice(manifest).map_err(|e| e.to_string())?;
+		if manifest["version"].as_u64() != Some(MANIFEST_VERSION) {
+			return Err("Unsupported manifest version".into())
+		}
+
+		let protos = manifest["protos"]
+			.as_array()
+			.ok_or("The manifest does not contain any Proto-Fragment")?
+			.iter()
+			.map(|entry| {
+				let genesis_proto = entry["genesis"].as_str().ok_or("Invalid manifest entry")?;
+				let genesis_proto = hex::decode(genesis_proto).map_err(|e| e.to_string())?;
+				GenesisProto::<AccountId, BlockNumber>::decode(&mut &genesis_proto[..])
+					.map_err(|e| e.to_string())?;
+				Ok(genesis_proto)
+			})
+			.collect::<Result<Vec<Vec<u8>>, String>>()?;
+		let protos_count = protos.len();
+
+		let chain_spec = match chain_id {
+			"dev" => chain_spec::development_config_with_protos(protos)?,
+			"local_testnet" => chain_spec::local_testnet_config_with_protos(protos)?,
+			_ =>
+				return Err(
+					"Proto-Fragments can only be imported into the development chain or the local testnet chain"
+						.into(),
+				),
+		};
+		let json = chain_spec.as_json(self.raw)?;
+		match &self.out {
+			Some(out) => fs::write(out, json)?,
+			None => println!("{}", json),
+		}
+
+		log::info!(
+			"Imported {} Proto-Fragments into the chain specification {}",
+			protos_count,
+			chain_id
+		);
+
+		Ok(())
+	}
+}
+
+impl CliConfiguration for ImportProtosCmd {
+	fn shared_params(&self) -> &SharedParams {
+		&self.shared_params
+	}
+}
+
+/// Get **all the (key, value) pairs** of the **storage map** whose prefix is `prefix` at the block `at`.
+///
+/// `hash_len` is the length of the hash that precedes the key in the storage key (i.e 8 for `Twox64Concat` and 0 for `Identity`).
+fn read_map<C, BA, K: Decode, V: Decode>(
+	client: &C,
+	at: H256,
+	prefix: [u8; 32],
+	hash_len: usize,
+) -> sc_cli::Result<Vec<(K, V)>>
+where
+	C: StorageProvider<Block, BA>,
+	BA: Backend<Block>,
+{
+	client
+		.storage_pairs(at, &StorageKey(prefix.to_vec()))?
+		.into_iter()
+		.map(|(key, value)| {
+			let k = K::decode(&mut &key.0[prefix.len() + hash_len..])
+				.map_err(|e| format!("Invalid storage key: {}", e))?;
+			let v = V::decode(&mut &value.0[..])
+				.map_err(|e| format!("Invalid storage value: {}", e))?;
+			Ok((k, v))
+		})
+		.collect()
+}
+
+/// Get the **hashes of all the data** of the Proto-Fragment `genesis_proto` that was **indexed on the Fragnova Blockchain**
+/// (i.e its original data or its chunks, the data of its patches and its Metadata Objects)
+fn get_indexed_data_hashes(genesis_proto: &GenesisProto<AccountId, BlockNumber>) -> Vec<Hash256> {
+	let proto = &genesis_proto.proto;
+	let mut data_hashes = Vec::new();
+	if let Some(chunks) = &genesis_proto.chunks {
+		data_hashes.extend(chunks.iter());
+	} else if matches!(proto.data, ProtoData::Local(_)) && proto.category != Categories::Bundle {
+		data_hashes.push(genesis_proto.proto_hash);
+	}
+	for patch in proto.patches.iter() {
+		if matches!(patch.data, ProtoData::Local(_)) {
+			data_hashes.push(patch.data_hash);
+		}
+	}
+	data_hashes.extend(genesis_proto.metadata.iter().map(|(_, data_hash)| *data_hash));
+	data_hashes
+}
+
+/// Get the **entry of the manifest** that describes the Proto-Fragment `genesis_proto`
+fn get_manifest_entry(genesis_proto: &GenesisProto<AccountId, BlockNumber>) -> serde_json::Value {
+	let proto = &genesis_proto.proto;
+	let owner = match &proto.owner {
+		ProtoOwner::User(account_id) =>
+			serde_json::json!({"type": "internal", "value": account_id.to_string()}),
+		ProtoOwner::ExternalAsset(linked_asset) =>
+			serde_json::json!({"type": "external", "value": format!("{:?}", linked_asset)}),
+	};
+	let metadata: serde_json::Map<String, serde_json::Value> = genesis_proto
+		.metadata
+		.iter()
+		.map(|(metadata_key, data_hash)| {
+			(
+				String::from_utf8_lossy(metadata_key).into_owned(),
+				serde_json::Value::String(hex::encode(data_hash)),
+			)
+		})
+		.collect();
+
+	serde_json::json!({
+		"proto_hash": hex::encode(genesis_proto.proto_hash),
+		"category": format!("{:?}", proto.category),
+		"owner": owner,
+		"creator": proto.creator.to_string(),
+		"block": proto.block,
+		"tags": genesis_proto.tags.iter().map(|tag| String::from_utf8_lossy(tag).into_owned()).collect::<Vec<String>>(),
+		"metadata": metadata,
+		"references": proto.references.iter().map(hex::encode).collect::<Vec<String>>(),
+		"patches": proto.patches.iter().map(|patch| serde_json::json!({
+			"block": patch.block,
+			"data_hash": hex::encode(patch.data_hash),
+			"references": patch.references.iter().map(hex::encode).collect::<Vec<String>>(),
+		})).collect::<Vec<serde_json::Value>>(),
+		"chunks": genesis_proto.chunks.as_ref().map(|chunks| chunks.iter().map(hex::encode).collect::<Vec<String>>()),
+		"genesis": hex::encode(genesis_proto.encode()),
+	})
+}
+
+/// Append the unsigned varint (https://github.com/multiformats/unsigned-varint) `value` to `out`
+fn push_varint(out: &mut Vec<u8>, mut value: u64) {
+	while value >= 0x80 {
+		out.push((value as u8) | 0x80);
+		value >>= 7;
+	}
+	out.push(value as u8);
+}
+
+/// Encode the **header of a CARv1 archive** whose only root is the CID `root`
+/// (i.e the length-prefixed DAG-CBOR map `{"roots": [root], "version": 1}`)
+fn encode_car_header(root: &[u8]) -> Vec<u8> {
+	let mut header = vec![0xa2]; // a map of 2 entries
+	header.push(0x65); // a text string of 5 bytes
+	header.extend(b"roots");
+	header.push(0x81); // an array of 1 element
+	header.extend([0xd8, 0x2a]); // the tag 42 (i.e a CID)
+							  // a byte string of the CID prefixed with the identity Multibase (i.e 0x00)
+	header.extend([0x58, (root.len() + 1) as u8, 0x00]);
+	header.extend(root);
+	header.push(0x67); // a text string of 7 bytes
+	header.extend(b"version");
+	header.push(0x01);
+
+	let mut out = Vec::new();
+	push_varint(&mut out, header.len() as u64);
+	out.extend(header);
+	out
+}
+
+/// Append the **block** (i.e the length-prefixed CID and data) whose CID is `cid` and whose data is `data`
+/// to the CARv1 archive `archive`
+fn push_car_block(archive: &mut Vec<u8>, cid: &[u8], data: &[u8]) {
+	push_varint(archive, (cid.len() + data.len()) as u64);
+	archive.extend(cid);
+	archive.extend(data);
+}
+
+/// Read the **CARv1 archive** `archive` and return its root (it must have a single root) and its blocks (a list of CIDs and data)
+fn read_car(mut archive: &[u8]) -> Result<(Vec<u8>, Vec<(Vec<u8>, Vec<u8>)>), String> {
+	let header_len = read_varint(&mut archive).map_err(|e| format!("{:?}", e))? as usize;
+	let header = archive.get(..header_len).ok_or("Invalid CAR header")?;
+	archive = &archive[header_len..];
+
+	// the root is the byte string that follows the tag 42 (i.e a CID), without its identity Multibase prefix
+	let tag_position = header
+		.windows(2)
+		.position(|window| window == [0xd8, 0x2a])
+		.ok_or("The CAR archive does not have a root")?;
+	let root = match header.get(tag_position + 2..) {
+		Some([0x58, len, 0x00, rest @ ..]) => {
+			// the length includes the Multibase prefix
+			let len = (*len as usize).checked_sub(1).ok_or("Invalid CAR header")?;
+			rest.get(..len)
+		},
+		Some([len @ 0x41..=0x57, 0x00, rest @ ..]) => rest.get(..(*len - 0x40) as usize - 1),
+		_ => None,
+	}
+	.ok_or("Invalid CAR root")?
+	.to_vec();
+
+	let mut blocks = Vec::new();
+	while !archive.is_empty() {
+		let block_len = read_varint(&mut archive).map_err(|e| format!("{:?}", e))? as usize;
+		let block = archive.get(..block_len).ok_or("Truncated CAR block")?;
+		archive = &archive[block_len..];
+
+		let cid_len = get_cid_len(block)?;
+		blocks.push((block[..cid_len].to_vec(), block[cid_len..].to_vec()));
+	}
+
+	Ok((root, blocks))
+}
+
+/// Get the **length** of the **binary CID** that is at the beginning of the block `block`
+fn get_cid_len(block: &[u8]) -> Result<usize, String> {
+	// A CIDv0 is a bare `sha2-256` multihash
+	if block.starts_with(&[0x12, 0x20]) {
+		return Ok(34)
+	}
+
+	let mut bytes = block;
+	let mut read = || read_varint(&mut bytes).map_err(|e| format!("{:?}", e));
+	let (_version, _codec, _hash_code, digest_len) = (read()?, read()?, read()?, read()?);
+	let cid_len = block.len() - bytes.len() + digest_len as usize;
+	if cid_len > block.len() {
+		return Err("Invalid CID in CAR block".into())
+	}
+	Ok(cid_len)
+}
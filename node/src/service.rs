@@ -172,6 +172,8 @@ fn remote_keystore(_url: &String) -> Result<Arc<LocalKeystore>, &'static str> {
 pub fn new_full(
 	mut config: Configuration,
 	geth_url: Option<String>,
+	arweave_gateway_url: Option<String>,
+	ipfs_gateway_url: Option<String>,
 ) -> Result<TaskManager, ServiceError> {
 	let sc_service::PartialComponents {
 		client,
@@ -359,6 +361,7 @@ pub fn new_full(
 	}
 
 	sp_fragnova::init(geth_url);
+	sp_fragnova::init_data_gateways(arweave_gateway_url, ipfs_gateway_url);
 
 	// add some test keys when needed
 	if chain_type == sc_service::ChainType::Development {
@@ -22,7 +22,7 @@ use codec::{Compact, Decode, Encode};
 use frame_support::traits::{Currency, ExistenceRequirement, WithdrawReasons};
 pub use pallet::*;
 use pallet_clusters::Clusters;
-use pallet_fragments::Owners;
+use pallet_fragments::InstanceOwner;
 use pallet_protos::{Proto, ProtoOwner, Protos};
 use sp_fragnova::{fragments::InstanceUnit, Hash128, Hash256};
 use sp_std::vec::Vec;
@@ -581,14 +581,8 @@ pub mod pallet {
 		) -> DispatchResult {
 			match target {
 				LinkTarget::Fragment { definition_hash, edition, copy } => {
-					let owner = Owners::<T>::iter_prefix(definition_hash)
-						.find(|(_owner, vec_instances)| {
-							vec_instances.iter().any(|(edition_id, copy_id)| {
-								Compact(edition) == *edition_id && Compact(copy) == *copy_id
-							})
-						})
-						.ok_or(Error::<T>::LinkTargetOwnerNotFound)?
-						.0;
+					let owner = InstanceOwner::<T>::get((definition_hash, edition, copy))
+						.ok_or(Error::<T>::LinkTargetOwnerNotFound)?;
 					ensure!(*who == owner, Error::<T>::NotAllowed);
 					Ok(())
 				},
@@ -762,6 +762,12 @@ pub mod pallet {
 	}
 
 	impl<T: Config> Pallet<T> {
+		/// Whether the account `who` is a **member** of the **Cluster** `cluster_id` that has the **Role** `role_name`
+		pub fn has_role(cluster_id: &Hash128, who: &T::AccountId, role_name: &[u8]) -> bool {
+			let Some(name_index) = <Names<T>>::get(role_name) else { return false };
+			<Members<T>>::get(cluster_id, who).map_or(false, |roles| roles.contains(&name_index))
+		}
+
 		/// Utility function that checks for the existence of a name in storage and return its index.
 		///
 		/// - `name`: the reference of the name to lookup
@@ -17,7 +17,10 @@
 
 use codec::Codec;
 
-use pallet_fragments::{GetDefinitionsParams, GetInstanceOwnerParams, GetInstancesParams};
+use pallet_fragments::{
+	GetDefinitionsParams, GetInstanceOwnerParams, GetInstancesParams, GetRecipesParams,
+	GetSalePhaseParams,
+};
 use sp_std::vec::Vec;
 
 // Declares given traits as runtime apis
@@ -37,5 +40,14 @@ sp_api::decl_runtime_apis! {
 
 		/// Query the owner of a Fragment Instance. The return type is a String
 		fn get_instance_owner(params: GetInstanceOwnerParams<Vec<u8>>) -> Result<Vec<u8>, Vec<u8>>;
+
+		/// Query the balance of the vault of a Fragment Definition (i.e its sale proceeds). The return type is a JSON string
+		fn get_vault_balance(definition_hash: Vec<u8>) -> Result<Vec<u8>, Vec<u8>>;
+
+		/// Query the current Sale Phase of a Fragment Definition that is on sale. The return type is a JSON string
+		fn get_sale_phase(params: GetSalePhaseParams<AccountId, Vec<u8>>) -> Result<Vec<u8>, Vec<u8>>;
+
+		/// Query the crafting recipes. The return type is a JSON string
+		fn get_recipes(params: GetRecipesParams<AccountId>) -> Result<Vec<u8>, Vec<u8>>;
 	}
 }
@@ -8,7 +8,10 @@ use jsonrpsee::{
 	proc_macros::rpc,
 	types::error::{CallError, ErrorObject},
 };
-use pallet_fragments::{GetDefinitionsParams, GetInstanceOwnerParams, GetInstancesParams};
+use pallet_fragments::{
+	GetDefinitionsParams, GetInstanceOwnerParams, GetInstancesParams, GetRecipesParams,
+	GetSalePhaseParams,
+};
 use sp_api::ProvideRuntimeApi;
 use sp_blockchain::HeaderBackend;
 use sp_runtime::traits::{Block as BlockT};
@@ -44,6 +47,27 @@ pub trait FragmentsRpc<BlockHash, AccountId> {
 		param: GetInstanceOwnerParams<String>,
 		at: Option<BlockHash>,
 	) -> RpcResult<String>;
+	/// Query the balance of the vault of a Fragment Definition (i.e its sale proceeds). The return type is a JSON string
+	#[method(name = "getVaultBalance")]
+	fn get_vault_balance(
+		&self,
+		definition_hash: String,
+		at: Option<BlockHash>,
+	) -> RpcResult<String>;
+	/// Query the current Sale Phase of a Fragment Definition that is on sale. The return type is a JSON string
+	#[method(name = "getSalePhase")]
+	fn get_sale_phase(
+		&self,
+		param: GetSalePhaseParams<AccountId, String>,
+		at: Option<BlockHash>,
+	) -> RpcResult<String>;
+	/// Query the crafting recipes. The return type is a JSON string
+	#[method(name = "getRecipes")]
+	fn get_recipes(
+		&self,
+		param: GetRecipesParams<AccountId>,
+		at: Option<BlockHash>,
+	) -> RpcResult<String>;
 }
 
 // Structure that will implement the `FragmentsRpcServer` trait.
@@ -91,6 +115,13 @@ where
 			limit: param.limit,
 			owner: param.owner,
 			return_owners: param.return_owners,
+			categories: param.categories,
+			tags: param.tags.into_iter().map(|s| s.into_bytes()).collect(),
+			currency: param.currency.map(|s| s.into_bytes()),
+			published: param.published,
+			min_price: param.min_price,
+			max_price: param.max_price,
+			sold_out: param.sold_out,
 		};
 
 		let result_outer = api
@@ -124,6 +155,7 @@ where
 			definition_hash: param.definition_hash.into_bytes(),
 			owner: param.owner,
 			only_return_first_copies: param.only_return_first_copies,
+			return_nested: param.return_nested,
 		};
 
 		let result_outer = api
@@ -167,6 +199,84 @@ where
 			},
 		}
 	}
+
+	/// Query the balance of the vault of a Fragment Definition (i.e its sale proceeds). The return type is a JSON string
+	fn get_vault_balance(
+		&self,
+		definition_hash: String,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<String> {
+		let api = self.client.runtime_api();
+
+		// If the block hash is not supplied in `at`, use the best block's hash
+		let at_hash = at.unwrap_or_else(|| self.client.info().best_hash);
+
+		let result_outer = api
+			.get_vault_balance(at_hash, definition_hash.into_bytes())
+			.map(|bytes| bytes.map(|bytes| String::from_utf8(bytes).unwrap_or_default()));
+
+		match result_outer {
+			Err(e) => Err(runtime_error_into_rpc_err(e)),
+			Ok(result_outer) => match result_outer {
+				Err(e) => Err(runtime_error_into_rpc_err(e)),
+				Ok(result_inner) => Ok(result_inner),
+			},
+		}
+	}
+
+	/// Query the current Sale Phase of a Fragment Definition that is on sale. The return type is a JSON string
+	fn get_sale_phase(
+		&self,
+		param: GetSalePhaseParams<AccountId, String>,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<String> {
+		let api = self.client.runtime_api();
+
+		// If the block hash is not supplied in `at`, use the best block's hash
+		let at_hash = at.unwrap_or_else(|| self.client.info().best_hash);
+
+		let param_no_std = GetSalePhaseParams::<AccountId, Vec<u8>> {
+			definition_hash: param.definition_hash.into_bytes(),
+			account: param.account,
+			allowance: param.allowance,
+		};
+
+		let result_outer = api
+			.get_sale_phase(at_hash, param_no_std)
+			.map(|bytes| bytes.map(|bytes| String::from_utf8(bytes).unwrap_or_default()));
+
+		match result_outer {
+			Err(e) => Err(runtime_error_into_rpc_err(e)),
+			Ok(result_outer) => match result_outer {
+				Err(e) => Err(runtime_error_into_rpc_err(e)),
+				Ok(result_inner) => Ok(result_inner),
+			},
+		}
+	}
+
+	/// Query the crafting recipes. The return type is a JSON string
+	fn get_recipes(
+		&self,
+		param: GetRecipesParams<AccountId>,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<String> {
+		let api = self.client.runtime_api();
+
+		// If the block hash is not supplied in `at`, use the best block's hash
+		let at_hash = at.unwrap_or_else(|| self.client.info().best_hash);
+
+		let result_outer = api
+			.get_recipes(at_hash, param)
+			.map(|bytes| bytes.map(|bytes| String::from_utf8(bytes).unwrap_or_default()));
+
+		match result_outer {
+			Err(e) => Err(runtime_error_into_rpc_err(e)),
+			Ok(result_outer) => match result_outer {
+				Err(e) => Err(runtime_error_into_rpc_err(e)),
+				Ok(result_inner) => Ok(result_inner),
+			},
+		}
+	}
 }
 
 fn runtime_error_into_rpc_err(err: impl std::fmt::Debug) -> JsonRpseeError {
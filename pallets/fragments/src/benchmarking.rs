@@ -4,15 +4,17 @@
 
 use super::*;
 use frame_benchmarking::{account, benchmarks, vec, whitelisted_caller};
-use frame_support::{traits::Get, BoundedVec};
+use frame_support::{traits::Get, weights::Weight, BoundedVec};
 use frame_system::RawOrigin;
 use pallet_protos::{ProtoData, UsageLicense};
 use protos::{
 	categories::{Categories, TextCategories},
 	permissions::FragmentPerms,
+	traits::{VariableType, VariableTypeInfo},
 };
 use sp_core::crypto::UncheckedFrom;
 use sp_io::hashing::blake2_128;
+use sp_std::boxed::Box;
 
 use crate::Pallet as Fragments;
 use pallet_assets::Pallet as Assets;
@@ -91,6 +93,112 @@ benchmarks! {
 		assert_last_event::<T>(Event::<T>::DefinitionCreated { definition_hash: definition_hash}.into())
 	}
 
+	set_definition_metadata { // Benchmark setup phase
+		let d in 1 .. MAX_DATA_LENGTH; // `data`'s length
+		let caller: T::AccountId = whitelisted_caller();
+
+		let proto_data = b"Je suis Data".to_vec();
+		Protos::<T>::upload(
+			RawOrigin::Signed(caller.clone()).into(),
+			Vec::<Hash256>::new(),
+			Categories::Text(TextCategories::Plain),
+			Vec::<BoundedVec<u8, _>>::new().try_into().unwrap(),
+			None,
+			UsageLicense::Closed,
+			None,
+			ProtoData::Local(proto_data.clone()),
+		)?;
+		let proto_hash = blake2_256(&proto_data);
+
+		let metadata = DefinitionMetadata::<BoundedVec<u8, _>, _> {
+			name: b"Je suis un Nom".to_vec().try_into().unwrap(),
+			currency: Currency::Native,
+			_reserved1: None,
+			_reserved2: None,
+			_reserved3: None,
+		};
+		Fragments::<T>::create(
+			RawOrigin::Signed(caller.clone()).into(),
+			proto_hash,
+			metadata.clone(),
+			FragmentPerms::EDIT | FragmentPerms::TRANSFER,
+			None,
+			None
+		)?;
+		let definition_hash = blake2_128(
+			&[&proto_hash[..], &metadata.name.encode(), &metadata.currency.encode()].concat(),
+		);
+
+		// a metadata key that was never used before is also added to `MetaKeys`
+		let metadata_key: BoundedVec<u8, _> = b"image".to_vec().try_into().unwrap();
+		let data = vec![7u8; d as usize];
+
+	}: _(RawOrigin::Signed(caller), definition_hash, metadata_key, data) // Execution phase
+	verify { // Optional verification phase
+		assert_last_event::<T>(
+			Event::<T>::DefinitionMetadataChanged { definition_hash, metadata_key: b"image".to_vec() }.into()
+		)
+	}
+
+	set_instance_metadata { // Benchmark setup phase
+		let d in 1 .. MAX_DATA_LENGTH; // `data`'s length
+		let caller: T::AccountId = whitelisted_caller();
+
+		let proto_data = b"Je suis Data".to_vec();
+		Protos::<T>::upload(
+			RawOrigin::Signed(caller.clone()).into(),
+			Vec::<Hash256>::new(),
+			Categories::Text(TextCategories::Plain),
+			Vec::<BoundedVec<u8, _>>::new().try_into().unwrap(),
+			None,
+			UsageLicense::Closed,
+			None,
+			ProtoData::Local(proto_data.clone()),
+		)?;
+		let proto_hash = blake2_256(&proto_data);
+
+		let metadata = DefinitionMetadata::<BoundedVec<u8, _>, _> {
+			name: b"Je suis un Nom".to_vec().try_into().unwrap(),
+			currency: Currency::Native,
+			_reserved1: None,
+			_reserved2: None,
+			_reserved3: None,
+		};
+		Fragments::<T>::create(
+			RawOrigin::Signed(caller.clone()).into(),
+			proto_hash,
+			metadata.clone(),
+			FragmentPerms::EDIT | FragmentPerms::TRANSFER,
+			None,
+			None
+		)?;
+		let definition_hash = blake2_128(
+			&[&proto_hash[..], &metadata.name.encode(), &metadata.currency.encode()].concat(),
+		);
+
+		Fragments::<T>::mint(
+			RawOrigin::Signed(caller.clone()).into(),
+			definition_hash,
+			FragmentBuyOptions::Quantity(1),
+			None
+		)?;
+
+		// a metadata key that was never used before is also added to `MetaKeys` and to the Fragment Instance's `metadata`
+		let metadata_key: BoundedVec<u8, _> = b"image".to_vec().try_into().unwrap();
+		let data = vec![7u8; d as usize];
+
+	}: _(RawOrigin::Signed(caller), definition_hash, 1, 1, metadata_key, data) // Execution phase
+	verify { // Optional verification phase
+		assert_last_event::<T>(
+			Event::<T>::InstanceMetadataChanged {
+				definition_hash,
+				edition_id: 1,
+				copy_id: 1,
+				metadata_key: b"image".to_vec()
+			}.into()
+		)
+	}
+
 	publish { // Benchmark setup phase
 		let caller: T::AccountId = whitelisted_caller();
 
@@ -132,8 +240,17 @@ benchmarks! {
 		let quantity = Some(7); // making `quantity` Some causes an if condition to execute
 		let expires: Option<T::BlockNumber> = Some(T::BlockNumber::from(7u32));
 		let amount: Option<InstanceUnit> = Some(7);
+		// the more Sale Phases there are, the more Sale Phases `publish()` needs to validate
+		let phases: Vec<SalePhase<T::BlockNumber>> = (0..MAX_SALE_PHASES as u32)
+			.map(|i| SalePhase {
+				start: T::BlockNumber::from(i),
+				price: Compact(price),
+				allowlist: Some([7u8; 32]),
+				max_per_account: Some(Compact(7)),
+			})
+			.collect();
 
-	}: publish(RawOrigin::Signed(caller), definition_hash, price, quantity, expires, amount) // Execution phase
+	}: publish(RawOrigin::Signed(caller), definition_hash, price, quantity, expires, amount, phases) // Execution phase
 	verify { // Optional verification phase
 		assert_last_event::<T>(Event::<T>::Publishing { definition_hash: definition_hash}.into())
 	}
@@ -178,7 +295,8 @@ benchmarks! {
 			7, // price
 			None,
 			None,
-			None
+			None,
+			Vec::new()
 		)?;
 
 	}: unpublish(RawOrigin::Signed(caller), definition_hash) // Execution phase
@@ -341,7 +459,8 @@ benchmarks! {
 			// The aforementioned DB write operation is the modification of the value `units_left` for the Publishing Struct in the `Publishing` StorageMap
 			Some(q + 1).map(|ms| ms.into()),
 			None,
-			None
+			None,
+			Vec::new()
 		)?;
 		_ = <Balances::<T> as fungible::Mutate<T::AccountId>>::mint_into(
 			&caller.clone(),
@@ -351,7 +470,7 @@ benchmarks! {
 
 		let options = FragmentBuyOptions::Quantity(q.into());
 
-	}: buy(RawOrigin::Signed(caller.clone()), definition_hash, options) // Execution phase
+	}: buy(RawOrigin::Signed(caller.clone()), definition_hash, options, None) // Execution phase
 	verify { // Optional verification phase
 		for edition_id in 1..=q {
 			assert_has_event::<T>(
@@ -413,7 +532,8 @@ benchmarks! {
 			// The aforementioned DB write operation is the modification of the value `units_left` for the Publishing Struct in the `Publishing` StorageMap
 			Some(7),
 			None,
-			None
+			None,
+			Vec::new()
 		)?;
 
 		_ = <Balances::<T> as fungible::Mutate<T::AccountId>>::mint_into(
@@ -424,7 +544,7 @@ benchmarks! {
 
 		let options = FragmentBuyOptions::UniqueData(vec![7u8; d as usize]);
 
-	}: buy(RawOrigin::Signed(caller.clone()), definition_hash, options) // Execution phase
+	}: buy(RawOrigin::Signed(caller.clone()), definition_hash, options, None) // Execution phase
 	verify { // Optional verification phase
 		assert_has_event::<T>(
 			Event::<T>::InventoryAdded {
@@ -553,7 +673,7 @@ benchmarks! {
 		// by making `new_permissions` Some, it executes an extra if-statement block
 		let new_permissions = Some(FragmentPerms::TRANSFER);
 		// by making `expiration` Some, it causes an extra DB write operation (only if instance has copy perms).
-		// This aforementioned DB write operation adds a key to the StorageMap `Expirations`
+		// This aforementioned DB write operation adds a key to the StorageDoubleMap `ExpirationQueue`
 		let expiration = Some(T::BlockNumber::from(7u32));
 
 	}: give(RawOrigin::Signed(caller.clone()), definition_hash, edition, copy, to.clone(), new_permissions, expiration) // Execution phase
@@ -567,6 +687,1886 @@ benchmarks! {
 		)
 	}
 
+	withdraw_proceeds { // Benchmark setup phase
+		let caller: T::AccountId = whitelisted_caller();
+		let to: T::AccountId = account("Sample", 100, SEED);
+
+		let proto_data = b"Je suis Data".to_vec();
+		Protos::<T>::upload(
+			RawOrigin::Signed(caller.clone()).into(),
+			Vec::<Hash256>::new(),
+			Categories::Text(TextCategories::Plain),
+			Vec::<BoundedVec<u8, _>>::new().try_into().unwrap(),
+			None,
+			UsageLicense::Closed,
+			None,
+			ProtoData::Local(proto_data.clone()),
+		)?;
+		let proto_hash = blake2_256(&proto_data);
+
+		let metadata = DefinitionMetadata::<BoundedVec<u8, _>, _> {
+			name: b"Je suis un Nom".to_vec().try_into().unwrap(),
+			currency: Currency::Native,
+			_reserved1: None,
+			_reserved2: None,
+			_reserved3: None,
+		};
+		Fragments::<T>::create(
+			RawOrigin::Signed(caller.clone()).into(),
+			proto_hash,
+			metadata.clone(),
+			FragmentPerms::EDIT | FragmentPerms::TRANSFER,
+			None,
+			None
+		)?;
+		let definition_hash = blake2_128(
+			&[&proto_hash[..], &metadata.name.encode(), &metadata.currency.encode()].concat(),
+		);
+
+		let amount = 7u32;
+		let vault: T::AccountId = sp_fragnova::get_account_id(b"pallet-fragments-create", &definition_hash);
+		_ = <Balances::<T> as fungible::Mutate<T::AccountId>>::mint_into(
+			&vault,
+			<T as pallet_balances::Config>::Balance::from(amount)
+			+ <Balances::<T> as fungible::Inspect<T::AccountId>>::minimum_balance(),
+		);
+		// make sure `to` can receive `amount` even if it is below the existential deposit
+		_ = <Balances::<T> as fungible::Mutate<T::AccountId>>::mint_into(
+			&to,
+			<Balances::<T> as fungible::Inspect<T::AccountId>>::minimum_balance(),
+		);
+
+	}: _(RawOrigin::Signed(caller), definition_hash, amount as u128, T::Lookup::unlookup(to.clone())) // Execution phase
+	verify { // Optional verification phase
+		assert_last_event::<T>(
+			Event::<T>::ProceedsWithdrawn {
+				definition_hash,
+				amount: amount as u128,
+				to,
+			}.into()
+		)
+	}
+
+	burn { // Benchmark setup phase
+		let caller: T::AccountId = whitelisted_caller();
+
+		let proto_data = b"Je suis Data".to_vec();
+		Protos::<T>::upload(
+			RawOrigin::Signed(caller.clone()).into(),
+			Vec::<Hash256>::new(),
+			Categories::Text(TextCategories::Plain),
+			Vec::<BoundedVec<u8, _>>::new().try_into().unwrap(),
+			None,
+			UsageLicense::Closed,
+			None,
+			ProtoData::Local(proto_data.clone()),
+		)?;
+		let proto_hash = blake2_256(&proto_data);
+
+		let metadata = DefinitionMetadata::<BoundedVec<u8, _>, _> {
+			name: b"Je suis un Nom".to_vec().try_into().unwrap(),
+			currency: Currency::Native,
+			_reserved1: None,
+			_reserved2: None,
+			_reserved3: None,
+		};
+		Fragments::<T>::create(
+			RawOrigin::Signed(caller.clone()).into(),
+			proto_hash,
+			metadata.clone(),
+			FragmentPerms::EDIT | FragmentPerms::TRANSFER,
+			// we make the Definition unique, because this causes `burn()` to also clean `UniqueData2Edition`
+			Some(UniqueOptions { mutable: false, _reserved1: None, _reserved2: None, _reserved3: None }),
+			None
+		)?;
+		let definition_hash = blake2_128(
+			&[&proto_hash[..], &metadata.name.encode(), &metadata.currency.encode()].concat(),
+		);
+
+		Fragments::<T>::mint(
+			RawOrigin::Signed(caller.clone()).into(),
+			definition_hash,
+			FragmentBuyOptions::UniqueData(b"I Dati".to_vec()),
+			None
+		)?;
+
+	}: _(RawOrigin::Signed(caller.clone()), definition_hash, 1, 1) // Execution phase
+	verify { // Optional verification phase
+		assert_last_event::<T>(
+			Event::<T>::Burned {
+				account_id: caller,
+				definition_hash,
+				fragment_id: (1, 1)
+			}.into()
+		)
+	}
+
+	set_burns_replenish_supply { // Benchmark setup phase
+		let caller: T::AccountId = whitelisted_caller();
+
+		let proto_data = b"Je suis Data".to_vec();
+		Protos::<T>::upload(
+			RawOrigin::Signed(caller.clone()).into(),
+			Vec::<Hash256>::new(),
+			Categories::Text(TextCategories::Plain),
+			Vec::<BoundedVec<u8, _>>::new().try_into().unwrap(),
+			None,
+			UsageLicense::Closed,
+			None,
+			ProtoData::Local(proto_data.clone()),
+		)?;
+		let proto_hash = blake2_256(&proto_data);
+
+		let metadata = DefinitionMetadata::<BoundedVec<u8, _>, _> {
+			name: b"Je suis un Nom".to_vec().try_into().unwrap(),
+			currency: Currency::Native,
+			_reserved1: None,
+			_reserved2: None,
+			_reserved3: None,
+		};
+		Fragments::<T>::create(
+			RawOrigin::Signed(caller.clone()).into(),
+			proto_hash,
+			metadata.clone(),
+			FragmentPerms::EDIT | FragmentPerms::TRANSFER,
+			None,
+			Some(1)
+		)?;
+		let definition_hash = blake2_128(
+			&[&proto_hash[..], &metadata.name.encode(), &metadata.currency.encode()].concat(),
+		);
+
+	}: _(RawOrigin::Signed(caller), definition_hash, true) // Execution phase
+	verify { // Optional verification phase
+		assert_last_event::<T>(
+			Event::<T>::BurnsReplenishSupplyChanged {
+				definition_hash,
+				replenish: true
+			}.into()
+		)
+	}
+
+	split_stack { // Benchmark setup phase
+		let caller: T::AccountId = whitelisted_caller();
+		let to: T::AccountId = account("Sample", 100, SEED);
+
+		let proto_data = b"Je suis Data".to_vec();
+		Protos::<T>::upload(
+			RawOrigin::Signed(caller.clone()).into(),
+			Vec::<Hash256>::new(),
+			Categories::Text(TextCategories::Plain),
+			Vec::<BoundedVec<u8, _>>::new().try_into().unwrap(),
+			None,
+			UsageLicense::Closed,
+			None,
+			ProtoData::Local(proto_data.clone()),
+		)?;
+		let proto_hash = blake2_256(&proto_data);
+
+		let metadata = DefinitionMetadata::<BoundedVec<u8, _>, _> {
+			name: b"Je suis un Nom".to_vec().try_into().unwrap(),
+			currency: Currency::Native,
+			_reserved1: None,
+			_reserved2: None,
+			_reserved3: None,
+		};
+		Fragments::<T>::create(
+			RawOrigin::Signed(caller.clone()).into(),
+			proto_hash,
+			metadata.clone(),
+			FragmentPerms::EDIT | FragmentPerms::TRANSFER,
+			None, // non-unique
+			None
+		)?;
+		let definition_hash = blake2_128(
+			&[&proto_hash[..], &metadata.name.encode(), &metadata.currency.encode()].concat(),
+		);
+
+		Fragments::<T>::mint(
+			RawOrigin::Signed(caller.clone()).into(),
+			definition_hash,
+			FragmentBuyOptions::Quantity(1),
+			Some(10) // stack amount
+		)?;
+
+	}: _(RawOrigin::Signed(caller), definition_hash, 1, 1, 4, T::Lookup::unlookup(to)) // Execution phase
+	verify { // Optional verification phase
+		assert_last_event::<T>(
+			Event::<T>::StackSplit {
+				definition_hash,
+				fragment_id: (1, 1),
+				new_fragment_id: (1, 2),
+				amount: 4
+			}.into()
+		)
+	}
+
+	merge_stacks { // Benchmark setup phase
+		let caller: T::AccountId = whitelisted_caller();
+
+		let proto_data = b"Je suis Data".to_vec();
+		Protos::<T>::upload(
+			RawOrigin::Signed(caller.clone()).into(),
+			Vec::<Hash256>::new(),
+			Categories::Text(TextCategories::Plain),
+			Vec::<BoundedVec<u8, _>>::new().try_into().unwrap(),
+			None,
+			UsageLicense::Closed,
+			None,
+			ProtoData::Local(proto_data.clone()),
+		)?;
+		let proto_hash = blake2_256(&proto_data);
+
+		let metadata = DefinitionMetadata::<BoundedVec<u8, _>, _> {
+			name: b"Je suis un Nom".to_vec().try_into().unwrap(),
+			currency: Currency::Native,
+			_reserved1: None,
+			_reserved2: None,
+			_reserved3: None,
+		};
+		Fragments::<T>::create(
+			RawOrigin::Signed(caller.clone()).into(),
+			proto_hash,
+			metadata.clone(),
+			FragmentPerms::EDIT | FragmentPerms::TRANSFER,
+			None, // non-unique
+			None
+		)?;
+		let definition_hash = blake2_128(
+			&[&proto_hash[..], &metadata.name.encode(), &metadata.currency.encode()].concat(),
+		);
+
+		Fragments::<T>::mint(
+			RawOrigin::Signed(caller.clone()).into(),
+			definition_hash,
+			FragmentBuyOptions::Quantity(1),
+			Some(10) // stack amount
+		)?;
+		Fragments::<T>::split_stack(
+			RawOrigin::Signed(caller.clone()).into(),
+			definition_hash,
+			1,
+			1,
+			4,
+			T::Lookup::unlookup(caller.clone())
+		)?;
+
+	}: _(RawOrigin::Signed(caller), definition_hash, 1, 1, 2) // Execution phase
+	verify { // Optional verification phase
+		assert_last_event::<T>(
+			Event::<T>::StacksMerged {
+				definition_hash,
+				fragment_id: (1, 1),
+				merged_fragment_id: (1, 2),
+				amount: 4
+			}.into()
+		)
+	}
+
+	consume { // Benchmark setup phase
+		let caller: T::AccountId = whitelisted_caller();
+
+		let proto_data = b"Je suis Data".to_vec();
+		Protos::<T>::upload(
+			RawOrigin::Signed(caller.clone()).into(),
+			Vec::<Hash256>::new(),
+			Categories::Text(TextCategories::Plain),
+			Vec::<BoundedVec<u8, _>>::new().try_into().unwrap(),
+			None,
+			UsageLicense::Closed,
+			None,
+			ProtoData::Local(proto_data.clone()),
+		)?;
+		let proto_hash = blake2_256(&proto_data);
+
+		let metadata = DefinitionMetadata::<BoundedVec<u8, _>, _> {
+			name: b"Je suis un Nom".to_vec().try_into().unwrap(),
+			currency: Currency::Native,
+			_reserved1: None,
+			_reserved2: None,
+			_reserved3: None,
+		};
+		Fragments::<T>::create(
+			RawOrigin::Signed(caller.clone()).into(),
+			proto_hash,
+			metadata.clone(),
+			FragmentPerms::EDIT | FragmentPerms::TRANSFER,
+			None, // non-unique
+			None
+		)?;
+		let definition_hash = blake2_128(
+			&[&proto_hash[..], &metadata.name.encode(), &metadata.currency.encode()].concat(),
+		);
+
+		Fragments::<T>::mint(
+			RawOrigin::Signed(caller.clone()).into(),
+			definition_hash,
+			FragmentBuyOptions::Quantity(1),
+			Some(10) // stack amount
+		)?;
+
+		// we consume the whole stack, because this causes `consume()` to also burn the Fragment Instance
+	}: _(RawOrigin::Signed(caller), definition_hash, 1, 1, 10) // Execution phase
+	verify { // Optional verification phase
+		assert_last_event::<T>(
+			Event::<T>::StackConsumed {
+				definition_hash,
+				fragment_id: (1, 1),
+				amount: 10,
+				left: 0
+			}.into()
+		)
+	}
+
+	mint_to_many { // Benchmark setup phase
+		let r in 1 .. MAX_MINT_RECIPIENTS as u32; // number of recipients
+		let q in 1 .. MAX_QUANTITY_TO_MINT; // number of Fragment Instances minted to the first recipient
+		let caller: T::AccountId = whitelisted_caller();
+
+		let proto_data = b"Je suis Data".to_vec();
+		Protos::<T>::upload(
+			RawOrigin::Signed(caller.clone()).into(),
+			Vec::<Hash256>::new(),
+			Categories::Text(TextCategories::Plain),
+			Vec::<BoundedVec<u8, _>>::new().try_into().unwrap(),
+			None,
+			UsageLicense::Closed,
+			None,
+			ProtoData::Local(proto_data.clone()),
+		)?;
+		let proto_hash = blake2_256(&proto_data);
+
+		let metadata = DefinitionMetadata::<BoundedVec<u8, _>, _> {
+			name: b"Je suis un Nom".to_vec().try_into().unwrap(),
+			currency: Currency::Native,
+			_reserved1: None,
+			_reserved2: None,
+			_reserved3: None,
+		};
+		Fragments::<T>::create(
+			RawOrigin::Signed(caller.clone()).into(),
+			proto_hash,
+			metadata.clone(),
+			FragmentPerms::EDIT | FragmentPerms::TRANSFER,
+			None, // non-unique
+			// we make the Definition's `max_supply` Some,
+			// because this causes `mint_to_many()` to check if `max_supply` is exceeded
+			Some(q + r).map(|ms| ms.into())
+		)?;
+		let definition_hash = blake2_128(
+			&[&proto_hash[..], &metadata.name.encode(), &metadata.currency.encode()].concat(),
+		);
+
+		let recipients = (0..r)
+			.map(|i| (T::Lookup::unlookup(account("Sample", i, SEED)), if i == 0 { q as InstanceUnit } else { 1 }))
+			.collect::<Vec<_>>();
+
+	}: _(RawOrigin::Signed(caller), definition_hash, recipients, Some(7), None) // Execution phase
+	verify { // Optional verification phase
+		assert_last_event::<T>(
+			Event::<T>::InventoryAdded {
+				account_id: account("Sample", r - 1, SEED),
+				definition_hash: definition_hash,
+				fragment_id: ((q + r - 1).into(), 1)
+			}.into()
+		)
+	}
+
+	start_claim { // Benchmark setup phase
+		let caller: T::AccountId = whitelisted_caller();
+
+		let proto_data = b"Je suis Data".to_vec();
+		Protos::<T>::upload(
+			RawOrigin::Signed(caller.clone()).into(),
+			Vec::<Hash256>::new(),
+			Categories::Text(TextCategories::Plain),
+			Vec::<BoundedVec<u8, _>>::new().try_into().unwrap(),
+			None,
+			UsageLicense::Closed,
+			None,
+			ProtoData::Local(proto_data.clone()),
+		)?;
+		let proto_hash = blake2_256(&proto_data);
+
+		let metadata = DefinitionMetadata::<BoundedVec<u8, _>, _> {
+			name: b"Je suis un Nom".to_vec().try_into().unwrap(),
+			currency: Currency::Native,
+			_reserved1: None,
+			_reserved2: None,
+			_reserved3: None,
+		};
+		Fragments::<T>::create(
+			RawOrigin::Signed(caller.clone()).into(),
+			proto_hash,
+			metadata.clone(),
+			FragmentPerms::EDIT | FragmentPerms::TRANSFER,
+			None, // non-unique
+			None
+		)?;
+		let definition_hash = blake2_128(
+			&[&proto_hash[..], &metadata.name.encode(), &metadata.currency.encode()].concat(),
+		);
+
+		let merkle_root = [7u8; 32];
+
+	}: _(RawOrigin::Signed(caller), definition_hash, merkle_root, None, None, Some(7)) // Execution phase
+	verify { // Optional verification phase
+		assert_last_event::<T>(Event::<T>::ClaimCampaignStarted { definition_hash, merkle_root }.into())
+	}
+
+	end_claim { // Benchmark setup phase
+		let caller: T::AccountId = whitelisted_caller();
+
+		let proto_data = b"Je suis Data".to_vec();
+		Protos::<T>::upload(
+			RawOrigin::Signed(caller.clone()).into(),
+			Vec::<Hash256>::new(),
+			Categories::Text(TextCategories::Plain),
+			Vec::<BoundedVec<u8, _>>::new().try_into().unwrap(),
+			None,
+			UsageLicense::Closed,
+			None,
+			ProtoData::Local(proto_data.clone()),
+		)?;
+		let proto_hash = blake2_256(&proto_data);
+
+		let metadata = DefinitionMetadata::<BoundedVec<u8, _>, _> {
+			name: b"Je suis un Nom".to_vec().try_into().unwrap(),
+			currency: Currency::Native,
+			_reserved1: None,
+			_reserved2: None,
+			_reserved3: None,
+		};
+		Fragments::<T>::create(
+			RawOrigin::Signed(caller.clone()).into(),
+			proto_hash,
+			metadata.clone(),
+			FragmentPerms::EDIT | FragmentPerms::TRANSFER,
+			None, // non-unique
+			None
+		)?;
+		let definition_hash = blake2_128(
+			&[&proto_hash[..], &metadata.name.encode(), &metadata.currency.encode()].concat(),
+		);
+
+		Fragments::<T>::start_claim(
+			RawOrigin::Signed(caller.clone()).into(),
+			definition_hash,
+			[7u8; 32],
+			None,
+			None,
+			None
+		)?;
+
+	}: _(RawOrigin::Signed(caller), definition_hash) // Execution phase
+	verify { // Optional verification phase
+		assert_last_event::<T>(Event::<T>::ClaimCampaignEnded { definition_hash }.into())
+	}
+
+	claim_definition_that_has_non_unique_capability { // Benchmark setup phase
+		let q in 1 .. MAX_QUANTITY_TO_MINT; // `FragmentBuyOptions::Quantity(quantity)`'s `quantity's` length
+		let caller: T::AccountId = whitelisted_caller();
+
+		let proto_data = b"Je suis Data".to_vec();
+		Protos::<T>::upload(
+			RawOrigin::Signed(caller.clone()).into(),
+			Vec::<Hash256>::new(),
+			Categories::Text(TextCategories::Plain),
+			Vec::<BoundedVec<u8, _>>::new().try_into().unwrap(),
+			None,
+			UsageLicense::Closed,
+			None,
+			ProtoData::Local(proto_data.clone()),
+		)?;
+		let proto_hash = blake2_256(&proto_data);
+
+		let metadata = DefinitionMetadata::<BoundedVec<u8, _>, _> {
+			name: b"Je suis un Nom".to_vec().try_into().unwrap(),
+			currency: Currency::Native,
+			_reserved1: None,
+			_reserved2: None,
+			_reserved3: None,
+		};
+		Fragments::<T>::create(
+			RawOrigin::Signed(caller.clone()).into(),
+			proto_hash,
+			metadata.clone(),
+			FragmentPerms::EDIT | FragmentPerms::TRANSFER,
+			None, // non-unique
+			// we make the Definition's `max_supply` Some,
+			// because this causes `claim()` to check if `max_supply` is exceeded
+			Some(q + 1).map(|ms| ms.into())
+		)?;
+		let definition_hash = blake2_128(
+			&[&proto_hash[..], &metadata.name.encode(), &metadata.currency.encode()].concat(),
+		);
+
+		let quantity = q as InstanceUnit;
+		// we use a Merkle Proof of maximum length (i.e the proof of the first leaf of the biggest Merkle Tree), because this causes `claim()` to do the most hashing
+		let proof = MerkleProof {
+			leaf_index: 0,
+			leaf_count: u32::MAX,
+			proof: (0..MAX_MERKLE_PROOF_LENGTH).map(|i| [i as u8; 32]).collect(),
+		};
+		let leaf = blake2_256(&(&caller, quantity).encode());
+		let merkle_root = proof.proof.iter().fold(
+			blake2_256(&[&[sp_fragnova::merkle::LEAF_PREFIX][..], &leaf[..]].concat()),
+			|node, sibling| blake2_256(&[&[sp_fragnova::merkle::NODE_PREFIX][..], &node[..], &sibling[..]].concat())
+		);
+		Fragments::<T>::start_claim(
+			RawOrigin::Signed(caller.clone()).into(),
+			definition_hash,
+			merkle_root,
+			None,
+			None,
+			Some(7) // stack amount
+		)?;
+
+		let options = FragmentBuyOptions::Quantity(quantity);
+
+	}: claim(RawOrigin::Signed(caller.clone()), definition_hash, options, proof) // Execution phase
+	verify { // Optional verification phase
+		assert_last_event::<T>(
+			Event::<T>::Claimed {
+				definition_hash,
+				account_id: caller,
+				quantity
+			}.into()
+		)
+	}
+
+	claim_definition_that_has_unique_capability { // Benchmark setup phase
+		let d in 1 .. MAX_DATA_LENGTH; // `FragmentBuyOptions::UniqueData(data)`'s `data's` length
+		let caller: T::AccountId = whitelisted_caller();
+
+		let proto_data = b"Je suis Data".to_vec();
+		Protos::<T>::upload(
+			RawOrigin::Signed(caller.clone()).into(),
+			Vec::<Hash256>::new(),
+			Categories::Text(TextCategories::Plain),
+			Vec::<BoundedVec<u8, _>>::new().try_into().unwrap(),
+			None,
+			UsageLicense::Closed,
+			None,
+			ProtoData::Local(proto_data.clone()),
+		)?;
+		let proto_hash = blake2_256(&proto_data);
+
+		let metadata = DefinitionMetadata::<BoundedVec<u8, _>, _> {
+			name: b"Je suis un Nom".to_vec().try_into().unwrap(),
+			currency: Currency::Native,
+			_reserved1: None,
+			_reserved2: None,
+			_reserved3: None,
+		};
+		Fragments::<T>::create(
+			RawOrigin::Signed(caller.clone()).into(),
+			proto_hash,
+			metadata.clone(),
+			FragmentPerms::EDIT | FragmentPerms::TRANSFER,
+			Some(UniqueOptions {mutable: false, _reserved1: None,
+				_reserved2: None,
+				_reserved3: None,}), // unique,
+			// we make the Definition's `max_supply` Some,
+			// because this causes `claim()` to check if `max_supply` is exceeded
+			Some(7)
+		)?;
+		let definition_hash = blake2_128(
+			&[&proto_hash[..], &metadata.name.encode(), &metadata.currency.encode()].concat(),
+		);
+
+		let quantity: InstanceUnit = 1;
+		// we use a Merkle Proof of maximum length (i.e the proof of the first leaf of the biggest Merkle Tree), because this causes `claim()` to do the most hashing
+		let proof = MerkleProof {
+			leaf_index: 0,
+			leaf_count: u32::MAX,
+			proof: (0..MAX_MERKLE_PROOF_LENGTH).map(|i| [i as u8; 32]).collect(),
+		};
+		let leaf = blake2_256(&(&caller, quantity).encode());
+		let merkle_root = proof.proof.iter().fold(
+			blake2_256(&[&[sp_fragnova::merkle::LEAF_PREFIX][..], &leaf[..]].concat()),
+			|node, sibling| blake2_256(&[&[sp_fragnova::merkle::NODE_PREFIX][..], &node[..], &sibling[..]].concat())
+		);
+		Fragments::<T>::start_claim(
+			RawOrigin::Signed(caller.clone()).into(),
+			definition_hash,
+			merkle_root,
+			None,
+			None,
+			Some(7) // stack amount
+		)?;
+
+		let options = FragmentBuyOptions::UniqueData(vec![7u8; d as usize]);
+
+	}: claim(RawOrigin::Signed(caller.clone()), definition_hash, options, proof) // Execution phase
+	verify { // Optional verification phase
+		assert_last_event::<T>(
+			Event::<T>::Claimed {
+				definition_hash,
+				account_id: caller,
+				quantity
+			}.into()
+		)
+	}
+
+	sweep_expirations { // Benchmark setup phase
+		let n in 0 .. MAX_QUANTITY_TO_MINT; // number of Fragment Instances that expire
+		let caller: T::AccountId = whitelisted_caller();
+
+		let proto_data = b"Je suis Data".to_vec();
+		Protos::<T>::upload(
+			RawOrigin::Signed(caller.clone()).into(),
+			Vec::<Hash256>::new(),
+			Categories::Text(TextCategories::Plain),
+			Vec::<BoundedVec<u8, _>>::new().try_into().unwrap(),
+			None,
+			UsageLicense::Closed,
+			None,
+			ProtoData::Local(proto_data.clone()),
+		)?;
+		let proto_hash = blake2_256(&proto_data);
+
+		let metadata = DefinitionMetadata::<BoundedVec<u8, _>, _> {
+			name: b"Je suis un Nom".to_vec().try_into().unwrap(),
+			currency: Currency::Native,
+			_reserved1: None,
+			_reserved2: None,
+			_reserved3: None,
+		};
+		Fragments::<T>::create(
+			RawOrigin::Signed(caller.clone()).into(),
+			proto_hash,
+			metadata.clone(),
+			FragmentPerms::EDIT | FragmentPerms::TRANSFER,
+			None, // non-unique
+			None
+		)?;
+		let definition_hash = blake2_128(
+			&[&proto_hash[..], &metadata.name.encode(), &metadata.currency.encode()].concat(),
+		);
+
+		let expiring_at = frame_system::Pallet::<T>::block_number() + 1u32.into();
+		if n > 0 {
+			// every Fragment Instance is owned by a different account, because this causes `sweep_expirations()` to mutate a different `Owners` and `Inventory` entry per Fragment Instance
+			let recipients = (0..n)
+				.map(|i| (T::Lookup::unlookup(account("Sample", i, SEED)), 1))
+				.collect::<Vec<_>>();
+			Fragments::<T>::mint_to_many(
+				RawOrigin::Signed(caller.clone()).into(),
+				definition_hash,
+				recipients,
+				None,
+				Some(expiring_at)
+			)?;
+		}
+		// only the block `expiring_at` is left to sweep
+		<ExpirationCursor<T>>::put(expiring_at);
+
+	}: { Fragments::<T>::sweep_expirations(expiring_at, Weight::MAX); } // Execution phase
+	verify { // Optional verification phase
+		assert_eq!(<ExpirationQueue<T>>::iter_key_prefix(expiring_at).count(), 0);
+		for edition_id in 1..=n {
+			assert!(<Fragments<T>>::get((definition_hash, edition_id as InstanceUnit, 1)).is_none());
+		}
+	}
+
+	renew { // Benchmark setup phase
+		let caller: T::AccountId = whitelisted_caller();
+
+		let proto_data = b"Je suis Data".to_vec();
+		Protos::<T>::upload(
+			RawOrigin::Signed(caller.clone()).into(),
+			Vec::<Hash256>::new(),
+			Categories::Text(TextCategories::Plain),
+			Vec::<BoundedVec<u8, _>>::new().try_into().unwrap(),
+			None,
+			UsageLicense::Closed,
+			None,
+			ProtoData::Local(proto_data.clone()),
+		)?;
+		let proto_hash = blake2_256(&proto_data);
+
+		let metadata = DefinitionMetadata::<BoundedVec<u8, _>, _> {
+			name: b"Je suis un Nom".to_vec().try_into().unwrap(),
+			currency: Currency::Native,
+			_reserved1: None,
+			_reserved2: None,
+			_reserved3: None,
+		};
+		Fragments::<T>::create(
+			RawOrigin::Signed(caller.clone()).into(),
+			proto_hash,
+			metadata.clone(),
+			FragmentPerms::EDIT | FragmentPerms::TRANSFER,
+			None, // non-unique
+			None
+		)?;
+		let definition_hash = blake2_128(
+			&[&proto_hash[..], &metadata.name.encode(), &metadata.currency.encode()].concat(),
+		);
+
+		let price: u32 = 7;
+		Fragments::<T>::publish(
+			RawOrigin::Signed(caller.clone()).into(),
+			definition_hash,
+			price.into(),
+			None,
+			None,
+			None,
+			Vec::new()
+		)?;
+		_ = <Balances::<T> as fungible::Mutate<T::AccountId>>::mint_into(
+			&caller.clone(),
+			<T as pallet_balances::Config>::Balance::from(price)
+			+ <Balances::<T> as fungible::Inspect<T::AccountId>>::minimum_balance(),
+		);
+
+		let expiring_at = frame_system::Pallet::<T>::block_number() + 10u32.into();
+		Fragments::<T>::mint_to_many(
+			RawOrigin::Signed(caller.clone()).into(),
+			definition_hash,
+			vec![(T::Lookup::unlookup(caller.clone()), 1)],
+			None,
+			Some(expiring_at)
+		)?;
+
+	}: _(RawOrigin::Signed(caller), definition_hash, 1, 1) // Execution phase
+	verify { // Optional verification phase
+		assert!(<Fragments<T>>::get((definition_hash, 1, 1)).unwrap().expiring_at.unwrap() > expiring_at);
+	}
+
+	rent { // Benchmark setup phase
+		let caller: T::AccountId = whitelisted_caller();
+
+		let proto_data = b"Je suis Data".to_vec();
+		Protos::<T>::upload(
+			RawOrigin::Signed(caller.clone()).into(),
+			Vec::<Hash256>::new(),
+			Categories::Text(TextCategories::Plain),
+			Vec::<BoundedVec<u8, _>>::new().try_into().unwrap(),
+			None,
+			UsageLicense::Closed,
+			None,
+			ProtoData::Local(proto_data.clone()),
+		)?;
+		let proto_hash = blake2_256(&proto_data);
+
+		let metadata = DefinitionMetadata::<BoundedVec<u8, _>, _> {
+			name: b"Je suis un Nom".to_vec().try_into().unwrap(),
+			currency: Currency::Native,
+			_reserved1: None,
+			_reserved2: None,
+			_reserved3: None,
+		};
+		Fragments::<T>::create(
+			RawOrigin::Signed(caller.clone()).into(),
+			proto_hash,
+			metadata.clone(),
+			FragmentPerms::EDIT | FragmentPerms::TRANSFER,
+			None, // non-unique
+			None
+		)?;
+		let definition_hash = blake2_128(
+			&[&proto_hash[..], &metadata.name.encode(), &metadata.currency.encode()].concat(),
+		);
+
+		Fragments::<T>::mint(
+			RawOrigin::Signed(caller.clone()).into(),
+			definition_hash,
+			FragmentBuyOptions::Quantity(1),
+			None
+		)?;
+
+		let to: T::AccountId = account("Sample", 100, SEED);
+		let until = frame_system::Pallet::<T>::block_number() + 10u32.into();
+
+	}: _(RawOrigin::Signed(caller), definition_hash, 1, 1, T::Lookup::unlookup(to.clone()), until) // Execution phase
+	verify { // Optional verification phase
+		assert_last_event::<T>(
+			Event::<T>::Rented {
+				definition_hash,
+				fragment_id: (1, 1),
+				renter: to,
+				until
+			}.into()
+		)
+	}
+
+	end_rental { // Benchmark setup phase
+		let caller: T::AccountId = whitelisted_caller();
+
+		let proto_data = b"Je suis Data".to_vec();
+		Protos::<T>::upload(
+			RawOrigin::Signed(caller.clone()).into(),
+			Vec::<Hash256>::new(),
+			Categories::Text(TextCategories::Plain),
+			Vec::<BoundedVec<u8, _>>::new().try_into().unwrap(),
+			None,
+			UsageLicense::Closed,
+			None,
+			ProtoData::Local(proto_data.clone()),
+		)?;
+		let proto_hash = blake2_256(&proto_data);
+
+		let metadata = DefinitionMetadata::<BoundedVec<u8, _>, _> {
+			name: b"Je suis un Nom".to_vec().try_into().unwrap(),
+			currency: Currency::Native,
+			_reserved1: None,
+			_reserved2: None,
+			_reserved3: None,
+		};
+		Fragments::<T>::create(
+			RawOrigin::Signed(caller.clone()).into(),
+			proto_hash,
+			metadata.clone(),
+			FragmentPerms::EDIT | FragmentPerms::TRANSFER,
+			None, // non-unique
+			None
+		)?;
+		let definition_hash = blake2_128(
+			&[&proto_hash[..], &metadata.name.encode(), &metadata.currency.encode()].concat(),
+		);
+
+		Fragments::<T>::mint(
+			RawOrigin::Signed(caller.clone()).into(),
+			definition_hash,
+			FragmentBuyOptions::Quantity(1),
+			None
+		)?;
+
+		let renter: T::AccountId = account("Sample", 100, SEED);
+		Fragments::<T>::rent(
+			RawOrigin::Signed(caller.clone()).into(),
+			definition_hash,
+			1,
+			1,
+			T::Lookup::unlookup(renter.clone()),
+			frame_system::Pallet::<T>::block_number() + 10u32.into()
+		)?;
+
+	}: _(RawOrigin::Signed(renter), definition_hash, 1, 1) // Execution phase
+	verify { // Optional verification phase
+		assert_last_event::<T>(
+			Event::<T>::RentalEnded {
+				definition_hash,
+				fragment_id: (1, 1)
+			}.into()
+		)
+	}
+
+	approve { // Benchmark setup phase
+		let caller: T::AccountId = whitelisted_caller();
+		let owner: T::AccountId = account("Sample", 100, SEED);
+		let operator: T::AccountId = account("Sample", 101, SEED);
+
+		let proto_data = b"Je suis Data".to_vec();
+		Protos::<T>::upload(
+			RawOrigin::Signed(owner.clone()).into(),
+			Vec::<Hash256>::new(),
+			Categories::Text(TextCategories::Plain),
+			Vec::<BoundedVec<u8, _>>::new().try_into().unwrap(),
+			None,
+			UsageLicense::Closed,
+			None,
+			ProtoData::Local(proto_data.clone()),
+		)?;
+		let proto_hash = blake2_256(&proto_data);
+
+		let metadata = DefinitionMetadata::<BoundedVec<u8, _>, _> {
+			name: b"Je suis un Nom".to_vec().try_into().unwrap(),
+			currency: Currency::Native,
+			_reserved1: None,
+			_reserved2: None,
+			_reserved3: None,
+		};
+		Fragments::<T>::create(
+			RawOrigin::Signed(owner.clone()).into(),
+			proto_hash,
+			metadata.clone(),
+			FragmentPerms::EDIT | FragmentPerms::TRANSFER,
+			None, // non-unique
+			None
+		)?;
+		let definition_hash = blake2_128(
+			&[&proto_hash[..], &metadata.name.encode(), &metadata.currency.encode()].concat(),
+		);
+
+		Fragments::<T>::mint(
+			RawOrigin::Signed(owner.clone()).into(),
+			definition_hash,
+			FragmentBuyOptions::Quantity(1),
+			None
+		)?;
+
+		// the caller is approved for all of the owner's Fragment Instances, so that `OperatorApprovals` is also read
+		Fragments::<T>::set_approval_for_all(
+			RawOrigin::Signed(owner.clone()).into(),
+			definition_hash,
+			T::Lookup::unlookup(caller.clone()),
+			true
+		)?;
+
+	}: _(RawOrigin::Signed(caller), definition_hash, 1, 1, Some(T::Lookup::unlookup(operator.clone()))) // Execution phase
+	verify { // Optional verification phase
+		assert_last_event::<T>(
+			Event::<T>::Approval {
+				definition_hash,
+				fragment_id: (1, 1),
+				owner,
+				operator: Some(operator)
+			}.into()
+		)
+	}
+
+	set_approval_for_all { // Benchmark setup phase
+		let owner: T::AccountId = whitelisted_caller();
+		let operator: T::AccountId = account("Sample", 100, SEED);
+
+		let proto_data = b"Je suis Data".to_vec();
+		Protos::<T>::upload(
+			RawOrigin::Signed(owner.clone()).into(),
+			Vec::<Hash256>::new(),
+			Categories::Text(TextCategories::Plain),
+			Vec::<BoundedVec<u8, _>>::new().try_into().unwrap(),
+			None,
+			UsageLicense::Closed,
+			None,
+			ProtoData::Local(proto_data.clone()),
+		)?;
+		let proto_hash = blake2_256(&proto_data);
+
+		let metadata = DefinitionMetadata::<BoundedVec<u8, _>, _> {
+			name: b"Je suis un Nom".to_vec().try_into().unwrap(),
+			currency: Currency::Native,
+			_reserved1: None,
+			_reserved2: None,
+			_reserved3: None,
+		};
+		Fragments::<T>::create(
+			RawOrigin::Signed(owner.clone()).into(),
+			proto_hash,
+			metadata.clone(),
+			FragmentPerms::EDIT | FragmentPerms::TRANSFER,
+			None, // non-unique
+			None
+		)?;
+		let definition_hash = blake2_128(
+			&[&proto_hash[..], &metadata.name.encode(), &metadata.currency.encode()].concat(),
+		);
+
+	}: _(RawOrigin::Signed(owner.clone()), definition_hash, T::Lookup::unlookup(operator.clone()), true) // Execution phase
+	verify { // Optional verification phase
+		assert_last_event::<T>(
+			Event::<T>::ApprovalForAll {
+				definition_hash,
+				owner,
+				operator,
+				approved: true
+			}.into()
+		)
+	}
+
+	propose_swap { // Benchmark setup phase
+		let n in 1 .. MAX_SWAP_ITEMS as u32;
+
+		let caller: T::AccountId = whitelisted_caller();
+		let taker: T::AccountId = account("Sample", 100, SEED);
+
+		let proto_data = b"Je suis Data".to_vec();
+		Protos::<T>::upload(
+			RawOrigin::Signed(caller.clone()).into(),
+			Vec::<Hash256>::new(),
+			Categories::Text(TextCategories::Plain),
+			Vec::<BoundedVec<u8, _>>::new().try_into().unwrap(),
+			None,
+			UsageLicense::Closed,
+			None,
+			ProtoData::Local(proto_data.clone()),
+		)?;
+		let proto_hash = blake2_256(&proto_data);
+
+		let metadata = DefinitionMetadata::<BoundedVec<u8, _>, _> {
+			name: b"Je suis un Nom".to_vec().try_into().unwrap(),
+			currency: Currency::Native,
+			_reserved1: None,
+			_reserved2: None,
+			_reserved3: None,
+		};
+		Fragments::<T>::create(
+			RawOrigin::Signed(caller.clone()).into(),
+			proto_hash,
+			metadata.clone(),
+			FragmentPerms::EDIT | FragmentPerms::TRANSFER, // no copy permission
+			None, // non-unique
+			None
+		)?;
+		let definition_hash = blake2_128(
+			&[&proto_hash[..], &metadata.name.encode(), &metadata.currency.encode()].concat(),
+		);
+
+		Fragments::<T>::mint(
+			RawOrigin::Signed(caller.clone()).into(),
+			definition_hash,
+			FragmentBuyOptions::Quantity(n as u64),
+			None
+		)?;
+		let offer = SwapBundle::<T::AssetId> {
+			instances: (1..=n as u64).map(|edition_id| (definition_hash, edition_id, 1)).collect(),
+			protos: Vec::new(),
+			amount: None,
+		};
+		let ask = SwapBundle::<T::AssetId> { instances: Vec::new(), protos: Vec::new(), amount: None };
+		let expiration = frame_system::Pallet::<T>::block_number() + 10u32.into();
+
+	}: _(RawOrigin::Signed(caller.clone()), T::Lookup::unlookup(taker.clone()), offer, ask, expiration) // Execution phase
+	verify { // Optional verification phase
+		assert_last_event::<T>(
+			Event::<T>::SwapProposed {
+				swap_id: 0,
+				maker: caller,
+				taker
+			}.into()
+		)
+	}
+
+	accept_swap { // Benchmark setup phase
+		let n in 1 .. MAX_SWAP_ITEMS as u32;
+
+		let caller: T::AccountId = account("Sample", 101, SEED);
+		let taker: T::AccountId = whitelisted_caller();
+
+		let proto_data = b"Je suis Data".to_vec();
+		Protos::<T>::upload(
+			RawOrigin::Signed(caller.clone()).into(),
+			Vec::<Hash256>::new(),
+			Categories::Text(TextCategories::Plain),
+			Vec::<BoundedVec<u8, _>>::new().try_into().unwrap(),
+			None,
+			UsageLicense::Closed,
+			None,
+			ProtoData::Local(proto_data.clone()),
+		)?;
+		let proto_hash = blake2_256(&proto_data);
+
+		let metadata = DefinitionMetadata::<BoundedVec<u8, _>, _> {
+			name: b"Je suis un Nom".to_vec().try_into().unwrap(),
+			currency: Currency::Native,
+			_reserved1: None,
+			_reserved2: None,
+			_reserved3: None,
+		};
+		Fragments::<T>::create(
+			RawOrigin::Signed(caller.clone()).into(),
+			proto_hash,
+			metadata.clone(),
+			FragmentPerms::EDIT | FragmentPerms::TRANSFER, // no copy permission
+			None, // non-unique
+			None
+		)?;
+		let definition_hash = blake2_128(
+			&[&proto_hash[..], &metadata.name.encode(), &metadata.currency.encode()].concat(),
+		);
+
+		Fragments::<T>::mint(
+			RawOrigin::Signed(caller.clone()).into(),
+			definition_hash,
+			FragmentBuyOptions::Quantity(n as u64),
+			None
+		)?;
+		let offer = SwapBundle::<T::AssetId> {
+			instances: (1..=n as u64).map(|edition_id| (definition_hash, edition_id, 1)).collect(),
+			protos: Vec::new(),
+			amount: None,
+		};
+		let ask = SwapBundle::<T::AssetId> { instances: Vec::new(), protos: Vec::new(), amount: None };
+		let expiration = frame_system::Pallet::<T>::block_number() + 10u32.into();
+
+		Fragments::<T>::propose_swap(
+			RawOrigin::Signed(caller.clone()).into(),
+			T::Lookup::unlookup(taker.clone()),
+			offer,
+			ask,
+			expiration
+		)?;
+
+	}: _(RawOrigin::Signed(taker.clone()), 0) // Execution phase
+	verify { // Optional verification phase
+		assert_last_event::<T>(Event::<T>::SwapAccepted { swap_id: 0 }.into());
+		assert_eq!(Inventory::<T>::get(taker, definition_hash).unwrap().len(), n as usize);
+	}
+
+	cancel_swap { // Benchmark setup phase
+		let n in 1 .. MAX_SWAP_ITEMS as u32;
+
+		let caller: T::AccountId = whitelisted_caller();
+		let taker: T::AccountId = account("Sample", 100, SEED);
+
+		let proto_data = b"Je suis Data".to_vec();
+		Protos::<T>::upload(
+			RawOrigin::Signed(caller.clone()).into(),
+			Vec::<Hash256>::new(),
+			Categories::Text(TextCategories::Plain),
+			Vec::<BoundedVec<u8, _>>::new().try_into().unwrap(),
+			None,
+			UsageLicense::Closed,
+			None,
+			ProtoData::Local(proto_data.clone()),
+		)?;
+		let proto_hash = blake2_256(&proto_data);
+
+		let metadata = DefinitionMetadata::<BoundedVec<u8, _>, _> {
+			name: b"Je suis un Nom".to_vec().try_into().unwrap(),
+			currency: Currency::Native,
+			_reserved1: None,
+			_reserved2: None,
+			_reserved3: None,
+		};
+		Fragments::<T>::create(
+			RawOrigin::Signed(caller.clone()).into(),
+			proto_hash,
+			metadata.clone(),
+			FragmentPerms::EDIT | FragmentPerms::TRANSFER, // no copy permission
+			None, // non-unique
+			None
+		)?;
+		let definition_hash = blake2_128(
+			&[&proto_hash[..], &metadata.name.encode(), &metadata.currency.encode()].concat(),
+		);
+
+		Fragments::<T>::mint(
+			RawOrigin::Signed(caller.clone()).into(),
+			definition_hash,
+			FragmentBuyOptions::Quantity(n as u64),
+			None
+		)?;
+		let offer = SwapBundle::<T::AssetId> {
+			instances: (1..=n as u64).map(|edition_id| (definition_hash, edition_id, 1)).collect(),
+			protos: Vec::new(),
+			amount: None,
+		};
+		let ask = SwapBundle::<T::AssetId> { instances: Vec::new(), protos: Vec::new(), amount: None };
+		let expiration = frame_system::Pallet::<T>::block_number() + 10u32.into();
+
+		Fragments::<T>::propose_swap(
+			RawOrigin::Signed(caller.clone()).into(),
+			T::Lookup::unlookup(taker),
+			offer,
+			ask,
+			expiration
+		)?;
+
+	}: _(RawOrigin::Signed(caller), 0) // Execution phase
+	verify { // Optional verification phase
+		assert_last_event::<T>(Event::<T>::SwapCancelled { swap_id: 0 }.into())
+	}
+
+	nest { // Benchmark setup phase
+		let caller: T::AccountId = whitelisted_caller();
+
+		let proto_data = b"Je suis Data".to_vec();
+		Protos::<T>::upload(
+			RawOrigin::Signed(caller.clone()).into(),
+			Vec::<Hash256>::new(),
+			Categories::Text(TextCategories::Plain),
+			Vec::<BoundedVec<u8, _>>::new().try_into().unwrap(),
+			None,
+			UsageLicense::Closed,
+			None,
+			ProtoData::Local(proto_data.clone()),
+		)?;
+		let proto_hash = blake2_256(&proto_data);
+
+		let metadata = DefinitionMetadata::<BoundedVec<u8, _>, _> {
+			name: b"Je suis un Nom".to_vec().try_into().unwrap(),
+			currency: Currency::Native,
+			_reserved1: None,
+			_reserved2: None,
+			_reserved3: None,
+		};
+		Fragments::<T>::create(
+			RawOrigin::Signed(caller.clone()).into(),
+			proto_hash,
+			metadata.clone(),
+			FragmentPerms::EDIT | FragmentPerms::TRANSFER,
+			None, // non-unique
+			None
+		)?;
+		let definition_hash = blake2_128(
+			&[&proto_hash[..], &metadata.name.encode(), &metadata.currency.encode()].concat(),
+		);
+
+		Fragments::<T>::mint(
+			RawOrigin::Signed(caller.clone()).into(),
+			definition_hash,
+			FragmentBuyOptions::Quantity(3),
+			None
+		)?;
+
+		// the parent is nested itself, so that the walk up to the outermost Fragment Instance is also benchmarked
+		Fragments::<T>::nest(
+			RawOrigin::Signed(caller.clone()).into(),
+			definition_hash,
+			1,
+			1,
+			(definition_hash, 2, 1)
+		)?;
+
+	}: _(RawOrigin::Signed(caller), definition_hash, 3, 1, (definition_hash, 1, 1)) // Execution phase
+	verify { // Optional verification phase
+		assert_last_event::<T>(
+			Event::<T>::Nested {
+				definition_hash,
+				fragment_id: (3, 1),
+				parent: (definition_hash, 1, 1)
+			}.into()
+		)
+	}
+
+	unnest { // Benchmark setup phase
+		let caller: T::AccountId = whitelisted_caller();
+
+		let proto_data = b"Je suis Data".to_vec();
+		Protos::<T>::upload(
+			RawOrigin::Signed(caller.clone()).into(),
+			Vec::<Hash256>::new(),
+			Categories::Text(TextCategories::Plain),
+			Vec::<BoundedVec<u8, _>>::new().try_into().unwrap(),
+			None,
+			UsageLicense::Closed,
+			None,
+			ProtoData::Local(proto_data.clone()),
+		)?;
+		let proto_hash = blake2_256(&proto_data);
+
+		let metadata = DefinitionMetadata::<BoundedVec<u8, _>, _> {
+			name: b"Je suis un Nom".to_vec().try_into().unwrap(),
+			currency: Currency::Native,
+			_reserved1: None,
+			_reserved2: None,
+			_reserved3: None,
+		};
+		Fragments::<T>::create(
+			RawOrigin::Signed(caller.clone()).into(),
+			proto_hash,
+			metadata.clone(),
+			FragmentPerms::EDIT | FragmentPerms::TRANSFER,
+			None, // non-unique
+			None
+		)?;
+		let definition_hash = blake2_128(
+			&[&proto_hash[..], &metadata.name.encode(), &metadata.currency.encode()].concat(),
+		);
+
+		Fragments::<T>::mint(
+			RawOrigin::Signed(caller.clone()).into(),
+			definition_hash,
+			FragmentBuyOptions::Quantity(3),
+			None
+		)?;
+
+		// the parent is nested itself, so that the walk up to the outermost Fragment Instance is also benchmarked
+		Fragments::<T>::nest(
+			RawOrigin::Signed(caller.clone()).into(),
+			definition_hash,
+			1,
+			1,
+			(definition_hash, 2, 1)
+		)?;
+		Fragments::<T>::nest(
+			RawOrigin::Signed(caller.clone()).into(),
+			definition_hash,
+			3,
+			1,
+			(definition_hash, 1, 1)
+		)?;
+
+	}: _(RawOrigin::Signed(caller.clone()), definition_hash, 3, 1) // Execution phase
+	verify { // Optional verification phase
+		assert_last_event::<T>(
+			Event::<T>::Unnested { definition_hash, fragment_id: (3, 1), owner: caller }.into()
+		)
+	}
+
+	as_instance { // Benchmark setup phase
+		let caller: T::AccountId = whitelisted_caller();
+
+		let proto_data = b"Je suis Data".to_vec();
+		Protos::<T>::upload(
+			RawOrigin::Signed(caller.clone()).into(),
+			Vec::<Hash256>::new(),
+			Categories::Text(TextCategories::Plain),
+			Vec::<BoundedVec<u8, _>>::new().try_into().unwrap(),
+			None,
+			UsageLicense::Closed,
+			None,
+			ProtoData::Local(proto_data.clone()),
+		)?;
+		let proto_hash = blake2_256(&proto_data);
+
+		let metadata = DefinitionMetadata::<BoundedVec<u8, _>, _> {
+			name: b"Je suis un Nom".to_vec().try_into().unwrap(),
+			currency: Currency::Native,
+			_reserved1: None,
+			_reserved2: None,
+			_reserved3: None,
+		};
+		Fragments::<T>::create(
+			RawOrigin::Signed(caller.clone()).into(),
+			proto_hash,
+			metadata.clone(),
+			FragmentPerms::EDIT | FragmentPerms::TRANSFER,
+			None, // non-unique
+			None
+		)?;
+		let definition_hash = blake2_128(
+			&[&proto_hash[..], &metadata.name.encode(), &metadata.currency.encode()].concat(),
+		);
+
+		Fragments::<T>::mint(
+			RawOrigin::Signed(caller.clone()).into(),
+			definition_hash,
+			FragmentBuyOptions::Quantity(1),
+			None
+		)?;
+
+		// the cheapest call, so that only the overhead of `as_instance()` is benchmarked
+		let call: <T as pallet_proxy::Config>::RuntimeCall =
+			frame_system::Call::<T>::remark { remark: vec![] }.into();
+
+	}: _(RawOrigin::Signed(caller), definition_hash, 1, 1, Box::new(call)) // Execution phase
+
+	register_recipe { // Benchmark setup phase
+		let n in 1 .. MAX_RECIPE_ITEMS as u32; // number of outputs
+		let caller: T::AccountId = whitelisted_caller();
+
+		let proto_data = b"Je suis Data".to_vec();
+		Protos::<T>::upload(
+			RawOrigin::Signed(caller.clone()).into(),
+			Vec::<Hash256>::new(),
+			Categories::Text(TextCategories::Plain),
+			Vec::<BoundedVec<u8, _>>::new().try_into().unwrap(),
+			None,
+			UsageLicense::Closed,
+			None,
+			ProtoData::Local(proto_data.clone()),
+		)?;
+		let proto_hash = blake2_256(&proto_data);
+
+		// every output has its own Fragment Definition, so that every output is checked
+		let mut outputs = Vec::new();
+		for i in 0..n {
+			let metadata = DefinitionMetadata::<BoundedVec<u8, _>, _> {
+				name: [&b"Je suis un Nom"[..], &i.to_le_bytes()[..]].concat().try_into().unwrap(),
+				currency: Currency::Native,
+				_reserved1: None,
+				_reserved2: None,
+				_reserved3: None,
+			};
+			Fragments::<T>::create(
+				RawOrigin::Signed(caller.clone()).into(),
+				proto_hash,
+				metadata.clone(),
+				FragmentPerms::EDIT | FragmentPerms::TRANSFER,
+				None, // non-unique
+				None
+			)?;
+			let definition_hash = blake2_128(
+				&[&proto_hash[..], &metadata.name.encode(), &metadata.currency.encode()].concat(),
+			);
+			outputs.push(RecipeOutput { definition_hash, quantity: 1, stack_amount: None });
+		}
+
+		let inputs = vec![RecipeInput {
+			definition_hash: outputs[0].definition_hash,
+			amount: RecipeInputAmount::Quantity(1),
+		}];
+
+	}: _(RawOrigin::Signed(caller.clone()), inputs, outputs, Some((Currency::Native, 1_000))) // Execution phase
+	verify { // Optional verification phase
+		assert_last_event::<T>(Event::<T>::RecipeRegistered { recipe_id: 0, owner: caller }.into())
+	}
+
+	set_recipe_enabled { // Benchmark setup phase
+		let caller: T::AccountId = whitelisted_caller();
+
+		let proto_data = b"Je suis Data".to_vec();
+		Protos::<T>::upload(
+			RawOrigin::Signed(caller.clone()).into(),
+			Vec::<Hash256>::new(),
+			Categories::Text(TextCategories::Plain),
+			Vec::<BoundedVec<u8, _>>::new().try_into().unwrap(),
+			None,
+			UsageLicense::Closed,
+			None,
+			ProtoData::Local(proto_data.clone()),
+		)?;
+		let proto_hash = blake2_256(&proto_data);
+
+		let metadata = DefinitionMetadata::<BoundedVec<u8, _>, _> {
+			name: b"Je suis un Nom".to_vec().try_into().unwrap(),
+			currency: Currency::Native,
+			_reserved1: None,
+			_reserved2: None,
+			_reserved3: None,
+		};
+		Fragments::<T>::create(
+			RawOrigin::Signed(caller.clone()).into(),
+			proto_hash,
+			metadata.clone(),
+			FragmentPerms::EDIT | FragmentPerms::TRANSFER,
+			None, // non-unique
+			None
+		)?;
+		let definition_hash = blake2_128(
+			&[&proto_hash[..], &metadata.name.encode(), &metadata.currency.encode()].concat(),
+		);
+
+		Fragments::<T>::register_recipe(
+			RawOrigin::Signed(caller.clone()).into(),
+			vec![RecipeInput { definition_hash, amount: RecipeInputAmount::Quantity(1) }],
+			vec![RecipeOutput { definition_hash, quantity: 1, stack_amount: None }],
+			None
+		)?;
+
+	}: _(RawOrigin::Signed(caller), 0, false) // Execution phase
+	verify { // Optional verification phase
+		assert_last_event::<T>(Event::<T>::RecipeEnabledChanged { recipe_id: 0, enabled: false }.into())
+	}
+
+	craft { // Benchmark setup phase
+		let n in 1 .. MAX_CRAFT_INPUTS as u32; // number of Fragment Instances used as inputs
+		let q in 1 .. MAX_CRAFT_OUTPUT_QUANTITY as u32; // number of Fragment Instances created
+		let caller: T::AccountId = whitelisted_caller();
+
+		let proto_data = b"Je suis Data".to_vec();
+		Protos::<T>::upload(
+			RawOrigin::Signed(caller.clone()).into(),
+			Vec::<Hash256>::new(),
+			Categories::Text(TextCategories::Plain),
+			Vec::<BoundedVec<u8, _>>::new().try_into().unwrap(),
+			None,
+			UsageLicense::Closed,
+			None,
+			ProtoData::Local(proto_data.clone()),
+		)?;
+		let proto_hash = blake2_256(&proto_data);
+
+		let metadata = DefinitionMetadata::<BoundedVec<u8, _>, _> {
+			name: b"Je suis un Nom".to_vec().try_into().unwrap(),
+			currency: Currency::Native,
+			_reserved1: None,
+			_reserved2: None,
+			_reserved3: None,
+		};
+		Fragments::<T>::create(
+			RawOrigin::Signed(caller.clone()).into(),
+			proto_hash,
+			metadata.clone(),
+			FragmentPerms::EDIT | FragmentPerms::TRANSFER,
+			None, // non-unique
+			None
+		)?;
+		let input_definition_hash = blake2_128(
+			&[&proto_hash[..], &metadata.name.encode(), &metadata.currency.encode()].concat(),
+		);
+
+		let metadata = DefinitionMetadata::<BoundedVec<u8, _>, _> {
+			name: b"Je suis un autre Nom".to_vec().try_into().unwrap(),
+			currency: Currency::Native,
+			_reserved1: None,
+			_reserved2: None,
+			_reserved3: None,
+		};
+		Fragments::<T>::create(
+			RawOrigin::Signed(caller.clone()).into(),
+			proto_hash,
+			metadata.clone(),
+			FragmentPerms::EDIT | FragmentPerms::TRANSFER,
+			None, // non-unique
+			None
+		)?;
+		let output_definition_hash = blake2_128(
+			&[&proto_hash[..], &metadata.name.encode(), &metadata.currency.encode()].concat(),
+		);
+
+		Fragments::<T>::mint(
+			RawOrigin::Signed(caller.clone()).into(),
+			input_definition_hash,
+			FragmentBuyOptions::Quantity(n as u64),
+			None
+		)?;
+
+		Fragments::<T>::register_recipe(
+			RawOrigin::Signed(caller.clone()).into(),
+			vec![RecipeInput {
+				definition_hash: input_definition_hash,
+				amount: RecipeInputAmount::Quantity(n as u64),
+			}],
+			vec![RecipeOutput { definition_hash: output_definition_hash, quantity: q as u64, stack_amount: None }],
+			None
+		)?;
+
+		let inputs = (1..=n as u64).map(|edition_id| (input_definition_hash, edition_id, 1)).collect::<Vec<_>>();
+
+	}: _(RawOrigin::Signed(caller.clone()), 0, inputs) // Execution phase
+	verify { // Optional verification phase
+		assert_last_event::<T>(Event::<T>::Crafted { recipe_id: 0, account_id: caller }.into())
+	}
+
+	set_unique_data_validator { // Benchmark setup phase
+		let caller: T::AccountId = whitelisted_caller();
+		let validator: T::AccountId = account("Sample", 100, SEED);
+
+		let proto_data = b"Je suis Data".to_vec();
+		Protos::<T>::upload(
+			RawOrigin::Signed(caller.clone()).into(),
+			Vec::<Hash256>::new(),
+			Categories::Text(TextCategories::Plain),
+			Vec::<BoundedVec<u8, _>>::new().try_into().unwrap(),
+			None,
+			UsageLicense::Closed,
+			None,
+			ProtoData::Local(proto_data.clone()),
+		)?;
+		let proto_hash = blake2_256(&proto_data);
+
+		let metadata = DefinitionMetadata::<BoundedVec<u8, _>, _> {
+			name: b"Je suis un Nom".to_vec().try_into().unwrap(),
+			currency: Currency::Native,
+			_reserved1: None,
+			_reserved2: None,
+			_reserved3: None,
+		};
+		Fragments::<T>::create(
+			RawOrigin::Signed(caller.clone()).into(),
+			proto_hash,
+			metadata.clone(),
+			FragmentPerms::EDIT | FragmentPerms::TRANSFER,
+			Some(UniqueOptions { mutable: false, _reserved1: None, _reserved2: None, _reserved3: None }),
+			None
+		)?;
+		let definition_hash = blake2_128(
+			&[&proto_hash[..], &metadata.name.encode(), &metadata.currency.encode()].concat(),
+		);
+
+	}: _(RawOrigin::Signed(caller), definition_hash, Some(validator.clone())) // Execution phase
+	verify { // Optional verification phase
+		assert_last_event::<T>(
+			Event::<T>::UniqueDataValidatorChanged {
+				definition_hash,
+				validator: Some(validator)
+			}.into()
+		)
+	}
+
+	update_unique_data { // Benchmark setup phase
+		let d in 1 .. MAX_DATA_LENGTH; // `data`'s length
+		let caller: T::AccountId = whitelisted_caller();
+
+		let proto_data = b"Je suis Data".to_vec();
+		Protos::<T>::upload(
+			RawOrigin::Signed(caller.clone()).into(),
+			Vec::<Hash256>::new(),
+			Categories::Text(TextCategories::Plain),
+			Vec::<BoundedVec<u8, _>>::new().try_into().unwrap(),
+			None,
+			UsageLicense::Closed,
+			None,
+			ProtoData::Local(proto_data.clone()),
+		)?;
+		let proto_hash = blake2_256(&proto_data);
+
+		let metadata = DefinitionMetadata::<BoundedVec<u8, _>, _> {
+			name: b"Je suis un Nom".to_vec().try_into().unwrap(),
+			currency: Currency::Native,
+			_reserved1: None,
+			_reserved2: None,
+			_reserved3: None,
+		};
+		Fragments::<T>::create(
+			RawOrigin::Signed(caller.clone()).into(),
+			proto_hash,
+			metadata.clone(),
+			FragmentPerms::EDIT | FragmentPerms::TRANSFER,
+			Some(UniqueOptions { mutable: true, _reserved1: None, _reserved2: None, _reserved3: None }),
+			None
+		)?;
+		let definition_hash = blake2_128(
+			&[&proto_hash[..], &metadata.name.encode(), &metadata.currency.encode()].concat(),
+		);
+
+		Fragments::<T>::mint(
+			RawOrigin::Signed(caller.clone()).into(),
+			definition_hash,
+			FragmentBuyOptions::UniqueData(b"Je suis Data Unique".to_vec()),
+			None
+		)?;
+
+		let data = vec![7u8; d as usize];
+		let data_hash = blake2_256(&data);
+
+	}: _(RawOrigin::Signed(caller), definition_hash, 1, 1, data) // Execution phase
+	verify { // Optional verification phase
+		assert_last_event::<T>(
+			Event::<T>::UniqueDataUpdated {
+				definition_hash,
+				fragment_id: (1, 1),
+				data_hash
+			}.into()
+		)
+	}
+
+	set_metadata_key_policy { // Benchmark setup phase
+		let caller: T::AccountId = whitelisted_caller();
+
+		let proto_data = b"Je suis Data".to_vec();
+		Protos::<T>::upload(
+			RawOrigin::Signed(caller.clone()).into(),
+			Vec::<Hash256>::new(),
+			Categories::Text(TextCategories::Plain),
+			Vec::<BoundedVec<u8, _>>::new().try_into().unwrap(),
+			None,
+			UsageLicense::Closed,
+			None,
+			ProtoData::Local(proto_data.clone()),
+		)?;
+		let proto_hash = blake2_256(&proto_data);
+
+		let metadata = DefinitionMetadata::<BoundedVec<u8, _>, _> {
+			name: b"Je suis un Nom".to_vec().try_into().unwrap(),
+			currency: Currency::Native,
+			_reserved1: None,
+			_reserved2: None,
+			_reserved3: None,
+		};
+		Fragments::<T>::create(
+			RawOrigin::Signed(caller.clone()).into(),
+			proto_hash,
+			metadata.clone(),
+			FragmentPerms::EDIT | FragmentPerms::TRANSFER,
+			None,
+			None
+		)?;
+		let definition_hash = blake2_128(
+			&[&proto_hash[..], &metadata.name.encode(), &metadata.currency.encode()].concat(),
+		);
+
+		let metadata_key: BoundedVec<u8, _> = b"stats".to_vec().try_into().unwrap();
+
+	}: _(RawOrigin::Signed(caller), definition_hash, metadata_key, Some(MetadataKeyPolicy::Creator)) // Execution phase
+	verify { // Optional verification phase
+		assert_last_event::<T>(
+			Event::<T>::MetadataKeyPolicyChanged {
+				definition_hash,
+				metadata_key: b"stats".to_vec(),
+				policy: Some(MetadataKeyPolicy::Creator)
+			}.into()
+		)
+	}
+
+	set_attribute_schema { // Benchmark setup phase
+		let n in 1 .. (MAX_ATTRIBUTES as u32);
+		let caller: T::AccountId = whitelisted_caller();
+
+		let proto_data = b"Je suis Data".to_vec();
+		Protos::<T>::upload(
+			RawOrigin::Signed(caller.clone()).into(),
+			Vec::<Hash256>::new(),
+			Categories::Text(TextCategories::Plain),
+			Vec::<BoundedVec<u8, _>>::new().try_into().unwrap(),
+			None,
+			UsageLicense::Closed,
+			None,
+			ProtoData::Local(proto_data.clone()),
+		)?;
+		let proto_hash = blake2_256(&proto_data);
+
+		let metadata = DefinitionMetadata::<BoundedVec<u8, _>, _> {
+			name: b"Je suis un Nom".to_vec().try_into().unwrap(),
+			currency: Currency::Native,
+			_reserved1: None,
+			_reserved2: None,
+			_reserved3: None,
+		};
+		Fragments::<T>::create(
+			RawOrigin::Signed(caller.clone()).into(),
+			proto_hash,
+			metadata.clone(),
+			FragmentPerms::EDIT | FragmentPerms::TRANSFER,
+			None,
+			None
+		)?;
+		let definition_hash = blake2_128(
+			&[&proto_hash[..], &metadata.name.encode(), &metadata.currency.encode()].concat(),
+		);
+
+		let attribute_names = (0 .. n).map(|i| [&b"attribute"[..], &i.encode()[..]].concat()).collect::<Vec<Vec<u8>>>();
+		// SCALE encoding of a `protos::traits::Trait` whose records are `attribute_names` (the records have the type `VariableType::String`)
+		let trait_data = (
+			b"Je suis un Trait".to_vec(),
+			attribute_names.iter().map(|name| (
+				name.clone(),
+				vec![VariableTypeInfo { type_: VariableType::String, default: None }],
+			)).collect::<Vec<_>>(),
+		).encode();
+		Protos::<T>::upload(
+			RawOrigin::Signed(caller.clone()).into(),
+			Vec::<Hash256>::new(),
+			Categories::Trait(None),
+			Vec::<BoundedVec<u8, _>>::new().try_into().unwrap(),
+			None,
+			UsageLicense::Closed,
+			None,
+			ProtoData::Local(trait_data.clone()),
+		)?;
+
+		let attributes = attribute_names.into_iter().map(|name| (name, AttributeType::String)).collect::<Vec<_>>();
+
+	}: _(RawOrigin::Signed(caller), definition_hash, trait_data.clone(), attributes) // Execution phase
+	verify { // Optional verification phase
+		assert_last_event::<T>(
+			Event::<T>::AttributeSchemaChanged {
+				definition_hash,
+				trait_proto: blake2_256(&trait_data),
+			}.into()
+		)
+	}
+
+	set_instance_attributes { // Benchmark setup phase
+		let n in 1 .. (MAX_ATTRIBUTES as u32);
+		let caller: T::AccountId = whitelisted_caller();
+
+		let proto_data = b"Je suis Data".to_vec();
+		Protos::<T>::upload(
+			RawOrigin::Signed(caller.clone()).into(),
+			Vec::<Hash256>::new(),
+			Categories::Text(TextCategories::Plain),
+			Vec::<BoundedVec<u8, _>>::new().try_into().unwrap(),
+			None,
+			UsageLicense::Closed,
+			None,
+			ProtoData::Local(proto_data.clone()),
+		)?;
+		let proto_hash = blake2_256(&proto_data);
+
+		let metadata = DefinitionMetadata::<BoundedVec<u8, _>, _> {
+			name: b"Je suis un Nom".to_vec().try_into().unwrap(),
+			currency: Currency::Native,
+			_reserved1: None,
+			_reserved2: None,
+			_reserved3: None,
+		};
+		Fragments::<T>::create(
+			RawOrigin::Signed(caller.clone()).into(),
+			proto_hash,
+			metadata.clone(),
+			FragmentPerms::EDIT | FragmentPerms::TRANSFER,
+			None,
+			None
+		)?;
+		let definition_hash = blake2_128(
+			&[&proto_hash[..], &metadata.name.encode(), &metadata.currency.encode()].concat(),
+		);
+
+		let attribute_names = (0 .. n).map(|i| [&b"attribute"[..], &i.encode()[..]].concat()).collect::<Vec<Vec<u8>>>();
+		// SCALE encoding of a `protos::traits::Trait` whose records are `attribute_names` (the records have the type `VariableType::String`)
+		let trait_data = (
+			b"Je suis un Trait".to_vec(),
+			attribute_names.iter().map(|name| (
+				name.clone(),
+				vec![VariableTypeInfo { type_: VariableType::String, default: None }],
+			)).collect::<Vec<_>>(),
+		).encode();
+		Protos::<T>::upload(
+			RawOrigin::Signed(caller.clone()).into(),
+			Vec::<Hash256>::new(),
+			Categories::Trait(None),
+			Vec::<BoundedVec<u8, _>>::new().try_into().unwrap(),
+			None,
+			UsageLicense::Closed,
+			None,
+			ProtoData::Local(trait_data.clone()),
+		)?;
+
+		Fragments::<T>::set_attribute_schema(
+			RawOrigin::Signed(caller.clone()).into(),
+			definition_hash,
+			trait_data,
+			attribute_names.iter().map(|name| (name.clone(), AttributeType::String)).collect(),
+		)?;
+
+		// the attributes that are reserved to the creator are the most expensive to authorize
+		for name in attribute_names.iter() {
+			Fragments::<T>::set_metadata_key_policy(
+				RawOrigin::Signed(caller.clone()).into(),
+				definition_hash,
+				name.clone().try_into().unwrap(),
+				Some(MetadataKeyPolicy::Creator),
+			)?;
+		}
+
+		Fragments::<T>::mint(
+			RawOrigin::Signed(caller.clone()).into(),
+			definition_hash,
+			FragmentBuyOptions::Quantity(1),
+			None
+		)?;
+
+		let attributes = attribute_names
+			.into_iter()
+			.map(|name| (name, Some(AttributeValue::String(vec![b'a'; MAX_ATTRIBUTE_STRING_LENGTH]))))
+			.collect::<Vec<_>>();
+
+	}: _(RawOrigin::Signed(caller), definition_hash, 1, 1, attributes) // Execution phase
+	verify { // Optional verification phase
+		assert_last_event::<T>(
+			Event::<T>::InstanceAttributesUpdated {
+				definition_hash,
+				fragment_id: (1, 1),
+			}.into()
+		)
+	}
+
+	transfer_definition { // Benchmark setup phase
+		let caller: T::AccountId = whitelisted_caller();
+		let new_owner: T::AccountId = account("Sample", 100, SEED);
+
+		let proto_data = b"Je suis Data".to_vec();
+		Protos::<T>::upload(
+			RawOrigin::Signed(caller.clone()).into(),
+			Vec::<Hash256>::new(),
+			Categories::Text(TextCategories::Plain),
+			Vec::<BoundedVec<u8, _>>::new().try_into().unwrap(),
+			None,
+			UsageLicense::Closed,
+			None,
+			ProtoData::Local(proto_data.clone()),
+		)?;
+		let proto_hash = blake2_256(&proto_data);
+
+		let metadata = DefinitionMetadata::<BoundedVec<u8, _>, _> {
+			name: b"Je suis un Nom".to_vec().try_into().unwrap(),
+			currency: Currency::Native,
+			_reserved1: None,
+			_reserved2: None,
+			_reserved3: None,
+		};
+		Fragments::<T>::create(
+			RawOrigin::Signed(caller.clone()).into(),
+			proto_hash,
+			metadata.clone(),
+			FragmentPerms::EDIT | FragmentPerms::TRANSFER,
+			None,
+			None
+		)?;
+		let definition_hash = blake2_128(
+			&[&proto_hash[..], &metadata.name.encode(), &metadata.currency.encode()].concat(),
+		);
+
+	}: _(RawOrigin::Signed(caller), definition_hash, T::Lookup::unlookup(new_owner.clone())) // Execution phase
+	verify { // Optional verification phase
+		assert_last_event::<T>(Event::<T>::DefinitionTransferred { definition_hash, owner: new_owner }.into())
+	}
 
 	impl_benchmark_test_suite!(Fragments, crate::mock::new_test_ext(), crate::mock::Test);
 }
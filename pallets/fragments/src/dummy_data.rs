@@ -96,6 +96,8 @@ pub struct Publish {
 	/// If the Fragment instance represents a **stack of stackable items** (for e.g gold coins or arrows - https://runescape.fandom.com/wiki/Stackable_items),
 	/// the **number of items** to **top up** in the **stack of stackable items**
 	pub stack_amount: Option<u64>,
+
+	pub phases: Vec<SalePhase<u64>>,
 }
 
 #[derive(Clone)]
@@ -233,6 +235,7 @@ impl DummyData {
 			quantity: None,
 			expires: None,
 			stack_amount: None,
+			phases: Vec::new(),
 		};
 		let publish_with_max_supply = Publish {
 			definition: Definition {
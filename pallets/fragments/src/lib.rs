@@ -43,31 +43,42 @@ mod benchmarking;
 #[allow(missing_docs)]
 mod weights;
 
+pub mod migration;
+
 use codec::{Compact, Decode, Encode};
 pub use pallet::*;
 use sp_core::crypto::UncheckedFrom;
 pub use sp_fragnova::fragments::{
-	Currency, DefinitionMetadata, FragmentDefinition, FragmentInstance, InstanceUnit, UniqueOptions,
+	AttributeSchema, AttributeType, AttributeValue, Currency, DefinitionMetadata,
+	FragmentDefinition, FragmentInstance, InstanceUnit, UniqueOptions,
 };
-use sp_fragnova::{Hash128, Hash256};
+pub use sp_fragnova::merkle::MerkleProof;
+use sp_fragnova::{merkle, Hash128, Hash256};
 use sp_io::{
-	hashing::{blake2_128, blake2_256},
+	hashing::{blake2_128, blake2_256, twox_64},
 	transaction_index,
 };
-use sp_std::{collections::btree_map::BTreeMap, vec::Vec};
+use sp_std::{
+	collections::{btree_map::BTreeMap, btree_set::BTreeSet},
+	vec::Vec,
+};
 pub use weights::WeightInfo;
 
 #[cfg(feature = "std")]
 use serde::{Deserialize, Serialize};
 
-use protos::permissions::FragmentPerms;
+use protos::{
+	categories::Categories,
+	permissions::FragmentPerms,
+	traits::{Trait, VariableType},
+};
 
 use frame_support::dispatch::DispatchResult;
-use sp_runtime::traits::StaticLookup;
+use sp_runtime::traits::{One, StaticLookup, Zero};
 
 use frame_support::traits::tokens::{fungible, fungibles, ExistenceRequirement};
 
-use sp_runtime::SaturatedConversion;
+use sp_runtime::{Perbill, SaturatedConversion};
 
 use scale_info::prelude::{
 	format,
@@ -75,6 +86,10 @@ use scale_info::prelude::{
 };
 use serde_json::{json, Map, Value};
 
+/// **Name** of the **Cluster Role** whose members are **allowed to withdraw the sale proceeds** of the Fragment Definitions
+/// whose Proto-Fragment belongs to the Cluster (see `withdraw_proceeds()`)
+pub const PROCEEDS_ROLE_NAME: &[u8] = b"Treasurer";
+
 /// **Data Type** used to **Query and Filter for Fragment Definitions**
 #[derive(Encode, Decode, Clone, scale_info::TypeInfo)]
 #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
@@ -91,8 +106,20 @@ pub struct GetDefinitionsParams<TAccountId, TString> {
 	pub owner: Option<TAccountId>,
 	/// Whether to return the owner(s) of all the returned FDs
 	pub return_owners: bool,
-	// pub categories: Vec<Categories>,
-	// pub tags: Vec<TString>,
+	/// List of categories that the Proto-Fragment of the FD must have (see `pallet_protos::Pallet::filter_proto()`)
+	pub categories: Vec<Categories>,
+	/// List of tags that the Proto-Fragment of the FD must have
+	pub tags: Vec<TString>,
+	/// Currency of the FD (*optional*). It is either `"native"` or the **hex-encoded SCALE-encoded Asset ID** of a custom currency (same format as `get_vault_balance()`)
+	pub currency: Option<TString>,
+	/// Whether the FD should be on sale or not (*optional*)
+	pub published: Option<bool>,
+	/// Minimum price of the sale of the FD (*optional*). FDs that are not on sale are not returned if this is set.
+	pub min_price: Option<u128>,
+	/// Maximum price of the sale of the FD (*optional*). FDs that are not on sale are not returned if this is set.
+	pub max_price: Option<u128>,
+	/// Whether the sale of the FD should have no Fragment Instances left to buy or not (*optional*). FDs that are not on sale are not returned if this is set.
+	pub sold_out: Option<bool>,
 }
 
 #[cfg(test)]
@@ -105,6 +132,13 @@ impl<TAccountId, TString> Default for GetDefinitionsParams<TAccountId, TString>
 			metadata_keys: Default::default(),
 			owner: None,
 			return_owners: false,
+			categories: Default::default(),
+			tags: Default::default(),
+			currency: None,
+			published: None,
+			min_price: None,
+			max_price: None,
+			sold_out: None,
 		}
 	}
 }
@@ -127,6 +161,8 @@ pub struct GetInstancesParams<TAccountId, TString> {
 	pub owner: Option<TAccountId>,
 	/// Whether to only return FIs that have a Copy ID of 1
 	pub only_return_first_copies: bool,
+	/// Whether to also return the FIs that are nested in each returned FI (recursively, see `nest()`)
+	pub return_nested: bool,
 }
 #[cfg(test)]
 impl<TAccountId, TString: Default> Default for GetInstancesParams<TAccountId, TString> {
@@ -139,6 +175,7 @@ impl<TAccountId, TString: Default> Default for GetInstancesParams<TAccountId, TS
 			metadata_keys: Default::default(),
 			owner: None,
 			only_return_first_copies: Default::default(),
+			return_nested: Default::default(),
 		}
 	}
 }
@@ -155,6 +192,92 @@ pub struct GetInstanceOwnerParams<TString> {
 	pub copy_id: InstanceUnit,
 }
 
+/// **Data Type** used to **Query the current Sale Phase of a Fragment Definition that is on sale**
+#[derive(Encode, Decode, Clone, scale_info::TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct GetSalePhaseParams<TAccountId, TString> {
+	/// Fragment Definition that is on sale
+	pub definition_hash: TString,
+	/// Account whose number of bought Fragment Instances (and remaining allowance) in the current Sale Phase should also be returned
+	pub account: Option<TAccountId>,
+	/// Allowance of `account` in the allowlist of the current Sale Phase (if the current Sale Phase has an allowlist)
+	pub allowance: Option<InstanceUnit>,
+}
+
+/// **Data Type** used to **Query the crafting recipes** (see `register_recipe()`)
+#[derive(Encode, Decode, Clone, scale_info::TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct GetRecipesParams<TAccountId> {
+	/// Number of recipes to skip
+	pub from: u64,
+	/// Number of recipes to retrieve
+	pub limit: u64,
+	/// Owner of the recipes
+	pub owner: Option<TAccountId>,
+	/// Whether to only return the recipes that are enabled
+	pub only_enabled: bool,
+}
+
+/// Maximum number of Sale Phases that a sale of a Fragment Definition can have
+pub const MAX_SALE_PHASES: usize = 16;
+
+/// Struct **representing** a **phase of a sale of a Fragment Definition**.
+///
+/// A Sale Phase starts at the block number `start` and ends when the next Sale Phase starts (or when the sale ends).
+#[derive(Encode, Decode, Clone, scale_info::TypeInfo, Debug, PartialEq)]
+pub struct SalePhase<TBlockNum> {
+	/// Block number that the Sale Phase starts at
+	pub start: TBlockNum,
+	/// **Fee** that is **needed to be paid** to create a **single Fragment Instance** during the Sale Phase
+	pub price: Compact<u128>,
+	/// **Merkle Root** of the **allowlist** of the Sale Phase (*optional*).
+	///
+	/// The Merkle Tree is computed with `sp_fragnova::merkle::merkle_root()`, and each of its leaves is the `blake2_256` hash of the SCALE-encoded tuple `(account_id, allowance)`,
+	/// where `allowance` is the **maximum number of Fragment Instances** that `account_id` can buy during the Sale Phase.
+	/// If set, only the accounts in the allowlist can buy during the Sale Phase (see `AllowlistProof`).
+	pub allowlist: Option<Hash256>,
+	/// **Maximum number of Fragment Instances** that a **single account** can buy during the Sale Phase (*optional*)
+	pub max_per_account: Option<Compact<InstanceUnit>>,
+}
+
+/// Struct **representing** a **proof** that an **account** is in the **allowlist** of a **Sale Phase**
+#[derive(Encode, Decode, Clone, scale_info::TypeInfo, Debug, PartialEq)]
+pub struct AllowlistProof {
+	/// **Maximum number of Fragment Instances** that the account can buy during the Sale Phase
+	pub allowance: InstanceUnit,
+	/// **Merkle Proof** of the leaf `blake2_256((account_id, allowance).encode())` (see `sp_fragnova::merkle::merkle_proof()`)
+	pub proof: MerkleProof,
+}
+
+/// Maximum proportion of the block weight that `on_initialize()` uses to sweep expired Fragment Instances
+pub const EXPIRATION_SWEEP_RATIO: Perbill = Perbill::from_percent(5);
+
+/// Maximum number of recipients of `mint_to_many()`
+pub const MAX_MINT_RECIPIENTS: usize = 100;
+
+/// Maximum length of a Merkle Proof (i.e maximum depth of a Merkle Tree) that is accepted by `claim()`
+pub const MAX_MERKLE_PROOF_LENGTH: usize = 32;
+
+/// Struct **representing** a **claim campaign** (i.e an airdrop) of a **Fragment Definition**.
+///
+/// During a claim campaign, every account in the Merkle Tree whose Merkle Root is `merkle_root` can claim (only once)
+/// its quantity of Fragment Instances for free (see `claim()`).
+#[derive(Encode, Decode, Clone, scale_info::TypeInfo, Debug, PartialEq)]
+pub struct ClaimCampaign<TBlockNum> {
+	/// **Merkle Root** of the recipients of the claim campaign.
+	///
+	/// The Merkle Tree is computed with `sp_fragnova::merkle::merkle_root()`, and each of its leaves is the `blake2_256` hash of the SCALE-encoded tuple `(account_id, quantity)`,
+	/// where `quantity` is the **number of Fragment Instances** that `account_id` can claim.
+	pub merkle_root: Hash256,
+	/// Block number that the claim campaign ends at (*optional*)
+	pub expiration: Option<TBlockNum>,
+	/// Block number that the claimed Fragment Instances expire at (*optional*)
+	pub instance_expiration: Option<TBlockNum>,
+	/// If the Fragment instance represents a **stack of stackable items** (for e.g gold coins or arrows - https://runescape.fandom.com/wiki/Stackable_items),
+	/// the **number of items** to **top up** in the **stack of stackable items**
+	pub stack_amount: Option<Compact<InstanceUnit>>,
+}
+
 /// Struct **representing** a sale of the **Fragment Definition** .
 ///
 /// Note: When a Fragment Definition is put on sale, users can create Fragment Instances from it for a fee.
@@ -175,8 +298,13 @@ pub struct PublishingData<TBlockNum> {
 	/// If the Fragment instance represents a **stack of stackable items** (for e.g gold coins or arrows - https://runescape.fandom.com/wiki/Stackable_items),
 	/// the **number of items** to **top up** in the **stack of stackable items**
 	pub stack_amount: Option<Compact<InstanceUnit>>,
-	/// Reserved for future use
-	pub _reserved1: Option<()>,
+	/// **Ordered list of Sale Phases** of the sale (sorted by their starting block number).
+	///
+	/// If it is empty, the sale starts immediately and every Fragment Instance costs `price`.
+	/// Otherwise, nothing can be bought before the first Sale Phase starts and the price of the current Sale Phase is used instead of `price`.
+	///
+	/// Note: This field replaces a field that was reserved for future use (an empty `Vec` has the same encoding as `None`)
+	pub phases: Vec<SalePhase<TBlockNum>>,
 	/// Reserved for future use
 	pub _reserved2: Option<()>,
 	/// Reserved for future use
@@ -210,6 +338,143 @@ pub struct SecondarySaleData<TAccountId, TBlockNum> {
 	pub _reserved3: Option<()>,
 }
 
+/// Struct **representing** the **rental** of a **Fragment Instance** (see `rent()`)
+#[derive(Encode, Decode, Clone, scale_info::TypeInfo, Debug, PartialEq)]
+pub struct Rental<TAccountId, TBlockNum> {
+	/// Account ID that has the use rights of the Fragment Instance during the rental
+	pub renter: TAccountId,
+	/// Block number that the rental ends at (i.e the block number that the use rights return to the owner)
+	pub until: TBlockNum,
+}
+
+/// Maximum number of items (i.e Fragment Instances and Proto-Fragments) on each side of a swap (see `propose_swap()`)
+pub const MAX_SWAP_ITEMS: usize = 32;
+
+/// Maximum number of Fragment Instances that can be nested directly in a Fragment Instance (see `nest()`)
+pub const MAX_NESTED_INSTANCES: usize = 16;
+
+/// Maximum number of levels of Fragment Instances that can be nested below a Fragment Instance that is not nested itself (see `nest()`)
+pub const MAX_NESTING_DEPTH: u32 = 4;
+
+/// Struct **representing** one **side of a swap** (see `propose_swap()`), i.e a bundle of assets owned by a single account
+#[derive(Encode, Decode, Clone, scale_info::TypeInfo, Debug, PartialEq)]
+pub struct SwapBundle<TFungibleAsset> {
+	/// **Fragment Instances** of the bundle (each represented as a tuple that contains the Fragment Instance's Fragment Definition ID, Edition ID and Copy ID)
+	pub instances: Vec<(Hash128, InstanceUnit, InstanceUnit)>,
+	/// **Proto-Fragments** of the bundle
+	pub protos: Vec<Hash256>,
+	/// **Currency** and **amount of the currency** of the bundle (*optional*)
+	pub amount: Option<(Currency<TFungibleAsset>, u128)>,
+}
+
+impl<TFungibleAsset> SwapBundle<TFungibleAsset> {
+	/// Whether the bundle does not contain any asset
+	pub fn is_empty(&self) -> bool {
+		self.instances.is_empty() && self.protos.is_empty() && self.amount.is_none()
+	}
+}
+
+/// Struct **representing** a **pending swap** between two accounts (see `propose_swap()`)
+#[derive(Encode, Decode, Clone, scale_info::TypeInfo, Debug, PartialEq)]
+pub struct Swap<TAccountId, TBlockNum, TFungibleAsset> {
+	/// Account ID that proposed the swap
+	pub maker: TAccountId,
+	/// Account ID that can accept the swap
+	pub taker: TAccountId,
+	/// Assets that `maker` gives to `taker`
+	pub offer: SwapBundle<TFungibleAsset>,
+	/// Assets that `taker` gives to `maker`
+	pub ask: SwapBundle<TFungibleAsset>,
+	/// Block number that the swap expires at
+	pub expiration: TBlockNum,
+}
+
+/// Maximum weight (i.e gas limit) that the validator of the unique data of a Fragment Definition can consume
+/// every time a Fragment Instance with unique data is created (see `set_unique_data_validator()`)
+pub const MAX_UNIQUE_DATA_VALIDATOR_WEIGHT: u64 = 5_000_000_000;
+
+/// Enum **representing** who is **allowed** to **write** a **metadata key** of a **Fragment Definition** and of its **Fragment Instances**
+/// (see `set_metadata_key_policy()`)
+#[derive(Encode, Decode, Clone, scale_info::TypeInfo, Debug, PartialEq, Eq)]
+pub enum MetadataKeyPolicy {
+	/// The **owner of the Fragment Instance** (if it has the permission `FragmentPerms::EDIT`).
+	/// The metadata of the Fragment Definition can be written by its owner.
+	///
+	/// This is the policy of every metadata key that does not have an explicit policy.
+	Owner,
+	/// Only the **owner of the Fragment Definition**
+	Creator,
+	/// Only the **members of the Cluster `cluster_id`** that have the **Role `role_name`**
+	ClusterRole {
+		/// ID of the Cluster
+		cluster_id: Hash128,
+		/// Name of the Role
+		role_name: Vec<u8>,
+	},
+}
+
+/// Maximum number of previous unique data hashes that are kept for an Edition (see `update_unique_data()`)
+pub const MAX_UNIQUE_DATA_HISTORY: usize = 32;
+
+/// Maximum number of attributes in the attribute schema of a Fragment Definition (see `set_attribute_schema()`)
+pub const MAX_ATTRIBUTES: usize = 32;
+
+/// Maximum length (in bytes) of a string attribute value of a Fragment Instance (see `set_instance_attributes()`)
+pub const MAX_ATTRIBUTE_STRING_LENGTH: usize = 256;
+
+/// Maximum number of inputs (and of outputs) of a crafting recipe (see `register_recipe()`)
+pub const MAX_RECIPE_ITEMS: usize = 16;
+
+/// Maximum number of Fragment Instances that can be used as inputs in a single craft (see `craft()`)
+pub const MAX_CRAFT_INPUTS: usize = 32;
+
+/// Maximum total number of Fragment Instances that a crafting recipe can create (see `craft()`)
+pub const MAX_CRAFT_OUTPUT_QUANTITY: InstanceUnit = 100;
+
+/// Enum **representing** how much of a **Fragment Definition** an **input of a crafting recipe** requires
+#[derive(Encode, Decode, Clone, scale_info::TypeInfo, Debug, PartialEq, Eq)]
+pub enum RecipeInputAmount {
+	/// **Number of Fragment Instances** of the Fragment Definition that are **burned**
+	Quantity(InstanceUnit),
+	/// **Number of items** that are **consumed** from the **stacks of stackable items** represented by Fragment Instances of the Fragment Definition
+	StackAmount(InstanceUnit),
+}
+
+/// Struct **representing** an **input requirement** of a **crafting recipe**
+#[derive(Encode, Decode, Clone, scale_info::TypeInfo, Debug, PartialEq, Eq)]
+pub struct RecipeInput {
+	/// Fragment Definition of the Fragment Instances that are used as input
+	pub definition_hash: Hash128,
+	/// How much of the Fragment Definition is required
+	pub amount: RecipeInputAmount,
+}
+
+/// Struct **representing** an **output** of a **crafting recipe**
+#[derive(Encode, Decode, Clone, scale_info::TypeInfo, Debug, PartialEq, Eq)]
+pub struct RecipeOutput {
+	/// Fragment Definition of the Fragment Instances that are created
+	pub definition_hash: Hash128,
+	/// Number of Fragment Instances that are created
+	pub quantity: InstanceUnit,
+	/// If the created Fragment Instances represent a **stack of stackable items**, the **number of items** in each stack (*optional*)
+	pub stack_amount: Option<InstanceUnit>,
+}
+
+/// Struct **representing** a **crafting recipe** (see `register_recipe()`)
+#[derive(Encode, Decode, Clone, scale_info::TypeInfo, Debug, PartialEq)]
+pub struct Recipe<TAccountId, TFungibleAsset> {
+	/// Account ID that registered the recipe (and that receives its fee)
+	pub owner: TAccountId,
+	/// Input requirements of the recipe
+	pub inputs: Vec<RecipeInput>,
+	/// Outputs of the recipe
+	pub outputs: Vec<RecipeOutput>,
+	/// **Currency** and **amount of the currency** that is paid to `owner` every time the recipe is crafted (*optional*)
+	pub fee: Option<(Currency<TFungibleAsset>, u128)>,
+	/// Whether the recipe can be crafted
+	pub enabled: bool,
+}
+
 /// Enum indicating the different ways that one can purchase a Fragment Instance
 #[derive(Encode, Decode, Clone, scale_info::TypeInfo, Debug, PartialEq, Eq)]
 pub enum SecondarySaleBuyOptions {
@@ -234,25 +499,33 @@ pub enum FragmentBuyOptions {
 #[frame_support::pallet]
 pub mod pallet {
 	use super::*;
-	use frame_support::{pallet_prelude::*, Twox64Concat};
+	use frame_support::{
+		dispatch::GetDispatchInfo, pallet_prelude::*, traits::ReservableCurrency, Twox64Concat,
+	};
 	use frame_system::pallet_prelude::*;
 	use pallet_detach::{
 		DetachCollection, DetachHash, DetachRequest, DetachRequests, DetachedHashes,
 		SupportedChains,
 	};
-	use pallet_protos::{MetaKeys, MetaKeysIndex, Proto, ProtoOwner, Protos, ProtosByOwner};
+	use pallet_protos::{MetaKeys, MetaKeysIndex, Proto, ProtoOwner, Protos};
+	use sp_runtime::traits::Dispatchable;
+	use sp_std::boxed::Box;
 
 	/// Configure the pallet by specifying the parameters and types on which it depends.
 	#[pallet::config]
-	pub trait Config: frame_system::Config + pallet_protos::Config {
+	pub trait Config: frame_system::Config + pallet_protos::Config + pallet_proxy::Config {
 		/// Because this pallet emits events, it depends on the runtime's definition of an event.
 		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
 		/// Weight functions needed for pallet_fragments.
 		type WeightInfo: WeightInfo;
 	}
 
+	/// The current storage version of this pallet
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(3);
+
 	#[pallet::pallet]
 	#[pallet::generate_store(pub(super) trait Store)]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	#[pallet::without_storage_info]
 	pub struct Pallet<T>(_);
 
@@ -274,6 +547,13 @@ pub mod pallet {
 		FragmentDefinition<Vec<u8>, T::AssetId, T::AccountId, T::BlockNumber>,
 	>;
 
+	/// **StorageMap** that maps an **Account ID**
+	/// to the
+	/// **list of Fragment Definitions that are owned by the aforementioned Account ID** (see `transfer_definition()`)
+	#[pallet::storage]
+	pub type DefinitionsByOwner<T: Config> =
+		StorageMap<_, Twox64Concat, T::AccountId, Vec<Hash128>>;
+
 	/// **StorageMap** that maps a **Fragment Definition ID**
 	/// to a
 	/// ***PublishingData* struct (of the aforementioned Fragment Definition)**
@@ -281,6 +561,59 @@ pub mod pallet {
 	pub type Publishing<T: Config> =
 		StorageMap<_, Identity, Hash128, PublishingData<T::BlockNumber>>;
 
+	/// **StorageMap** that maps a **Fragment Definition ID**
+	/// to the
+	/// **number of sales of the aforementioned Fragment Definition that have ended** (i.e the index of its current sale)
+	#[pallet::storage]
+	pub type SaleIndex<T: Config> = StorageMap<_, Identity, Hash128, u32, ValueQuery>;
+
+	/// **StorageNMap** that maps a
+	/// **Fragment Definition ID, an index of one of its sales (see `SaleIndex`), an index of a Sale Phase of the aforementioned sale and an Account ID**
+	/// to the
+	/// **number of Fragment Instances that the Account ID has bought during the aforementioned Sale Phase**
+	///
+	/// Footnotes:
+	///
+	/// The entries of a sale are not removed when the sale ends (see `unpublish()`),
+	/// they are no longer read since the next sale of the Fragment Definition has another index.
+	#[pallet::storage]
+	pub type PhaseMints<T: Config> = StorageNMap<
+		_,
+		(
+			// Definition Hash
+			storage::Key<Identity, Hash128>,
+			// Sale index
+			storage::Key<Identity, u32>,
+			// Sale Phase index
+			storage::Key<Identity, u32>,
+			// Buyer
+			storage::Key<Blake2_128Concat, T::AccountId>,
+		),
+		Compact<InstanceUnit>,
+	>;
+
+	/// **StorageMap** that maps a **Fragment Definition ID**
+	/// to its **current claim campaign** (see `ClaimCampaign`)
+	#[pallet::storage]
+	pub type ClaimCampaigns<T: Config> =
+		StorageMap<_, Identity, Hash128, ClaimCampaign<T::BlockNumber>>;
+
+	/// **StorageDoubleMap** that maps the **Merkle Root of a claim campaign** and an **Account ID**
+	/// to the **number of Fragment Instances that the Account ID has claimed** during the aforementioned claim campaign
+	///
+	/// Footnotes:
+	///
+	/// The entries are keyed by the Merkle Root (and not by the Fragment Definition ID), so that a new claim campaign with a different Merkle Root starts afresh
+	#[pallet::storage]
+	pub type Claimed<T: Config> = StorageDoubleMap<
+		_,
+		Identity,
+		Hash256,
+		Blake2_128Concat,
+		T::AccountId,
+		Compact<InstanceUnit>,
+	>;
+
 	/// **StorageNMap** that maps a
 	/// **Fragment Instance's Fragment Definition ID, Edition ID and Copy ID**
 	/// to a
@@ -306,6 +639,86 @@ pub mod pallet {
 	#[pallet::storage]
 	pub type EditionsCount<T: Config> = StorageMap<_, Identity, Hash128, Compact<InstanceUnit>>;
 
+	/// **StorageMap** that maps a **Fragment Definition ID**
+	/// to the
+	/// **total number of Fragment Instances of the aforementioned Fragment Definition that have been burned** (see `burn()`)
+	#[pallet::storage]
+	pub type BurnedCount<T: Config> = StorageMap<_, Identity, Hash128, Compact<InstanceUnit>>;
+
+	/// **StorageMap** that maps a **Fragment Definition ID**
+	/// to the
+	/// **total number of Fragment Instances of the aforementioned Fragment Definition that have been removed from storage**
+	/// (i.e that were burned, consumed, merged into another stack or that expired)
+	///
+	/// Footnotes:
+	///
+	/// The Copy IDs of the removed Fragment Instances are never reused, so the number of Fragment Instances that currently exist
+	/// is the total number of copies (see `CopiesCount`) minus this number.
+	#[pallet::storage]
+	pub type RemovedCount<T: Config> = StorageMap<_, Identity, Hash128, Compact<InstanceUnit>>;
+
+	/// **StorageMap** that maps a **Fragment Definition ID**
+	/// to the
+	/// **total number of Edition IDs of the aforementioned Fragment Definition whose Fragment Instances (i.e all their copies) have all been burned**
+	#[pallet::storage]
+	pub type BurnedEditionsCount<T: Config> =
+		StorageMap<_, Identity, Hash128, Compact<InstanceUnit>>;
+
+	/// **StorageMap** that maps a **Fragment Definition ID**
+	/// to a
+	/// **boolean that indicates whether burned Editions free up supply**, i.e whether they are **no longer counted towards the `max_supply`** of the Fragment Definition
+	///
+	/// Footnotes:
+	///
+	/// By default burned Editions are still counted towards the `max_supply`, so burning never allows more Editions to be created than `max_supply`.
+	#[pallet::storage]
+	pub type BurnsReplenishSupply<T: Config> = StorageMap<_, Identity, Hash128, bool, ValueQuery>;
+
+	/// **StorageMap** that maps a **Fragment Definition ID** (of a Fragment Definition that has the unique capability)
+	/// to the **Account ID** of the **ink! contract** that **validates** the **unique data** of its **Fragment Instances**
+	/// (see `set_unique_data_validator()`)
+	#[pallet::storage]
+	pub type UniqueDataValidators<T: Config> = StorageMap<_, Identity, Hash128, T::AccountId>;
+
+	/// **StorageDoubleMap** that maps a **Fragment Definition ID** and a **metadata key**
+	/// to **who is allowed to write** the **metadata key** of the **Fragment Definition** and of its **Fragment Instances**
+	/// (see `set_metadata_key_policy()`)
+	///
+	/// Footnotes:
+	///
+	/// A metadata key that is not in this map has the policy `MetadataKeyPolicy::Owner`.
+	#[pallet::storage]
+	pub type MetadataKeyPolicies<T: Config> = StorageDoubleMap<
+		_,
+		Identity,
+		Hash128,
+		Blake2_128Concat,
+		BoundedVec<u8, <T as pallet_protos::Config>::StringLimit>,
+		MetadataKeyPolicy,
+	>;
+
+	/// **StorageMap** that maps a **Fragment Definition ID**
+	/// to its **attribute schema**, i.e the **typed attributes** that its **Fragment Instances** can have
+	/// (see `set_attribute_schema()`)
+	#[pallet::storage]
+	pub type AttributeSchemas<T: Config> = StorageMap<_, Identity, Hash128, AttributeSchema>;
+
+	/// **StorageNMap** that maps a
+	/// **Fragment Instance's Fragment Definition ID, Edition ID and Copy ID**
+	/// to the **values of its attributes** (see `set_instance_attributes()`)
+	#[pallet::storage]
+	pub type InstanceAttributes<T: Config> = StorageNMap<
+		_,
+		(
+			storage::Key<Identity, Hash128>,
+			// Editions
+			storage::Key<Identity, InstanceUnit>,
+			// Copies
+			storage::Key<Identity, InstanceUnit>,
+		),
+		BTreeMap<Vec<u8>, AttributeValue>,
+	>;
+
 	/// **StorageMap** that maps a **tuple that contains a Fragment Definition ID and an Edition ID**
 	/// to the
 	/// **total number of Fragment Instances that have the Fragment Definition ID and the Edition ID**
@@ -350,6 +763,16 @@ pub mod pallet {
 		InstanceUnit, // Edition ID
 	>;
 
+	/// **StorageMap** that maps a **tuple that contains a Fragment Definition ID and an Edition ID**
+	/// to the **previous unique data hashes** of the **Edition** (oldest first, see `update_unique_data()`)
+	///
+	/// Footnotes:
+	///
+	/// Only the last `MAX_UNIQUE_DATA_HISTORY` hashes are kept. Every update is also recorded by the event `UniqueDataUpdated`.
+	#[pallet::storage]
+	pub type UniqueDataHistory<T: Config> =
+		StorageMap<_, Identity, (Hash128, InstanceUnit), Vec<Hash256>, ValueQuery>;
+
 	/// StorageDoubleMap that maps a **Fragment Definition and a Fragnova Account ID**
 	/// to a
 	/// **list of Fragment Instances of the Fragment Definition that is owned by the Fragnova Account ID**
@@ -369,6 +792,26 @@ pub mod pallet {
 		Vec<(Compact<InstanceUnit>, Compact<InstanceUnit>)>,
 	>;
 
+	/// **StorageNMap** that maps a
+	/// **Fragment Instance's Fragment Definition ID, Edition ID and Copy ID**
+	/// to the
+	/// **Fragnova Account ID that owns the Fragment Instance**
+	///
+	/// This storage item is an index of `Owners`, so that the owner of a Fragment Instance can be found without iterating over all the owners of its Fragment Definition
+	#[pallet::storage]
+	pub type InstanceOwner<T: Config> = StorageNMap<
+		_,
+		// Keys are using Identity for compression, as we deteministically create fragments
+		(
+			storage::Key<Identity, Hash128>,
+			// Editions
+			storage::Key<Identity, InstanceUnit>,
+			// Copies
+			storage::Key<Identity, InstanceUnit>,
+		),
+		T::AccountId,
+	>;
+
 	/// StorageDoubleMap that maps a **Fragnova Account ID and a Fragment Definition**
 	/// to a
 	/// **list of Fragment Instances of the Fragment Definition that is owned by the Fragnova Account ID**
@@ -388,64 +831,202 @@ pub mod pallet {
 		Vec<(Compact<InstanceUnit>, Compact<InstanceUnit>)>,
 	>;
 
-	/// StorageMap that maps the **Block Number**
-	/// to a
-	/// **list of Fragment Instances that expire on that Block
-	/// (note: each FI in the list is represented as a tuple that contains the Fragment Instance's Fragment Definition ID, the Fragment Instance's Edition ID and
-	/// the Fragment Instance's Copy ID)**
+	/// **StorageNMap** that maps a
+	/// **Fragment Instance's Fragment Definition ID, Edition ID and Copy ID**
+	/// to its **rental** (see `Rental`)
 	///
 	/// Footnotes:
 	///
-	///  Fragment Instances can expire, we process expirations every `on_finalize`
+	/// A rental ends by itself once its `until` block number is reached (see `get_active_rental()`),
+	/// its entry is removed the next time the Fragment Instance is rented, transferred or removed.
 	#[pallet::storage]
-	pub type Expirations<T: Config> = StorageMap<
+	pub type Rentals<T: Config> = StorageNMap<
 		_,
-		Twox64Concat,
-		T::BlockNumber,
-		Vec<(Hash128, Compact<InstanceUnit>, Compact<InstanceUnit>)>,
+		(
+			storage::Key<Identity, Hash128>,
+			// Editions
+			storage::Key<Identity, InstanceUnit>,
+			// Copies
+			storage::Key<Identity, InstanceUnit>,
+		),
+		Rental<T::AccountId, T::BlockNumber>,
 	>;
 
-	/// **StorageMap** that maps a **Fragment Definition ID and a Number** to a **Data Hash**
+	/// **StorageNMap** that maps a **Fragment Instance** (represented as a tuple that contains the
+	/// **Fragment Instance's Fragment Definition ID, Edition ID and Copy ID**)
+	/// to the **Account ID** that is **approved to transfer it on behalf of its owner** (see `approve()`)
+	///
+	/// Footnotes:
+	///
+	/// The approval is cleared whenever the Fragment Instance is transferred or removed.
 	#[pallet::storage]
-	pub type DataHashMap<T: Config> =
-		StorageDoubleMap<_, Identity, Hash128, Identity, Compact<u64>, Hash256>;
-	/// **StorageMap** that maps a **Fragment Definition ID** to the **total number of "Numbers" (see `DataHashMap` to understand what "Numbers" means) that fall under it**
+	pub type InstanceApprovals<T: Config> = StorageNMap<
+		_,
+		(
+			storage::Key<Identity, Hash128>,
+			// Editions
+			storage::Key<Identity, InstanceUnit>,
+			// Copies
+			storage::Key<Identity, InstanceUnit>,
+		),
+		T::AccountId,
+	>;
+
+	/// **StorageNMap** that maps an **owner's Account ID**, a **Fragment Definition ID** and an **operator's Account ID**
+	/// to `()`, if the operator is **approved to transfer every Fragment Instance of the Fragment Definition** that the owner owns
+	/// (see `set_approval_for_all()`)
 	#[pallet::storage]
-	pub type DataHashMapIndex<T: Config> = StorageMap<_, Identity, Hash128, u64>;
+	pub type OperatorApprovals<T: Config> = StorageNMap<
+		_,
+		(
+			storage::Key<Blake2_128Concat, T::AccountId>,
+			storage::Key<Identity, Hash128>,
+			storage::Key<Blake2_128Concat, T::AccountId>,
+		),
+		(),
+	>;
 
-	#[allow(missing_docs)]
-	#[pallet::event]
-	#[pallet::generate_deposit(pub(super) fn deposit_event)]
-	pub enum Event<T: Config> {
-		/// New definition created by account, definition hash
-		DefinitionCreated { definition_hash: Hash128 },
-		/// A Fragment Definition metadata has changed
-		DefinitionMetadataChanged { definition_hash: Hash128, metadata_key: Vec<u8> },
-		/// A Fragment Instance metadata has changed
-		InstanceMetadataChanged {
-			definition_hash: Hash128,
-			edition_id: InstanceUnit,
-			copy_id: InstanceUnit,
-			metadata_key: Vec<u8>,
-		},
-		/// Fragment sale has been opened
-		Publishing { definition_hash: Hash128 },
-		/// Fragment sale has been closed
-		Unpublishing { definition_hash: Hash128 },
-		/// Inventory item has been added to account
-		InventoryAdded {
-			account_id: T::AccountId,
-			definition_hash: Hash128,
-			fragment_id: (InstanceUnit, InstanceUnit),
-		},
-		/// Inventory item has removed added from account
-		InventoryRemoved {
-			account_id: T::AccountId,
-			definition_hash: Hash128,
-			fragment_id: (InstanceUnit, InstanceUnit),
-		},
-		/// Inventory has been updated
-		InventoryUpdated {
+	/// **StorageMap** that maps a **Swap ID** to its **pending swap** (see `Swap`)
+	#[pallet::storage]
+	pub type Swaps<T: Config> =
+		StorageMap<_, Twox64Concat, u64, Swap<T::AccountId, T::BlockNumber, T::AssetId>>;
+
+	/// **StorageValue** that equals the **Swap ID of the next proposed swap**
+	#[pallet::storage]
+	pub type NextSwapId<T: Config> = StorageValue<_, u64, ValueQuery>;
+
+	/// **StorageNMap** that maps a **Fragment Instance** (represented as a tuple that contains the
+	/// **Fragment Instance's Fragment Definition ID, Edition ID and Copy ID**)
+	/// to the **block number until which it cannot be transferred** because it is offered in a pending swap
+	///
+	/// Footnotes:
+	///
+	/// A lock ends by itself once its block number is reached (see `is_instance_locked()`),
+	/// its entry is removed when the swap is accepted or cancelled, or when the Fragment Instance is transferred or removed.
+	#[pallet::storage]
+	pub type LockedInstances<T: Config> = StorageNMap<
+		_,
+		(
+			storage::Key<Identity, Hash128>,
+			// Editions
+			storage::Key<Identity, InstanceUnit>,
+			// Copies
+			storage::Key<Identity, InstanceUnit>,
+		),
+		T::BlockNumber,
+	>;
+
+	/// **StorageNMap** that maps a **nested Fragment Instance** (represented by a tuple that contains the
+	/// **Fragment Instance's Fragment Definition ID, Edition ID and Copy ID**)
+	/// to the **Fragment Instance that it is nested in** (see `nest()`)
+	///
+	/// Footnotes:
+	///
+	/// A nested Fragment Instance is owned by the account of the Fragment Instance that it is nested in (see `get_fragment_account_id()`)
+	#[pallet::storage]
+	pub type InstanceParent<T: Config> = StorageNMap<
+		_,
+		(
+			storage::Key<Identity, Hash128>,
+			// Editions
+			storage::Key<Identity, InstanceUnit>,
+			// Copies
+			storage::Key<Identity, InstanceUnit>,
+		),
+		(Hash128, InstanceUnit, InstanceUnit),
+	>;
+
+	/// **StorageNMap** that maps a **Fragment Instance** (represented by a tuple that contains the
+	/// **Fragment Instance's Fragment Definition ID, Edition ID and Copy ID**)
+	/// to the **list of Fragment Instances that are nested directly in it** (see `nest()`)
+	#[pallet::storage]
+	pub type InstanceChildren<T: Config> = StorageNMap<
+		_,
+		(
+			storage::Key<Identity, Hash128>,
+			// Editions
+			storage::Key<Identity, InstanceUnit>,
+			// Copies
+			storage::Key<Identity, InstanceUnit>,
+		),
+		Vec<(Hash128, InstanceUnit, InstanceUnit)>,
+	>;
+
+	/// **StorageMap** that maps a **Recipe ID** to its **crafting recipe** (see `register_recipe()`)
+	#[pallet::storage]
+	pub type Recipes<T: Config> =
+		StorageMap<_, Twox64Concat, u64, Recipe<T::AccountId, T::AssetId>>;
+
+	/// **StorageValue** that equals the **Recipe ID** of the **next crafting recipe to be registered**
+	#[pallet::storage]
+	pub type NextRecipeId<T: Config> = StorageValue<_, u64, ValueQuery>;
+
+	/// **StorageDoubleMap** that maps a **Block Number** and a **Fragment Instance**
+	/// (represented as a tuple that contains the Fragment Instance's Fragment Definition ID, the Fragment Instance's Edition ID and
+	/// the Fragment Instance's Copy ID) to `()`, if the Fragment Instance expires at that Block Number
+	///
+	/// Footnotes:
+	///
+	/// Fragment Instances can expire. Expired Fragment Instances are swept from storage in `on_initialize` and `on_idle` under a weight budget (see `sweep_expirations()`),
+	/// so a Fragment Instance may stay in storage for a few blocks after it has expired.
+	/// Such a Fragment Instance is nevertheless treated as expired (see `is_expired()`).
+	#[pallet::storage]
+	pub type ExpirationQueue<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		T::BlockNumber,
+		Twox64Concat,
+		(Hash128, InstanceUnit, InstanceUnit),
+		(),
+	>;
+
+	/// **StorageValue** that stores the **first Block Number whose expired Fragment Instances have not all been swept yet** (see `ExpirationQueue`)
+	#[pallet::storage]
+	pub type ExpirationCursor<T: Config> = StorageValue<_, T::BlockNumber, ValueQuery>;
+
+	/// **StorageMap** that maps a **Fragment Definition ID and a Number** to a **Data Hash**
+	#[pallet::storage]
+	pub type DataHashMap<T: Config> =
+		StorageDoubleMap<_, Identity, Hash128, Identity, Compact<u64>, Hash256>;
+	/// **StorageMap** that maps a **Fragment Definition ID** to the **total number of "Numbers" (see `DataHashMap` to understand what "Numbers" means) that fall under it**
+	#[pallet::storage]
+	pub type DataHashMapIndex<T: Config> = StorageMap<_, Identity, Hash128, u64>;
+
+	#[allow(missing_docs)]
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// New definition created by account, definition hash
+		DefinitionCreated { definition_hash: Hash128 },
+		/// The ownership of a Fragment Definition has been transferred
+		DefinitionTransferred { definition_hash: Hash128, owner: T::AccountId },
+		/// A Fragment Definition metadata has changed
+		DefinitionMetadataChanged { definition_hash: Hash128, metadata_key: Vec<u8> },
+		/// A Fragment Instance metadata has changed
+		InstanceMetadataChanged {
+			definition_hash: Hash128,
+			edition_id: InstanceUnit,
+			copy_id: InstanceUnit,
+			metadata_key: Vec<u8>,
+		},
+		/// Fragment sale has been opened
+		Publishing { definition_hash: Hash128 },
+		/// Fragment sale has been closed
+		Unpublishing { definition_hash: Hash128 },
+		/// Inventory item has been added to account
+		InventoryAdded {
+			account_id: T::AccountId,
+			definition_hash: Hash128,
+			fragment_id: (InstanceUnit, InstanceUnit),
+		},
+		/// Inventory item has removed added from account
+		InventoryRemoved {
+			account_id: T::AccountId,
+			definition_hash: Hash128,
+			fragment_id: (InstanceUnit, InstanceUnit),
+		},
+		/// Inventory has been updated
+		InventoryUpdated {
 			account_id: T::AccountId,
 			definition_hash: Hash128,
 			fragment_id: (InstanceUnit, InstanceUnit),
@@ -460,6 +1041,117 @@ pub mod pallet {
 		Resell { definition_hash: Hash128, fragment_id: (InstanceUnit, InstanceUnit) },
 		/// End Resale of Instance
 		EndResale { definition_hash: Hash128, fragment_id: (InstanceUnit, InstanceUnit) },
+		/// A Fragment Instance has been burned
+		Burned {
+			account_id: T::AccountId,
+			definition_hash: Hash128,
+			fragment_id: (InstanceUnit, InstanceUnit),
+		},
+		/// Items have been split off the stack of a Fragment Instance into a new copy of it
+		StackSplit {
+			definition_hash: Hash128,
+			fragment_id: (InstanceUnit, InstanceUnit),
+			new_fragment_id: (InstanceUnit, InstanceUnit),
+			amount: InstanceUnit,
+		},
+		/// The stack of a Fragment Instance has been merged into the stack of another Fragment Instance of the same Edition
+		StacksMerged {
+			definition_hash: Hash128,
+			fragment_id: (InstanceUnit, InstanceUnit),
+			merged_fragment_id: (InstanceUnit, InstanceUnit),
+			amount: InstanceUnit,
+		},
+		/// Items of the stack of a Fragment Instance have been consumed
+		StackConsumed {
+			definition_hash: Hash128,
+			fragment_id: (InstanceUnit, InstanceUnit),
+			amount: InstanceUnit,
+			left: InstanceUnit,
+		},
+		/// The supply policy for burned Editions of a Fragment Definition has changed
+		BurnsReplenishSupplyChanged { definition_hash: Hash128, replenish: bool },
+		/// A claim campaign of a Fragment Definition has started
+		ClaimCampaignStarted { definition_hash: Hash128, merkle_root: Hash256 },
+		/// A claim campaign of a Fragment Definition has ended
+		ClaimCampaignEnded { definition_hash: Hash128 },
+		/// Fragment Instances of a Fragment Definition have been claimed
+		Claimed { definition_hash: Hash128, account_id: T::AccountId, quantity: InstanceUnit },
+		/// The expiration of a Fragment Instance has been extended
+		Renewed {
+			definition_hash: Hash128,
+			fragment_id: (InstanceUnit, InstanceUnit),
+			expiring_at: T::BlockNumber,
+		},
+		/// A Fragment Instance has been rented out
+		Rented {
+			definition_hash: Hash128,
+			fragment_id: (InstanceUnit, InstanceUnit),
+			renter: T::AccountId,
+			until: T::BlockNumber,
+		},
+		/// The rental of a Fragment Instance has been ended by its renter
+		RentalEnded { definition_hash: Hash128, fragment_id: (InstanceUnit, InstanceUnit) },
+		/// Sale proceeds have been withdrawn from the vault of a Fragment Definition
+		ProceedsWithdrawn { definition_hash: Hash128, amount: u128, to: T::AccountId },
+		/// The approved operator of a Fragment Instance has changed (`None` if the approval was revoked)
+		Approval {
+			definition_hash: Hash128,
+			fragment_id: (InstanceUnit, InstanceUnit),
+			owner: T::AccountId,
+			operator: Option<T::AccountId>,
+		},
+		/// An operator has been approved (or disapproved) to transfer every Fragment Instance of a Fragment Definition owned by `owner`
+		ApprovalForAll {
+			definition_hash: Hash128,
+			owner: T::AccountId,
+			operator: T::AccountId,
+			approved: bool,
+		},
+		/// A swap has been proposed
+		SwapProposed { swap_id: u64, maker: T::AccountId, taker: T::AccountId },
+		/// A swap has been accepted (i.e the assets of both sides have been exchanged)
+		SwapAccepted { swap_id: u64 },
+		/// A swap has been cancelled
+		SwapCancelled { swap_id: u64 },
+		/// A Fragment Instance has been nested in another Fragment Instance
+		Nested {
+			definition_hash: Hash128,
+			fragment_id: (InstanceUnit, InstanceUnit),
+			parent: (Hash128, InstanceUnit, InstanceUnit),
+		},
+		/// A nested Fragment Instance has been taken out of the Fragment Instance that it was nested in
+		Unnested {
+			definition_hash: Hash128,
+			fragment_id: (InstanceUnit, InstanceUnit),
+			owner: T::AccountId,
+		},
+		/// A crafting recipe has been registered
+		RecipeRegistered { recipe_id: u64, owner: T::AccountId },
+		/// A crafting recipe has been enabled or disabled
+		RecipeEnabledChanged { recipe_id: u64, enabled: bool },
+		/// A crafting recipe has been crafted
+		Crafted { recipe_id: u64, account_id: T::AccountId },
+		/// The validator of the unique data of a Fragment Definition has changed
+		UniqueDataValidatorChanged { definition_hash: Hash128, validator: Option<T::AccountId> },
+		/// The unique data of a Fragment Instance has been updated
+		UniqueDataUpdated {
+			definition_hash: Hash128,
+			fragment_id: (InstanceUnit, InstanceUnit),
+			data_hash: Hash256,
+		},
+		/// The policy of a metadata key of a Fragment Definition has changed
+		MetadataKeyPolicyChanged {
+			definition_hash: Hash128,
+			metadata_key: Vec<u8>,
+			policy: Option<MetadataKeyPolicy>,
+		},
+		/// The attribute schema of a Fragment Definition has changed
+		AttributeSchemaChanged { definition_hash: Hash128, trait_proto: Hash256 },
+		/// The attributes of a Fragment Instance have been updated
+		InstanceAttributesUpdated {
+			definition_hash: Hash128,
+			fragment_id: (InstanceUnit, InstanceUnit),
+		},
 	}
 
 	// Errors inform users that something went wrong.
@@ -509,6 +1201,84 @@ pub mod pallet {
 		DefinitionMetadataKeyIsEmpty,
 		/// Fragment Instance's Metadata key is empty
 		InstanceMetadataKeyIsEmpty,
+		/// Fragment Instances of the Fragment Definition have already been created
+		InstancesAlreadyCreated,
+		/// Fragment Instance does not represent a stack of stackable items
+		NotStackable,
+		/// Stack of stackable items does not have enough items
+		InsufficientStackAmount,
+		/// Stacks of stackable items have different permissions or expiration and cannot be merged
+		StacksNotCompatible,
+		/// Sale Phases are not valid (there are too many of them, they are not sorted by their starting block number or one of them starts after the sale expires)
+		InvalidSalePhases,
+		/// Sale has not started yet
+		SaleNotStarted,
+		/// Account is not in the allowlist of the current Sale Phase
+		NotAllowlisted,
+		/// Account cannot buy that many Fragment Instances during the current Sale Phase
+		PhaseAllowanceExceeded,
+		/// Too many recipients
+		TooManyRecipients,
+		/// Claim campaign already open
+		ClaimCampaignAlreadyOpen,
+		/// Account is not a recipient of the claim campaign (or the Merkle Proof is not valid)
+		NotClaimable,
+		/// Account has already claimed its Fragment Instances
+		AlreadyClaimed,
+		/// Fragment Instance cannot be renewed (it does not expire or its Fragment Definition is not published)
+		NotRenewable,
+		/// Fragment Instance is rented out
+		Rented,
+		/// Fragment Instance is not rented out
+		NotRented,
+		/// Fragment Instance is locked in a pending swap
+		Locked,
+		/// Swap not found
+		SwapNotFound,
+		/// Too many items on one side of the swap
+		TooManySwapItems,
+		/// The Fragment Instance cannot be nested, or have Fragment Instances nested in it
+		NotNestable,
+		/// The Fragment Instance is not nested in another Fragment Instance
+		NotNested,
+		/// The Fragment Instance has Fragment Instances nested in it
+		HasNestedInstances,
+		/// Too many Fragment Instances are nested in the Fragment Instance
+		TooManyNestedInstances,
+		/// The nesting would exceed the maximum nesting depth
+		NestingTooDeep,
+		/// A Fragment Instance cannot be nested in itself or in a Fragment Instance nested in it
+		NestingCycle,
+		/// Crafting recipe not found
+		RecipeNotFound,
+		/// The crafting recipe is disabled
+		RecipeDisabled,
+		/// Too many inputs or outputs
+		TooManyRecipeItems,
+		/// The Fragment Instances used as inputs do not match the input requirements of the crafting recipe
+		RecipeInputsNotMet,
+		/// The unique data was rejected by the validator of the Fragment Definition
+		UniqueDataNotValid,
+		/// The Fragment Definition does not have mutable unique data
+		UniqueDataNotMutable,
+		/// The unique data of an Edition that has more than one copy cannot be updated
+		EditionHasMultipleCopies,
+		/// The Fragment Instance does not have the permission `FragmentPerms::EDIT`
+		NotEditable,
+		/// The metadata key can only be written by the owner of the Fragment Definition
+		MetadataKeyReservedForCreator,
+		/// The metadata key can only be written by the members of a Cluster that have a specific Role
+		MetadataKeyReservedForClusterRole,
+		/// The Proto-Fragment is not a Trait
+		NotATrait,
+		/// The Fragment Definition does not have an attribute schema
+		NoAttributeSchema,
+		/// The attribute is not declared in the attribute schema (or in its Trait)
+		AttributeNotInSchema,
+		/// The attribute value does not have the type declared in the attribute schema
+		AttributeTypeMismatch,
+		/// The type of the attribute is not one of the types of its record in the Trait
+		AttributeTypeNotInTrait,
 	}
 
 	// Dispatchable functions allows users to interact with the pallet and invoke state changes.
@@ -518,8 +1288,15 @@ pub mod pallet {
 	impl<T: Config> Pallet<T> {
 		/// **Create** a **Fragment Definition** using an **existing Proto-Fragment**.
 		///
-		/// Note: **Only** the **Proto-Fragment's owner** is **allowed** to **create** a **Fragment Definition**
-		/// using the **Proto-Fragment**
+		/// Note: The **Proto-Fragment's owner** is **always allowed** to **create** a **Fragment Definition**
+		/// using the **Proto-Fragment**. Any other account is **allowed** to do so **only if** the **Proto-Fragment's license** permits it
+		/// (see `pallet_protos::UsageLicense`).
+		///
+		/// The **caller** is **recorded** as the **creator** and the **owner** of the **Fragment Definition**. It is the owner (and not the Proto-Fragment's owner)
+		/// that is allowed to manage the Fragment Definition (e.g `publish()`, `mint()` and `set_definition_metadata()`),
+		/// and the ownership can be transferred independently of the Proto-Fragment (see `transfer_definition()`).
+		/// If the owner is not the Proto-Fragment's owner, a share of every sale may be routed to the Proto-Fragment's owner
+		/// (see `pallet_protos::set_license_royalty()`).
 		///
 		/// # Arguments
 		///
@@ -560,16 +1337,11 @@ pub mod pallet {
 				_reserved3: None,
 			};
 
-			let proto: Proto<T::AccountId, T::BlockNumber> =
-				<Protos<T>>::get(proto_hash).ok_or(Error::<T>::ProtoNotFound)?; // Get `Proto` struct from `proto_hash`
-
-			let proto_owner: T::AccountId = match proto.owner {
-				// Get `proto_owner` from `proto.owner`
-				ProtoOwner::User(owner) => Ok(owner),
-				_ => Err(Error::<T>::ProtoOwnerNotFound),
-			}?;
+			ensure!(<Protos<T>>::contains_key(proto_hash), Error::<T>::ProtoNotFound);
 
-			ensure!(who == proto_owner, Error::<T>::NoPermission); // Only proto owner can create a fragment definition from proto
+			// the proto owner can always create a fragment definition from its proto, anyone else needs the proto's license to allow it
+			pallet_protos::Pallet::<T>::check_license(&[proto_hash], &who)
+				.map_err(|_| Error::<T>::NoPermission)?;
 
 			ensure!(
 				!<DetachedHashes<T>>::contains_key(&DetachHash::Proto(proto_hash)),
@@ -630,6 +1402,7 @@ pub mod pallet {
 				unique,
 				max_supply: max_supply.map(|x| Compact(x)),
 				creator: who.clone(),
+				owner: who.clone(),
 				created_at: current_block_number,
 				custom_metadata: BTreeMap::new(),
 				_reserved1: None,
@@ -639,6 +1412,7 @@ pub mod pallet {
 			<Definitions<T>>::insert(&hash, fragment_data);
 
 			Proto2Fragments::<T>::append(&proto_hash, hash);
+			<DefinitionsByOwner<T>>::append(&who, hash);
 
 			Self::deposit_event(Event::DefinitionCreated { definition_hash: hash });
 			Ok(())
@@ -648,13 +1422,16 @@ pub mod pallet {
 		/// to the **BTreeMap field `custom_metadata`** of the **existing Fragment Definition's Struct Instance**.
 		/// Furthermore, this function also indexes `data` in the Blockchain's Database and stores it in the IPFS
 		///
+		/// Note: By default, **only** the **Fragment Definition's owner** is **allowed** to write its metadata,
+		/// unless the metadata key is reserved to a Cluster Role (see `set_metadata_key_policy()`).
+		///
 		/// # Arguments
 		///
 		/// * `origin` - The origin of the extrinsic / dispatchable function
 		/// * `definition_hash` - **ID of the Fragment Definition**
 		/// * `metadata_key` - The key (of the key-value pair) that is added in the BTreeMap field `custom_metadata` of the existing Fragment Definition's Struct Instance
 		/// * `data` - The hash of `data` is used as the value (of the key-value pair) that is added in the BTreeMap field `custom_metadata` of the existing Fragment Definition's Struct Instance
-		#[pallet::weight(50_000)]
+		#[pallet::weight(<T as Config>::WeightInfo::set_definition_metadata(data.len() as u32))]
 		#[pallet::call_index(1)]
 		pub fn set_definition_metadata(
 			origin: OriginFor<T>,
@@ -669,16 +1446,15 @@ pub mod pallet {
 
 			ensure!(!metadata_key.is_empty(), Error::<T>::DefinitionMetadataKeyIsEmpty);
 
-			let proto_hash =
-				<Definitions<T>>::get(definition_hash).ok_or(Error::<T>::NotFound)?.proto_hash; // Get `proto_hash` from `definition_hash`
-			let proto: Proto<T::AccountId, T::BlockNumber> =
-				<Protos<T>>::get(proto_hash).ok_or(Error::<T>::ProtoNotFound)?;
-			let proto_owner: T::AccountId = match proto.owner {
-				// Get `proto_owner` from `proto`
-				ProtoOwner::User(owner) => Ok(owner),
-				_ => Err(Error::<T>::ProtoOwnerNotFound),
-			}?;
-			ensure!(who == proto_owner, Error::<T>::NoPermission); // Ensure `who` is `proto_owner`
+			let definition = <Definitions<T>>::get(definition_hash).ok_or(Error::<T>::NotFound)?;
+			let proto_hash = definition.proto_hash;
+			match <MetadataKeyPolicies<T>>::get(definition_hash, &metadata_key) {
+				None | Some(MetadataKeyPolicy::Owner) => {
+					ensure!(who == definition.owner, Error::<T>::NoPermission) // Ensure `who` is the owner of the Fragment Definition
+				},
+				Some(policy) =>
+					Self::ensure_can_write_reserved_metadata_key(&who, &definition.owner, &policy)?,
+			}
 
 			// TO REVIEW
 			ensure!(
@@ -737,6 +1513,10 @@ pub mod pallet {
 		/// whose Edition ID is `edition_id` and whose Copy ID is `copy_id`).
 		/// Furthermore, this function also indexes `data` in the Blockchain's Database and stores it in the IPFS
 		///
+		/// Note: By default, **only** the **Fragment Instance's owner** is **allowed** to write its metadata,
+		/// and **only** if the Fragment Instance has the permission **FragmentPerms::EDIT**.
+		/// A metadata key can also be reserved by the Fragment Definition's owner (see `set_metadata_key_policy()`).
+		///
 		/// # Arguments
 		///
 		/// * `origin` - The origin of the extrinsic / dispatchable function
@@ -745,7 +1525,7 @@ pub mod pallet {
 		/// * `copy_id` - **Copy ID of the Fragment Instance**
 		/// * `metadata_key` - The key (of the key-value pair) that is added in the BTreeMap field `metadata` of the existing Fragment Instance's Struct Instance
 		/// * `data` - The hash of `data` is used as the value (of the key-value pair) that is added in the BTreeMap field `metadata` of the existing Fragment Instance's Struct Instance
-		#[pallet::weight(50_000)]
+		#[pallet::weight(<T as Config>::WeightInfo::set_instance_metadata(data.len() as u32))]
 		#[pallet::call_index(2)]
 		pub fn set_instance_metadata(
 			origin: OriginFor<T>,
@@ -773,12 +1553,14 @@ pub mod pallet {
 			let instance_struct = <Fragments<T>>::get((definition_hash, edition_id, copy_id))
 				.ok_or(Error::<T>::NotFound)?;
 
-			let owned_instances =
-				<Inventory<T>>::get(who.clone(), definition_hash).ok_or(Error::<T>::NotFound)?;
-			ensure!(
-				owned_instances.contains(&(Compact(edition_id), Compact(copy_id))),
-				Error::<T>::NoPermission
-			);
+			Self::ensure_can_write_instance_metadata_key(
+				&who,
+				&definition_hash,
+				edition_id,
+				copy_id,
+				&instance_struct,
+				&metadata_key,
+			)?;
 
 			let data_hash = blake2_256(&data);
 
@@ -848,7 +1630,7 @@ pub mod pallet {
 
 		/// Put the **Fragment Definition `definition_hash`** on sale. When a Fragment Definition is put on sale, users can create Fragment Instances from it for a fee.
 		///
-		/// Note: **Only** the **Fragment's Proto-Fragment's owner** is **allowed** to put the **Fragment** on sale
+		/// Note: **Only** the **Fragment Definition's owner** is **allowed** to put the **Fragment** on sale
 		///
 		/// # Arguments
 		///
@@ -859,6 +1641,7 @@ pub mod pallet {
 		/// * `expires` (*optional*) - **Block number** that the sale ends at (*optional*)
 		/// * `amount` (*optional*) - If the Fragment instance represents a **stack of stackable items** (for e.g gold coins or arrows - https://runescape.fandom.com/wiki/Stackable_items),
 		/// the **number of items** to **top up** in the **stack of stackable items**
+		/// * `phases` - **Ordered list of Sale Phases** of the sale (see `SalePhase`). If it is empty, the sale starts immediately at the price `price`.
 		#[pallet::weight(<T as Config>::WeightInfo::publish())]
 		#[pallet::call_index(3)]
 		pub fn publish(
@@ -868,21 +1651,23 @@ pub mod pallet {
 			quantity: Option<InstanceUnit>,
 			expires: Option<T::BlockNumber>,
 			stack_amount: Option<InstanceUnit>,
+			phases: Vec<SalePhase<T::BlockNumber>>,
 		) -> DispatchResult {
 			let who = ensure_signed(origin)?;
 
-			let proto_hash =
-				<Definitions<T>>::get(definition_hash).ok_or(Error::<T>::NotFound)?.proto_hash; // Get `proto_hash` from `definition_hash`
-			let proto: Proto<T::AccountId, T::BlockNumber> =
-				<Protos<T>>::get(proto_hash).ok_or(Error::<T>::ProtoNotFound)?; // Get `proto` from `proto_hash`
-
-			let proto_owner: T::AccountId = match proto.owner {
-				// Get `proto_owner` from `proto`
-				ProtoOwner::User(owner) => Ok(owner),
-				_ => Err(Error::<T>::ProtoOwnerNotFound),
-			}?;
+			ensure!(phases.len() <= MAX_SALE_PHASES, Error::<T>::InvalidSalePhases);
+			ensure!(
+				phases.windows(2).all(|pair| pair[0].start < pair[1].start),
+				Error::<T>::InvalidSalePhases
+			);
+			// a Sale Phase that starts once the sale has expired would never be active
+			if let (Some(expires), Some(last_phase)) = (expires, phases.last()) {
+				ensure!(last_phase.start < expires, Error::<T>::InvalidSalePhases);
+			}
 
-			ensure!(who == proto_owner, Error::<T>::NoPermission); // Ensure `who` is `proto_owner`
+			let definition = <Definitions<T>>::get(definition_hash).ok_or(Error::<T>::NotFound)?;
+			let proto_hash = definition.proto_hash;
+			ensure!(who == definition.owner, Error::<T>::NoPermission); // Ensure `who` is the owner of the Fragment Definition
 
 			// TO REVIEW
 			ensure!(
@@ -892,13 +1677,9 @@ pub mod pallet {
 
 			ensure!(!<Publishing<T>>::contains_key(&definition_hash), Error::<T>::SaleAlreadyOpen); // Ensure `definition_hash` isn't already published
 
-			let fragment_data =
-				<Definitions<T>>::get(definition_hash).ok_or(Error::<T>::NotFound)?; // Get `FragmentDefinition` struct from `definition_hash`
-
-			if let Some(max_supply) = fragment_data.max_supply {
+			if let Some(max_supply) = definition.max_supply {
 				let max: InstanceUnit = max_supply.into();
-				let existing: InstanceUnit =
-					<EditionsCount<T>>::get(&definition_hash).unwrap_or(Compact(0)).into();
+				let existing: InstanceUnit = Self::get_supply(&definition_hash);
 				let left = max.saturating_sub(existing); // `left` = `max` - `existing`
 				if left == 0 {
 					return Err(Error::<T>::MaxSupplyReached.into())
@@ -921,7 +1702,7 @@ pub mod pallet {
 					units_left: quantity.map(|x| Compact(x)),
 					expiration: expires,
 					stack_amount: stack_amount.map(|x| Compact(x)),
-					_reserved1: None,
+					phases,
 					_reserved2: None,
 					_reserved3: None,
 				},
@@ -935,7 +1716,7 @@ pub mod pallet {
 		/// Take the **Fragment Definition `definition_hash`** off sale.
 		/// When a Fragment Definition is put on sale, users can create Fragment Instances from it for a fee.
 		///
-		/// Note: **Only** the **Fragment's Proto-Fragment's owner** is **allowed** to take the Fragment off sale
+		/// Note: **Only** the **Fragment Definition's owner** is **allowed** to take the Fragment off sale
 		///
 		/// # Arguments
 		///
@@ -946,18 +1727,9 @@ pub mod pallet {
 		pub fn unpublish(origin: OriginFor<T>, definition_hash: Hash128) -> DispatchResult {
 			let who = ensure_signed(origin)?;
 
-			let proto_hash =
-				<Definitions<T>>::get(definition_hash).ok_or(Error::<T>::NotFound)?.proto_hash; // Get `proto_hash` from `definition_hash`
-			let proto: Proto<T::AccountId, T::BlockNumber> =
-				<Protos<T>>::get(proto_hash).ok_or(Error::<T>::ProtoNotFound)?;
-
-			let proto_owner: T::AccountId = match proto.owner {
-				// Get `proto_owner` from `proto`
-				ProtoOwner::User(owner) => Ok(owner),
-				_ => Err(Error::<T>::ProtoOwnerNotFound),
-			}?;
-
-			ensure!(who == proto_owner, Error::<T>::NoPermission); // Ensure `who` is `proto_owner`
+			let definition = <Definitions<T>>::get(definition_hash).ok_or(Error::<T>::NotFound)?;
+			let proto_hash = definition.proto_hash;
+			ensure!(who == definition.owner, Error::<T>::NoPermission); // Ensure `who` is the owner of the Fragment Definition
 
 			// TO REVIEW
 			ensure!(
@@ -970,6 +1742,9 @@ pub mod pallet {
 			// ! Writing
 
 			<Publishing<T>>::remove(&definition_hash); // Remove Fragment Definition `definition_hash` from `Publishing`
+			<SaleIndex<T>>::mutate(&definition_hash, |sale_index| {
+				*sale_index = sale_index.saturating_add(1)
+			}); // Forget what was bought during the Sale Phases of the sale
 
 			Self::deposit_event(Event::Unpublishing { definition_hash });
 
@@ -981,7 +1756,7 @@ pub mod pallet {
 		///
 		/// Note: **Each created Fragment instance** will have a **different Edition ID** and a **Copy ID of "1"**.
 		///
-		/// Note: **Only** the **Fragment Definition's Proto-Fragment's owner** is **allowed** to
+		/// Note: **Only** the **Fragment Definition's owner** is **allowed** to
 		/// create instance(s) of the Fragment in this extrinsic function.
 		///
 		/// # Arguments
@@ -999,6 +1774,7 @@ pub mod pallet {
 		#[pallet::weight(match options {
 		FragmentBuyOptions::Quantity(q) => <T as Config>::WeightInfo::mint_definition_that_has_non_unique_capability(*q as u32),
 		FragmentBuyOptions::UniqueData(d) => <T as Config>::WeightInfo::mint_definition_that_has_unique_capability(d.len() as u32)
+			.saturating_add(Weight::from_ref_time(MAX_UNIQUE_DATA_VALIDATOR_WEIGHT)) // the validator of the unique data (if any) is paid by the caller
 		})]
 		#[pallet::call_index(5)]
 		pub fn mint(
@@ -1006,23 +1782,14 @@ pub mod pallet {
 			definition_hash: Hash128,
 			options: FragmentBuyOptions,
 			stack_amount: Option<InstanceUnit>,
-		) -> DispatchResult {
+		) -> DispatchResultWithPostInfo {
 			let who = ensure_signed(origin)?;
 
 			let current_block_number = <frame_system::Pallet<T>>::block_number();
 
-			let proto_hash =
-				<Definitions<T>>::get(definition_hash).ok_or(Error::<T>::NotFound)?.proto_hash; // Get `proto_hash` from `definition_hash`
-			let proto: Proto<T::AccountId, T::BlockNumber> =
-				<Protos<T>>::get(proto_hash).ok_or(Error::<T>::ProtoNotFound)?; // Get `proto` from `proto_hash`
-
-			let proto_owner: T::AccountId = match proto.owner {
-				// Get `proto_owner` from `proto`
-				ProtoOwner::User(owner) => Ok(owner),
-				_ => Err(Error::<T>::ProtoOwnerNotFound),
-			}?;
-
-			ensure!(who == proto_owner, Error::<T>::NoPermission); // Ensure `who` is `proto_owner`
+			let definition = <Definitions<T>>::get(definition_hash).ok_or(Error::<T>::NotFound)?;
+			let proto_hash = definition.proto_hash;
+			ensure!(who == definition.owner, Error::<T>::NoPermission); // Ensure `who` is the owner of the Fragment Definition
 
 			// TO REVIEW
 			ensure!(
@@ -1038,7 +1805,7 @@ pub mod pallet {
 
 			// ! Writing
 
-			Self::mint_fragments(
+			let validator_weight = Self::mint_fragments(
 				&who,
 				&definition_hash,
 				None, // PublishingData (optional)
@@ -1047,7 +1814,19 @@ pub mod pallet {
 				current_block_number,
 				None, // Block Number the Fragment(s) expire at (optional)
 				stack_amount.map(|x| Compact(x)),
-			)
+			)?;
+
+			// refund the weight that the validator of the unique data did not consume
+			Ok(match &options {
+				FragmentBuyOptions::UniqueData(data) => Some(
+					<T as Config>::WeightInfo::mint_definition_that_has_unique_capability(
+						data.len() as u32,
+					)
+					.saturating_add(validator_weight),
+				)
+				.into(),
+				FragmentBuyOptions::Quantity(_) => ().into(),
+			})
 		}
 
 		/// Allows the Caller Account ID `origin` to create Fragment instance(s) of the Fragment Definition `definition_hash`,
@@ -1057,7 +1836,9 @@ pub mod pallet {
 		///
 		/// Note: The total fee that the buyer (i.e the Caller Account ID `origin`) must pay is the
 		/// specified price-per-instance multiplied by the total number of instance(s) that the buyer wants to create.
-		/// This amount will be transferred to the Fragment Definition's Vault's Account ID.
+		/// This amount will be transferred to the Fragment Definition's Vault's Account ID,
+		/// except for the license royalty of the Proto-Fragment (if any) which is transferred to the Proto-Fragment's owner
+		/// (see `pallet_protos::set_license_royalty()`).
 		///
 		///
 		///
@@ -1068,18 +1849,24 @@ pub mod pallet {
 		/// * `options` - **Enum** indicating whether to
 		/// **create one Fragment Instance with custom data attached to it** or whether to
 		/// **create multiple Fragment Instances (with no custom data attached)**
+		/// * `allowlist_proof` (*optional*) - **Proof** that the **Caller Account ID** is in the **allowlist** of the **current Sale Phase**.
+		/// Only required if the current Sale Phase has an allowlist.
 		///
 		/// TODO - `*=q as u32` might cause problems if q is too big (since q is u64)!!!
 		#[pallet::weight(match options {
 		FragmentBuyOptions::Quantity(q) => <T as Config>::WeightInfo::buy_definition_that_has_non_unique_capability(*q as u32),
 		FragmentBuyOptions::UniqueData(d) => <T as Config>::WeightInfo::buy_definition_that_has_unique_capability(d.len() as u32)
-		})]
+			.saturating_add(Weight::from_ref_time(MAX_UNIQUE_DATA_VALIDATOR_WEIGHT)) // the validator of the unique data (if any) is paid by the caller
+		}
+		// Tracking the Fragment Instances bought during the current Sale Phase
+		.saturating_add(T::DbWeight::get().reads_writes(1, 1)))]
 		#[pallet::call_index(6)]
 		pub fn buy(
 			origin: OriginFor<T>,
 			definition_hash: Hash128,
 			options: FragmentBuyOptions,
-		) -> DispatchResult {
+			allowlist_proof: Option<AllowlistProof>,
+		) -> DispatchResultWithPostInfo {
 			let who = ensure_signed(origin)?;
 
 			let current_block_number = <frame_system::Pallet<T>>::block_number();
@@ -1093,8 +1880,6 @@ pub mod pallet {
 				ensure!(units_left > Compact(0), Error::<T>::SoldOut);
 			}
 
-			let price: u128 = sale.price.into();
-
 			let fragment_data =
 				<Definitions<T>>::get(definition_hash).ok_or(Error::<T>::NotFound)?;
 
@@ -1106,13 +1891,71 @@ pub mod pallet {
 				_ => 1u64,
 			};
 
+			let phase = Self::get_current_sale_phase(&sale, current_block_number)?;
+
+			let price: u128 = match &phase {
+				Some((_, phase)) => phase.price.into(),
+				None => sale.price.into(),
+			};
+
+			// Number of Fragment Instances bought by `who` during the current Sale Phase (including this purchase)
+			let sale_index = <SaleIndex<T>>::get(&definition_hash);
+			let phase_mints = if let Some((phase_index, phase)) = &phase {
+				let bought: InstanceUnit =
+					<PhaseMints<T>>::get((definition_hash, sale_index, phase_index, &who))
+						.unwrap_or(Compact(0))
+						.into();
+				let bought = bought.saturating_add(quantity);
+
+				if let Some(max_per_account) = phase.max_per_account {
+					ensure!(
+						bought <= InstanceUnit::from(max_per_account),
+						Error::<T>::PhaseAllowanceExceeded
+					);
+				}
+				if let Some(allowlist) = phase.allowlist {
+					let allowlist_proof =
+						allowlist_proof.as_ref().ok_or(Error::<T>::NotAllowlisted)?;
+					ensure!(
+						Self::verify_allowlist_proof(&allowlist, &who, allowlist_proof),
+						Error::<T>::NotAllowlisted
+					);
+					ensure!(
+						bought <= allowlist_proof.allowance,
+						Error::<T>::PhaseAllowanceExceeded
+					);
+				}
+
+				Some((*phase_index, bought))
+			} else {
+				None
+			};
+
 			let price = price.saturating_mul(quantity as u128); // `price` = `price` * `quantity`
 
+			// share of `price` that is paid to the Proto-Fragment's owner (if the Fragment Definition is owned by another account)
+			let royalty = Self::get_license_royalty(&fragment_data, price);
+
 			Self::can_transfer_currency(&who, &vault, price, fragment_data.metadata.currency)?;
+			if let Some((proto_owner, royalty)) = &royalty {
+				Self::can_transfer_currency(
+					&who,
+					proto_owner,
+					*royalty,
+					fragment_data.metadata.currency,
+				)?;
+			}
 
 			// ! Writing
 
-			Self::mint_fragments(
+			if let Some((phase_index, bought)) = phase_mints {
+				<PhaseMints<T>>::insert(
+					(definition_hash, sale_index, phase_index, &who),
+					Compact(bought),
+				);
+			}
+
+			let validator_weight = Self::mint_fragments(
 				&who,
 				&definition_hash,
 				Some(&sale), // PublishingData (optional)
@@ -1123,9 +1966,30 @@ pub mod pallet {
 				sale.stack_amount,
 			)?;
 
-			Self::transfer_currency(&who, &vault, price, fragment_data.metadata.currency)?;
+			let vault_amount =
+				price.saturating_sub(royalty.as_ref().map_or(0, |(_, royalty)| *royalty));
+			Self::transfer_currency(&who, &vault, vault_amount, fragment_data.metadata.currency)?;
+			if let Some((proto_owner, royalty)) = &royalty {
+				Self::transfer_currency(
+					&who,
+					proto_owner,
+					*royalty,
+					fragment_data.metadata.currency,
+				)?;
+			}
 
-			Ok(())
+			// refund the weight that the validator of the unique data did not consume
+			Ok(match &options {
+				FragmentBuyOptions::UniqueData(data) => Some(
+					<T as Config>::WeightInfo::buy_definition_that_has_unique_capability(
+						data.len() as u32,
+					)
+					.saturating_add(T::DbWeight::get().reads_writes(1, 1))
+					.saturating_add(validator_weight),
+				)
+				.into(),
+				FragmentBuyOptions::Quantity(_) => ().into(),
+			})
 		}
 
 		/// Give a **Fragment Instance** to **`to`**.
@@ -1134,7 +1998,8 @@ pub mod pallet {
 		/// then it is copied and the copy's ownership is assigned to `to`.
 		/// Otherwise, its ownership is transferred from `origin` to `to`.
 		///
-		/// Note: **Only** the **Fragment Instance's owner** is **allowed** to give the Fragment Instance
+		/// Note: **Only** the **Fragment Instance's owner** or an **operator approved by the owner** (see `approve()` and `set_approval_for_all()`)
+		/// is **allowed** to give the Fragment Instance
 		///
 		/// # Arguments
 		///
@@ -1162,8 +2027,16 @@ pub mod pallet {
 		) -> DispatchResult {
 			let who = ensure_signed(origin)?;
 
+			let owner = <InstanceOwner<T>>::get((definition_hash, edition_id, copy_id))
+				.ok_or(Error::<T>::NotFound)?;
+			ensure!(
+				who == owner ||
+					Self::is_approved(&owner, &who, &definition_hash, edition_id, copy_id),
+				Error::<T>::NoPermission
+			);
+
 			Self::can_transfer_instance(
-				&who,
+				&owner,
 				&definition_hash,
 				edition_id,
 				copy_id,
@@ -1175,7 +2048,7 @@ pub mod pallet {
 
 			// now we take two different paths if item can be copied or not
 			Self::transfer_instance(
-				&who,
+				&owner,
 				&to,
 				&definition_hash,
 				edition_id,
@@ -1187,8 +2060,18 @@ pub mod pallet {
 			Ok(())
 		}
 
-		/// Create the **Account ID** of the **Fragment Instance whose Fragment Definition ID is `class`,
-		/// whose Edition ID is `edition`** and whose Copy ID is `copy`**
+		/// Create the **account** of the **Fragment Instance whose Fragment Definition ID is `definition_hash`,
+		/// whose Edition ID is `edition`** and whose Copy ID is `copy`** (see `get_fragment_account_id()`),
+		/// so that the Fragment Instance can hold NOVA, assets and other Fragment Instances.
+		///
+		/// The **existential deposit** of the account is **paid by the owner of the Fragment Instance**
+		/// (unless the account already exists), and the **owner of the Fragment Instance** is made the **proxy of the account**.
+		///
+		/// Footnotes:
+		///
+		/// The proxy of the account always is the current owner of the Fragment Instance,
+		/// i.e it is replaced whenever the Fragment Instance is transferred (see `transfer_instance()`).
+		/// The current owner can also dispatch calls as the account using `as_instance()`.
 		///
 		/// # Arguments
 		///
@@ -1213,17 +2096,23 @@ pub mod pallet {
 			ensure!(ids.contains(&(Compact(edition), Compact(copy))), Error::<T>::NoPermission);
 
 			// create an account for a specific fragment
-			// we need an existential amount deposit to be able to create the vault account
+			// we need an existential amount deposit to be able to create the account, which is paid by the owner
 			let frag_account = Self::get_fragment_account_id(definition_hash, edition, copy);
 			let minimum_balance =
 				<pallet_balances::Pallet<T> as fungible::Inspect<T::AccountId>>::minimum_balance();
-			let _ = <pallet_balances::Pallet<T> as fungible::Mutate<T::AccountId>>::mint_into(
+			if <pallet_balances::Pallet<T> as fungible::Inspect<T::AccountId>>::balance(
 				&frag_account,
-				minimum_balance,
-			);
+			) < minimum_balance
+			{
+				<pallet_balances::Pallet<T> as frame_support::traits::Currency<T::AccountId>>::transfer(
+					&who,
+					&frag_account,
+					minimum_balance,
+					ExistenceRequirement::KeepAlive
+				).map_err(|_| Error::<T>::InsufficientBalance)?;
+			}
 
-			// TODO Make owner pay for deposit actually!
-			// TODO setup proxy
+			Self::set_instance_account_proxy(&frag_account, &who);
 
 			Ok(())
 		}
@@ -1366,6 +2255,14 @@ pub mod pallet {
 			match (secondary_sale_data.secondary_sale_type, options) {
 				(SecondarySaleType::Normal(price), SecondarySaleBuyOptions::Normal) => {
 					Self::can_transfer_currency(&who, &secondary_sale_data.owner, price, currency)?;
+					Self::can_transfer_instance(
+						&secondary_sale_data.owner,
+						&definition_hash,
+						edition_id,
+						copy_id,
+						secondary_sale_data.new_permissions,
+						secondary_sale_data.expiration,
+					)?;
 
 					// ! Writing
 
@@ -1439,6 +2336,10 @@ pub mod pallet {
 					Error::<T>::NoPermission
 				);
 				ensure!(instance.expiring_at.is_none(), Error::<T>::NoPermission);
+				ensure!(
+					!<InstanceChildren<T>>::contains_key((definition_hash, edition_id, 1)),
+					Error::<T>::HasNestedInstances
+				);
 
 				let detach_hash =
 					DetachHash::Instance(definition_hash, Compact(*edition_id), Compact(1));
@@ -1467,305 +2368,3046 @@ pub mod pallet {
 
 			Ok(())
 		}
-	}
-
-	#[pallet::hooks]
-	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
-		/// During the block finalization phase,
-		/// clear all the *Fragment instance*-related Storage Items of any information regarding
-		/// Fragment instances that have already expired
-		fn on_finalize(n: T::BlockNumber) {
-			let expiring = <Expirations<T>>::take(n);
-			if let Some(expiring) = expiring {
-				for item in expiring {
-					// remove from Fragments
-					<Fragments<T>>::remove((item.0, u64::from(item.1), u64::from(item.2)));
-					for (owner, items) in <Owners<T>>::iter_prefix(item.0) {
-						let index = items.iter().position(|x| x == &(item.1, item.2));
-						if let Some(index) = index {
-							// remove from Owners
-							<Owners<T>>::mutate(item.0, owner.clone(), |x| {
-								if let Some(x) = x {
-									x.remove(index);
-								}
-							});
-
-							// remove from Inventory
-							<Inventory<T>>::mutate(owner.clone(), item.0, |x| {
-								if let Some(x) = x {
-									let index = x.iter().position(|y| y == &(item.1, item.2));
-									if let Some(index) = index {
-										x.remove(index);
-									}
-								}
-							});
-
-							// trigger an Event
-							Self::deposit_event(Event::Expired {
-								account_id: owner,
-								definition_hash: item.0,
-								fragment_id: (item.1.into(), item.2.into()),
-							});
-
-							// fragments are unique so we are done here
-							break
-						}
-					}
-				}
-			}
-		}
-	}
 
-	impl<T: Config> Pallet<T> {
-		/// Get the **Account ID** of the **Fragment Instance whose Fragment Definition ID is `definition_hash`,
-		/// whose Edition ID is `edition`** and whose Copy ID is `copy`**
+		/// **Withdraw** `amount` of the **sale proceeds** of the **Fragment Definition `definition_hash`** to `to`.
 		///
-		/// This Account ID is determinstically computed using the Fragment Definition ID `class_hash`, the Edition ID `edition` and the Copy ID `copy`
-		pub fn get_fragment_account_id(
+		/// The sale proceeds are stored in the **vault of the Fragment Definition** (i.e the account into which every payment of `buy()` is transferred),
+		/// in the currency of the Fragment Definition. The vault is always kept alive, so its existential deposit cannot be withdrawn.
+		///
+		/// Note: **Only** the **Fragment Definition's owner** (or, if the Fragment Definition's owner is the Proto-Fragment's owner, a member of the Proto-Fragment's Cluster that has the Role `PROCEEDS_ROLE_NAME`)
+		/// is **allowed** to withdraw the sale proceeds
+		///
+		/// # Arguments
+		///
+		/// * `origin` - **Origin** of the **extrinsic function**
+		/// * `definition_hash` - **ID** of the **Fragment Definition**
+		/// * `amount` - **Amount** of the **currency of the Fragment Definition** to withdraw
+		/// * `to` - **Account ID** that receives the withdrawn amount
+		#[pallet::weight(<T as Config>::WeightInfo::withdraw_proceeds())]
+		#[pallet::call_index(13)]
+		pub fn withdraw_proceeds(
+			origin: OriginFor<T>,
 			definition_hash: Hash128,
-			edition: InstanceUnit,
-			copy: InstanceUnit,
-		) -> T::AccountId {
-			let hash = blake2_256(
-				&[&b"fragments-account"[..], &definition_hash, &edition.encode(), &copy.encode()]
-					.concat(),
+			amount: u128,
+			to: <T::Lookup as StaticLookup>::Source,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let to = T::Lookup::lookup(to)?;
+
+			ensure!(amount > 0, Error::<T>::ParamsNotValid);
+
+			let definition = <Definitions<T>>::get(definition_hash).ok_or(Error::<T>::NotFound)?;
+			let proto: Proto<T::AccountId, T::BlockNumber> =
+				<Protos<T>>::get(definition.proto_hash).ok_or(Error::<T>::ProtoNotFound)?;
+
+			let is_definition_owner = definition.owner == who;
+			// the treasurers of the Proto-Fragment's Cluster can only withdraw the proceeds of the Fragment Definitions owned by the Proto-Fragment's owner
+			let is_cluster_treasurer = matches!(&proto.owner, ProtoOwner::User(owner) if *owner == definition.owner) &&
+				proto.cluster.map_or(false, |cluster_id| {
+					pallet_clusters::Pallet::<T>::has_role(&cluster_id, &who, PROCEEDS_ROLE_NAME)
+				});
+			ensure!(is_definition_owner || is_cluster_treasurer, Error::<T>::NoPermission);
+
+			let vault: T::AccountId =
+				sp_fragnova::get_account_id(b"pallet-fragments-create", &definition_hash);
+
+			// the vault must keep its existential deposit
+			Self::can_transfer_currency(&vault, &to, amount, definition.metadata.currency)?;
+
+			// ! Writing
+
+			Self::transfer_currency(&vault, &to, amount, definition.metadata.currency)?;
+
+			Self::deposit_event(Event::ProceedsWithdrawn { definition_hash, amount, to });
+
+			Ok(())
+		}
+
+		/// **Burn** (i.e permanently destroy) the **Fragment Instance** whose **Fragment Definition ID is `definition_hash`**,
+		/// whose **Edition ID is `edition_id`** and whose **Copy ID is `copy_id`**.
+		///
+		/// The Fragment Instance is removed from every storage item that references it (including any open secondary sale of it)
+		/// and the burned counts of the Fragment Definition are updated (see `BurnedCount` and `BurnedEditionsCount`).
+		///
+		/// Note: **Only** the **Fragment Instance's owner** is **allowed** to burn it,
+		/// and **only** if the Fragment Instance has the permission **FragmentPerms::EDIT**
+		///
+		/// # Arguments
+		///
+		/// * `origin` - **Origin** of the **extrinsic function**
+		/// * `definition_hash` - Fragment Definition ID of the Fragment Instance
+		/// * `edition_id` - Edition ID of the Fragment Instance
+		/// * `copy_id` - Copy ID of the Fragment instance
+		#[pallet::weight(<T as Config>::WeightInfo::burn())]
+		#[pallet::call_index(14)]
+		pub fn burn(
+			origin: OriginFor<T>,
+			definition_hash: Hash128,
+			edition_id: InstanceUnit,
+			copy_id: InstanceUnit,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let item_data = Self::can_edit_instance(&who, &definition_hash, edition_id, copy_id)?;
+
+			// ! Writing
+
+			Self::burn_instance(&who, &definition_hash, edition_id, copy_id, &item_data);
+
+			Ok(())
+		}
+
+		/// Set **whether burned Editions** of the **Fragment Definition `definition_hash`** **free up supply**,
+		/// i.e whether they are **no longer counted towards the `max_supply`** of the Fragment Definition.
+		///
+		/// Note: **Only** the **Fragment Definition's owner** is **allowed** to call this extrinsic,
+		/// and **only before** any **Fragment Instance** of the **Fragment Definition** has been **created**
+		///
+		/// # Arguments
+		///
+		/// * `origin` - **Origin** of the **extrinsic function**
+		/// * `definition_hash` - **ID** of the **Fragment Definition**
+		/// * `replenish` - Whether burned Editions free up supply
+		#[pallet::weight(<T as Config>::WeightInfo::set_burns_replenish_supply())]
+		#[pallet::call_index(15)]
+		pub fn set_burns_replenish_supply(
+			origin: OriginFor<T>,
+			definition_hash: Hash128,
+			replenish: bool,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let definition = <Definitions<T>>::get(definition_hash).ok_or(Error::<T>::NotFound)?;
+			ensure!(who == definition.owner, Error::<T>::NoPermission);
+
+			// the supply policy must be known by the buyers before the first Fragment Instance is created
+			ensure!(
+				!<EditionsCount<T>>::contains_key(definition_hash),
+				Error::<T>::InstancesAlreadyCreated
 			);
-			T::AccountId::decode(&mut &hash[..]).expect("T::AccountId should decode")
+
+			// ! Writing
+
+			<BurnsReplenishSupply<T>>::insert(definition_hash, replenish);
+
+			Self::deposit_event(Event::BurnsReplenishSupplyChanged { definition_hash, replenish });
+
+			Ok(())
 		}
 
-		/// Create `quantity` number of Fragment Instances from the Fragment Definition `definition_hash` and assigns their ownership to `to`
+		/// **Split** `amount` **items** off the **stack of stackable items** represented by a **Fragment Instance**
+		/// into a **new copy** of the Fragment Instance whose ownership is assigned to `to`.
+		///
+		/// The new copy has the same Edition ID, permissions, expiration, unique data and metadata as the original Fragment Instance,
+		/// but a new Copy ID. The `stack_amount` of the original Fragment Instance is reduced by `amount`.
+		///
+		/// Note: **Only** the **Fragment Instance's owner** is **allowed** to split it,
+		/// and **only** if the Fragment Instance has the permission **FragmentPerms::TRANSFER**
 		///
 		/// # Arguments
 		///
-		/// * `to` - **Account ID** to assign ownernship of the created Fragment instances to
-		/// * `definition_hash` - ID of the Fragment Definition
-		/// * `sale` - Struct **representing** a **sale of the Fragment Definition**, if the **Fragment Definition** is **currently on sale**
-		/// * `options` - **Enum** indicating whether to
-		/// **create one Fragment Instance with custom data attached to it** or whether to
-		/// **create multiple Fragment Instances (with no custom data attached)**
-		/// * `quantity` - **Number of Fragment Instances** to **create**
-		/// * `current_block_number` - **Current block number** of the **Fragnova Blockchain**
-		/// * `expiring_at` (*optional*) - **Block Number** that the **Fragment Instance** will **expire at**
-		/// * `amount` (*optional*) - If the Fragment Instance(s) represent a **stack of stackable items**
-		/// (for e.g gold coins or arrows - https://runescape.fandom.com/wiki/Stackable_items),
-		/// `amount` is the **number of items** to **top up** in the **stack of stackable items**
-		pub fn mint_fragments(
-			to: &T::AccountId,
-			definition_hash: &Hash128,
-			sale: Option<&PublishingData<T::BlockNumber>>,
-			options: &FragmentBuyOptions,
-			quantity: u64,
-			current_block_number: T::BlockNumber,
-			expiring_at: Option<T::BlockNumber>,
-			stack_amount: Option<Compact<InstanceUnit>>,
+		/// * `origin` - **Origin** of the **extrinsic function**
+		/// * `definition_hash` - Fragment Definition ID of the Fragment Instance
+		/// * `edition_id` - Edition ID of the Fragment Instance
+		/// * `copy_id` - Copy ID of the Fragment instance
+		/// * `amount` - Number of items to move into the new copy. Must be smaller than the `stack_amount` of the Fragment Instance.
+		/// * `to` - **Account ID** to assign the ownership of the new copy to
+		#[pallet::weight(<T as Config>::WeightInfo::split_stack())]
+		#[pallet::call_index(16)]
+		pub fn split_stack(
+			origin: OriginFor<T>,
+			definition_hash: Hash128,
+			edition_id: InstanceUnit,
+			copy_id: InstanceUnit,
+			amount: InstanceUnit,
+			to: <T::Lookup as StaticLookup>::Source,
 		) -> DispatchResult {
-			use frame_support::ensure;
+			let who = ensure_signed(origin)?;
+			let to = T::Lookup::lookup(to)?;
 
-			if let Some(expiring_at) = expiring_at {
-				ensure!(expiring_at > current_block_number, Error::<T>::ParamsNotValid); // Ensure `expiring_at` > `current_block_number`
-			}
+			Self::can_transfer_instance(&who, &definition_hash, edition_id, copy_id, None, None)?;
 
-			let fragment_data =
-				<Definitions<T>>::get(definition_hash).ok_or(Error::<T>::NotFound)?;
+			let mut item_data = <Fragments<T>>::get((definition_hash, edition_id, copy_id))
+				.ok_or(Error::<T>::NotFound)?;
 
-			// we need this to index transactions
-			let extrinsic_index = <frame_system::Pallet<T>>::extrinsic_index() // `<frame_system::Pallet<T>>::extrinsic_index()` is defined as: "Gets the index of extrinsic that is currently executing." (https://paritytech.github.io/substrate/master/frame_system/pallet/struct.Pallet.html#method.extrinsic_index)
-				.ok_or(Error::<T>::SystematicFailure)?;
+			let stack_amount: InstanceUnit =
+				item_data.stack_amount.ok_or(Error::<T>::NotStackable)?.into();
+			ensure!(amount > 0 && amount < stack_amount, Error::<T>::InsufficientStackAmount);
 
-			let (data_hash, data_len) = match options {
-				FragmentBuyOptions::UniqueData(data) => {
-					if fragment_data.unique.is_none() || quantity != 1 {
-						return Err(Error::<T>::ParamsNotValid.into())
-					}
+			let copy: InstanceUnit = <CopiesCount<T>>::get((definition_hash, edition_id))
+				.ok_or(Error::<T>::NotFound)?
+				.into();
+			let copy = copy + 1;
 
-					let data_hash = blake2_256(&data);
+			// ! Writing
 
-					ensure!(
-						!<UniqueData2Edition<T>>::contains_key(definition_hash, data_hash),
-						Error::<T>::UniqueDataExists
-					);
+			item_data.stack_amount = Some(Compact(stack_amount - amount));
+			<Fragments<T>>::insert((definition_hash, edition_id, copy_id), &item_data);
 
-					(Some(data_hash), Some(data.len()))
-				},
-				FragmentBuyOptions::Quantity(_) => {
-					if fragment_data.unique.is_some() {
-						return Err(Error::<T>::ParamsNotValid.into())
-					}
+			item_data.stack_amount = Some(Compact(amount));
+			<Fragments<T>>::insert((definition_hash, edition_id, copy), &item_data);
+			if let Some(attributes) =
+				<InstanceAttributes<T>>::get((definition_hash, edition_id, copy_id))
+			{
+				<InstanceAttributes<T>>::insert((definition_hash, edition_id, copy), attributes);
+			}
 
-					(None, None)
-				},
-			};
+			<CopiesCount<T>>::insert((definition_hash, edition_id), Compact(copy));
 
-			let existing: InstanceUnit =
-				<EditionsCount<T>>::get(&definition_hash).unwrap_or(Compact(0)).into();
+			<Owners<T>>::append(definition_hash, to.clone(), (Compact(edition_id), Compact(copy)));
+			<InstanceOwner<T>>::insert((definition_hash, edition_id, copy), to.clone());
+			<Inventory<T>>::append(
+				to.clone(),
+				definition_hash,
+				(Compact(edition_id), Compact(copy)),
+			);
 
-			if let Some(sale) = sale {
-				// if limited amount let's reduce the amount of units left
-				if let Some(units_left) = sale.units_left {
-					if quantity > units_left.into() {
-						return Err(Error::<T>::PublishedQuantityReached.into())
-					} else {
-						<Publishing<T>>::mutate(&*definition_hash, |sale| {
-							if let Some(sale) = sale {
-								let left: InstanceUnit = units_left.into();
-								sale.units_left = Some(Compact(left - quantity));
-							}
-						});
-					}
-				}
-			} else {
-				// We still don't wanna go over supply limit
-				if let Some(max_supply) = fragment_data.max_supply {
-					let max: InstanceUnit = max_supply.into();
-					let left = max.saturating_sub(existing); // `left` = `max` - `existing`
-					if quantity > left {
-						// Ensure the function parameter `quantity` is smaller than or equal to `left`
-						return Err(Error::<T>::MaxSupplyReached.into())
-					}
-				}
+			if let Some(expiring_at) = item_data.expiring_at {
+				<ExpirationQueue<T>>::insert(expiring_at, (definition_hash, edition_id, copy), ());
 			}
 
-			// ! Writing if successful
+			Self::deposit_event(Event::InventoryAdded {
+				account_id: to,
+				definition_hash,
+				fragment_id: (edition_id, copy),
+			});
+			Self::deposit_event(Event::StackSplit {
+				definition_hash,
+				fragment_id: (edition_id, copy_id),
+				new_fragment_id: (edition_id, copy),
+				amount,
+			});
 
-			<Definitions<T>>::mutate(definition_hash, |fragment| {
-				// Get the `FragmentDefinition` struct from `definition_hash`
-				if let Some(fragment) = fragment {
-					for id in existing..(existing + quantity) {
-						let id = id + 1u64;
-						let cid = Compact(id); // `cid` stands for "compact id"
+			Ok(())
+		}
 
-						<Fragments<T>>::insert(
-							(definition_hash, id, 1),
-							FragmentInstance {
-								permissions: fragment.permissions,
-								created_at: current_block_number,
-								custom_data: data_hash,
-								expiring_at,
-								stack_amount,
-								metadata: BTreeMap::new(),
-								_reserved1: None,
-								_reserved2: None,
-								_reserved3: None,
-							},
-						);
+		/// **Merge** the **stack of stackable items** of the **Fragment Instance `other_copy_id`** into the
+		/// **stack of stackable items** of the **Fragment Instance `copy_id`** (both Fragment Instances must have the same Edition ID).
+		///
+		/// The Fragment Instance `other_copy_id` is removed afterwards.
+		///
+		/// Note: **Only** the **owner** of **both Fragment Instances** is **allowed** to merge them,
+		/// and **only** if **both** have the permission **FragmentPerms::EDIT**.
+		/// Both Fragment Instances must also have the **same permissions and expiration**, so that merging never changes what can be done with an item.
+		///
+		/// # Arguments
+		///
+		/// * `origin` - **Origin** of the **extrinsic function**
+		/// * `definition_hash` - Fragment Definition ID of the Fragment Instances
+		/// * `edition_id` - Edition ID of the Fragment Instances
+		/// * `copy_id` - Copy ID of the Fragment instance that receives the items
+		/// * `other_copy_id` - Copy ID of the Fragment instance whose items are moved (and which is removed)
+		#[pallet::weight(<T as Config>::WeightInfo::merge_stacks())]
+		#[pallet::call_index(17)]
+		pub fn merge_stacks(
+			origin: OriginFor<T>,
+			definition_hash: Hash128,
+			edition_id: InstanceUnit,
+			copy_id: InstanceUnit,
+			other_copy_id: InstanceUnit,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
 
-						<CopiesCount<T>>::insert((definition_hash, id), Compact(1));
+			ensure!(copy_id != other_copy_id, Error::<T>::ParamsNotValid);
 
-						<Inventory<T>>::append(to.clone(), definition_hash, (cid, Compact(1))); // **Add** the **Fragment Intstance whose Fragment Definition is `definition_hash`, Edition ID is `cid` and Copy ID is 1**  to the **inventory of `to`**
+			let mut item_data =
+				Self::can_edit_instance(&who, &definition_hash, edition_id, copy_id)?;
+			let other_item_data =
+				Self::can_edit_instance(&who, &definition_hash, edition_id, other_copy_id)?;
 
-						<Owners<T>>::append(definition_hash, to.clone(), (cid, Compact(1)));
+			let stack_amount: InstanceUnit =
+				item_data.stack_amount.ok_or(Error::<T>::NotStackable)?.into();
+			let other_stack_amount: InstanceUnit =
+				other_item_data.stack_amount.ok_or(Error::<T>::NotStackable)?.into();
 
-						if let Some(expiring_at) = expiring_at {
-							<Expirations<T>>::append(
-								expiring_at,
-								(*definition_hash, cid, Compact(1)),
-							);
-						}
-						Self::deposit_event(Event::InventoryAdded {
-							account_id: to.clone(),
-							definition_hash: *definition_hash,
-							fragment_id: (id, 1),
-						});
-					}
+			ensure!(
+				item_data.permissions == other_item_data.permissions &&
+					item_data.expiring_at == other_item_data.expiring_at &&
+					<InstanceAttributes<T>>::get((definition_hash, edition_id, copy_id)) ==
+						<InstanceAttributes<T>>::get((
+							definition_hash,
+							edition_id,
+							other_copy_id
+						)),
+				Error::<T>::StacksNotCompatible
+			);
 
-					if let (Some(data_hash), Some(data_len)) = (data_hash, data_len) {
-						<UniqueData2Edition<T>>::insert(definition_hash, data_hash, existing); // if `data` exists, `quantity` is ensured to be 1
+			// ! Writing
 
-						// index immutable data for IPFS discovery
-						transaction_index::index(extrinsic_index, data_len as u32, data_hash);
-					}
+			item_data.stack_amount = Some(Compact(stack_amount.saturating_add(other_stack_amount)));
+			<Fragments<T>>::insert((definition_hash, edition_id, copy_id), item_data);
 
-					<EditionsCount<T>>::insert(definition_hash, Compact(existing + quantity));
-				}
+			Self::remove_instance(
+				&who,
+				&definition_hash,
+				edition_id,
+				other_copy_id,
+				&other_item_data,
+			);
+
+			Self::deposit_event(Event::InventoryRemoved {
+				account_id: who,
+				definition_hash,
+				fragment_id: (edition_id, other_copy_id),
+			});
+			Self::deposit_event(Event::StacksMerged {
+				definition_hash,
+				fragment_id: (edition_id, copy_id),
+				merged_fragment_id: (edition_id, other_copy_id),
+				amount: other_stack_amount,
 			});
 
-			Ok(())
+			Ok(())
+		}
+
+		/// **Consume** `amount` **items** of the **stack of stackable items** represented by a **Fragment Instance**.
+		///
+		/// If the stack becomes empty, the Fragment Instance is burned (see `burn()`).
+		///
+		/// Note: **Only** the **Fragment Instance's owner** is **allowed** to consume it,
+		/// and **only** if the Fragment Instance has the permission **FragmentPerms::EDIT**
+		///
+		/// # Arguments
+		///
+		/// * `origin` - **Origin** of the **extrinsic function**
+		/// * `definition_hash` - Fragment Definition ID of the Fragment Instance
+		/// * `edition_id` - Edition ID of the Fragment Instance
+		/// * `copy_id` - Copy ID of the Fragment instance
+		/// * `amount` - Number of items to consume
+		#[pallet::weight(<T as Config>::WeightInfo::consume())]
+		#[pallet::call_index(18)]
+		pub fn consume(
+			origin: OriginFor<T>,
+			definition_hash: Hash128,
+			edition_id: InstanceUnit,
+			copy_id: InstanceUnit,
+			amount: InstanceUnit,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let mut item_data =
+				Self::can_edit_instance(&who, &definition_hash, edition_id, copy_id)?;
+
+			let stack_amount: InstanceUnit =
+				item_data.stack_amount.ok_or(Error::<T>::NotStackable)?.into();
+			ensure!(amount > 0 && amount <= stack_amount, Error::<T>::InsufficientStackAmount);
+
+			// ! Writing
+
+			let left = stack_amount - amount;
+			if left == 0 {
+				Self::burn_instance(&who, &definition_hash, edition_id, copy_id, &item_data);
+			} else {
+				item_data.stack_amount = Some(Compact(left));
+				<Fragments<T>>::insert((definition_hash, edition_id, copy_id), item_data);
+			}
+
+			Self::deposit_event(Event::StackConsumed {
+				definition_hash,
+				fragment_id: (edition_id, copy_id),
+				amount,
+				left,
+			});
+
+			Ok(())
+		}
+
+		/// Create **Fragment Instances** from the **Fragment Definition `definition_hash`** and
+		/// **assign their ownership** to **multiple recipients** (i.e a batched `mint()`)
+		///
+		/// Note: **Each created Fragment instance** will have a **different Edition ID** and a **Copy ID of "1"**.
+		///
+		/// Note: **Only** the **Fragment Definition's owner** is **allowed** to
+		/// create instances of the Fragment in this extrinsic function,
+		/// and **only** if the Fragment Definition does not have the **unique** capability.
+		///
+		/// # Arguments
+		///
+		/// * `origin` - **Origin** of the **extrinsic function**
+		/// * `definition_hash` - **ID* of the **Fragment Definition**
+		/// * `recipients` - List of recipients along with the **number of Fragment Instances** to create for each of them
+		/// (at most `MAX_MINT_RECIPIENTS`)
+		/// * `stack_amount` (*optional*) - If the Fragment Instances represent a **stack of stackable items**
+		/// (for e.g gold coins or arrows - https://runescape.fandom.com/wiki/Stackable_items),
+		/// `stack_amount` is the **number of items** to **top up** in the **stack of stackable items**
+		/// * `expiring_at` (*optional*) - Block number that the created Fragment Instances expire at
+		#[pallet::weight(<T as Config>::WeightInfo::mint_to_many(
+			recipients.len() as u32,
+			recipients.iter().fold(0u64, |acc, (_, q)| acc.saturating_add(*q)).min(u32::MAX as u64) as u32
+		))]
+		#[pallet::call_index(19)]
+		pub fn mint_to_many(
+			origin: OriginFor<T>,
+			definition_hash: Hash128,
+			recipients: Vec<(<T::Lookup as StaticLookup>::Source, InstanceUnit)>,
+			stack_amount: Option<InstanceUnit>,
+			expiring_at: Option<T::BlockNumber>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			ensure!(recipients.len() <= MAX_MINT_RECIPIENTS, Error::<T>::TooManyRecipients);
+
+			let current_block_number = <frame_system::Pallet<T>>::block_number();
+
+			let definition = <Definitions<T>>::get(definition_hash).ok_or(Error::<T>::NotFound)?;
+			let proto_hash = definition.proto_hash;
+			ensure!(who == definition.owner, Error::<T>::NoPermission); // Ensure `who` is the owner of the Fragment Definition
+
+			ensure!(
+				!<DetachedHashes<T>>::contains_key(&DetachHash::Proto(proto_hash)),
+				Error::<T>::Detached
+			); // Ensure `proto_hash` isn't detached
+
+			ensure!(definition.unique.is_none(), Error::<T>::ParamsNotValid); // Unique Fragment Instances require their own unique data
+
+			let recipients = recipients
+				.into_iter()
+				.map(|(to, quantity)| Ok((T::Lookup::lookup(to)?, quantity)))
+				.collect::<Result<Vec<(T::AccountId, InstanceUnit)>, DispatchError>>()?;
+
+			// ! Writing
+
+			for (to, quantity) in recipients {
+				Self::mint_fragments(
+					&to,
+					&definition_hash,
+					None, // PublishingData (optional)
+					&FragmentBuyOptions::Quantity(quantity),
+					quantity,
+					current_block_number,
+					expiring_at,
+					stack_amount.map(|x| Compact(x)),
+				)?; // non-unique, so there is no validator weight
+			}
+
+			Ok(())
+		}
+
+		/// Start a **claim campaign** (i.e an airdrop) for the **Fragment Definition `definition_hash`**
+		///
+		/// Every account in the Merkle Tree whose Merkle Root is `merkle_root` will be able to claim (once)
+		/// its quantity of Fragment Instances for free using `claim()` (see `ClaimCampaign`).
+		///
+		/// Note: **Only** the **Fragment Definition's owner** is **allowed** to start a claim campaign.
+		///
+		/// # Arguments
+		///
+		/// * `origin` - **Origin** of the **extrinsic function**
+		/// * `definition_hash` - **ID* of the **Fragment Definition**
+		/// * `merkle_root` - Merkle Root of the `(account_id, quantity)` pairs that can be claimed
+		/// * `expiration` (*optional*) - Block number that the claim campaign ends at
+		/// * `instance_expiration` (*optional*) - Block number that the claimed Fragment Instances expire at
+		/// * `stack_amount` (*optional*) - If the Fragment Instances represent a **stack of stackable items**,
+		/// the **number of items** in each claimed **stack of stackable items**
+		#[pallet::weight(<T as Config>::WeightInfo::start_claim())]
+		#[pallet::call_index(20)]
+		pub fn start_claim(
+			origin: OriginFor<T>,
+			definition_hash: Hash128,
+			merkle_root: Hash256,
+			expiration: Option<T::BlockNumber>,
+			instance_expiration: Option<T::BlockNumber>,
+			stack_amount: Option<InstanceUnit>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let definition = <Definitions<T>>::get(definition_hash).ok_or(Error::<T>::NotFound)?;
+			let proto_hash = definition.proto_hash;
+			ensure!(who == definition.owner, Error::<T>::NoPermission); // Ensure `who` is the owner of the Fragment Definition
+
+			ensure!(
+				!<DetachedHashes<T>>::contains_key(&DetachHash::Proto(proto_hash)),
+				Error::<T>::Detached
+			); // Ensure `proto_hash` isn't detached
+
+			ensure!(
+				!<ClaimCampaigns<T>>::contains_key(&definition_hash),
+				Error::<T>::ClaimCampaignAlreadyOpen
+			);
+
+			let current_block_number = <frame_system::Pallet<T>>::block_number();
+			if let Some(expiration) = expiration {
+				ensure!(expiration > current_block_number, Error::<T>::ParamsNotValid); // Ensure `expiration` > `current_block_number`
+			}
+			if let Some(instance_expiration) = instance_expiration {
+				ensure!(instance_expiration > current_block_number, Error::<T>::ParamsNotValid);
+				// Ensure `instance_expiration` > `current_block_number`
+			}
+
+			// ! Writing
+
+			<ClaimCampaigns<T>>::insert(
+				definition_hash,
+				ClaimCampaign {
+					merkle_root,
+					expiration,
+					instance_expiration,
+					stack_amount: stack_amount.map(|x| Compact(x)),
+				},
+			);
+
+			Self::deposit_event(Event::ClaimCampaignStarted { definition_hash, merkle_root });
+
+			Ok(())
+		}
+
+		/// End the **claim campaign** of the **Fragment Definition `definition_hash`**
+		///
+		/// Note: **Only** the **Fragment Definition's owner** is **allowed** to end a claim campaign.
+		///
+		/// # Arguments
+		///
+		/// * `origin` - **Origin** of the **extrinsic function**
+		/// * `definition_hash` - **ID* of the **Fragment Definition**
+		#[pallet::weight(<T as Config>::WeightInfo::end_claim())]
+		#[pallet::call_index(21)]
+		pub fn end_claim(origin: OriginFor<T>, definition_hash: Hash128) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let definition = <Definitions<T>>::get(definition_hash).ok_or(Error::<T>::NotFound)?;
+			ensure!(who == definition.owner, Error::<T>::NoPermission); // Ensure `who` is the owner of the Fragment Definition
+
+			ensure!(<ClaimCampaigns<T>>::contains_key(&definition_hash), Error::<T>::NotFound);
+
+			// ! Writing
+
+			<ClaimCampaigns<T>>::remove(&definition_hash);
+
+			Self::deposit_event(Event::ClaimCampaignEnded { definition_hash });
+
+			Ok(())
+		}
+
+		/// Claim the **Fragment Instances** that the Caller Account ID `origin` is entitled to
+		/// in the **current claim campaign** of the **Fragment Definition `definition_hash`** (see `start_claim()`)
+		///
+		/// Note: Each account can only claim once per claim campaign.
+		/// The claimed Fragment Instances count towards the Fragment Definition's maximum supply.
+		///
+		/// # Arguments
+		///
+		/// * `origin` - **Origin** of the **extrinsic function**
+		/// * `definition_hash` - **ID* of the **Fragment Definition**
+		/// * `options` - **Enum** indicating whether to
+		/// **claim one Fragment Instance with custom data attached to it** (if the Fragment Definition has the unique capability) or whether to
+		/// **claim multiple Fragment Instances (with no custom data attached)**.
+		/// In the latter case, the quantity must be the one that is in the Merkle Tree.
+		/// * `proof` - Merkle Proof that `blake2_256((origin, quantity).encode())` is a leaf of the claim campaign's Merkle Tree (see `sp_fragnova::merkle::merkle_proof()`)
+		#[pallet::weight(match options {
+		FragmentBuyOptions::Quantity(q) => <T as Config>::WeightInfo::claim_definition_that_has_non_unique_capability(*q as u32),
+		FragmentBuyOptions::UniqueData(d) => <T as Config>::WeightInfo::claim_definition_that_has_unique_capability(d.len() as u32)
+			.saturating_add(Weight::from_ref_time(MAX_UNIQUE_DATA_VALIDATOR_WEIGHT)) // the validator of the unique data (if any) is paid by the caller
+		})]
+		#[pallet::call_index(22)]
+		pub fn claim(
+			origin: OriginFor<T>,
+			definition_hash: Hash128,
+			options: FragmentBuyOptions,
+			proof: MerkleProof,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+
+			ensure!(proof.proof.len() <= MAX_MERKLE_PROOF_LENGTH, Error::<T>::ParamsNotValid);
+
+			let current_block_number = <frame_system::Pallet<T>>::block_number();
+
+			let campaign =
+				<ClaimCampaigns<T>>::get(&definition_hash).ok_or(Error::<T>::NotFound)?;
+
+			if let Some(expiration) = campaign.expiration {
+				ensure!(current_block_number < expiration, Error::<T>::Expired);
+			}
+
+			let proto_hash =
+				<Definitions<T>>::get(definition_hash).ok_or(Error::<T>::NotFound)?.proto_hash; // Get `proto_hash` from `definition_hash`
+			ensure!(
+				!<DetachedHashes<T>>::contains_key(&DetachHash::Proto(proto_hash)),
+				Error::<T>::Detached
+			); // Ensure `proto_hash` isn't detached
+
+			ensure!(
+				!<Claimed<T>>::contains_key(&campaign.merkle_root, &who),
+				Error::<T>::AlreadyClaimed
+			);
+
+			let quantity = match options {
+				// Number of fragment instances to claim
+				FragmentBuyOptions::Quantity(quantity) => u64::from(quantity),
+				_ => 1u64,
+			};
+
+			let leaf = blake2_256(&(&who, quantity).encode());
+			ensure!(
+				merkle::verify_proof(&campaign.merkle_root, &leaf, &proof),
+				Error::<T>::NotClaimable
+			);
+
+			// ! Writing
+
+			let validator_weight = Self::mint_fragments(
+				&who,
+				&definition_hash,
+				None, // PublishingData (optional)
+				&options,
+				quantity,
+				current_block_number,
+				campaign.instance_expiration,
+				campaign.stack_amount,
+			)?;
+
+			<Claimed<T>>::insert(&campaign.merkle_root, &who, Compact(quantity));
+
+			Self::deposit_event(Event::Claimed { definition_hash, account_id: who, quantity });
+
+			// refund the weight that the validator of the unique data did not consume
+			Ok(match &options {
+				FragmentBuyOptions::UniqueData(data) => Some(
+					<T as Config>::WeightInfo::claim_definition_that_has_unique_capability(
+						data.len() as u32,
+					)
+					.saturating_add(validator_weight),
+				)
+				.into(),
+				FragmentBuyOptions::Quantity(_) => ().into(),
+			})
+		}
+
+		/// **Renew** a **Fragment Instance that expires**, i.e extend its expiration by its original lifetime
+		/// (the number of blocks between its creation and its current expiration),
+		/// by paying the **price of its Fragment Definition** into the **Fragment Definition's vault**.
+		///
+		/// Note: **Only** the **Fragment Instance's owner** is **allowed** to renew it,
+		/// and **only** if its **Fragment Definition is published** (see `publish()`)
+		///
+		/// # Arguments
+		///
+		/// * `origin` - **Origin** of the **extrinsic function**
+		/// * `definition_hash` - Fragment Definition ID of the Fragment Instance
+		/// * `edition_id` - Edition ID of the Fragment Instance
+		/// * `copy_id` - Copy ID of the Fragment instance
+		#[pallet::weight(<T as Config>::WeightInfo::renew())]
+		#[pallet::call_index(23)]
+		pub fn renew(
+			origin: OriginFor<T>,
+			definition_hash: Hash128,
+			edition_id: InstanceUnit,
+			copy_id: InstanceUnit,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			ensure!(
+				!<DetachedHashes<T>>::contains_key(&DetachHash::Instance(
+					definition_hash,
+					Compact(edition_id),
+					Compact(copy_id)
+				)),
+				Error::<T>::Detached
+			);
+
+			let current_block_number = <frame_system::Pallet<T>>::block_number();
+
+			let mut item_data = <Fragments<T>>::get((definition_hash, edition_id, copy_id))
+				.ok_or(Error::<T>::NotFound)?;
+			ensure!(!Self::is_expired(&item_data, current_block_number), Error::<T>::NotFound);
+
+			let owner = <InstanceOwner<T>>::get((definition_hash, edition_id, copy_id))
+				.ok_or(Error::<T>::NotFound)?;
+			ensure!(who == owner, Error::<T>::NoPermission);
+
+			let expiring_at = item_data.expiring_at.ok_or(Error::<T>::NotRenewable)?;
+			let sale = <Publishing<T>>::get(&definition_hash).ok_or(Error::<T>::NotRenewable)?;
+			let currency = <Definitions<T>>::get(&definition_hash)
+				.ok_or(Error::<T>::NotFound)?
+				.metadata
+				.currency;
+
+			let vault: T::AccountId =
+				sp_fragnova::get_account_id(b"pallet-fragments-create", &definition_hash);
+			let price: u128 = sale.price.into();
+
+			let lifetime = expiring_at.saturating_sub(item_data.created_at);
+			let new_expiring_at = expiring_at.saturating_add(lifetime);
+
+			Self::can_transfer_currency(&who, &vault, price, currency)?;
+
+			// ! Writing
+
+			Self::transfer_currency(&who, &vault, price, currency)?;
+
+			<ExpirationQueue<T>>::remove(expiring_at, (definition_hash, edition_id, copy_id));
+			<ExpirationQueue<T>>::insert(
+				new_expiring_at,
+				(definition_hash, edition_id, copy_id),
+				(),
+			);
+
+			item_data.expiring_at = Some(new_expiring_at);
+			<Fragments<T>>::insert((definition_hash, edition_id, copy_id), item_data);
+
+			Self::deposit_event(Event::Renewed {
+				definition_hash,
+				fragment_id: (edition_id, copy_id),
+				expiring_at: new_expiring_at,
+			});
+
+			Ok(())
+		}
+
+		/// **Rent out** a **Fragment Instance** to `to` **until the block number `until`**.
+		///
+		/// During the rental, `to` has the **use rights** of the Fragment Instance (see `get_instance_user()`),
+		/// but neither `to` nor the owner can transfer or edit the Fragment Instance.
+		/// Once the block number `until` is reached, the use rights automatically return to the owner.
+		///
+		/// Note: **Only** the **Fragment Instance's owner** is **allowed** to rent it out,
+		/// and **only** if the Fragment Instance has the permission **FragmentPerms::TRANSFER**
+		///
+		/// # Arguments
+		///
+		/// * `origin` - **Origin** of the **extrinsic function**
+		/// * `definition_hash` - Fragment Definition ID of the Fragment Instance
+		/// * `edition_id` - Edition ID of the Fragment Instance
+		/// * `copy_id` - Copy ID of the Fragment instance
+		/// * `to` - **Account ID** to rent the Fragment Instance out to
+		/// * `until` - Block number that the rental ends at
+		#[pallet::weight(<T as Config>::WeightInfo::rent())]
+		#[pallet::call_index(24)]
+		pub fn rent(
+			origin: OriginFor<T>,
+			definition_hash: Hash128,
+			edition_id: InstanceUnit,
+			copy_id: InstanceUnit,
+			to: <T::Lookup as StaticLookup>::Source,
+			until: T::BlockNumber,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let to = T::Lookup::lookup(to)?;
+			ensure!(to != who, Error::<T>::ParamsNotValid);
+
+			Self::can_transfer_instance(&who, &definition_hash, edition_id, copy_id, None, None)?;
+
+			ensure!(until > <frame_system::Pallet<T>>::block_number(), Error::<T>::ParamsNotValid);
+
+			// ! Writing
+
+			<Rentals<T>>::insert(
+				(definition_hash, edition_id, copy_id),
+				Rental { renter: to.clone(), until },
+			);
+
+			Self::deposit_event(Event::Rented {
+				definition_hash,
+				fragment_id: (edition_id, copy_id),
+				renter: to,
+				until,
+			});
+
+			Ok(())
+		}
+
+		/// **End** the **rental** of a **Fragment Instance** before its end block number,
+		/// i.e return the use rights of the Fragment Instance to its owner.
+		///
+		/// Note: **Only** the **Fragment Instance's renter** is **allowed** to end its rental.
+		///
+		/// # Arguments
+		///
+		/// * `origin` - **Origin** of the **extrinsic function**
+		/// * `definition_hash` - Fragment Definition ID of the Fragment Instance
+		/// * `edition_id` - Edition ID of the Fragment Instance
+		/// * `copy_id` - Copy ID of the Fragment instance
+		#[pallet::weight(<T as Config>::WeightInfo::end_rental())]
+		#[pallet::call_index(25)]
+		pub fn end_rental(
+			origin: OriginFor<T>,
+			definition_hash: Hash128,
+			edition_id: InstanceUnit,
+			copy_id: InstanceUnit,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let rental = Self::get_active_rental(&definition_hash, edition_id, copy_id)
+				.ok_or(Error::<T>::NotRented)?;
+			ensure!(who == rental.renter, Error::<T>::NoPermission);
+
+			// ! Writing
+
+			<Rentals<T>>::remove((definition_hash, edition_id, copy_id));
+
+			Self::deposit_event(Event::RentalEnded {
+				definition_hash,
+				fragment_id: (edition_id, copy_id),
+			});
+
+			Ok(())
+		}
+
+		/// **Approve** `operator` to **transfer a Fragment Instance on behalf of its owner** (see `give()`).
+		/// If `operator` is `None`, the current approval of the Fragment Instance is revoked.
+		///
+		/// A Fragment Instance has at most one approved operator, and its approval is cleared once it is transferred.
+		///
+		/// Note: **Only** the **Fragment Instance's owner** or an **operator approved for all of the owner's Fragment Instances of the Fragment Definition**
+		/// (see `set_approval_for_all()`) is **allowed** to approve an operator
+		///
+		/// # Arguments
+		///
+		/// * `origin` - **Origin** of the **extrinsic function**
+		/// * `definition_hash` - Fragment Definition ID of the Fragment Instance
+		/// * `edition_id` - Edition ID of the Fragment Instance
+		/// * `copy_id` - Copy ID of the Fragment instance
+		/// * `operator` (*optional*) - **Account ID** to approve
+		#[pallet::weight(<T as Config>::WeightInfo::approve())]
+		#[pallet::call_index(26)]
+		pub fn approve(
+			origin: OriginFor<T>,
+			definition_hash: Hash128,
+			edition_id: InstanceUnit,
+			copy_id: InstanceUnit,
+			operator: Option<<T::Lookup as StaticLookup>::Source>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let item_data = <Fragments<T>>::get((definition_hash, edition_id, copy_id))
+				.ok_or(Error::<T>::NotFound)?;
+			ensure!(
+				!Self::is_expired(&item_data, <frame_system::Pallet<T>>::block_number()),
+				Error::<T>::NotFound
+			);
+
+			let owner = <InstanceOwner<T>>::get((definition_hash, edition_id, copy_id))
+				.ok_or(Error::<T>::NotFound)?;
+			ensure!(
+				who == owner ||
+					<OperatorApprovals<T>>::contains_key((&owner, definition_hash, &who)),
+				Error::<T>::NoPermission
+			);
+
+			let operator = operator.map(T::Lookup::lookup).transpose()?;
+			if let Some(operator) = &operator {
+				ensure!(*operator != owner, Error::<T>::ParamsNotValid);
+			}
+
+			// ! Writing
+
+			match &operator {
+				Some(operator) =>
+					<InstanceApprovals<T>>::insert((definition_hash, edition_id, copy_id), operator),
+				None => <InstanceApprovals<T>>::remove((definition_hash, edition_id, copy_id)),
+			}
+
+			Self::deposit_event(Event::Approval {
+				definition_hash,
+				fragment_id: (edition_id, copy_id),
+				owner,
+				operator,
+			});
+
+			Ok(())
+		}
+
+		/// **Approve** (or **disapprove**) `operator` to **transfer every Fragment Instance of the Fragment Definition `definition_hash`
+		/// that is owned by `origin`** (including the ones that `origin` will own in the future).
+		///
+		/// Unlike the approval of a single Fragment Instance (see `approve()`), this approval is not cleared when a Fragment Instance is transferred.
+		///
+		/// # Arguments
+		///
+		/// * `origin` - **Origin** of the **extrinsic function**
+		/// * `definition_hash` - Fragment Definition ID
+		/// * `operator` - **Account ID** to approve (or disapprove)
+		/// * `approved` - Whether `operator` is approved
+		#[pallet::weight(<T as Config>::WeightInfo::set_approval_for_all())]
+		#[pallet::call_index(27)]
+		pub fn set_approval_for_all(
+			origin: OriginFor<T>,
+			definition_hash: Hash128,
+			operator: <T::Lookup as StaticLookup>::Source,
+			approved: bool,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			ensure!(<Definitions<T>>::contains_key(&definition_hash), Error::<T>::NotFound);
+
+			let operator = T::Lookup::lookup(operator)?;
+			ensure!(operator != who, Error::<T>::ParamsNotValid);
+
+			// ! Writing
+
+			if approved {
+				<OperatorApprovals<T>>::insert((&who, definition_hash, &operator), ());
+			} else {
+				<OperatorApprovals<T>>::remove((&who, definition_hash, &operator));
+			}
+
+			Self::deposit_event(Event::ApprovalForAll {
+				definition_hash,
+				owner: who,
+				operator,
+				approved,
+			});
+
+			Ok(())
+		}
+
+		/// **Propose a swap** of the assets `offer` (owned by `origin`) for the assets `ask` (owned by `taker`).
+		///
+		/// The Fragment Instances and Proto-Fragments of `offer` are **locked until the block number `expiration`**,
+		/// so that they cannot be transferred while the swap is pending.
+		/// The amount of currency of each side is only transferred when the swap is accepted (see `accept_swap()`).
+		///
+		/// # Arguments
+		///
+		/// * `origin` - **Origin** of the **extrinsic function**
+		/// * `taker` - **Account ID** that can accept the swap
+		/// * `offer` - Assets that `origin` gives to `taker`
+		/// * `ask` - Assets that `taker` gives to `origin`
+		/// * `expiration` - Block number that the swap expires at
+		#[pallet::weight(<T as Config>::WeightInfo::propose_swap(
+			(offer.instances.len() + offer.protos.len()).min(MAX_SWAP_ITEMS) as u32
+		))]
+		#[pallet::call_index(28)]
+		pub fn propose_swap(
+			origin: OriginFor<T>,
+			taker: <T::Lookup as StaticLookup>::Source,
+			offer: SwapBundle<T::AssetId>,
+			ask: SwapBundle<T::AssetId>,
+			expiration: T::BlockNumber,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let taker = T::Lookup::lookup(taker)?;
+			ensure!(taker != who, Error::<T>::ParamsNotValid);
+
+			ensure!(
+				expiration > <frame_system::Pallet<T>>::block_number(),
+				Error::<T>::ParamsNotValid
+			);
+
+			Self::ensure_valid_swap_bundle(&ask)?;
+			ensure!(!offer.is_empty() || !ask.is_empty(), Error::<T>::ParamsNotValid);
+
+			Self::can_transfer_swap_bundle(&who, &taker, &offer, false)?;
+
+			// ! Writing
+
+			for (definition_hash, edition_id, copy_id) in offer.instances.iter() {
+				<LockedInstances<T>>::insert((definition_hash, edition_id, copy_id), expiration);
+			}
+			for proto_hash in offer.protos.iter() {
+				pallet_protos::Pallet::<T>::lock_proto(proto_hash, expiration);
+			}
+
+			let swap_id = <NextSwapId<T>>::get();
+			<NextSwapId<T>>::put(swap_id.saturating_add(1));
+
+			<Swaps<T>>::insert(
+				swap_id,
+				Swap { maker: who.clone(), taker: taker.clone(), offer, ask, expiration },
+			);
+
+			Self::deposit_event(Event::SwapProposed { swap_id, maker: who, taker });
+
+			Ok(())
+		}
+
+		/// **Accept** the **pending swap** `swap_id`, i.e **atomically exchange** the assets of both sides of the swap.
+		///
+		/// Note: **Only** the **taker of the swap** is **allowed** to accept it, and **only before it expires**
+		///
+		/// # Arguments
+		///
+		/// * `origin` - **Origin** of the **extrinsic function**
+		/// * `swap_id` - Swap ID
+		#[pallet::weight(<T as Config>::WeightInfo::accept_swap(2 * MAX_SWAP_ITEMS as u32))]
+		#[pallet::call_index(29)]
+		pub fn accept_swap(origin: OriginFor<T>, swap_id: u64) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let swap = <Swaps<T>>::get(swap_id).ok_or(Error::<T>::SwapNotFound)?;
+			ensure!(who == swap.taker, Error::<T>::NoPermission);
+			ensure!(
+				<frame_system::Pallet<T>>::block_number() < swap.expiration,
+				Error::<T>::Expired
+			);
+
+			// the assets of the maker are locked by this swap
+			Self::can_transfer_swap_bundle(&swap.maker, &swap.taker, &swap.offer, true)?;
+			Self::can_transfer_swap_bundle(&swap.taker, &swap.maker, &swap.ask, false)?;
+
+			// ! Writing
+
+			Self::unlock_swap_bundle(&swap.maker, &swap.offer);
+			<Swaps<T>>::remove(swap_id);
+
+			Self::transfer_swap_bundle(&swap.maker, &swap.taker, &swap.offer)?;
+			Self::transfer_swap_bundle(&swap.taker, &swap.maker, &swap.ask)?;
+
+			Self::deposit_event(Event::SwapAccepted { swap_id });
+
+			Ok(())
+		}
+
+		/// **Cancel** the **pending swap** `swap_id` and **unlock** the assets that were offered in it.
+		///
+		/// Note: **Only** the **maker of the swap** is **allowed** to cancel it before it expires.
+		/// Once it has expired, anyone can cancel it.
+		///
+		/// # Arguments
+		///
+		/// * `origin` - **Origin** of the **extrinsic function**
+		/// * `swap_id` - Swap ID
+		#[pallet::weight(<T as Config>::WeightInfo::cancel_swap(MAX_SWAP_ITEMS as u32))]
+		#[pallet::call_index(30)]
+		pub fn cancel_swap(origin: OriginFor<T>, swap_id: u64) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let swap = <Swaps<T>>::get(swap_id).ok_or(Error::<T>::SwapNotFound)?;
+			ensure!(
+				who == swap.maker || <frame_system::Pallet<T>>::block_number() >= swap.expiration,
+				Error::<T>::NoPermission
+			);
+
+			// ! Writing
+
+			Self::unlock_swap_bundle(&swap.maker, &swap.offer);
+			<Swaps<T>>::remove(swap_id);
+
+			Self::deposit_event(Event::SwapCancelled { swap_id });
+
+			Ok(())
+		}
+
+		/// **Nest** the **Fragment Instance** `(definition_hash, edition_id, copy_id)` in the **Fragment Instance `parent`**,
+		/// i.e **transfer** it to the **account of `parent`** (see `get_fragment_account_id()`).
+		///
+		/// Since a nested Fragment Instance is owned by the account of the Fragment Instance that it is nested in,
+		/// it is carried along whenever that Fragment Instance is transferred.
+		///
+		/// Note: **Only** the **owner of both Fragment Instances** is **allowed** to call this extrinsic.
+		/// If `parent` is nested itself, its owner is the owner of the outermost Fragment Instance that it is nested in.
+		///
+		/// Footnotes:
+		///
+		/// A Fragment Instance that is copyable or that expires cannot have Fragment Instances nested in it,
+		/// and a copyable Fragment Instance cannot be nested.
+		/// At most `MAX_NESTED_INSTANCES` Fragment Instances can be nested directly in a Fragment Instance,
+		/// and at most `MAX_NESTING_DEPTH` levels of Fragment Instances can be nested below a Fragment Instance that is not nested itself.
+		///
+		/// # Arguments
+		///
+		/// * `origin` - **Origin** of the **extrinsic function**
+		/// * `definition_hash` - Fragment Definition ID of the Fragment Instance to nest
+		/// * `edition_id` - Edition ID of the Fragment Instance to nest
+		/// * `copy_id` - Copy ID of the Fragment Instance to nest
+		/// * `parent` - Fragment Definition ID, Edition ID and Copy ID of the Fragment Instance to nest it in
+		#[pallet::weight(<T as Config>::WeightInfo::nest())]
+		#[pallet::call_index(31)]
+		pub fn nest(
+			origin: OriginFor<T>,
+			definition_hash: Hash128,
+			edition_id: InstanceUnit,
+			copy_id: InstanceUnit,
+			parent: (Hash128, InstanceUnit, InstanceUnit),
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let (parent_definition_hash, parent_edition_id, parent_copy_id) = parent;
+
+			ensure!(
+				!<DetachedHashes<T>>::contains_key(&DetachHash::Instance(
+					parent_definition_hash,
+					Compact(parent_edition_id),
+					Compact(parent_copy_id)
+				)),
+				Error::<T>::Detached
+			);
+			let parent_data =
+				<Fragments<T>>::get((parent_definition_hash, parent_edition_id, parent_copy_id))
+					.ok_or(Error::<T>::NotFound)?;
+			ensure!(
+				(parent_data.permissions & FragmentPerms::COPY) != FragmentPerms::COPY &&
+					parent_data.expiring_at.is_none(),
+				Error::<T>::NotNestable
+			);
+
+			let item_data = <Fragments<T>>::get((definition_hash, edition_id, copy_id))
+				.ok_or(Error::<T>::NotFound)?;
+			ensure!(
+				(item_data.permissions & FragmentPerms::COPY) != FragmentPerms::COPY,
+				Error::<T>::NotNestable
+			);
+
+			// walk up from `parent` to the outermost Fragment Instance that it is nested in
+			let mut root = parent;
+			let mut parent_depth = 0;
+			while let Some(grandparent) = <InstanceParent<T>>::get(root) {
+				ensure!(root != (definition_hash, edition_id, copy_id), Error::<T>::NestingCycle);
+				root = grandparent;
+				parent_depth += 1;
+			}
+			ensure!(root != (definition_hash, edition_id, copy_id), Error::<T>::NestingCycle);
+
+			let root_owner = <InstanceOwner<T>>::get(root).ok_or(Error::<T>::NotFound)?;
+			ensure!(who == root_owner, Error::<T>::NoPermission);
+			// the contents of a Fragment Instance that is offered in a pending swap cannot change
+			ensure!(!Self::is_instance_locked(&root.0, root.1, root.2), Error::<T>::Locked);
+
+			ensure!(
+				parent_depth + 1 + Self::get_nesting_height(&definition_hash, edition_id, copy_id) <=
+					MAX_NESTING_DEPTH,
+				Error::<T>::NestingTooDeep
+			);
+			ensure!(
+				<InstanceChildren<T>>::decode_len(parent).unwrap_or(0) < MAX_NESTED_INSTANCES,
+				Error::<T>::TooManyNestedInstances
+			);
+
+			Self::can_transfer_instance(&who, &definition_hash, edition_id, copy_id, None, None)?;
+
+			// ! Writing
+
+			let parent_account = Self::get_fragment_account_id(
+				parent_definition_hash,
+				parent_edition_id,
+				parent_copy_id,
+			);
+			Self::transfer_instance(
+				&who,
+				&parent_account,
+				&definition_hash,
+				edition_id,
+				copy_id,
+				None,
+				None,
+			)?;
+
+			<InstanceParent<T>>::insert((definition_hash, edition_id, copy_id), parent);
+			<InstanceChildren<T>>::append(parent, (definition_hash, edition_id, copy_id));
+
+			Self::deposit_event(Event::Nested {
+				definition_hash,
+				fragment_id: (edition_id, copy_id),
+				parent,
+			});
+
+			Ok(())
+		}
+
+		/// **Take** the **nested Fragment Instance** `(definition_hash, edition_id, copy_id)` **out** of the **Fragment Instance that it is nested in**,
+		/// i.e **transfer** it from the **account of that Fragment Instance** to the **caller**.
+		///
+		/// Note: **Only** the **owner of the Fragment Instance that it is nested in** is **allowed** to call this extrinsic.
+		/// If that Fragment Instance is nested itself, its owner is the owner of the outermost Fragment Instance that it is nested in.
+		///
+		/// # Arguments
+		///
+		/// * `origin` - **Origin** of the **extrinsic function**
+		/// * `definition_hash` - Fragment Definition ID of the nested Fragment Instance
+		/// * `edition_id` - Edition ID of the nested Fragment Instance
+		/// * `copy_id` - Copy ID of the nested Fragment Instance
+		#[pallet::weight(<T as Config>::WeightInfo::unnest())]
+		#[pallet::call_index(32)]
+		pub fn unnest(
+			origin: OriginFor<T>,
+			definition_hash: Hash128,
+			edition_id: InstanceUnit,
+			copy_id: InstanceUnit,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let parent = <InstanceParent<T>>::get((definition_hash, edition_id, copy_id))
+				.ok_or(Error::<T>::NotNested)?;
+
+			let root = Self::get_root_instance(parent);
+			let root_owner = <InstanceOwner<T>>::get(root).ok_or(Error::<T>::NotFound)?;
+			ensure!(who == root_owner, Error::<T>::NoPermission);
+			// the contents of a Fragment Instance that is offered in a pending swap cannot change
+			ensure!(!Self::is_instance_locked(&root.0, root.1, root.2), Error::<T>::Locked);
+
+			let parent_account = Self::get_fragment_account_id(parent.0, parent.1, parent.2);
+			Self::can_transfer_instance(
+				&parent_account,
+				&definition_hash,
+				edition_id,
+				copy_id,
+				None,
+				None,
+			)?;
+
+			// ! Writing
+
+			// `transfer_instance()` also removes the Fragment Instance from `parent`
+			Self::transfer_instance(
+				&parent_account,
+				&who,
+				&definition_hash,
+				edition_id,
+				copy_id,
+				None,
+				None,
+			)?;
+
+			Self::deposit_event(Event::Unnested {
+				definition_hash,
+				fragment_id: (edition_id, copy_id),
+				owner: who,
+			});
+
+			Ok(())
+		}
+
+		/// **Dispatch** the **call `call`** with the **account of the Fragment Instance** as its **signed origin** (see `get_fragment_account_id()`).
+		///
+		/// This allows the owner of a Fragment Instance to spend the NOVA, assets and Fragment Instances held by the Fragment Instance.
+		///
+		/// Note: **Only** the **owner of the Fragment Instance** is **allowed** to call this extrinsic.
+		/// The account of a nested Fragment Instance is controlled by the account of the Fragment Instance that it is nested in.
+		///
+		/// # Arguments
+		///
+		/// * `origin` - **Origin** of the **extrinsic function**
+		/// * `definition_hash` - Fragment Definition ID of the Fragment Instance
+		/// * `edition_id` - Edition ID of the Fragment Instance
+		/// * `copy_id` - Copy ID of the Fragment Instance
+		/// * `call` - Call to dispatch
+		#[pallet::weight({
+			let di = call.get_dispatch_info();
+			(<T as Config>::WeightInfo::as_instance().saturating_add(di.weight), di.class)
+		})]
+		#[pallet::call_index(33)]
+		pub fn as_instance(
+			origin: OriginFor<T>,
+			definition_hash: Hash128,
+			edition_id: InstanceUnit,
+			copy_id: InstanceUnit,
+			call: Box<<T as pallet_proxy::Config>::RuntimeCall>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			ensure!(
+				!<DetachedHashes<T>>::contains_key(&DetachHash::Instance(
+					definition_hash,
+					Compact(edition_id),
+					Compact(copy_id)
+				)),
+				Error::<T>::Detached
+			);
+			let item_data = <Fragments<T>>::get((definition_hash, edition_id, copy_id))
+				.ok_or(Error::<T>::NotFound)?;
+			ensure!(
+				!Self::is_expired(&item_data, <frame_system::Pallet<T>>::block_number()),
+				Error::<T>::NotFound
+			);
+
+			let owner = <InstanceOwner<T>>::get((definition_hash, edition_id, copy_id))
+				.ok_or(Error::<T>::NotFound)?;
+			ensure!(who == owner, Error::<T>::NoPermission);
+			// the holdings of a Fragment Instance that is offered in a pending swap cannot change
+			ensure!(
+				!Self::is_instance_locked(&definition_hash, edition_id, copy_id),
+				Error::<T>::Locked
+			);
+
+			// ! Writing
+
+			let account = Self::get_fragment_account_id(definition_hash, edition_id, copy_id);
+			call.dispatch(frame_system::RawOrigin::Signed(account).into())
+				.map(|_| ())
+				.map_err(|e| e.error)
+		}
+
+		/// **Register** a **crafting recipe** that **burns or consumes** the **Fragment Instances `inputs`**
+		/// and **creates** the **Fragment Instances `outputs`** (see `craft()`).
+		///
+		/// The recipe is enabled as soon as it is registered.
+		///
+		/// Note: The caller **must** be the **owner of every Fragment Definition in `outputs`**,
+		/// since crafting the recipe creates Fragment Instances of these Fragment Definitions (even if they are not published).
+		///
+		/// # Arguments
+		///
+		/// * `origin` - **Origin** of the **extrinsic function**
+		/// * `inputs` - Input requirements of the recipe (at most `MAX_RECIPE_ITEMS`, with at most one per Fragment Definition)
+		/// * `outputs` - Outputs of the recipe (at most `MAX_RECIPE_ITEMS`, creating at most `MAX_CRAFT_OUTPUT_QUANTITY` Fragment Instances in total).
+		/// Fragment Definitions that have the unique capability cannot be outputs.
+		/// * `fee` (*optional*) - Currency and amount of the currency that is paid to the caller every time the recipe is crafted
+		#[pallet::weight(<T as Config>::WeightInfo::register_recipe(outputs.len().min(MAX_RECIPE_ITEMS) as u32))]
+		#[pallet::call_index(34)]
+		pub fn register_recipe(
+			origin: OriginFor<T>,
+			inputs: Vec<RecipeInput>,
+			outputs: Vec<RecipeOutput>,
+			fee: Option<(Currency<T::AssetId>, u128)>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			ensure!(
+				inputs.len() <= MAX_RECIPE_ITEMS && outputs.len() <= MAX_RECIPE_ITEMS,
+				Error::<T>::TooManyRecipeItems
+			);
+			ensure!(!inputs.is_empty() && !outputs.is_empty(), Error::<T>::ParamsNotValid);
+
+			let mut input_definitions = BTreeSet::new();
+			inputs.iter().try_for_each(|input| -> DispatchResult {
+				ensure!(
+					input_definitions.insert(input.definition_hash),
+					Error::<T>::ParamsNotValid
+				);
+				let amount = match input.amount {
+					RecipeInputAmount::Quantity(amount) |
+					RecipeInputAmount::StackAmount(amount) => amount,
+				};
+				ensure!(amount > 0, Error::<T>::ParamsNotValid);
+				ensure!(
+					<Definitions<T>>::contains_key(input.definition_hash),
+					Error::<T>::NotFound
+				);
+				Ok(())
+			})?;
+
+			let output_quantity = outputs.iter().try_fold(0 as InstanceUnit, |acc, output| {
+				ensure!(output.quantity > 0, Error::<T>::ParamsNotValid);
+				ensure!(
+					<Definitions<T>>::get(output.definition_hash)
+						.ok_or(Error::<T>::NotFound)?
+						.unique
+						.is_none(),
+					Error::<T>::ParamsNotValid
+				);
+				Self::ensure_can_mint_recipe_output(&who, &output.definition_hash)?;
+				Ok::<_, DispatchError>(acc.saturating_add(output.quantity))
+			})?;
+			ensure!(output_quantity <= MAX_CRAFT_OUTPUT_QUANTITY, Error::<T>::TooManyRecipeItems);
+
+			if let Some((_, amount)) = fee {
+				ensure!(amount > 0, Error::<T>::ParamsNotValid);
+			}
+
+			// ! Writing
+
+			let recipe_id = <NextRecipeId<T>>::get();
+			<NextRecipeId<T>>::put(recipe_id.saturating_add(1));
+
+			<Recipes<T>>::insert(
+				recipe_id,
+				Recipe { owner: who.clone(), inputs, outputs, fee, enabled: true },
+			);
+
+			Self::deposit_event(Event::RecipeRegistered { recipe_id, owner: who });
+
+			Ok(())
+		}
+
+		/// **Enable** or **disable** the **crafting recipe** `recipe_id`.
+		///
+		/// Note: **Only** the **owner of the recipe** is **allowed** to call this extrinsic.
+		///
+		/// # Arguments
+		///
+		/// * `origin` - **Origin** of the **extrinsic function**
+		/// * `recipe_id` - Recipe ID
+		/// * `enabled` - Whether the recipe can be crafted
+		#[pallet::weight(<T as Config>::WeightInfo::set_recipe_enabled())]
+		#[pallet::call_index(35)]
+		pub fn set_recipe_enabled(
+			origin: OriginFor<T>,
+			recipe_id: u64,
+			enabled: bool,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let mut recipe = <Recipes<T>>::get(recipe_id).ok_or(Error::<T>::RecipeNotFound)?;
+			ensure!(who == recipe.owner, Error::<T>::NoPermission);
+
+			// ! Writing
+
+			recipe.enabled = enabled;
+			<Recipes<T>>::insert(recipe_id, recipe);
+
+			Self::deposit_event(Event::RecipeEnabledChanged { recipe_id, enabled });
+
+			Ok(())
+		}
+
+		/// **Craft** the **crafting recipe** `recipe_id`, i.e **burn or consume** the **Fragment Instances `inputs`**
+		/// (that must match the input requirements of the recipe), **pay** the **fee of the recipe** (if any)
+		/// and **create** the **outputs of the recipe** for the caller.
+		///
+		/// A Fragment Instance used for an input that requires a quantity is burned.
+		/// The Fragment Instances used for an input that requires a stack amount are consumed in the given order
+		/// (and every Fragment Instance whose stack becomes empty is burned, see `consume()`).
+		///
+		/// Note: **Only** the **owner of the Fragment Instances `inputs`** is **allowed** to use them,
+		/// and **only** if they have the permission **FragmentPerms::EDIT**
+		///
+		/// # Arguments
+		///
+		/// * `origin` - **Origin** of the **extrinsic function**
+		/// * `recipe_id` - Recipe ID
+		/// * `inputs` - Fragment Instances to use as inputs (each represented as a tuple that contains the Fragment Instance's Fragment Definition ID, Edition ID and Copy ID),
+		/// at most `MAX_CRAFT_INPUTS`
+		#[pallet::weight(<T as Config>::WeightInfo::craft(
+			inputs.len().min(MAX_CRAFT_INPUTS) as u32,
+			MAX_CRAFT_OUTPUT_QUANTITY as u32
+		))]
+		#[pallet::call_index(36)]
+		pub fn craft(
+			origin: OriginFor<T>,
+			recipe_id: u64,
+			inputs: Vec<(Hash128, InstanceUnit, InstanceUnit)>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let recipe = <Recipes<T>>::get(recipe_id).ok_or(Error::<T>::RecipeNotFound)?;
+			ensure!(recipe.enabled, Error::<T>::RecipeDisabled);
+
+			ensure!(inputs.len() <= MAX_CRAFT_INPUTS, Error::<T>::TooManyRecipeItems);
+			let mut unique_inputs = BTreeSet::new();
+			ensure!(
+				inputs.iter().all(|input| unique_inputs.insert(*input)),
+				Error::<T>::ParamsNotValid
+			);
+
+			// the Fragment Instances that are used as inputs, grouped by Fragment Definition
+			let mut instances_by_definition: BTreeMap<
+				Hash128,
+				Vec<(InstanceUnit, InstanceUnit, FragmentInstance<T::BlockNumber>)>,
+			> = BTreeMap::new();
+			for (definition_hash, edition_id, copy_id) in inputs {
+				let item_data =
+					Self::can_edit_instance(&who, &definition_hash, edition_id, copy_id)?;
+				instances_by_definition
+					.entry(definition_hash)
+					.or_default()
+					.push((edition_id, copy_id, item_data));
+			}
+
+			ensure!(
+				instances_by_definition.len() == recipe.inputs.len(),
+				Error::<T>::RecipeInputsNotMet
+			);
+			for input in recipe.inputs.iter() {
+				let instances = instances_by_definition
+					.get(&input.definition_hash)
+					.ok_or(Error::<T>::RecipeInputsNotMet)?;
+				match input.amount {
+					RecipeInputAmount::Quantity(quantity) => ensure!(
+						instances.len() as InstanceUnit == quantity,
+						Error::<T>::RecipeInputsNotMet
+					),
+					RecipeInputAmount::StackAmount(amount) => {
+						let total = instances.iter().try_fold(
+							0 as InstanceUnit,
+							|acc, (_, _, item_data)| -> Result<InstanceUnit, DispatchError> {
+								let stack_amount: InstanceUnit =
+									item_data.stack_amount.ok_or(Error::<T>::NotStackable)?.into();
+								Ok(acc.saturating_add(stack_amount))
+							},
+						)?;
+						ensure!(total >= amount, Error::<T>::RecipeInputsNotMet);
+					},
+				}
+			}
+
+			if let Some((currency, amount)) = recipe.fee {
+				Self::can_transfer_currency(&who, &recipe.owner, amount, currency)?;
+			}
+
+			for output in recipe.outputs.iter() {
+				Self::ensure_can_mint_recipe_output(&recipe.owner, &output.definition_hash)?;
+				let definition =
+					<Definitions<T>>::get(output.definition_hash).ok_or(Error::<T>::NotFound)?;
+				if let Some(max_supply) = definition.max_supply {
+					let max: InstanceUnit = max_supply.into();
+					ensure!(
+						output.quantity <=
+							max.saturating_sub(Self::get_supply(&output.definition_hash)),
+						Error::<T>::MaxSupplyReached
+					);
+				}
+			}
+
+			// ! Writing
+
+			for input in recipe.inputs.iter() {
+				let instances = instances_by_definition
+					.remove(&input.definition_hash)
+					.ok_or(Error::<T>::RecipeInputsNotMet)?;
+				match input.amount {
+					RecipeInputAmount::Quantity(_) => {
+						for (edition_id, copy_id, item_data) in instances {
+							Self::burn_instance(
+								&who,
+								&input.definition_hash,
+								edition_id,
+								copy_id,
+								&item_data,
+							);
+						}
+					},
+					RecipeInputAmount::StackAmount(amount) => {
+						let mut to_consume = amount;
+						for (edition_id, copy_id, mut item_data) in instances {
+							if to_consume == 0 {
+								break
+							}
+							let stack_amount: InstanceUnit =
+								item_data.stack_amount.ok_or(Error::<T>::NotStackable)?.into();
+							let consumed = to_consume.min(stack_amount);
+							to_consume -= consumed;
+
+							let left = stack_amount - consumed;
+							if left == 0 {
+								Self::burn_instance(
+									&who,
+									&input.definition_hash,
+									edition_id,
+									copy_id,
+									&item_data,
+								);
+							} else {
+								item_data.stack_amount = Some(Compact(left));
+								<Fragments<T>>::insert(
+									(input.definition_hash, edition_id, copy_id),
+									item_data,
+								);
+							}
+
+							Self::deposit_event(Event::StackConsumed {
+								definition_hash: input.definition_hash,
+								fragment_id: (edition_id, copy_id),
+								amount: consumed,
+								left,
+							});
+						}
+					},
+				}
+			}
+
+			if let Some((currency, amount)) = recipe.fee {
+				Self::transfer_currency(&who, &recipe.owner, amount, currency)?;
+			}
+
+			let current_block_number = <frame_system::Pallet<T>>::block_number();
+			for output in recipe.outputs.iter() {
+				Self::mint_fragments(
+					&who,
+					&output.definition_hash,
+					None, // PublishingData (optional)
+					&FragmentBuyOptions::Quantity(output.quantity),
+					output.quantity,
+					current_block_number,
+					None,
+					output.stack_amount.map(|x| Compact(x)),
+				)?; // non-unique, so there is no validator weight
+			}
+
+			Self::deposit_event(Event::Crafted { recipe_id, account_id: who });
+
+			Ok(())
+		}
+
+		/// Set the **ink! contract** that **validates** the **unique data** of the **Fragment Instances** of the **Fragment Definition `definition_hash`**.
+		///
+		/// Every time a Fragment Instance with unique data is created (see `mint()`, `buy()` and `claim()`),
+		/// the contract is called (with the SCALE-encoded tuple `(definition_hash, caller, unique_data)` as input data, and the caller as origin)
+		/// and the Fragment Instance is only created if the contract returns the SCALE-encoded boolean `true`.
+		/// The weight consumed by the contract (at most `MAX_UNIQUE_DATA_VALIDATOR_WEIGHT`) is paid by the caller,
+		/// the rest of `MAX_UNIQUE_DATA_VALIDATOR_WEIGHT` is refunded.
+		///
+		/// Note: **Only** the **Fragment Definition's owner** is **allowed** to call this extrinsic,
+		/// and **only before** any **Fragment Instance** of the **Fragment Definition** has been **created**
+		///
+		/// # Arguments
+		///
+		/// * `origin` - **Origin** of the **extrinsic function**
+		/// * `definition_hash` - **ID** of the **Fragment Definition** (that must have the unique capability)
+		/// * `validator` (*optional*) - Account ID of the ink! contract. If `None`, the unique data is no longer validated.
+		#[pallet::weight(<T as Config>::WeightInfo::set_unique_data_validator())]
+		#[pallet::call_index(37)]
+		pub fn set_unique_data_validator(
+			origin: OriginFor<T>,
+			definition_hash: Hash128,
+			validator: Option<T::AccountId>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let definition = <Definitions<T>>::get(definition_hash).ok_or(Error::<T>::NotFound)?;
+			ensure!(who == definition.owner, Error::<T>::NoPermission);
+
+			ensure!(definition.unique.is_some(), Error::<T>::ParamsNotValid);
+
+			// the unique data of every Fragment Instance of the Fragment Definition must have been accepted by the same validator
+			ensure!(
+				!<EditionsCount<T>>::contains_key(definition_hash),
+				Error::<T>::InstancesAlreadyCreated
+			);
+
+			// ! Writing
+
+			if let Some(validator) = validator.as_ref() {
+				<UniqueDataValidators<T>>::insert(definition_hash, validator);
+			} else {
+				<UniqueDataValidators<T>>::remove(definition_hash);
+			}
+
+			Self::deposit_event(Event::UniqueDataValidatorChanged { definition_hash, validator });
+
+			Ok(())
+		}
+
+		/// **Update** the **unique data** of a **Fragment Instance** whose **Fragment Definition** has **mutable unique data**.
+		///
+		/// The previous unique data hash is kept in the history of the Edition (see `UniqueDataHistory`)
+		/// and can be used again by any Fragment Instance of the Fragment Definition.
+		/// The new unique data must be accepted by the validator of the Fragment Definition (if any, see `set_unique_data_validator()`).
+		///
+		/// Note: **Only** the **Fragment Instance's owner** is **allowed** to update its unique data,
+		/// and **only** if the Fragment Instance has the permission **FragmentPerms::EDIT**
+		/// and is the only copy of its Edition
+		///
+		/// # Arguments
+		///
+		/// * `origin` - **Origin** of the **extrinsic function**
+		/// * `definition_hash` - Fragment Definition ID of the Fragment Instance
+		/// * `edition_id` - Edition ID of the Fragment Instance
+		/// * `copy_id` - Copy ID of the Fragment Instance
+		/// * `data` - New unique data of the Fragment Instance
+		#[pallet::weight(<T as Config>::WeightInfo::update_unique_data(data.len() as u32)
+			.saturating_add(Weight::from_ref_time(MAX_UNIQUE_DATA_VALIDATOR_WEIGHT)) // the validator of the unique data (if any) is paid by the caller
+		)]
+		#[pallet::call_index(38)]
+		pub fn update_unique_data(
+			origin: OriginFor<T>,
+			definition_hash: Hash128,
+			edition_id: InstanceUnit,
+			copy_id: InstanceUnit,
+			data: Vec<u8>,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+
+			let definition = <Definitions<T>>::get(definition_hash).ok_or(Error::<T>::NotFound)?;
+			ensure!(
+				definition.unique.map_or(false, |unique| unique.mutable),
+				Error::<T>::UniqueDataNotMutable
+			);
+
+			let mut item_data =
+				Self::can_edit_instance(&who, &definition_hash, edition_id, copy_id)?;
+
+			// `UniqueData2Edition` maps the unique data to the whole Edition, so all of its copies share the same unique data
+			ensure!(
+				<Fragments<T>>::iter_key_prefix((definition_hash, edition_id)).nth(1).is_none(),
+				Error::<T>::EditionHasMultipleCopies
+			);
+
+			let data_hash = blake2_256(&data);
+			ensure!(
+				!<UniqueData2Edition<T>>::contains_key(definition_hash, data_hash),
+				Error::<T>::UniqueDataExists
+			);
+
+			let validator_weight = Self::validate_unique_data(&who, &definition_hash, &data)?;
+
+			// we need this to index transactions
+			let extrinsic_index = <frame_system::Pallet<T>>::extrinsic_index()
+				.ok_or(Error::<T>::SystematicFailure)?;
+
+			// ! Writing
+
+			if let Some(previous_data_hash) = item_data.custom_data {
+				<UniqueData2Edition<T>>::remove(definition_hash, previous_data_hash);
+				<UniqueDataHistory<T>>::mutate((definition_hash, edition_id), |history| {
+					if history.len() >= MAX_UNIQUE_DATA_HISTORY {
+						history.remove(0);
+					}
+					history.push(previous_data_hash);
+				});
+			}
+			<UniqueData2Edition<T>>::insert(definition_hash, data_hash, edition_id);
+
+			item_data.custom_data = Some(data_hash);
+			<Fragments<T>>::insert((definition_hash, edition_id, copy_id), item_data);
+
+			// index data for IPFS discovery
+			transaction_index::index(extrinsic_index, data.len() as u32, data_hash);
+
+			Self::deposit_event(Event::UniqueDataUpdated {
+				definition_hash,
+				fragment_id: (edition_id, copy_id),
+				data_hash,
+			});
+
+			// refund the weight that the validator of the unique data did not consume
+			Ok(Some(
+				<T as Config>::WeightInfo::update_unique_data(data.len() as u32)
+					.saturating_add(validator_weight),
+			)
+			.into())
+		}
+
+		/// Set **who is allowed to write** the **metadata key `metadata_key`** of the **Fragment Definition `definition_hash`**
+		/// and of its **Fragment Instances** (see `set_definition_metadata()` and `set_instance_metadata()`).
+		///
+		/// For example, a metadata key "stats" can be reserved to the members of the game server's Cluster.
+		///
+		/// Note: **Only** the **Fragment Definition's owner** is **allowed** to call this extrinsic
+		///
+		/// # Arguments
+		///
+		/// * `origin` - **Origin** of the **extrinsic function**
+		/// * `definition_hash` - **ID** of the **Fragment Definition**
+		/// * `metadata_key` - Metadata key
+		/// * `policy` (*optional*) - Who is allowed to write the metadata key. If `None`, the metadata key has the policy `MetadataKeyPolicy::Owner`.
+		#[pallet::weight(<T as Config>::WeightInfo::set_metadata_key_policy())]
+		#[pallet::call_index(39)]
+		pub fn set_metadata_key_policy(
+			origin: OriginFor<T>,
+			definition_hash: Hash128,
+			metadata_key: BoundedVec<u8, <T as pallet_protos::Config>::StringLimit>,
+			policy: Option<MetadataKeyPolicy>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			ensure!(!metadata_key.is_empty(), Error::<T>::DefinitionMetadataKeyIsEmpty);
+
+			let definition = <Definitions<T>>::get(definition_hash).ok_or(Error::<T>::NotFound)?;
+			let proto_hash = definition.proto_hash;
+			ensure!(who == definition.owner, Error::<T>::NoPermission);
+
+			ensure!(
+				!<DetachedHashes<T>>::contains_key(&DetachHash::Proto(proto_hash)),
+				Error::<T>::Detached
+			);
+
+			if let Some(MetadataKeyPolicy::ClusterRole { role_name, .. }) = policy.as_ref() {
+				ensure!(
+					!role_name.is_empty() &&
+						role_name.len() <=
+							<T as pallet_protos::Config>::StringLimit::get() as usize,
+					Error::<T>::ParamsNotValid
+				);
+			}
+
+			// ! Writing
+
+			match policy.as_ref() {
+				None | Some(MetadataKeyPolicy::Owner) =>
+					<MetadataKeyPolicies<T>>::remove(definition_hash, &metadata_key),
+				Some(policy) =>
+					<MetadataKeyPolicies<T>>::insert(definition_hash, &metadata_key, policy),
+			}
+
+			Self::deposit_event(Event::MetadataKeyPolicyChanged {
+				definition_hash,
+				metadata_key: metadata_key.into(),
+				policy,
+			});
+
+			Ok(())
+		}
+
+		/// Set the **attribute schema** of the **Fragment Definition `definition_hash`**,
+		/// i.e the **typed attributes** that its **Fragment Instances** can have (see `set_instance_attributes()`).
+		///
+		/// Every attribute must be a **record** of the **Trait Proto-Fragment** whose data is `trait_data`,
+		/// and its **type** must be **one of the types** of that **record** (see `is_attribute_type_of()`).
+		///
+		/// Note: **Only** the **Fragment Definition's owner** is **allowed** to call this extrinsic,
+		/// and **only before** any Fragment Instance of the Fragment Definition is created
+		///
+		/// # Arguments
+		///
+		/// * `origin` - **Origin** of the **extrinsic function**
+		/// * `definition_hash` - **ID** of the **Fragment Definition**
+		/// * `trait_data` - **Data** of the **Trait Proto-Fragment** (the ID of the Trait Proto-Fragment is the hash of its data)
+		/// * `attributes` - **Name** and **type** of **every attribute**
+		#[pallet::weight(<T as Config>::WeightInfo::set_attribute_schema(attributes.len() as u32))]
+		#[pallet::call_index(40)]
+		pub fn set_attribute_schema(
+			origin: OriginFor<T>,
+			definition_hash: Hash128,
+			trait_data: Vec<u8>,
+			attributes: Vec<(Vec<u8>, AttributeType)>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			ensure!(
+				!attributes.is_empty() && attributes.len() <= MAX_ATTRIBUTES,
+				Error::<T>::ParamsNotValid
+			);
+			// an attribute name is also used as a metadata key (see `set_metadata_key_policy()`)
+			ensure!(
+				attributes.iter().all(|(name, _)| !name.is_empty() &&
+					name.len() <= <T as pallet_protos::Config>::StringLimit::get() as usize),
+				Error::<T>::ParamsNotValid
+			);
+			ensure!(
+				attributes.iter().map(|(name, _)| name).collect::<BTreeSet<_>>().len() ==
+					attributes.len(),
+				Error::<T>::ParamsNotValid
+			);
+
+			let definition = <Definitions<T>>::get(definition_hash).ok_or(Error::<T>::NotFound)?;
+			let proto_hash = definition.proto_hash;
+			ensure!(who == definition.owner, Error::<T>::NoPermission);
+
+			ensure!(
+				!<DetachedHashes<T>>::contains_key(&DetachHash::Proto(proto_hash)),
+				Error::<T>::Detached
+			);
+
+			// the attributes of existing Fragment Instances were validated against the previous schema
+			ensure!(
+				!<EditionsCount<T>>::contains_key(definition_hash),
+				Error::<T>::InstancesAlreadyCreated
+			);
+
+			let trait_proto = blake2_256(&trait_data);
+			let trait_proto_struct: Proto<T::AccountId, T::BlockNumber> =
+				<Protos<T>>::get(trait_proto).ok_or(Error::<T>::ProtoNotFound)?;
+			ensure!(
+				trait_proto_struct.category == Categories::Trait(Some(twox_64(&trait_data))),
+				Error::<T>::NotATrait
+			);
+			let trait_struct =
+				Trait::decode(&mut &trait_data[..]).map_err(|_| Error::<T>::NotATrait)?;
+			for (name, attribute_type) in attributes.iter() {
+				let record = trait_struct
+					.records
+					.iter()
+					.find(|record| {
+						let record_name: &[u8] = record.name.as_ref();
+						record_name == &name[..]
+					})
+					.ok_or(Error::<T>::AttributeNotInSchema)?;
+				ensure!(
+					record.types.iter().any(|type_info| Self::is_attribute_type_of(
+						attribute_type,
+						&type_info.type_
+					)),
+					Error::<T>::AttributeTypeNotInTrait
+				);
+			}
+
+			// ! Writing
+
+			<AttributeSchemas<T>>::insert(
+				definition_hash,
+				AttributeSchema { trait_proto, attributes },
+			);
+
+			Self::deposit_event(Event::AttributeSchemaChanged { definition_hash, trait_proto });
+
+			Ok(())
+		}
+
+		/// **Set** (or **remove**) **attributes** of a **Fragment Instance** (whose Fragment Definition ID is `definition_hash`,
+		/// whose Edition ID is `edition_id` and whose Copy ID is `copy_id`).
+		/// Every attribute value is **validated** against the **attribute schema** of the Fragment Definition (see `set_attribute_schema()`).
+		///
+		/// Note: The name of an attribute is also a metadata key. Therefore, by default **only** the **Fragment Instance's owner** is **allowed** to write an attribute,
+		/// and **only** if the Fragment Instance has the permission **FragmentPerms::EDIT**.
+		/// An attribute can also be reserved by the Fragment Definition's owner (see `set_metadata_key_policy()`).
+		///
+		/// # Arguments
+		///
+		/// * `origin` - **Origin** of the **extrinsic function**
+		/// * `definition_hash` - **ID of the Fragment Instance's Fragment Definition**
+		/// * `edition_id` - **Edition ID of the Fragment Instance**
+		/// * `copy_id` - **Copy ID of the Fragment Instance**
+		/// * `attributes` - **Name** and **value** of **every attribute** to write. If the value is `None`, the attribute is removed.
+		#[pallet::weight(<T as Config>::WeightInfo::set_instance_attributes(attributes.len() as u32))]
+		#[pallet::call_index(41)]
+		pub fn set_instance_attributes(
+			origin: OriginFor<T>,
+			definition_hash: Hash128,
+			edition_id: InstanceUnit,
+			copy_id: InstanceUnit,
+			attributes: Vec<(Vec<u8>, Option<AttributeValue>)>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			ensure!(
+				!attributes.is_empty() && attributes.len() <= MAX_ATTRIBUTES,
+				Error::<T>::ParamsNotValid
+			);
+
+			ensure!(
+				!<DetachedHashes<T>>::contains_key(&DetachHash::Instance(
+					definition_hash,
+					Compact(edition_id),
+					Compact(copy_id)
+				)),
+				Error::<T>::Detached
+			);
+
+			let instance_struct = <Fragments<T>>::get((definition_hash, edition_id, copy_id))
+				.ok_or(Error::<T>::NotFound)?;
+
+			let schema =
+				<AttributeSchemas<T>>::get(definition_hash).ok_or(Error::<T>::NoAttributeSchema)?;
+
+			for (name, value) in attributes.iter() {
+				let (_, attribute_type) = schema
+					.attributes
+					.iter()
+					.find(|(attribute_name, _)| attribute_name == name)
+					.ok_or(Error::<T>::AttributeNotInSchema)?;
+
+				if let Some(value) = value {
+					ensure!(
+						value.attribute_type() == *attribute_type,
+						Error::<T>::AttributeTypeMismatch
+					);
+					if let AttributeValue::String(string) = value {
+						ensure!(
+							string.len() <= MAX_ATTRIBUTE_STRING_LENGTH &&
+								sp_std::str::from_utf8(string).is_ok(),
+							Error::<T>::ParamsNotValid
+						);
+					}
+				}
+
+				let metadata_key: BoundedVec<u8, <T as pallet_protos::Config>::StringLimit> =
+					name.clone().try_into().map_err(|_| Error::<T>::ParamsNotValid)?;
+				Self::ensure_can_write_instance_metadata_key(
+					&who,
+					&definition_hash,
+					edition_id,
+					copy_id,
+					&instance_struct,
+					&metadata_key,
+				)?;
+			}
+
+			// ! Writing
+
+			<InstanceAttributes<T>>::mutate_exists(
+				(definition_hash, edition_id, copy_id),
+				|values| {
+					let mut map = values.take().unwrap_or_default();
+					for (name, value) in attributes {
+						match value {
+							Some(value) => {
+								map.insert(name, value);
+							},
+							None => {
+								map.remove(&name);
+							},
+						}
+					}
+					if !map.is_empty() {
+						*values = Some(map);
+					}
+				},
+			);
+
+			Self::deposit_event(Event::InstanceAttributesUpdated {
+				definition_hash,
+				fragment_id: (edition_id, copy_id),
+			});
+
+			Ok(())
+		}
+
+		/// **Transfer** the **ownership** of the **Fragment Definition `definition_hash`** to **`to`**.
+		///
+		/// The owner of a Fragment Definition is the account that is allowed to manage it (e.g `publish()`, `mint()` and `set_definition_metadata()`)
+		/// and to withdraw its sale proceeds (see `withdraw_proceeds()`).
+		/// The Proto-Fragment of the Fragment Definition (and its other Fragment Definitions) is **not** transferred.
+		///
+		/// Note: **Only** the **Fragment Definition's owner** is **allowed** to transfer it
+		///
+		/// # Arguments
+		///
+		/// * `origin` - **Origin** of the **extrinsic function**
+		/// * `definition_hash` - **ID** of the **Fragment Definition**
+		/// * `to` - **Account ID** to transfer the Fragment Definition to
+		#[pallet::weight(<T as Config>::WeightInfo::transfer_definition())]
+		#[pallet::call_index(42)]
+		pub fn transfer_definition(
+			origin: OriginFor<T>,
+			definition_hash: Hash128,
+			to: <T::Lookup as StaticLookup>::Source,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let mut definition =
+				<Definitions<T>>::get(definition_hash).ok_or(Error::<T>::NotFound)?;
+			ensure!(who == definition.owner, Error::<T>::NoPermission); // Ensure `who` is the owner of the Fragment Definition
+
+			ensure!(
+				!<DetachedHashes<T>>::contains_key(&DetachHash::Proto(definition.proto_hash)),
+				Error::<T>::Detached
+			);
+
+			let to = T::Lookup::lookup(to)?;
+			ensure!(to != who, Error::<T>::ParamsNotValid);
+
+			// ! Writing
+
+			<DefinitionsByOwner<T>>::mutate(&who, |definitions| {
+				if let Some(definitions) = definitions {
+					definitions.retain(|current_hash| *current_hash != definition_hash);
+				}
+			});
+			<DefinitionsByOwner<T>>::append(&to, definition_hash);
+
+			definition.owner = to.clone();
+			<Definitions<T>>::insert(definition_hash, definition);
+
+			Self::deposit_event(Event::DefinitionTransferred { definition_hash, owner: to });
+
+			Ok(())
+		}
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		/// At the start of every block, sweep the expired Fragment Instances from storage,
+		/// using at most `EXPIRATION_SWEEP_RATIO` of the maximum block weight (see `sweep_expirations()`)
+		fn on_initialize(n: T::BlockNumber) -> Weight {
+			Self::sweep_expirations(n, EXPIRATION_SWEEP_RATIO * T::BlockWeights::get().max_block)
+		}
+
+		/// Use the remaining weight of the block to sweep the expired Fragment Instances
+		/// that could not be swept in `on_initialize()`
+		fn on_idle(n: T::BlockNumber, remaining_weight: Weight) -> Weight {
+			Self::sweep_expirations(n, remaining_weight)
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// Get the **Account ID** of the **Fragment Instance whose Fragment Definition ID is `definition_hash`,
+		/// whose Edition ID is `edition`** and whose Copy ID is `copy`**
+		///
+		/// This Account ID is determinstically computed using the Fragment Definition ID `class_hash`, the Edition ID `edition` and the Copy ID `copy`
+		pub fn get_fragment_account_id(
+			definition_hash: Hash128,
+			edition: InstanceUnit,
+			copy: InstanceUnit,
+		) -> T::AccountId {
+			let hash = blake2_256(
+				&[&b"fragments-account"[..], &definition_hash, &edition.encode(), &copy.encode()]
+					.concat(),
+			);
+			T::AccountId::decode(&mut &hash[..]).expect("T::AccountId should decode")
+		}
+
+		/// Make `delegate` the **only proxy** of the **account of a Fragment Instance** (see `create_account()`).
+		///
+		/// Any other proxy of the account is removed (and its deposit is returned to the account),
+		/// so that a previous owner of the Fragment Instance cannot keep controlling the account.
+		///
+		/// * `frag_account` - Account ID of the Fragment Instance (see `get_fragment_account_id()`)
+		/// * `delegate` - Account ID of the owner of the Fragment Instance
+		pub fn set_instance_account_proxy(frag_account: &T::AccountId, delegate: &T::AccountId) {
+			let (_, deposit) = pallet_proxy::Proxies::<T>::take(frag_account);
+			<T as pallet_proxy::Config>::Currency::unreserve(frag_account, deposit);
+
+			// use the same logic of proxy anonymous (see `pallet_clusters::create_cluster()`)
+			let proxy_def = pallet_proxy::ProxyDefinition {
+				delegate: delegate.clone(),
+				proxy_type: T::ProxyType::default(),
+				delay: T::BlockNumber::default(),
+			};
+			pallet_proxy::Proxies::<T>::insert(
+				frag_account,
+				(BoundedVec::truncate_from(sp_std::vec![proxy_def]), Zero::zero()),
+			);
+		}
+
+		/// Whether the Fragment Instance can be edited (i.e modified, consumed or burned) by `who`.
+		/// Returns the `FragmentInstance` struct of the Fragment Instance if so.
+		///
+		/// * `who` - Fragnova Account ID that wants to edit the Fragment Instance
+		/// * `definition_hash` - Fragment Definition of the Fragment Instance
+		/// * `edition_id` - Edition ID of the Fragment Instance
+		/// * `copy_id` - Copy ID of the Fragment Instance
+		pub fn can_edit_instance(
+			who: &T::AccountId,
+			definition_hash: &Hash128,
+			edition_id: InstanceUnit,
+			copy_id: InstanceUnit,
+		) -> Result<FragmentInstance<T::BlockNumber>, DispatchError> {
+			ensure!(
+				!<DetachedHashes<T>>::contains_key(&DetachHash::Instance(
+					*definition_hash,
+					Compact(edition_id),
+					Compact(copy_id)
+				)),
+				Error::<T>::Detached
+			);
+
+			let item_data = <Fragments<T>>::get((definition_hash, edition_id, copy_id))
+				.ok_or(Error::<T>::NotFound)?;
+
+			// no go if will expire this block
+			ensure!(
+				!Self::is_expired(&item_data, <frame_system::Pallet<T>>::block_number()),
+				Error::<T>::NotFound
+			);
+
+			// Only the owner of this fragment can edit it
+			let ids = <Inventory<T>>::get(who.clone(), definition_hash)
+				.ok_or(Error::<T>::NoPermission)?;
+			ensure!(
+				ids.contains(&(Compact(edition_id), Compact(copy_id))),
+				Error::<T>::NoPermission
+			);
+
+			// a rented out Fragment Instance cannot be edited until its rental ends
+			ensure!(
+				Self::get_active_rental(definition_hash, edition_id, copy_id).is_none(),
+				Error::<T>::Rented
+			);
+
+			// a Fragment Instance that is offered in a pending swap cannot be edited until the swap ends
+			ensure!(
+				!Self::is_instance_locked(definition_hash, edition_id, copy_id),
+				Error::<T>::Locked
+			);
+
+			// the Fragment Instances nested in a Fragment Instance must be taken out of it first
+			ensure!(
+				!<InstanceChildren<T>>::contains_key((definition_hash, edition_id, copy_id)),
+				Error::<T>::HasNestedInstances
+			);
+
+			ensure!(
+				(item_data.permissions & FragmentPerms::EDIT) == FragmentPerms::EDIT,
+				Error::<T>::NoPermission
+			);
+
+			Ok(item_data)
+		}
+
+		/// Remove the Fragment Instance (owned by `owner`) from every storage item that references it
+		///
+		/// * `owner` - Fragnova Account ID that owns the Fragment Instance
+		/// * `definition_hash` - Fragment Definition of the Fragment Instance
+		/// * `edition_id` - Edition ID of the Fragment Instance
+		/// * `copy_id` - Copy ID of the Fragment Instance
+		/// * `item_data` - `FragmentInstance` struct of the Fragment Instance
+		pub fn remove_instance(
+			owner: &T::AccountId,
+			definition_hash: &Hash128,
+			edition_id: InstanceUnit,
+			copy_id: InstanceUnit,
+			item_data: &FragmentInstance<T::BlockNumber>,
+		) {
+			<Fragments<T>>::remove((definition_hash, edition_id, copy_id));
+			<InstanceOwner<T>>::remove((definition_hash, edition_id, copy_id));
+			<InstanceAttributes<T>>::remove((definition_hash, edition_id, copy_id));
+			<Rentals<T>>::remove((definition_hash, edition_id, copy_id));
+			<InstanceApprovals<T>>::remove((definition_hash, edition_id, copy_id));
+			<LockedInstances<T>>::remove((definition_hash, edition_id, copy_id));
+			Self::unlink_nested_instance(definition_hash, edition_id, copy_id);
+
+			<Owners<T>>::mutate(definition_hash, owner.clone(), |x| {
+				if let Some(x) = x {
+					x.retain(|y| y != &(Compact(edition_id), Compact(copy_id)));
+				}
+			});
+			<Inventory<T>>::mutate(owner.clone(), definition_hash, |x| {
+				if let Some(x) = x {
+					x.retain(|y| y != &(Compact(edition_id), Compact(copy_id)));
+				}
+			});
+
+			if let Some(expiring_at) = item_data.expiring_at {
+				<ExpirationQueue<T>>::remove(expiring_at, (*definition_hash, edition_id, copy_id));
+			}
+
+			<Definition2SecondarySales<T>>::remove((definition_hash, edition_id, copy_id));
+
+			Self::increment_removed_count(definition_hash);
+		}
+
+		/// Increment the number of Fragment Instances of the Fragment Definition `definition_hash` that have been removed from storage (see `RemovedCount`)
+		fn increment_removed_count(definition_hash: &Hash128) {
+			<RemovedCount<T>>::mutate(definition_hash, |count| {
+				*count = Some(Compact(count.unwrap_or(Compact(0)).0.saturating_add(1)));
+			});
+		}
+
+		/// Burn the Fragment Instance (owned by `owner`), i.e remove it and update the burned counts of its Fragment Definition
+		///
+		/// * `owner` - Fragnova Account ID that owns the Fragment Instance
+		/// * `definition_hash` - Fragment Definition of the Fragment Instance
+		/// * `edition_id` - Edition ID of the Fragment Instance
+		/// * `copy_id` - Copy ID of the Fragment Instance
+		/// * `item_data` - `FragmentInstance` struct of the Fragment Instance
+		pub fn burn_instance(
+			owner: &T::AccountId,
+			definition_hash: &Hash128,
+			edition_id: InstanceUnit,
+			copy_id: InstanceUnit,
+			item_data: &FragmentInstance<T::BlockNumber>,
+		) {
+			Self::remove_instance(owner, definition_hash, edition_id, copy_id, item_data);
+
+			<BurnedCount<T>>::mutate(definition_hash, |count| {
+				*count = Some(Compact(count.unwrap_or(Compact(0)).0.saturating_add(1)));
+			});
+
+			// the whole Edition is burned once its last copy is burned
+			if <Fragments<T>>::iter_key_prefix((*definition_hash, edition_id)).next().is_none() {
+				if let Some(data_hash) = item_data.custom_data {
+					<UniqueData2Edition<T>>::remove(definition_hash, data_hash);
+				}
+				<UniqueDataHistory<T>>::remove((*definition_hash, edition_id));
+				<BurnedEditionsCount<T>>::mutate(definition_hash, |count| {
+					*count = Some(Compact(count.unwrap_or(Compact(0)).0.saturating_add(1)));
+				});
+			}
+
+			Self::deposit_event(Event::Burned {
+				account_id: owner.clone(),
+				definition_hash: *definition_hash,
+				fragment_id: (edition_id, copy_id),
+			});
+		}
+
+		/// Get the **current Sale Phase** (and its index) of the **sale `sale`** at the block number `current_block_number`.
+		///
+		/// Returns `None` if the sale has no Sale Phases, and the error `SaleNotStarted` if its first Sale Phase has not started yet.
+		pub fn get_current_sale_phase(
+			sale: &PublishingData<T::BlockNumber>,
+			current_block_number: T::BlockNumber,
+		) -> Result<Option<(u32, SalePhase<T::BlockNumber>)>, Error<T>> {
+			if sale.phases.is_empty() {
+				return Ok(None)
+			}
+			sale.phases
+				.iter()
+				.enumerate()
+				.rev()
+				.find(|(_, phase)| phase.start <= current_block_number)
+				.map(|(index, phase)| Some((index as u32, phase.clone())))
+				.ok_or(Error::<T>::SaleNotStarted)
+		}
+
+		/// Whether `allowlist_proof` proves that `who` is in the allowlist whose Merkle Root is `allowlist`.
+		///
+		/// The leaf of `who` is `blake2_256((who, allowance).encode())` (see `sp_fragnova::merkle::verify_proof()`).
+		pub fn verify_allowlist_proof(
+			allowlist: &Hash256,
+			who: &T::AccountId,
+			allowlist_proof: &AllowlistProof,
+		) -> bool {
+			let leaf = blake2_256(&(who, allowlist_proof.allowance).encode());
+			merkle::verify_proof(allowlist, &leaf, &allowlist_proof.proof)
+		}
+
+		/// Get the **number of Editions** of the **Fragment Definition `definition_hash`** that are **counted towards its `max_supply`**.
+		///
+		/// This is the number of Editions created so far, minus the Editions that have been completely burned
+		/// if the Fragment Definition allows burned Editions to free up supply (see `BurnsReplenishSupply`)
+		pub fn get_supply(definition_hash: &Hash128) -> InstanceUnit {
+			let existing: InstanceUnit =
+				<EditionsCount<T>>::get(definition_hash).unwrap_or(Compact(0)).into();
+			if <BurnsReplenishSupply<T>>::get(definition_hash) {
+				let burned: InstanceUnit =
+					<BurnedEditionsCount<T>>::get(definition_hash).unwrap_or(Compact(0)).into();
+				existing.saturating_sub(burned)
+			} else {
+				existing
+			}
+		}
+
+		/// Create `quantity` number of Fragment Instances from the Fragment Definition `definition_hash` and assigns their ownership to `to`
+		///
+		/// # Arguments
+		///
+		/// * `to` - **Account ID** to assign ownernship of the created Fragment instances to
+		/// * `definition_hash` - ID of the Fragment Definition
+		/// * `sale` - Struct **representing** a **sale of the Fragment Definition**, if the **Fragment Definition** is **currently on sale**
+		/// * `options` - **Enum** indicating whether to
+		/// **create one Fragment Instance with custom data attached to it** or whether to
+		/// **create multiple Fragment Instances (with no custom data attached)**
+		/// * `quantity` - **Number of Fragment Instances** to **create**
+		/// * `current_block_number` - **Current block number** of the **Fragnova Blockchain**
+		/// * `expiring_at` (*optional*) - **Block Number** that the **Fragment Instance** will **expire at**
+		/// * `amount` (*optional*) - If the Fragment Instance(s) represent a **stack of stackable items**
+		/// (for e.g gold coins or arrows - https://runescape.fandom.com/wiki/Stackable_items),
+		/// `amount` is the **number of items** to **top up** in the **stack of stackable items**
+		///
+		/// Returns the weight consumed by the validator of the unique data (see `validate_unique_data()`).
+		pub fn mint_fragments(
+			to: &T::AccountId,
+			definition_hash: &Hash128,
+			sale: Option<&PublishingData<T::BlockNumber>>,
+			options: &FragmentBuyOptions,
+			quantity: u64,
+			current_block_number: T::BlockNumber,
+			expiring_at: Option<T::BlockNumber>,
+			stack_amount: Option<Compact<InstanceUnit>>,
+		) -> Result<Weight, DispatchError> {
+			use frame_support::ensure;
+
+			if let Some(expiring_at) = expiring_at {
+				ensure!(expiring_at > current_block_number, Error::<T>::ParamsNotValid); // Ensure `expiring_at` > `current_block_number`
+			}
+
+			let fragment_data =
+				<Definitions<T>>::get(definition_hash).ok_or(Error::<T>::NotFound)?;
+
+			// we need this to index transactions
+			let extrinsic_index = <frame_system::Pallet<T>>::extrinsic_index() // `<frame_system::Pallet<T>>::extrinsic_index()` is defined as: "Gets the index of extrinsic that is currently executing." (https://paritytech.github.io/substrate/master/frame_system/pallet/struct.Pallet.html#method.extrinsic_index)
+				.ok_or(Error::<T>::SystematicFailure)?;
+
+			let mut validator_weight = Weight::zero();
+			let (data_hash, data_len) = match options {
+				FragmentBuyOptions::UniqueData(data) => {
+					if fragment_data.unique.is_none() || quantity != 1 {
+						return Err(Error::<T>::ParamsNotValid.into())
+					}
+
+					let data_hash = blake2_256(&data);
+
+					ensure!(
+						!<UniqueData2Edition<T>>::contains_key(definition_hash, data_hash),
+						Error::<T>::UniqueDataExists
+					);
+
+					validator_weight = Self::validate_unique_data(to, definition_hash, data)?;
+
+					(Some(data_hash), Some(data.len()))
+				},
+				FragmentBuyOptions::Quantity(_) => {
+					if fragment_data.unique.is_some() {
+						return Err(Error::<T>::ParamsNotValid.into())
+					}
+
+					(None, None)
+				},
+			};
+
+			let existing: InstanceUnit =
+				<EditionsCount<T>>::get(&definition_hash).unwrap_or(Compact(0)).into();
+
+			if let Some(sale) = sale {
+				// if limited amount let's reduce the amount of units left
+				if let Some(units_left) = sale.units_left {
+					if quantity > units_left.into() {
+						return Err(Error::<T>::PublishedQuantityReached.into())
+					} else {
+						<Publishing<T>>::mutate(&*definition_hash, |sale| {
+							if let Some(sale) = sale {
+								let left: InstanceUnit = units_left.into();
+								sale.units_left = Some(Compact(left - quantity));
+							}
+						});
+					}
+				}
+			} else {
+				// We still don't wanna go over supply limit
+				if let Some(max_supply) = fragment_data.max_supply {
+					let max: InstanceUnit = max_supply.into();
+					let left = max.saturating_sub(Self::get_supply(definition_hash)); // `left` = `max` - `supply`
+					if quantity > left {
+						// Ensure the function parameter `quantity` is smaller than or equal to `left`
+						return Err(Error::<T>::MaxSupplyReached.into())
+					}
+				}
+			}
+
+			// ! Writing if successful
+
+			<Definitions<T>>::mutate(definition_hash, |fragment| {
+				// Get the `FragmentDefinition` struct from `definition_hash`
+				if let Some(fragment) = fragment {
+					for id in existing..(existing + quantity) {
+						let id = id + 1u64;
+						let cid = Compact(id); // `cid` stands for "compact id"
+
+						<Fragments<T>>::insert(
+							(definition_hash, id, 1),
+							FragmentInstance {
+								permissions: fragment.permissions,
+								created_at: current_block_number,
+								custom_data: data_hash,
+								expiring_at,
+								stack_amount,
+								metadata: BTreeMap::new(),
+								_reserved1: None,
+								_reserved2: None,
+								_reserved3: None,
+							},
+						);
+
+						<CopiesCount<T>>::insert((definition_hash, id), Compact(1));
+
+						<Inventory<T>>::append(to.clone(), definition_hash, (cid, Compact(1))); // **Add** the **Fragment Intstance whose Fragment Definition is `definition_hash`, Edition ID is `cid` and Copy ID is 1**  to the **inventory of `to`**
+
+						<Owners<T>>::append(definition_hash, to.clone(), (cid, Compact(1)));
+						<InstanceOwner<T>>::insert((definition_hash, id, 1), to.clone());
+
+						if let Some(expiring_at) = expiring_at {
+							<ExpirationQueue<T>>::insert(
+								expiring_at,
+								(*definition_hash, id, 1),
+								(),
+							);
+						}
+						Self::deposit_event(Event::InventoryAdded {
+							account_id: to.clone(),
+							definition_hash: *definition_hash,
+							fragment_id: (id, 1),
+						});
+					}
+
+					if let (Some(data_hash), Some(data_len)) = (data_hash, data_len) {
+						<UniqueData2Edition<T>>::insert(definition_hash, data_hash, existing); // if `data` exists, `quantity` is ensured to be 1
+
+						// index immutable data for IPFS discovery
+						transaction_index::index(extrinsic_index, data_len as u32, data_hash);
+					}
+
+					<EditionsCount<T>>::insert(definition_hash, Compact(existing + quantity));
+				}
+			});
+
+			Ok(validator_weight)
+		}
+
+		/// Whether `amount` amount of token `currency` can be transferred from `from` to `to`
+		pub fn can_transfer_currency(
+			from: &T::AccountId,
+			to: &T::AccountId,
+			amount: u128,
+			currency: Currency<T::AssetId>,
+		) -> DispatchResult {
+			match currency {
+				Currency::Custom(currency) => {
+					let minimum_balance_needed_to_exist =
+						<pallet_assets::Pallet<T> as fungibles::Inspect<T::AccountId>>::minimum_balance(currency);
+					let price_balance: <pallet_assets::Pallet<T> as fungibles::Inspect<
+						T::AccountId,
+					>>::Balance = amount.saturated_into();
+
+					ensure!(
+						<pallet_assets::Pallet<T> as fungibles::Inspect<T::AccountId>>::balance(
+							currency, from
+						) >= price_balance + minimum_balance_needed_to_exist,
+						Error::<T>::InsufficientBalance
+					);
+					ensure!(
+						<pallet_assets::Pallet<T> as fungibles::Inspect<T::AccountId>>::balance(
+							currency, to
+						) + price_balance >= minimum_balance_needed_to_exist,
+						Error::<T>::ReceiverBelowMinimumBalance
+					);
+				},
+				Currency::Native => {
+					let minimum_balance_needed_to_exist =
+						<pallet_balances::Pallet<T> as fungible::Inspect<T::AccountId>>::minimum_balance();
+					let price_balance: <pallet_balances::Pallet<T> as fungible::Inspect<
+						T::AccountId,
+					>>::Balance = amount.saturated_into();
+
+					ensure!(
+						pallet_balances::Pallet::<T>::free_balance(from) >=
+							price_balance + minimum_balance_needed_to_exist,
+						Error::<T>::InsufficientBalance
+					);
+					ensure!(
+						pallet_balances::Pallet::<T>::free_balance(to) + price_balance >=
+							minimum_balance_needed_to_exist,
+						Error::<T>::ReceiverBelowMinimumBalance
+					);
+				},
+			}
+
+			Ok(())
+		}
+
+		/// Transfer `amount` amount of token `currency` from `from` to `to`
+		pub fn transfer_currency(
+			from: &T::AccountId,
+			to: &T::AccountId,
+			amount: u128,
+			currency: Currency<T::AssetId>,
+		) -> DispatchResult {
+			match currency {
+				Currency::Custom(currency) => {
+					<pallet_assets::Pallet<T> as fungibles::Transfer<T::AccountId>>::transfer(
+						// transfer `amount` units of `currency` from `from` to `to`
+						currency,
+						from,
+						to,
+						amount.saturated_into(),
+						true, // The debited account must stay alive at the end of the operation; an error is returned if this cannot be achieved legally.
+					)
+					.map_err(|_| Error::<T>::InsufficientBalance)?;
+				},
+				Currency::Native => {
+					<pallet_balances::Pallet<T> as frame_support::traits::Currency<T::AccountId>>::transfer(
+						from,
+						to,
+						amount.saturated_into(),
+						ExistenceRequirement::KeepAlive
+					).map_err(|_| Error::<T>::InsufficientBalance)?;
+				},
+			}
+
+			Ok(())
+		}
+
+		/// Get the **account** that is **paid** the **license royalty** of the **Proto-Fragment** of the Fragment Definition `definition`
+		/// when `price` is paid for it, and the **amount** of the license royalty (see `pallet_protos::set_license_royalty()`).
+		///
+		/// Returns `None` if the Proto-Fragment has no license royalty, if the Fragment Definition is owned by the Proto-Fragment's owner
+		/// or if the license royalty amounts to nothing.
+		pub fn get_license_royalty(
+			definition: &FragmentDefinition<Vec<u8>, T::AssetId, T::AccountId, T::BlockNumber>,
+			price: u128,
+		) -> Option<(T::AccountId, u128)> {
+			let royalty = <pallet_protos::LicenseRoyalties<T>>::get(definition.proto_hash)?;
+			let proto_owner = match <Protos<T>>::get(definition.proto_hash)?.owner {
+				ProtoOwner::User(owner) if owner != definition.owner => owner,
+				_ => return None,
+			};
+			let amount = royalty.mul_floor(price);
+			if amount == 0 {
+				return None
+			}
+			Some((proto_owner, amount))
+		}
+
+		/// Ensure that `who` is **allowed** to **write** the **metadata key `metadata_key`** of a **Fragment Instance**
+		/// (see `set_metadata_key_policy()`)
+		///
+		/// * `who` - Account ID that writes the metadata key
+		/// * `definition_hash` - Fragment Definition of the Fragment Instance
+		/// * `edition_id` - Edition ID of the Fragment Instance
+		/// * `copy_id` - Copy ID of the Fragment Instance
+		/// * `instance_struct` - `FragmentInstance` struct of the Fragment Instance
+		/// * `metadata_key` - Metadata key
+		pub fn ensure_can_write_instance_metadata_key(
+			who: &T::AccountId,
+			definition_hash: &Hash128,
+			edition_id: InstanceUnit,
+			copy_id: InstanceUnit,
+			instance_struct: &FragmentInstance<T::BlockNumber>,
+			metadata_key: &BoundedVec<u8, <T as pallet_protos::Config>::StringLimit>,
+		) -> DispatchResult {
+			match <MetadataKeyPolicies<T>>::get(definition_hash, metadata_key) {
+				None | Some(MetadataKeyPolicy::Owner) => {
+					let owned_instances = <Inventory<T>>::get(who.clone(), definition_hash)
+						.ok_or(Error::<T>::NotFound)?;
+					ensure!(
+						owned_instances.contains(&(Compact(edition_id), Compact(copy_id))),
+						Error::<T>::NoPermission
+					);
+					ensure!(
+						(instance_struct.permissions & FragmentPerms::EDIT) == FragmentPerms::EDIT,
+						Error::<T>::NotEditable
+					);
+				},
+				Some(policy) => {
+					let definition_owner =
+						<Definitions<T>>::get(definition_hash).ok_or(Error::<T>::NotFound)?.owner;
+					Self::ensure_can_write_reserved_metadata_key(who, &definition_owner, &policy)?;
+				},
+			}
+
+			Ok(())
+		}
+
+		/// Ensure that `who` is **allowed** to **write** a **metadata key** whose **policy** is **`policy`**,
+		/// where `policy` is not `MetadataKeyPolicy::Owner`
+		///
+		/// * `who` - Account ID that writes the metadata key
+		/// * `definition_owner` - Owner of the Fragment Definition
+		/// * `policy` - Policy of the metadata key
+		pub fn ensure_can_write_reserved_metadata_key(
+			who: &T::AccountId,
+			definition_owner: &T::AccountId,
+			policy: &MetadataKeyPolicy,
+		) -> DispatchResult {
+			match policy {
+				MetadataKeyPolicy::Owner => Err(Error::<T>::ParamsNotValid.into()),
+				MetadataKeyPolicy::Creator => {
+					ensure!(who == definition_owner, Error::<T>::MetadataKeyReservedForCreator);
+					Ok(())
+				},
+				MetadataKeyPolicy::ClusterRole { cluster_id, role_name } => {
+					ensure!(
+						pallet_clusters::Pallet::<T>::has_role(cluster_id, who, role_name),
+						Error::<T>::MetadataKeyReservedForClusterRole
+					);
+					Ok(())
+				},
+			}
+		}
+
+		/// Whether an **attribute** of **type `attribute_type`** can **hold** a **value** of the **Trait record type `variable_type`**
+		pub fn is_attribute_type_of(
+			attribute_type: &AttributeType,
+			variable_type: &VariableType,
+		) -> bool {
+			matches!(
+				(attribute_type, variable_type),
+				(AttributeType::Number, VariableType::Int(_))
+					| (AttributeType::String, VariableType::String)
+					| (AttributeType::Hash, VariableType::Bytes)
+			)
+		}
+
+		/// Ensure that the **unique data `data`** is **accepted** by the **validator of the unique data** of the **Fragment Definition `definition_hash`**
+		/// (if the Fragment Definition has one, see `set_unique_data_validator()`)
+		///
+		/// * `who` - Account ID that is creating the Fragment Instance
+		/// * `definition_hash` - Fragment Definition of the Fragment Instance
+		/// * `data` - Unique data of the Fragment Instance
+		///
+		/// Returns the weight consumed by the validator (zero if the Fragment Definition has no validator).
+		pub fn validate_unique_data(
+			who: &T::AccountId,
+			definition_hash: &Hash128,
+			data: &Vec<u8>,
+		) -> Result<Weight, DispatchError> {
+			let Some(validator) = <UniqueDataValidators<T>>::get(definition_hash) else {
+				return Ok(Weight::zero())
+			};
+
+			let input_data = (definition_hash, who, data).encode();
+			let exec_result = <pallet_contracts::Pallet<T>>::bare_call(
+				who.clone(),
+				validator,
+				0u32.saturated_into(),
+				Weight::from_ref_time(MAX_UNIQUE_DATA_VALIDATOR_WEIGHT),
+				None,
+				input_data,
+				false,
+				pallet_contracts::Determinism::Deterministic,
+			);
+			let result = exec_result.result.map_err(|e| {
+				log::debug!("Unique data validator error: {:?}", e);
+				Error::<T>::UniqueDataNotValid
+			})?;
+
+			ensure!(
+				!result.did_revert() && matches!(bool::decode(&mut &result.data[..]), Ok(true)),
+				Error::<T>::UniqueDataNotValid
+			);
+
+			Ok(exec_result.gas_consumed)
+		}
+
+		/// Ensure that the **owner of a crafting recipe** can **create Fragment Instances** of the **Fragment Definition `definition_hash`**
+		/// (i.e that it owns the Proto-Fragment of the Fragment Definition, and that the Proto-Fragment is not detached)
+		///
+		/// * `owner` - Owner of the crafting recipe
+		/// * `definition_hash` - Fragment Definition of an output of the crafting recipe
+		pub fn ensure_can_mint_recipe_output(
+			owner: &T::AccountId,
+			definition_hash: &Hash128,
+		) -> DispatchResult {
+			let definition = <Definitions<T>>::get(definition_hash).ok_or(Error::<T>::NotFound)?;
+			ensure!(*owner == definition.owner, Error::<T>::NoPermission);
+			ensure!(
+				!<DetachedHashes<T>>::contains_key(&DetachHash::Proto(definition.proto_hash)),
+				Error::<T>::Detached
+			);
+			Ok(())
+		}
+
+		/// Whether the Fragment Instance `item_data` has expired at the block number `block_number`
+		///
+		/// Note: An expired Fragment Instance may still be in storage, until it is swept by `sweep_expirations()`
+		pub fn is_expired(
+			item_data: &FragmentInstance<T::BlockNumber>,
+			block_number: T::BlockNumber,
+		) -> bool {
+			item_data.expiring_at.map_or(false, |expiring_at| block_number >= expiring_at)
+		}
+
+		/// Whether `operator` is approved to transfer the Fragment Instance on behalf of `owner`,
+		/// either specifically (see `approve()`) or for all of `owner`'s Fragment Instances of the Fragment Definition (see `set_approval_for_all()`)
+		///
+		/// * `owner` - Fragnova Account ID that owns the Fragment Instance
+		/// * `operator` - Fragnova Account ID to check
+		/// * `definition_hash` - Fragment Definition of the Fragment Instance
+		/// * `edition_id` - Edition ID of the Fragment Instance
+		/// * `copy_id` - Copy ID of the Fragment Instance
+		pub fn is_approved(
+			owner: &T::AccountId,
+			operator: &T::AccountId,
+			definition_hash: &Hash128,
+			edition_id: InstanceUnit,
+			copy_id: InstanceUnit,
+		) -> bool {
+			<InstanceApprovals<T>>::get((definition_hash, edition_id, copy_id)).as_ref() ==
+				Some(operator) ||
+				<OperatorApprovals<T>>::contains_key((owner, definition_hash, operator))
+		}
+
+		/// Whether a Fragment Instance is locked in a pending swap, i.e whether its lock has not ended yet
+		///
+		/// * `definition_hash` - Fragment Definition of the Fragment Instance
+		/// * `edition_id` - Edition ID of the Fragment Instance
+		/// * `copy_id` - Copy ID of the Fragment Instance
+		pub fn is_instance_locked(
+			definition_hash: &Hash128,
+			edition_id: InstanceUnit,
+			copy_id: InstanceUnit,
+		) -> bool {
+			<LockedInstances<T>>::get((definition_hash, edition_id, copy_id))
+				.map_or(false, |until| <frame_system::Pallet<T>>::block_number() < until)
+		}
+
+		/// Ensure that a side of a swap is not too large and does not contain the same asset twice
+		///
+		/// * `bundle` - Side of the swap
+		pub fn ensure_valid_swap_bundle(bundle: &SwapBundle<T::AssetId>) -> DispatchResult {
+			ensure!(
+				bundle.instances.len() + bundle.protos.len() <= MAX_SWAP_ITEMS,
+				Error::<T>::TooManySwapItems
+			);
+			let mut instances = BTreeSet::new();
+			let mut protos = BTreeSet::new();
+			ensure!(
+				bundle.instances.iter().all(|instance| instances.insert(instance)) &&
+					bundle.protos.iter().all(|proto_hash| protos.insert(proto_hash)),
+				Error::<T>::ParamsNotValid
+			);
+			Ok(())
+		}
+
+		/// Whether every asset of a side of a swap can be transferred from `from` to `to`
+		///
+		/// * `from` - Fragnova Account ID that owns the assets
+		/// * `to` - Fragnova Account ID to transfer the assets to
+		/// * `bundle` - Side of the swap
+		/// * `ignore_locks` - Whether the assets can be locked (i.e by the swap itself)
+		pub fn can_transfer_swap_bundle(
+			from: &T::AccountId,
+			to: &T::AccountId,
+			bundle: &SwapBundle<T::AssetId>,
+			ignore_locks: bool,
+		) -> DispatchResult {
+			Self::ensure_valid_swap_bundle(bundle)?;
+
+			for (definition_hash, edition_id, copy_id) in bundle.instances.iter() {
+				if ignore_locks {
+					Self::can_transfer_unlocked_instance(
+						from,
+						definition_hash,
+						*edition_id,
+						*copy_id,
+						None,
+						None,
+					)?;
+				} else {
+					Self::can_transfer_instance(
+						from,
+						definition_hash,
+						*edition_id,
+						*copy_id,
+						None,
+						None,
+					)?;
+				}
+			}
+			for proto_hash in bundle.protos.iter() {
+				pallet_protos::Pallet::<T>::can_transfer_proto(from, proto_hash)?;
+				if !ignore_locks {
+					ensure!(!pallet_protos::Pallet::<T>::is_locked(proto_hash), Error::<T>::Locked);
+				}
+			}
+			if let Some((currency, amount)) = bundle.amount {
+				Self::can_transfer_currency(from, to, amount, currency)?;
+			}
+
+			Ok(())
+		}
+
+		/// Unlock the Fragment Instances and the Proto-Fragments of a side of a swap, if they are still owned by `owner`
+		/// (otherwise they were transferred after the swap expired, and their lock may belong to another swap)
+		///
+		/// * `owner` - Fragnova Account ID that offered the assets
+		/// * `bundle` - Side of the swap
+		pub fn unlock_swap_bundle(owner: &T::AccountId, bundle: &SwapBundle<T::AssetId>) {
+			for (definition_hash, edition_id, copy_id) in bundle.instances.iter() {
+				if <InstanceOwner<T>>::get((definition_hash, edition_id, copy_id)).as_ref() ==
+					Some(owner)
+				{
+					<LockedInstances<T>>::remove((definition_hash, edition_id, copy_id));
+				}
+			}
+			for proto_hash in bundle.protos.iter() {
+				if <Protos<T>>::get(proto_hash)
+					.map_or(false, |proto| proto.owner == ProtoOwner::User(owner.clone()))
+				{
+					pallet_protos::Pallet::<T>::unlock_proto(proto_hash);
+				}
+			}
+		}
+
+		/// Transfer every asset of a side of a swap from `from` to `to`
+		///
+		/// Note: This function does not check anything, see `can_transfer_swap_bundle()`
+		///
+		/// * `from` - Fragnova Account ID that owns the assets
+		/// * `to` - Fragnova Account ID to transfer the assets to
+		/// * `bundle` - Side of the swap
+		pub fn transfer_swap_bundle(
+			from: &T::AccountId,
+			to: &T::AccountId,
+			bundle: &SwapBundle<T::AssetId>,
+		) -> DispatchResult {
+			for (definition_hash, edition_id, copy_id) in bundle.instances.iter() {
+				Self::transfer_instance(
+					from,
+					to,
+					definition_hash,
+					*edition_id,
+					*copy_id,
+					None,
+					None,
+				)?;
+			}
+			for proto_hash in bundle.protos.iter() {
+				pallet_protos::Pallet::<T>::transfer_proto(
+					proto_hash,
+					ProtoOwner::User(from.clone()),
+					to.clone(),
+				);
+			}
+			if let Some((currency, amount)) = bundle.amount {
+				Self::transfer_currency(from, to, amount, currency)?;
+			}
+
+			Ok(())
+		}
+
+		/// Get the **rental** of a Fragment Instance, **if the rental has not ended yet**
+		///
+		/// * `definition_hash` - Fragment Definition of the Fragment Instance
+		/// * `edition_id` - Edition ID of the Fragment Instance
+		/// * `copy_id` - Copy ID of the Fragment Instance
+		pub fn get_active_rental(
+			definition_hash: &Hash128,
+			edition_id: InstanceUnit,
+			copy_id: InstanceUnit,
+		) -> Option<Rental<T::AccountId, T::BlockNumber>> {
+			<Rentals<T>>::get((definition_hash, edition_id, copy_id))
+				.filter(|rental| <frame_system::Pallet<T>>::block_number() < rental.until)
+		}
+
+		/// Get the **Account ID** that has the **use rights** of a Fragment Instance,
+		/// i.e its renter if the Fragment Instance is rented out, otherwise its owner.
+		///
+		/// Returns `None` if the Fragment Instance does not exist or has expired.
+		///
+		/// * `definition_hash` - Fragment Definition of the Fragment Instance
+		/// * `edition_id` - Edition ID of the Fragment Instance
+		/// * `copy_id` - Copy ID of the Fragment Instance
+		pub fn get_instance_user(
+			definition_hash: &Hash128,
+			edition_id: InstanceUnit,
+			copy_id: InstanceUnit,
+		) -> Option<T::AccountId> {
+			let item_data = <Fragments<T>>::get((definition_hash, edition_id, copy_id))?;
+			if Self::is_expired(&item_data, <frame_system::Pallet<T>>::block_number()) {
+				return None
+			}
+			match Self::get_active_rental(definition_hash, edition_id, copy_id) {
+				Some(rental) => Some(rental.renter),
+				None => <InstanceOwner<T>>::get((definition_hash, edition_id, copy_id)),
+			}
+		}
+
+		/// Get the **outermost Fragment Instance** that the **Fragment Instance `instance`** is nested in
+		/// (or `instance` itself if it is not nested)
+		pub fn get_root_instance(
+			instance: (Hash128, InstanceUnit, InstanceUnit),
+		) -> (Hash128, InstanceUnit, InstanceUnit) {
+			let mut root = instance;
+			while let Some(parent) = <InstanceParent<T>>::get(root) {
+				root = parent;
+			}
+			root
+		}
+
+		/// Get the **number of levels of Fragment Instances** that are **nested below** the **Fragment Instance**
+		/// (i.e 0 if no Fragment Instance is nested in it)
+		///
+		/// * `definition_hash` - Fragment Definition of the Fragment Instance
+		/// * `edition_id` - Edition ID of the Fragment Instance
+		/// * `copy_id` - Copy ID of the Fragment Instance
+		pub fn get_nesting_height(
+			definition_hash: &Hash128,
+			edition_id: InstanceUnit,
+			copy_id: InstanceUnit,
+		) -> u32 {
+			<InstanceChildren<T>>::get((definition_hash, edition_id, copy_id))
+				.unwrap_or_default()
+				.iter()
+				.map(|(child_definition_hash, child_edition_id, child_copy_id)| {
+					1 + Self::get_nesting_height(
+						child_definition_hash,
+						*child_edition_id,
+						*child_copy_id,
+					)
+				})
+				.max()
+				.unwrap_or(0)
+		}
+
+		/// Remove the **Fragment Instance** from the **list of nested Fragment Instances** of the **Fragment Instance that it is nested in** (if any)
+		///
+		/// * `definition_hash` - Fragment Definition of the Fragment Instance
+		/// * `edition_id` - Edition ID of the Fragment Instance
+		/// * `copy_id` - Copy ID of the Fragment Instance
+		pub fn unlink_nested_instance(
+			definition_hash: &Hash128,
+			edition_id: InstanceUnit,
+			copy_id: InstanceUnit,
+		) {
+			if let Some(parent) = <InstanceParent<T>>::take((definition_hash, edition_id, copy_id))
+			{
+				<InstanceChildren<T>>::mutate_exists(parent, |children| {
+					if let Some(list) = children {
+						list.retain(|child| *child != (*definition_hash, edition_id, copy_id));
+						if list.is_empty() {
+							*children = None;
+						}
+					}
+				});
+			}
+		}
+
+		/// Remove the expired Fragment Instance from storage and emit the `Expired` event
+		///
+		/// * `definition_hash` - Fragment Definition of the Fragment Instance
+		/// * `edition_id` - Edition ID of the Fragment Instance
+		/// * `copy_id` - Copy ID of the Fragment Instance
+		pub fn expire_instance(
+			definition_hash: &Hash128,
+			edition_id: InstanceUnit,
+			copy_id: InstanceUnit,
+		) {
+			<Fragments<T>>::remove((definition_hash, edition_id, copy_id));
+			<InstanceAttributes<T>>::remove((definition_hash, edition_id, copy_id));
+			<Definition2SecondarySales<T>>::remove((definition_hash, edition_id, copy_id));
+			<Rentals<T>>::remove((definition_hash, edition_id, copy_id));
+			<InstanceApprovals<T>>::remove((definition_hash, edition_id, copy_id));
+			<LockedInstances<T>>::remove((definition_hash, edition_id, copy_id));
+			Self::unlink_nested_instance(definition_hash, edition_id, copy_id);
+			Self::increment_removed_count(definition_hash);
+
+			if let Some(owner) = <InstanceOwner<T>>::take((definition_hash, edition_id, copy_id)) {
+				<Owners<T>>::mutate(definition_hash, owner.clone(), |x| {
+					if let Some(x) = x {
+						x.retain(|y| y != &(Compact(edition_id), Compact(copy_id)));
+					}
+				});
+				<Inventory<T>>::mutate(owner.clone(), definition_hash, |x| {
+					if let Some(x) = x {
+						x.retain(|y| y != &(Compact(edition_id), Compact(copy_id)));
+					}
+				});
+
+				Self::deposit_event(Event::Expired {
+					account_id: owner,
+					definition_hash: *definition_hash,
+					fragment_id: (edition_id, copy_id),
+				});
+			}
 		}
 
-		/// Whether `amount` amount of token `currency` can be transferred from `from` to `to`
-		pub fn can_transfer_currency(
-			from: &T::AccountId,
-			to: &T::AccountId,
-			amount: u128,
-			currency: Currency<T::AssetId>,
-		) -> DispatchResult {
-			match currency {
-				Currency::Custom(currency) => {
-					let minimum_balance_needed_to_exist =
-						<pallet_assets::Pallet<T> as fungibles::Inspect<T::AccountId>>::minimum_balance(currency);
-					let price_balance: <pallet_assets::Pallet<T> as fungibles::Inspect<
-						T::AccountId,
-					>>::Balance = amount.saturated_into();
+		/// Sweep the Fragment Instances that have expired at or before the block number `now` from storage,
+		/// starting from the block number `ExpirationCursor`, and without exceeding the weight `max_weight`.
+		///
+		/// Returns the weight that was consumed.
+		///
+		/// * `now` - Current block number
+		/// * `max_weight` - Maximum weight that can be consumed
+		pub fn sweep_expirations(now: T::BlockNumber, max_weight: Weight) -> Weight {
+			let base_weight = <T as Config>::WeightInfo::sweep_expirations(0);
+			if base_weight.any_gt(max_weight) {
+				return Weight::zero()
+			}
+			let instance_weight =
+				<T as Config>::WeightInfo::sweep_expirations(1).saturating_sub(base_weight);
+			let block_weight = T::DbWeight::get().reads(1);
 
-					ensure!(
-						<pallet_assets::Pallet<T> as fungibles::Inspect<T::AccountId>>::balance(
-							currency, from
-						) >= price_balance + minimum_balance_needed_to_exist,
-						Error::<T>::InsufficientBalance
-					);
-					ensure!(
-						<pallet_assets::Pallet<T> as fungibles::Inspect<T::AccountId>>::balance(
-							currency, to
-						) + price_balance >= minimum_balance_needed_to_exist,
-						Error::<T>::ReceiverBelowMinimumBalance
-					);
-				},
-				Currency::Native => {
-					let minimum_balance_needed_to_exist =
-						<pallet_balances::Pallet<T> as fungible::Inspect<T::AccountId>>::minimum_balance();
-					let price_balance: <pallet_balances::Pallet<T> as fungible::Inspect<
-						T::AccountId,
-					>>::Balance = amount.saturated_into();
+			let mut weight = base_weight;
+			let mut cursor = <ExpirationCursor<T>>::get();
 
-					ensure!(
-						pallet_balances::Pallet::<T>::free_balance(from) >=
-							price_balance + minimum_balance_needed_to_exist,
-						Error::<T>::InsufficientBalance
-					);
-					ensure!(
-						pallet_balances::Pallet::<T>::free_balance(to) + price_balance >=
-							minimum_balance_needed_to_exist,
-						Error::<T>::ReceiverBelowMinimumBalance
-					);
-				},
+			while cursor <= now {
+				if weight.saturating_add(block_weight).any_gt(max_weight) {
+					break
+				}
+				weight = weight.saturating_add(block_weight);
+
+				// maximum number of Fragment Instances that can still be swept
+				let remaining = (max_weight.saturating_sub(weight).ref_time() /
+					instance_weight.ref_time().max(1))
+				.min(u32::MAX as u64) as usize;
+
+				let expiring = <ExpirationQueue<T>>::iter_key_prefix(cursor)
+					.take(remaining.saturating_add(1))
+					.collect::<Vec<_>>();
+				let exhausted = expiring.len() <= remaining;
+
+				for (definition_hash, edition_id, copy_id) in expiring.into_iter().take(remaining) {
+					Self::expire_instance(&definition_hash, edition_id, copy_id);
+					<ExpirationQueue<T>>::remove(cursor, (definition_hash, edition_id, copy_id));
+					weight = weight.saturating_add(instance_weight);
+				}
+
+				if !exhausted {
+					break
+				}
+				cursor += One::one();
 			}
 
-			Ok(())
+			<ExpirationCursor<T>>::put(cursor);
+
+			weight
 		}
 
-		/// Transfer `amount` amount of token `currency` from `from` to `to`
-		pub fn transfer_currency(
+		/// Whether a Fragment Instance can be transferred
+		///
+		/// * `from` - Fragnova Account ID to transfer the Fragment Instance from
+		/// * `definition_hash` - Fragment Definition of the Fragment Instance
+		/// * `edition_id` - Edition ID of the Fragment Instance
+		/// * `copy_id` - Copy ID of the Fragment Instance
+		/// * `new_permissions` - New Permissions of the Fragment Instance after it is sold
+		/// * `expiration` -  Block number that the newly-copied Fragment Instance expires at. If the Fragment Instance is not copyable, this field is practically irrelevant.
+		pub fn can_transfer_instance(
 			from: &T::AccountId,
-			to: &T::AccountId,
-			amount: u128,
-			currency: Currency<T::AssetId>,
+			definition_hash: &Hash128,
+			edition_id: InstanceUnit,
+			copy_id: InstanceUnit,
+			new_permissions: Option<FragmentPerms>,
+			expiration: Option<T::BlockNumber>,
 		) -> DispatchResult {
-			match currency {
-				Currency::Custom(currency) => {
-					<pallet_assets::Pallet<T> as fungibles::Transfer<T::AccountId>>::transfer(
-						// transfer `amount` units of `currency` from `from` to `to`
-						currency,
-						from,
-						to,
-						amount.saturated_into(),
-						true, // The debited account must stay alive at the end of the operation; an error is returned if this cannot be achieved legally.
-					)
-					.map_err(|_| Error::<T>::InsufficientBalance)?;
-				},
-				Currency::Native => {
-					<pallet_balances::Pallet<T> as frame_support::traits::Currency<T::AccountId>>::transfer(
-						from,
-						to,
-						amount.saturated_into(),
-						ExistenceRequirement::KeepAlive
-					).map_err(|_| Error::<T>::InsufficientBalance)?;
-				},
-			}
+			// a Fragment Instance that is offered in a pending swap cannot be transferred
+			ensure!(
+				!Self::is_instance_locked(definition_hash, edition_id, copy_id),
+				Error::<T>::Locked
+			);
 
-			Ok(())
+			Self::can_transfer_unlocked_instance(
+				from,
+				definition_hash,
+				edition_id,
+				copy_id,
+				new_permissions,
+				expiration,
+			)
 		}
 
-		/// Whether a Fragment Instance can be transferred
+		/// Whether a Fragment Instance can be transferred, regardless of whether it is locked in a pending swap
 		///
 		/// * `from` - Fragnova Account ID to transfer the Fragment Instance from
 		/// * `definition_hash` - Fragment Definition of the Fragment Instance
@@ -1773,7 +5415,7 @@ pub mod pallet {
 		/// * `copy_id` - Copy ID of the Fragment Instance
 		/// * `new_permissions` - New Permissions of the Fragment Instance after it is sold
 		/// * `expiration` -  Block number that the newly-copied Fragment Instance expires at. If the Fragment Instance is not copyable, this field is practically irrelevant.
-		pub fn can_transfer_instance(
+		pub fn can_transfer_unlocked_instance(
 			from: &T::AccountId,
 			definition_hash: &Hash128,
 			edition_id: InstanceUnit,
@@ -1795,10 +5437,8 @@ pub mod pallet {
 			let item_data = <Fragments<T>>::get((definition_hash, edition_id, copy_id))
 				.ok_or(Error::<T>::NotFound)?;
 
-			// no go if will expire this block
-			if let Some(item_expiration) = item_data.expiring_at {
-				ensure!(current_block_number < item_expiration, Error::<T>::NotFound);
-			}
+			// no go if will expire this block (or has expired but has not been swept yet)
+			ensure!(!Self::is_expired(&item_data, current_block_number), Error::<T>::NotFound);
 
 			if let Some(expiration) = expiration {
 				ensure!(current_block_number < expiration, Error::<T>::ParamsNotValid);
@@ -1819,6 +5459,12 @@ pub mod pallet {
 				Error::<T>::NoPermission
 			);
 
+			// a rented out Fragment Instance cannot be transferred until its rental ends
+			ensure!(
+				Self::get_active_rental(definition_hash, edition_id, copy_id).is_none(),
+				Error::<T>::Rented
+			);
+
 			if let Some(new_perms) = new_permissions {
 				// ensure we only allow more restrictive permissions
 				if (item_data.permissions & FragmentPerms::EDIT) != FragmentPerms::EDIT {
@@ -1890,6 +5536,7 @@ pub mod pallet {
 					to.clone(),
 					(Compact(edition_id), Compact(copy)),
 				);
+				<InstanceOwner<T>>::insert((definition_hash, edition_id, copy), to.clone());
 
 				<Inventory<T>>::append(
 					to.clone(),
@@ -1909,19 +5556,29 @@ pub mod pallet {
 					} else {
 						expiring_at
 					};
-					<Expirations<T>>::append(
+					<ExpirationQueue<T>>::insert(
 						expiration,
-						(definition_hash, Compact(edition_id), Compact(copy)),
+						(*definition_hash, edition_id, copy),
+						(),
 					);
 				} else if let Some(expiration) = expiration {
 					item_data.expiring_at = Some(expiration);
-					<Expirations<T>>::append(
+					<ExpirationQueue<T>>::insert(
 						expiration,
-						(definition_hash, Compact(edition_id), Compact(copy)),
+						(*definition_hash, edition_id, copy),
+						(),
 					);
 				}
 
 				<Fragments<T>>::insert((definition_hash, edition_id, copy), item_data);
+				if let Some(attributes) =
+					<InstanceAttributes<T>>::get((definition_hash, edition_id, copy_id))
+				{
+					<InstanceAttributes<T>>::insert(
+						(definition_hash, edition_id, copy),
+						attributes,
+					);
+				}
 
 				Self::deposit_event(Event::InventoryAdded {
 					account_id: to.clone(),
@@ -1929,6 +5586,21 @@ pub mod pallet {
 					fragment_id: (edition_id, copy),
 				});
 			} else {
+				// remove the rental that has ended (if any)
+				<Rentals<T>>::remove((definition_hash, edition_id, copy_id));
+				// the approval of the Fragment Instance does not carry over to its new owner
+				<InstanceApprovals<T>>::remove((definition_hash, edition_id, copy_id));
+				// remove the lock that has ended (if any)
+				<LockedInstances<T>>::remove((definition_hash, edition_id, copy_id));
+				// a nested Fragment Instance is taken out of its parent once it leaves the parent's account
+				Self::unlink_nested_instance(definition_hash, edition_id, copy_id);
+				// the account of the Fragment Instance (if it has been created) is now controlled by its new owner
+				let frag_account =
+					Self::get_fragment_account_id(*definition_hash, edition_id, copy_id);
+				if pallet_proxy::Proxies::<T>::contains_key(&frag_account) {
+					Self::set_instance_account_proxy(&frag_account, to);
+				}
+
 				// we will remove from this account to give to new account
 				<Owners<T>>::mutate(definition_hash, from.clone(), |ids| {
 					if let Some(ids) = ids {
@@ -1953,6 +5625,7 @@ pub mod pallet {
 					to.clone(),
 					(Compact(edition_id), Compact(copy_id)),
 				);
+				<InstanceOwner<T>>::insert((definition_hash, edition_id, copy_id), to.clone());
 
 				<Inventory<T>>::append(
 					to.clone(),
@@ -1990,6 +5663,17 @@ pub mod pallet {
 		/// {
 		/// 	<definition-hash>: {
 		///			"name": <definition-name>,
+		///			"currency": <definition-currency>,
+		///			"sale": {
+		///				"price": <price>,
+		///				"units_left": <units-left>,
+		///				"expiration": <expiration-block-number>,
+		///				"stack_amount": <stack-amount>,
+		///				"phases": [
+		///					{ "start": <start>, "price": <price>, "allowlist": <merkle-root>, "max_per_account": <max-per-account> },
+		///					...
+		///				],
+		///			},
 		///			"owner": <definition-owner>,
 		///			"metadata": {
 		///				<metadata-key>: <data-hash>,
@@ -1997,37 +5681,68 @@ pub mod pallet {
 		///			},
 		/// 	...
 		/// }
+		///
+		/// Note: The field "sale" is `null` if the FD is not on sale
 		pub fn get_definitions(
 			params: GetDefinitionsParams<T::AccountId, Vec<u8>>,
 		) -> Result<Vec<u8>, Vec<u8>> {
 			let mut map = Map::new();
 
-			let list_definitions_final: Vec<Hash128> = if let Some(owner) = params.owner {
-				let list_protos_owner =
-					<ProtosByOwner<T>>::get(ProtoOwner::<T::AccountId>::User(owner))
-						.ok_or("Owner not found")?; // `owner` exists in `ProtosByOwner`
+			let filter_protos = !params.categories.is_empty() || !params.tags.is_empty();
+
+			let list_definitions_final: Vec<Hash128> = if let Some(owner) = params.owner.clone() {
+				let list_definitions_owner =
+					<DefinitionsByOwner<T>>::get(owner).ok_or("Owner not found")?; // `owner` exists in `DefinitionsByOwner`
+				let list_definitions_owner =
+					list_definitions_owner.into_iter().filter(|definition_hash| {
+						(!filter_protos ||
+							<Definitions<T>>::get(definition_hash).map_or(false, |definition| {
+								pallet_protos::Pallet::<T>::filter_proto(
+									&definition.proto_hash,
+									&params.tags,
+									&params.categories,
+									None,
+									&[],
+									None,
+								)
+							})) && Self::filter_definition(definition_hash, &params)
+					});
 				if params.desc {
 					// Sort in descending order
-					list_protos_owner
+					list_definitions_owner
+						.collect::<Vec<Hash128>>()
 						.into_iter()
 						.rev()
-						.filter_map(|proto_id| <Proto2Fragments<T>>::get(&proto_id))
-						.flatten()
 						.skip(params.from as usize)
 						.take(params.limit as usize)
 						.collect()
 				} else {
 					// Sort in ascending order
-					list_protos_owner
-						.into_iter()
-						.filter_map(|proto_id| <Proto2Fragments<T>>::get(&proto_id))
-						.flatten()
+					list_definitions_owner
 						.skip(params.from as usize)
 						.take(params.limit as usize)
 						.collect()
 				}
+			} else if filter_protos {
+				<Proto2Fragments<T>>::iter()
+					.filter(|(proto_hash, _)| {
+						pallet_protos::Pallet::<T>::filter_proto(
+							proto_hash,
+							&params.tags,
+							&params.categories,
+							None,
+							&[],
+							None,
+						)
+					})
+					.flat_map(|(_, definitions)| definitions)
+					.filter(|definition_hash| Self::filter_definition(definition_hash, &params))
+					.skip(params.from as usize)
+					.take(params.limit as usize)
+					.collect()
 			} else {
 				<Definitions<T>>::iter_keys()
+					.filter(|definition_hash| Self::filter_definition(definition_hash, &params))
 					.skip(params.from as usize)
 					.take(params.limit as usize)
 					.collect()
@@ -2048,7 +5763,7 @@ pub mod pallet {
 					.try_into()
 					.or(Err("Failed to convert `definition_id` to Hash128"))?;
 
-				let num_instances: InstanceUnit =
+				let num_copies: InstanceUnit =
 					if let Some(editions) = <EditionsCount<T>>::get(array_definition_id) {
 						let editions: InstanceUnit = editions.into();
 						(1..=editions)
@@ -2061,6 +5776,10 @@ pub mod pallet {
 					} else {
 						0
 					};
+				// the Fragment Instances that were removed (e.g burned) are no longer counted
+				let num_instances = num_copies.saturating_sub(
+					<RemovedCount<T>>::get(array_definition_id).unwrap_or(Compact(0)).into(),
+				);
 
 				(*map_definition).insert("num_instances".into(), num_instances.into());
 
@@ -2073,13 +5792,33 @@ pub mod pallet {
 						.map_err(|_| "Failed to convert u8 vec to sring")?
 						.into(),
 				);
-				// (*map_definition).insert("currency".into(), definition_struct.metadata.currency.into());
+				(*map_definition).insert(
+					"currency".into(),
+					Self::get_currency_in_json_format(&definition_struct.metadata.currency),
+				);
+
+				(*map_definition).insert(
+					"sale".into(),
+					<Publishing<T>>::get(array_definition_id).map_or(Value::Null, |sale| {
+						json!({
+							"price": u128::from(sale.price).to_string(),
+							"units_left": sale.units_left.map(InstanceUnit::from),
+							"expiration": sale.expiration.map(|block| block.saturated_into::<u64>()),
+							"stack_amount": sale.stack_amount.map(InstanceUnit::from),
+							"phases": sale.phases.iter().map(|phase| json!({
+								"start": phase.start.saturated_into::<u64>(),
+								"price": u128::from(phase.price).to_string(),
+								"allowlist": phase.allowlist.map(hex::encode),
+								"max_per_account": phase.max_per_account.map(InstanceUnit::from),
+							})).collect::<Vec<Value>>(),
+						})
+					}),
+				);
 
 				if params.return_owners {
-					let owner = <Protos<T>>::get(definition_struct.proto_hash)
-						.ok_or("Failed to get proto struct")?
-						.owner;
-					let json_owner = pallet_protos::Pallet::<T>::get_owner_in_json_format(owner);
+					let json_owner = pallet_protos::Pallet::<T>::get_owner_in_json_format(
+						ProtoOwner::User(definition_struct.owner),
+					);
 					(*map_definition).insert(String::from("owner"), json_owner);
 				}
 
@@ -2101,6 +5840,61 @@ pub mod pallet {
 			Ok(result.into_bytes())
 		}
 
+		/// Whether the Fragment Definition `definition_hash` matches the currency and sale filters of `params`
+		/// (i.e `currency`, `published`, `min_price`, `max_price` and `sold_out`)
+		fn filter_definition(
+			definition_hash: &Hash128,
+			params: &GetDefinitionsParams<T::AccountId, Vec<u8>>,
+		) -> bool {
+			if let Some(currency) = &params.currency {
+				let Some(definition) = <Definitions<T>>::get(definition_hash) else { return false };
+				if Self::get_currency_in_json_format(&definition.metadata.currency) !=
+					Value::from(String::from_utf8_lossy(currency).into_owned())
+				{
+					return false
+				}
+			}
+
+			let sale = <Publishing<T>>::get(definition_hash);
+
+			if let Some(published) = params.published {
+				if published != sale.is_some() {
+					return false
+				}
+			}
+
+			if params.min_price.is_none() && params.max_price.is_none() && params.sold_out.is_none()
+			{
+				return true
+			}
+
+			let Some(sale) = sale else { return false };
+
+			let price: u128 = sale.price.into();
+			if params.min_price.map_or(false, |min_price| price < min_price) ||
+				params.max_price.map_or(false, |max_price| price > max_price)
+			{
+				return false
+			}
+
+			if let Some(sold_out) = params.sold_out {
+				if sold_out != (sale.units_left.map(InstanceUnit::from) == Some(0)) {
+					return false
+				}
+			}
+
+			true
+		}
+
+		/// Get the currency `currency` in the JSON format used by the RPC functions,
+		/// i.e `"native"` or the **hex-encoded SCALE-encoded Asset ID** of the custom currency
+		fn get_currency_in_json_format(currency: &Currency<T::AssetId>) -> Value {
+			match currency {
+				Currency::Native => "native".into(),
+				Currency::Custom(currency) => hex::encode(currency.encode()).into(),
+			}
+		}
+
 		/// **Query** and **Return** **Fragment Instance(s)** based on **`params`**
 		///
 		/// The returned JSON string has the following format:
@@ -2112,8 +5906,16 @@ pub mod pallet {
 		///				<metadata-key>: <data-hash>,
 		/// 			...
 		///			},
+		///			"nested": {
+		///				"<definition-hash>.<edition-id>.<copy-id>": {
+		///					"nested": { ... },
+		///				},
+		///				...
+		///			},
 		/// 	...
 		/// }
+		///
+		/// Note: The field "nested" is only returned if `params.return_nested` is true
 		pub fn get_instances(
 			params: GetInstancesParams<T::AccountId, Vec<u8>>,
 		) -> Result<Vec<u8>, Vec<u8>> {
@@ -2162,9 +5964,18 @@ pub mod pallet {
 				.try_for_each(|(edition_id, copy_id)| -> Result<(), Vec<u8>> {
 					let mut map_instance = Map::new();
 
-					let instance_struct =
+					// removed Fragment Instances (e.g burned ones) are skipped
+					let Some(instance_struct) =
 						<Fragments<T>>::get((definition_hash, edition_id, copy_id))
-							.ok_or("Instance not found")?;
+					else {
+						return Ok(())
+					};
+
+					// expired Fragment Instances that have not been swept yet are not returned
+					if Self::is_expired(&instance_struct, <frame_system::Pallet<T>>::block_number())
+					{
+						return Ok(())
+					}
 
 					if !params.metadata_keys.is_empty() {
 						let metadata = instance_struct
@@ -2186,6 +5997,14 @@ pub mod pallet {
 							.insert("metadata".into(), map_of_matching_metadata_keys.into());
 					}
 
+					if params.return_nested {
+						map_instance.insert(
+							"nested".into(),
+							Self::get_nested_instances_map(&definition_hash, edition_id, copy_id)
+								.into(),
+						);
+					}
+
 					map.insert(format!("{}.{}", edition_id, copy_id), map_instance.into());
 
 					Ok(())
@@ -2196,6 +6015,276 @@ pub mod pallet {
 			Ok(result.into_bytes())
 		}
 
+		/// Get the **tree of Fragment Instances** that are **nested** in the **Fragment Instance** as a JSON map (see `get_instances()`)
+		///
+		/// * `definition_hash` - Fragment Definition of the Fragment Instance
+		/// * `edition_id` - Edition ID of the Fragment Instance
+		/// * `copy_id` - Copy ID of the Fragment Instance
+		pub fn get_nested_instances_map(
+			definition_hash: &Hash128,
+			edition_id: InstanceUnit,
+			copy_id: InstanceUnit,
+		) -> Map<String, Value> {
+			<InstanceChildren<T>>::get((definition_hash, edition_id, copy_id))
+				.unwrap_or_default()
+				.into_iter()
+				.map(|(child_definition_hash, child_edition_id, child_copy_id)| {
+					let mut map_child = Map::new();
+					map_child.insert(
+						"nested".into(),
+						Self::get_nested_instances_map(
+							&child_definition_hash,
+							child_edition_id,
+							child_copy_id,
+						)
+						.into(),
+					);
+					(
+						format!(
+							"{}.{}.{}",
+							hex::encode(child_definition_hash),
+							child_edition_id,
+							child_copy_id
+						),
+						map_child.into(),
+					)
+				})
+				.collect()
+		}
+
+		/// Query the **balance of the vault** of a **Fragment Definition** (i.e the account into which the sale proceeds are paid)
+		/// and the **amount that can be withdrawn** from it (see `withdraw_proceeds()`).
+		///
+		/// The returned JSON string has the following format:
+		///
+		/// {
+		/// 	"vault": <vault-account-id>,
+		/// 	"currency": "native" | <asset-id>,
+		/// 	"balance": <balance>,
+		/// 	"withdrawable": <balance - existential-deposit>,
+		/// }
+		///
+		/// Note: The balances are strings because they may not fit in a JSON number
+		pub fn get_vault_balance(definition_hash: Vec<u8>) -> Result<Vec<u8>, Vec<u8>> {
+			let definition_hash: Hash128 = hex::decode(definition_hash)
+				.map_err(|_| "Failed to convert string to u8 slice")?
+				.try_into()
+				.map_err(|_| "Failed to convert u8 slice to Hash128")?;
+
+			let definition =
+				<Definitions<T>>::get(definition_hash).ok_or("Fragment Definition not found")?;
+
+			let vault: T::AccountId =
+				sp_fragnova::get_account_id(b"pallet-fragments-create", &definition_hash);
+
+			let (currency, balance, minimum_balance): (Value, u128, u128) =
+				match definition.metadata.currency {
+					Currency::Native => (
+						Self::get_currency_in_json_format(&Currency::Native),
+						pallet_balances::Pallet::<T>::free_balance(&vault).saturated_into(),
+						<pallet_balances::Pallet<T> as fungible::Inspect<T::AccountId>>::minimum_balance()
+							.saturated_into(),
+					),
+					Currency::Custom(currency) => (
+						Self::get_currency_in_json_format(&Currency::Custom(currency)),
+						<pallet_assets::Pallet<T> as fungibles::Inspect<T::AccountId>>::balance(
+							currency, &vault,
+						)
+						.saturated_into(),
+						<pallet_assets::Pallet<T> as fungibles::Inspect<T::AccountId>>::minimum_balance(
+							currency,
+						)
+						.saturated_into(),
+					),
+				};
+
+			let result = json!({
+				"vault": hex::encode(vault),
+				"currency": currency,
+				"balance": balance.to_string(),
+				"withdrawable": balance.saturating_sub(minimum_balance).to_string(),
+			})
+			.to_string();
+
+			Ok(result.into_bytes())
+		}
+
+		/// **Query** and **Return** the **crafting recipes** based on **`params`**
+		///
+		/// The returned JSON string has the following format:
+		///
+		/// {
+		/// 	<recipe-id>: {
+		///			"owner": <recipe-owner>,
+		///			"enabled": <whether-the-recipe-can-be-crafted>,
+		///			"inputs": [
+		///				{ "definition_hash": <definition-hash>, "quantity": <quantity> } |
+		///				{ "definition_hash": <definition-hash>, "stack_amount": <stack-amount> },
+		///				...
+		///			],
+		///			"outputs": [
+		///				{ "definition_hash": <definition-hash>, "quantity": <quantity>, "stack_amount": <stack-amount> | null },
+		///				...
+		///			],
+		///			"fee": { "currency": "native" | <asset-id>, "amount": <amount> } | null,
+		///		},
+		/// 	...
+		/// }
+		///
+		/// Note: The fee amounts are strings because they may not fit in a JSON number
+		pub fn get_recipes(params: GetRecipesParams<T::AccountId>) -> Result<Vec<u8>, Vec<u8>> {
+			let mut map = Map::new();
+
+			<Recipes<T>>::iter()
+				.filter(|(_, recipe)| {
+					params.owner.as_ref().map_or(true, |owner| *owner == recipe.owner) &&
+						(!params.only_enabled || recipe.enabled)
+				})
+				.skip(params.from as usize)
+				.take(params.limit as usize)
+				.for_each(|(recipe_id, recipe)| {
+					let inputs = recipe
+						.inputs
+						.iter()
+						.map(|input| match input.amount {
+							RecipeInputAmount::Quantity(quantity) => json!({
+								"definition_hash": hex::encode(input.definition_hash),
+								"quantity": quantity,
+							}),
+							RecipeInputAmount::StackAmount(stack_amount) => json!({
+								"definition_hash": hex::encode(input.definition_hash),
+								"stack_amount": stack_amount,
+							}),
+						})
+						.collect::<Vec<Value>>();
+					let outputs = recipe
+						.outputs
+						.iter()
+						.map(|output| {
+							json!({
+								"definition_hash": hex::encode(output.definition_hash),
+								"quantity": output.quantity,
+								"stack_amount": output.stack_amount,
+							})
+						})
+						.collect::<Vec<Value>>();
+					let fee = recipe.fee.map_or(Value::Null, |(currency, amount)| {
+						let currency: Value = match currency {
+							Currency::Native => "native".into(),
+							Currency::Custom(currency) => hex::encode(currency.encode()).into(),
+						};
+						json!({ "currency": currency, "amount": amount.to_string() })
+					});
+
+					map.insert(
+						recipe_id.to_string(),
+						json!({
+							"owner": hex::encode(recipe.owner),
+							"enabled": recipe.enabled,
+							"inputs": inputs,
+							"outputs": outputs,
+							"fee": fee,
+						}),
+					);
+				});
+
+			let result = json!(map).to_string();
+
+			Ok(result.into_bytes())
+		}
+
+		/// Query the **current Sale Phase** of a **Fragment Definition that is on sale**.
+		///
+		/// The returned JSON string has the following format:
+		///
+		/// {
+		/// 	"phase": <index-of-the-current-sale-phase> | null,
+		/// 	"started": <whether-the-sale-has-started>,
+		/// 	"price": <price-per-fragment-instance>,
+		/// 	"start": <block-number-that-the-current-sale-phase-started-at> | null,
+		/// 	"next_phase_start": <block-number-that-the-next-sale-phase-starts-at> | null,
+		/// 	"allowlist": <merkle-root-of-the-allowlist> | null,
+		/// 	"max_per_account": <max-fragment-instances-per-account> | null,
+		/// 	"bought": <fragment-instances-bought-by-the-account-during-the-current-sale-phase>,
+		/// 	"remaining": <fragment-instances-that-the-account-can-still-buy-during-the-current-sale-phase> | null,
+		/// }
+		///
+		/// Note: `bought` and `remaining` are only returned if `params.account` is given. `remaining` is `null` if there is no limit.
+		/// If the current Sale Phase has an allowlist, `params.allowance` should be the allowance of `params.account` in the allowlist.
+		/// If the sale has not started yet, the first Sale Phase is returned.
+		pub fn get_sale_phase(
+			params: GetSalePhaseParams<T::AccountId, Vec<u8>>,
+		) -> Result<Vec<u8>, Vec<u8>> {
+			let definition_hash: Hash128 = hex::decode(params.definition_hash)
+				.map_err(|_| "Failed to convert string to u8 slice")?
+				.try_into()
+				.map_err(|_| "Failed to convert u8 slice to Hash128")?;
+
+			let sale = <Publishing<T>>::get(definition_hash)
+				.ok_or("Fragment Definition is not on sale")?;
+
+			let current_block_number = <frame_system::Pallet<T>>::block_number();
+
+			let (started, phase) = match Self::get_current_sale_phase(&sale, current_block_number) {
+				Ok(phase) => (true, phase),
+				Err(_) => (false, sale.phases.first().cloned().map(|phase| (0, phase))),
+			};
+
+			let mut map = Map::new();
+			map.insert("started".into(), started.into());
+
+			let Some((phase_index, phase)) = phase else {
+				map.insert("phase".into(), Value::Null);
+				map.insert("price".into(), u128::from(sale.price).to_string().into());
+				return Ok(Value::Object(map).to_string().into_bytes())
+			};
+
+			map.insert("phase".into(), phase_index.into());
+			map.insert("price".into(), u128::from(phase.price).to_string().into());
+			map.insert("start".into(), phase.start.saturated_into::<u64>().into());
+			map.insert(
+				"next_phase_start".into(),
+				sale.phases
+					.get(phase_index as usize + 1)
+					.map(|next_phase| next_phase.start.saturated_into::<u64>())
+					.into(),
+			);
+			map.insert("allowlist".into(), phase.allowlist.map(hex::encode).into());
+			map.insert(
+				"max_per_account".into(),
+				phase.max_per_account.map(InstanceUnit::from).into(),
+			);
+
+			if let Some(account) = params.account {
+				let bought: InstanceUnit = <PhaseMints<T>>::get((
+					definition_hash,
+					<SaleIndex<T>>::get(&definition_hash),
+					phase_index,
+					account,
+				))
+				.unwrap_or(Compact(0))
+				.into();
+
+				let limit = match (phase.max_per_account.map(InstanceUnit::from), phase.allowlist) {
+					(max_per_account, Some(_)) => Some(
+						params
+							.allowance
+							.unwrap_or(0)
+							.min(max_per_account.unwrap_or(InstanceUnit::MAX)),
+					),
+					(max_per_account, None) => max_per_account,
+				};
+
+				map.insert("bought".into(), bought.into());
+				map.insert(
+					"remaining".into(),
+					limit.map(|limit| limit.saturating_sub(bought)).into(),
+				);
+			}
+
+			Ok(Value::Object(map).to_string().into_bytes())
+		}
+
 		/// Query the owner of a Fragment Instance. The return type is a String
 		pub fn get_instance_owner(
 			params: GetInstanceOwnerParams<Vec<u8>>,
@@ -2213,15 +6302,9 @@ pub mod pallet {
 				return Err("Instance not found".into())
 			}
 
-			let owner = Owners::<T>::iter_prefix(definition_hash)
-				.find(|(_owner, vec_instances)| {
-					vec_instances.iter().any(|(edition_id, copy_id)| {
-						Compact(params.edition_id) == *edition_id &&
-							Compact(params.copy_id) == *copy_id
-					})
-				})
-				.ok_or("Owner not found (this should never happen)")?
-				.0;
+			let owner =
+				InstanceOwner::<T>::get((definition_hash, params.edition_id, params.copy_id))
+					.ok_or("Owner not found")?;
 
 			Ok(hex::encode(owner).into_bytes())
 		}
@@ -0,0 +1,212 @@
+//! Storage migrations of the pallet `fragments`
+
+use super::*;
+use frame_support::{
+	pallet_prelude::*,
+	traits::{GetStorageVersion, OnRuntimeUpgrade},
+};
+use sp_std::marker::PhantomData;
+
+/// Migration from storage version 0 to storage version 1
+pub mod v1 {
+	use super::*;
+
+	/// **Populate** the storage item `InstanceOwner` from the storage item `Owners`
+	/// (i.e index the owner of every existing Fragment Instance)
+	pub struct MigrateToV1<T>(PhantomData<T>);
+
+	impl<T: Config> OnRuntimeUpgrade for MigrateToV1<T> {
+		fn on_runtime_upgrade() -> Weight {
+			let on_chain_version = Pallet::<T>::on_chain_storage_version();
+			if on_chain_version != 0 {
+				log::info!(
+					target: "runtime::fragments",
+					"MigrateToV1 should be removed, on-chain storage version is {:?}",
+					on_chain_version
+				);
+				return T::DbWeight::get().reads(1)
+			}
+
+			let mut reads: u64 = 1;
+			let mut writes: u64 = 0;
+
+			for (definition_hash, owner, instances) in <Owners<T>>::iter() {
+				reads += 1;
+				for (edition_id, copy_id) in instances {
+					<InstanceOwner<T>>::insert(
+						(definition_hash, u64::from(edition_id), u64::from(copy_id)),
+						owner.clone(),
+					);
+					writes += 1;
+				}
+			}
+
+			StorageVersion::new(1).put::<Pallet<T>>();
+			writes += 1;
+
+			log::info!(
+				target: "runtime::fragments",
+				"MigrateToV1 indexed {} Fragment Instances",
+				writes - 1
+			);
+
+			T::DbWeight::get().reads_writes(reads, writes)
+		}
+	}
+}
+
+/// Migration from storage version 1 to storage version 2
+pub mod v2 {
+	use super::*;
+	use frame_support::storage_alias;
+
+	/// The storage item `Expirations` of storage version 1, that maps a **Block Number**
+	/// to the **list of Fragment Instances that expire on that Block**
+	#[storage_alias]
+	pub type Expirations<T: Config> = StorageMap<
+		Pallet<T>,
+		Twox64Concat,
+		<T as frame_system::Config>::BlockNumber,
+		Vec<(Hash128, Compact<InstanceUnit>, Compact<InstanceUnit>)>,
+	>;
+
+	/// **Move** the **scheduled expirations** of the storage item `Expirations` into the storage item `ExpirationQueue`,
+	/// and start sweeping the `ExpirationQueue` from the current block number
+	pub struct MigrateToV2<T>(PhantomData<T>);
+
+	impl<T: Config> OnRuntimeUpgrade for MigrateToV2<T> {
+		fn on_runtime_upgrade() -> Weight {
+			let on_chain_version = Pallet::<T>::on_chain_storage_version();
+			if on_chain_version != 1 {
+				log::info!(
+					target: "runtime::fragments",
+					"MigrateToV2 should be removed, on-chain storage version is {:?}",
+					on_chain_version
+				);
+				return T::DbWeight::get().reads(1)
+			}
+
+			let mut reads: u64 = 1;
+			let mut writes: u64 = 0;
+
+			for (expiring_at, instances) in Expirations::<T>::drain() {
+				reads += 1;
+				writes += 1;
+				for (definition_hash, edition_id, copy_id) in instances {
+					<ExpirationQueue<T>>::insert(
+						expiring_at,
+						(definition_hash, u64::from(edition_id), u64::from(copy_id)),
+						(),
+					);
+					writes += 1;
+				}
+			}
+
+			// the expirations of the previous blocks have already been processed by the former `on_finalize()`
+			<ExpirationCursor<T>>::put(<frame_system::Pallet<T>>::block_number());
+			reads += 1;
+			writes += 1;
+
+			StorageVersion::new(2).put::<Pallet<T>>();
+			writes += 1;
+
+			T::DbWeight::get().reads_writes(reads, writes)
+		}
+	}
+}
+
+/// Migration from storage version 2 to storage version 3
+pub mod v3 {
+	use super::*;
+	use pallet_protos::{ProtoOwner, Protos};
+
+	/// The struct `FragmentDefinition` of storage version 2, that does not have the field `owner`
+	#[derive(Encode, Decode)]
+	pub struct OldFragmentDefinition<TU8Array, TFungibleAsset, TAccountId, TBlockNum> {
+		/// **Proto-Fragment used** to **create** the **Fragment**
+		pub proto_hash: Hash256,
+		/// ***DefinitionMetadata* Struct**
+		pub metadata: DefinitionMetadata<TU8Array, TFungibleAsset>,
+		/// **Set of Actions** that are **allowed to be done** to **any Fragment Instance** when it **first gets created**
+		pub permissions: FragmentPerms,
+		/// Whether the **Fragment Definition** is **mutable**
+		pub unique: Option<UniqueOptions>,
+		/// If scarce, the max supply of the Fragment
+		pub max_supply: Option<Compact<InstanceUnit>>,
+		/// The creator of this class
+		pub creator: TAccountId,
+		/// The block number when the item was created
+		pub created_at: TBlockNum,
+		/// **Map** that maps the **Key of a Custom Metadata Object** to the **Hash of the aforementioned Custom Metadata Object**
+		pub custom_metadata: BTreeMap<Compact<u64>, Hash256>,
+		/// Reserved for future use
+		pub _reserved1: Option<()>,
+		/// Reserved for future use
+		pub _reserved2: Option<()>,
+		/// Reserved for future use
+		pub _reserved3: Option<()>,
+	}
+
+	/// **Add** the field `owner` to every existing **Fragment Definition**, and **populate** the storage item `DefinitionsByOwner` accordingly.
+	///
+	/// Before storage version 3, a Fragment Definition was managed by the **current owner of its Proto-Fragment**,
+	/// so the field `owner` is initialized with it (so that no one loses or gains control of an existing Fragment Definition).
+	/// If the Proto-Fragment is owned by an external asset (or no longer exists), the field `owner` is initialized with the creator of the Fragment Definition instead.
+	pub struct MigrateToV3<T>(PhantomData<T>);
+
+	impl<T: Config> OnRuntimeUpgrade for MigrateToV3<T> {
+		fn on_runtime_upgrade() -> Weight {
+			let on_chain_version = Pallet::<T>::on_chain_storage_version();
+			if on_chain_version != 2 {
+				log::info!(
+					target: "runtime::fragments",
+					"MigrateToV3 should be removed, on-chain storage version is {:?}",
+					on_chain_version
+				);
+				return T::DbWeight::get().reads(1)
+			}
+
+			let mut reads: u64 = 1;
+			let mut writes: u64 = 0;
+
+			let mut definitions_by_owner: BTreeMap<T::AccountId, Vec<Hash128>> = BTreeMap::new();
+
+			<Definitions<T>>::translate::<
+				OldFragmentDefinition<Vec<u8>, T::AssetId, T::AccountId, T::BlockNumber>,
+				_,
+			>(|definition_hash, old| {
+				reads += 2;
+				writes += 1;
+				let owner = match <Protos<T>>::get(old.proto_hash).map(|proto| proto.owner) {
+					Some(ProtoOwner::User(proto_owner)) => proto_owner,
+					_ => old.creator.clone(),
+				};
+				definitions_by_owner.entry(owner.clone()).or_default().push(definition_hash);
+				Some(FragmentDefinition {
+					proto_hash: old.proto_hash,
+					metadata: old.metadata,
+					permissions: old.permissions,
+					unique: old.unique,
+					max_supply: old.max_supply,
+					owner,
+					creator: old.creator,
+					created_at: old.created_at,
+					custom_metadata: old.custom_metadata,
+					_reserved1: old._reserved1,
+					_reserved2: old._reserved2,
+					_reserved3: old._reserved3,
+				})
+			});
+
+			for (owner, definitions) in definitions_by_owner {
+				<DefinitionsByOwner<T>>::insert(owner, definitions);
+				writes += 1;
+			}
+
+			StorageVersion::new(3).put::<Pallet<T>>();
+			writes += 1;
+
+			T::DbWeight::get().reads_writes(reads, writes)
+		}
+	}
+}
@@ -210,6 +210,11 @@ impl pallet_protos::Config for Test {
 	type StringLimit = StringLimit;
 	type DetachAccountLimit = ConstU32<20>;
 	type MaxTags = ConstU32<10>;
+	type UploadChunkSize = ConstU32<4>;
+	type MaxUploadChunks = ConstU32<16>;
+	type UploadChunkDeposit = ConstU128<1>;
+	type UploadSessionTimeout = ConstU64<100>;
+	type DataValidator = ();
 }
 
 impl pallet_accounts::Config for Test {
@@ -308,6 +313,6 @@ pub fn run_to_block(n: u64) {
 		}
 		System::set_block_number(System::block_number() + 1);
 		System::on_initialize(System::block_number());
-		// FragmentsPallet::on_initialize(System::block_number()); // Commented out since this function (`on_finalize`) doesn't exist in pallets/fragments/src/lib.rs
+		FragmentsPallet::on_initialize(System::block_number());
 	}
 }
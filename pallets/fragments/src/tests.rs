@@ -4,7 +4,7 @@ use crate::{dummy_data::*, mock::*, *};
 
 use crate::Event as FragmentsEvent;
 
-use frame_support::{assert_noop, assert_ok};
+use frame_support::{assert_noop, assert_ok, dispatch::DispatchResultWithPostInfo};
 use itertools::Itertools;
 use protos::permissions::FragmentPerms;
 
@@ -40,6 +40,14 @@ mod copied_from_pallet_protos {
 	}
 }
 
+/// Mint `amount` to the account `account_id`
+fn fund(account_id: <Test as frame_system::Config>::AccountId, amount: u128) {
+	_ = <Balances as fungible::Mutate<<Test as frame_system::Config>::AccountId>>::mint_into(
+		&account_id,
+		amount,
+	);
+}
+
 use create_tests::create;
 mod create_tests {
 	use super::*;
@@ -111,6 +119,7 @@ mod create_tests {
 				unique: definition.unique.clone(),
 				max_supply: definition.max_supply.map(|max_supply| Compact::from(max_supply)),
 				creator: dd.account_id,
+				owner: dd.account_id,
 				created_at: current_block_number,
 				custom_metadata: BTreeMap::new(),
 				_reserved1: None,
@@ -227,6 +236,7 @@ mod publish_tests {
 			publish.quantity,
 			publish.expires,
 			publish.stack_amount,
+			publish.phases.clone(),
 		)
 	}
 
@@ -254,7 +264,7 @@ mod publish_tests {
 				units_left: publish.quantity.map(|quantity| Compact::from(quantity)),
 				expiration: publish.expires,
 				stack_amount: publish.stack_amount.map(|amount| Compact::from(amount)),
-				_reserved1: None,
+				phases: publish.phases.clone(),
 				_reserved2: None,
 				_reserved3: None,
 			};
@@ -460,7 +470,10 @@ use mint_tests::mint_;
 mod mint_tests {
 	use super::*;
 
-	pub fn mint_(signer: <Test as frame_system::Config>::AccountId, mint: &Mint) -> DispatchResult {
+	pub fn mint_(
+		signer: <Test as frame_system::Config>::AccountId,
+		mint: &Mint,
+	) -> DispatchResultWithPostInfo {
 		FragmentsPallet::mint(
 			RuntimeOrigin::signed(signer),
 			mint.definition.get_definition_id(),
@@ -753,11 +766,15 @@ mod mint_tests {
 mod buy_tests {
 	use super::*;
 
-	fn buy_(signer: <Test as frame_system::Config>::AccountId, buy: &Buy) -> DispatchResult {
+	fn buy_(
+		signer: <Test as frame_system::Config>::AccountId,
+		buy: &Buy,
+	) -> DispatchResultWithPostInfo {
 		FragmentsPallet::buy(
 			RuntimeOrigin::signed(signer),
 			buy.publish.definition.get_definition_id(),
 			buy.buy_options.clone(),
+			None,
 		)
 	}
 
@@ -1475,11 +1492,10 @@ mod give_tests {
 				give.expiration
 			);
 
-			assert!(<Expirations<Test>>::get(&give.expiration.unwrap()).unwrap().contains(&(
-				give.mint.definition.get_definition_id(),
-				Compact(give.edition_id),
-				Compact(give.copy_id + 1)
-			)));
+			assert!(<ExpirationQueue<Test>>::contains_key(
+				give.expiration.unwrap(),
+				(give.mint.definition.get_definition_id(), give.edition_id, give.copy_id + 1)
+			));
 		});
 	}
 
@@ -1655,16 +1671,14 @@ mod give_tests {
 					..give.clone()
 				}
 			));
-			assert!(!<Expirations<Test>>::get(give.expiration.unwrap()).unwrap().contains(&(
-				give.mint.definition.get_definition_id(),
-				Compact(give.edition_id),
-				Compact(give.copy_id + 2)
-			)));
-			assert!(<Expirations<Test>>::get(give.expiration.unwrap() - 1).unwrap().contains(&(
-				give.mint.definition.get_definition_id(),
-				Compact(give.edition_id),
-				Compact(give.copy_id + 2)
-			)));
+			assert!(!<ExpirationQueue<Test>>::contains_key(
+				give.expiration.unwrap(),
+				(give.mint.definition.get_definition_id(), give.edition_id, give.copy_id + 2)
+			));
+			assert!(<ExpirationQueue<Test>>::contains_key(
+				give.expiration.unwrap() - 1,
+				(give.mint.definition.get_definition_id(), give.edition_id, give.copy_id + 2)
+			));
 
 			assert_ok!(give_(
 				give.to,
@@ -1675,12 +1689,13 @@ mod give_tests {
 					..give.clone()
 				}
 			));
-			assert!(!<Expirations<Test>>::contains_key(give.expiration.unwrap() + 1));
-			assert!(<Expirations<Test>>::get(give.expiration.unwrap()).unwrap().contains(&(
-				give.mint.definition.get_definition_id(),
-				Compact(give.edition_id),
-				Compact(give.copy_id + 3)
-			)));
+			assert!(<ExpirationQueue<Test>>::iter_key_prefix(give.expiration.unwrap() + 1)
+				.next()
+				.is_none());
+			assert!(<ExpirationQueue<Test>>::contains_key(
+				give.expiration.unwrap(),
+				(give.mint.definition.get_definition_id(), give.edition_id, give.copy_id + 3)
+			));
 		});
 	}
 
@@ -1716,8 +1731,25 @@ mod create_account_tests {
 			assert_ok!(upload(dd.account_id, &create_account.mint.definition.proto_fragment));
 			assert_ok!(create(dd.account_id, &create_account.mint.definition));
 			assert_ok!(mint_(dd.account_id, &create_account.mint));
+			fund(dd.account_id, 1_000_000);
+
+			let minimum_balance = <Balances as fungible::Inspect<
+				<Test as frame_system::Config>::AccountId,
+			>>::minimum_balance();
+			let frag_account = FragmentsPallet::get_fragment_account_id(
+				create_account.mint.definition.get_definition_id(),
+				create_account.edition_id,
+				create_account.copy_id,
+			);
 
 			assert_ok!(create_account_(dd.account_id, &create_account));
+
+			// the owner pays the existential deposit of the account
+			assert_eq!(Balances::free_balance(frag_account), minimum_balance);
+			assert_eq!(Balances::free_balance(dd.account_id), 1_000_000 - minimum_balance);
+			// the owner is the proxy of the account
+			assert!(pallet_proxy::Pallet::<Test>::find_proxy(&frag_account, &dd.account_id, None)
+				.is_ok());
 		});
 	}
 
@@ -1738,6 +1770,185 @@ mod create_account_tests {
 			);
 		});
 	}
+
+	#[test]
+	fn create_account_should_not_work_if_the_owner_cannot_pay_the_existential_deposit() {
+		new_test_ext().execute_with(|| {
+			let dd = DummyData::new();
+
+			let create_account = dd.create_account;
+
+			assert_ok!(upload(dd.account_id, &create_account.mint.definition.proto_fragment));
+			assert_ok!(create(dd.account_id, &create_account.mint.definition));
+			assert_ok!(mint_(dd.account_id, &create_account.mint));
+
+			assert_noop!(
+				create_account_(dd.account_id, &create_account),
+				Error::<Test>::InsufficientBalance
+			);
+		});
+	}
+
+	#[test]
+	fn transferring_the_fragment_instance_should_change_the_proxy_of_its_account() {
+		new_test_ext().execute_with(|| {
+			let dd = DummyData::new();
+
+			let create_account = dd.create_account;
+			let definition_hash = create_account.mint.definition.get_definition_id();
+
+			assert_ok!(upload(dd.account_id, &create_account.mint.definition.proto_fragment));
+			assert_ok!(create(dd.account_id, &create_account.mint.definition));
+			assert_ok!(mint_(dd.account_id, &create_account.mint));
+			fund(dd.account_id, 1_000_000);
+			assert_ok!(create_account_(dd.account_id, &create_account));
+
+			assert_ok!(FragmentsPallet::give(
+				RuntimeOrigin::signed(dd.account_id),
+				definition_hash,
+				create_account.edition_id,
+				create_account.copy_id,
+				dd.account_id_second,
+				None,
+				None
+			));
+
+			let frag_account = FragmentsPallet::get_fragment_account_id(
+				definition_hash,
+				create_account.edition_id,
+				create_account.copy_id,
+			);
+			assert!(pallet_proxy::Pallet::<Test>::find_proxy(
+				&frag_account,
+				&dd.account_id_second,
+				None
+			)
+			.is_ok());
+			assert!(pallet_proxy::Pallet::<Test>::find_proxy(&frag_account, &dd.account_id, None)
+				.is_err());
+		});
+	}
+}
+
+mod as_instance_tests {
+	use super::*;
+
+	fn as_instance_(
+		signer: <Test as frame_system::Config>::AccountId,
+		create_account: &CreateAccount,
+		call: RuntimeCall,
+	) -> DispatchResult {
+		FragmentsPallet::as_instance(
+			RuntimeOrigin::signed(signer),
+			create_account.mint.definition.get_definition_id(),
+			create_account.edition_id,
+			create_account.copy_id,
+			Box::new(call),
+		)
+	}
+
+	#[test]
+	fn as_instance_should_work() {
+		new_test_ext().execute_with(|| {
+			let dd = DummyData::new();
+
+			let create_account = dd.create_account;
+
+			assert_ok!(upload(dd.account_id, &create_account.mint.definition.proto_fragment));
+			assert_ok!(create(dd.account_id, &create_account.mint.definition));
+			assert_ok!(mint_(dd.account_id, &create_account.mint));
+
+			let frag_account = FragmentsPallet::get_fragment_account_id(
+				create_account.mint.definition.get_definition_id(),
+				create_account.edition_id,
+				create_account.copy_id,
+			);
+			_ = <Balances as fungible::Mutate<<Test as frame_system::Config>::AccountId>>::mint_into(
+				&frag_account,
+				10_000,
+			);
+
+			assert_ok!(as_instance_(
+				dd.account_id,
+				&create_account,
+				RuntimeCall::Balances(pallet_balances::Call::transfer {
+					dest: dd.account_id_second,
+					value: 1_000,
+				}),
+			));
+
+			assert_eq!(Balances::free_balance(frag_account), 9_000);
+			assert_eq!(Balances::free_balance(dd.account_id_second), 1_000);
+		});
+	}
+
+	#[test]
+	fn as_instance_should_not_work_if_the_user_is_not_the_owner_of_the_fragment_instance() {
+		new_test_ext().execute_with(|| {
+			let dd = DummyData::new();
+
+			let create_account = dd.create_account;
+
+			assert_ok!(upload(dd.account_id, &create_account.mint.definition.proto_fragment));
+			assert_ok!(create(dd.account_id, &create_account.mint.definition));
+			assert_ok!(mint_(dd.account_id, &create_account.mint));
+
+			assert_noop!(
+				as_instance_(
+					dd.account_id_second,
+					&create_account,
+					RuntimeCall::System(frame_system::Call::remark { remark: vec![] }),
+				),
+				Error::<Test>::NoPermission
+			);
+		});
+	}
+
+	#[test]
+	fn as_instance_should_work_if_the_fragment_instance_holds_another_fragment_instance() {
+		new_test_ext().execute_with(|| {
+			let dd = DummyData::new();
+			let mint = dd.mint_non_unique;
+			let definition_hash = mint.definition.get_definition_id();
+
+			assert_ok!(upload(dd.account_id, &mint.definition.proto_fragment));
+			assert_ok!(create(dd.account_id, &mint.definition));
+			assert_ok!(mint_(
+				dd.account_id,
+				&Mint { buy_options: FragmentBuyOptions::Quantity(2), ..mint.clone() }
+			));
+			// the Fragment Instance `(1, 1)` is held by the account of the Fragment Instance `(2, 1)`
+			assert_ok!(FragmentsPallet::give(
+				RuntimeOrigin::signed(dd.account_id),
+				definition_hash,
+				1,
+				1,
+				FragmentsPallet::get_fragment_account_id(definition_hash, 2, 1),
+				None,
+				None
+			));
+
+			assert_ok!(FragmentsPallet::as_instance(
+				RuntimeOrigin::signed(dd.account_id),
+				definition_hash,
+				2,
+				1,
+				Box::new(RuntimeCall::FragmentsPallet(crate::Call::give {
+					definition_hash,
+					edition_id: 1,
+					copy_id: 1,
+					to: dd.account_id_second,
+					new_permissions: None,
+					expiration: None,
+				})),
+			));
+
+			assert_eq!(
+				<InstanceOwner<Test>>::get((definition_hash, 1, 1)),
+				Some(dd.account_id_second)
+			);
+		});
+	}
 }
 
 use resell_tests::resell_;
@@ -2006,16 +2217,14 @@ mod resell_tests {
 					..give.clone()
 				}
 			));
-			assert!(!<Expirations<Test>>::get(give.expiration.unwrap()).unwrap().contains(&(
-				give.mint.definition.get_definition_id(),
-				Compact(give.edition_id),
-				Compact(give.copy_id + 2)
-			)));
-			assert!(<Expirations<Test>>::get(give.expiration.unwrap() - 1).unwrap().contains(&(
-				give.mint.definition.get_definition_id(),
-				Compact(give.edition_id),
-				Compact(give.copy_id + 2)
-			)));
+			assert!(!<ExpirationQueue<Test>>::contains_key(
+				give.expiration.unwrap(),
+				(give.mint.definition.get_definition_id(), give.edition_id, give.copy_id + 2)
+			));
+			assert!(<ExpirationQueue<Test>>::contains_key(
+				give.expiration.unwrap() - 1,
+				(give.mint.definition.get_definition_id(), give.edition_id, give.copy_id + 2)
+			));
 
 			assert_ok!(give_(
 				give.to,
@@ -2026,12 +2235,13 @@ mod resell_tests {
 					..give.clone()
 				}
 			));
-			assert!(!<Expirations<Test>>::contains_key(give.expiration.unwrap() + 1));
-			assert!(<Expirations<Test>>::get(give.expiration.unwrap()).unwrap().contains(&(
-				give.mint.definition.get_definition_id(),
-				Compact(give.edition_id),
-				Compact(give.copy_id + 3)
-			)));
+			assert!(<ExpirationQueue<Test>>::iter_key_prefix(give.expiration.unwrap() + 1)
+				.next()
+				.is_none());
+			assert!(<ExpirationQueue<Test>>::contains_key(
+				give.expiration.unwrap(),
+				(give.mint.definition.get_definition_id(), give.edition_id, give.copy_id + 3)
+			));
 		});
 	}
 }
@@ -2377,13 +2587,14 @@ mod secondary_buy_tests {
 				secondary_buy.resell.expiration
 			);
 
-			assert!(<Expirations<Test>>::get(&secondary_buy.resell.expiration.unwrap())
-				.unwrap()
-				.contains(&(
+			assert!(<ExpirationQueue<Test>>::contains_key(
+				secondary_buy.resell.expiration.unwrap(),
+				(
 					secondary_buy.resell.mint.definition.get_definition_id(),
-					Compact(secondary_buy.resell.edition_id),
-					Compact(secondary_buy.resell.copy_id + 1)
-				)));
+					secondary_buy.resell.edition_id,
+					secondary_buy.resell.copy_id + 1
+				)
+			));
 		});
 	}
 
@@ -2643,6 +2854,8 @@ mod detach_tests {
 
 mod get_definitions_tests {
 	use super::*;
+	use protos::categories::{Categories, ModelCategories, TextCategories};
+	use serde_json::Map;
 
 	#[test]
 	fn get_definitions_should_work() {
@@ -2668,6 +2881,8 @@ mod get_definitions_tests {
 					hex::encode(definition.get_definition_id()): {
 						"name": String::from_utf8(definition.metadata.name).unwrap(),
 						"num_instances": 0,
+						"currency": "native",
+						"sale": null,
 						"owner": {
 							"type": "internal",
 							"value": hex::encode(dd.account_id)
@@ -2701,6 +2916,8 @@ mod get_definitions_tests {
 					hex::encode(definition.get_definition_id()): {
 						"name": String::from_utf8(definition.metadata.name).unwrap(),
 						"num_instances": 0,
+						"currency": "native",
+						"sale": null,
 						"owner": {
 							"type": "internal",
 							"value": hex::encode(dd.account_id)
@@ -2730,104 +2947,393 @@ mod get_definitions_tests {
 			);
 		});
 	}
-}
 
-mod get_instances_tests {
-	use super::*;
-	use serde_json::Map;
+	fn definition_with_proto(
+		definition: &Definition,
+		data: &[u8],
+		category: Categories,
+		tags: Vec<Vec<u8>>,
+	) -> Definition {
+		let mut definition = definition.clone();
+		definition.proto_fragment.data = data.to_vec();
+		definition.proto_fragment.category = category;
+		definition.proto_fragment.tags = tags;
+		definition
+	}
 
 	#[test]
-	fn get_instances_should_work() {
+	fn get_definitions_should_filter_by_categories_and_tags() {
 		new_test_ext().execute_with(|| {
 			let dd = DummyData::new();
-			let mint = dd.mint_non_unique;
-			assert_ok!(upload(dd.account_id, &mint.definition.proto_fragment));
-			assert_ok!(create(dd.account_id, &mint.definition));
-			assert_ok!(mint_(dd.account_id, &mint));
 
-			let mut correct_map_instances = Map::new();
-			for edition_id in 1..=mint.get_quantity() {
-				correct_map_instances.insert(format!("{}.1", edition_id), Map::new().into());
+			let sword = definition_with_proto(
+				&dd.definition,
+				b"sword",
+				Categories::Model(ModelCategories::GltfFile),
+				vec![b"sword".to_vec()],
+			);
+			let shield = definition_with_proto(
+				&dd.definition,
+				b"shield",
+				Categories::Model(ModelCategories::GltfFile),
+				vec![b"shield".to_vec()],
+			);
+			let text = definition_with_proto(
+				&dd.definition,
+				b"text",
+				Categories::Text(TextCategories::Plain),
+				vec![b"sword".to_vec()],
+			);
+
+			for definition in [&sword, &shield, &text] {
+				assert_ok!(upload(dd.account_id, &definition.proto_fragment));
+				assert_ok!(create(dd.account_id, definition));
 			}
 
-			assert_eq!(
-				serde_json::from_slice::<Value>(
-					&FragmentsPallet::get_instances(GetInstancesParams {
-						definition_hash: hex::encode(mint.definition.get_definition_id())
-							.into_bytes(),
-						limit: u64::MAX,
-						..Default::default()
-					})
-					.unwrap()
+			let get_definition_hashes = |params: GetDefinitionsParams<_, Vec<u8>>| {
+				serde_json::from_slice::<Map<String, Value>>(
+					&FragmentsPallet::get_definitions(params).unwrap(),
 				)
-				.unwrap(),
-				json!(correct_map_instances)
-			)
-		});
-	}
-
-	#[test]
-	fn get_instances_should_work_if_only_return_first_copies_is_true() {
-		new_test_ext().execute_with(|| {
-			let dd = DummyData::new();
-			let give = dd.give_copy_perms;
-			assert_ok!(upload(dd.account_id, &give.mint.definition.proto_fragment));
-			assert_ok!(create(dd.account_id, &give.mint.definition));
-			assert_ok!(mint_(dd.account_id, &give.mint));
-			assert_ok!(give_(dd.account_id, &give));
+				.unwrap()
+				.keys()
+				.cloned()
+				.sorted()
+				.collect::<Vec<String>>()
+			};
 
 			assert_eq!(
-				serde_json::from_slice::<Value>(
-					&FragmentsPallet::get_instances(GetInstancesParams {
-						definition_hash: hex::encode(give.mint.definition.get_definition_id())
-							.into_bytes(),
-						limit: u64::MAX,
-						only_return_first_copies: true,
-						..Default::default()
-					})
-					.unwrap()
-				)
-				.unwrap(),
-				json!({
-					"1.1": {},
-				})
+				get_definition_hashes(GetDefinitionsParams {
+					limit: u64::MAX,
+					categories: vec![Categories::Model(ModelCategories::GltfFile)],
+					tags: vec![b"sword".to_vec()],
+					..Default::default()
+				}),
+				vec![hex::encode(sword.get_definition_id())]
+			);
+			assert_eq!(
+				get_definition_hashes(GetDefinitionsParams {
+					limit: u64::MAX,
+					owner: Some(dd.account_id),
+					categories: vec![Categories::Model(ModelCategories::GltfFile)],
+					tags: vec![b"sword".to_vec()],
+					..Default::default()
+				}),
+				vec![hex::encode(sword.get_definition_id())]
+			);
+			assert_eq!(
+				get_definition_hashes(GetDefinitionsParams {
+					limit: u64::MAX,
+					tags: vec![b"sword".to_vec()],
+					..Default::default()
+				}),
+				[hex::encode(sword.get_definition_id()), hex::encode(text.get_definition_id())]
+					.into_iter()
+					.sorted()
+					.collect::<Vec<String>>()
+			);
+			assert_eq!(
+				get_definition_hashes(GetDefinitionsParams {
+					limit: u64::MAX,
+					categories: vec![Categories::Model(ModelCategories::GltfFile)],
+					..Default::default()
+				}),
+				[hex::encode(sword.get_definition_id()), hex::encode(shield.get_definition_id())]
+					.into_iter()
+					.sorted()
+					.collect::<Vec<String>>()
 			);
 		});
 	}
 
 	#[test]
-	fn get_instances_should_work_if_only_return_first_copies_is_false() {
+	fn get_definitions_should_filter_by_currency_and_sale() {
 		new_test_ext().execute_with(|| {
 			let dd = DummyData::new();
-			let give = dd.give_copy_perms;
-			assert_ok!(upload(dd.account_id, &give.mint.definition.proto_fragment));
-			assert_ok!(create(dd.account_id, &give.mint.definition));
-			assert_ok!(mint_(dd.account_id, &give.mint));
-			assert_ok!(give_(dd.account_id, &give));
 
-			assert_eq!(
-				serde_json::from_slice::<Value>(
-					&FragmentsPallet::get_instances(GetInstancesParams {
-						definition_hash: hex::encode(give.mint.definition.get_definition_id())
-							.into(),
-						limit: u64::MAX,
-						only_return_first_copies: false,
-						..Default::default()
-					})
-					.unwrap()
-				)
-				.unwrap(),
-				json!({
-					"1.1": {},
-					"1.2": {},
-				})
+			let cheap = definition_with_proto(
+				&dd.definition,
+				b"cheap",
+				Categories::Text(TextCategories::Plain),
+				Vec::new(),
+			);
+			let expensive = definition_with_proto(
+				&dd.definition,
+				b"expensive",
+				Categories::Text(TextCategories::Plain),
+				Vec::new(),
+			);
+			let sold_out = definition_with_proto(
+				&dd.definition,
+				b"sold_out",
+				Categories::Text(TextCategories::Plain),
+				Vec::new(),
+			);
+			let unpublished = definition_with_proto(
+				&dd.definition,
+				b"unpublished",
+				Categories::Text(TextCategories::Plain),
+				Vec::new(),
 			);
-		});
-	}
 
-	#[test]
-	fn get_instances_should_work_if_owner_owns_instances() {
-		new_test_ext().execute_with(|| {
+			for definition in [&cheap, &expensive, &sold_out, &unpublished] {
+				assert_ok!(upload(dd.account_id, &definition.proto_fragment));
+				assert_ok!(create(dd.account_id, definition));
+			}
+			assert_ok!(publish_(
+				dd.account_id,
+				&Publish { definition: cheap.clone(), price: 10, ..dd.publish.clone() }
+			));
+			assert_ok!(publish_(
+				dd.account_id,
+				&Publish {
+					definition: expensive.clone(),
+					price: 1000,
+					quantity: Some(5),
+					..dd.publish.clone()
+				}
+			));
+			assert_ok!(publish_(
+				dd.account_id,
+				&Publish {
+					definition: sold_out.clone(),
+					price: 10,
+					quantity: Some(0),
+					..dd.publish.clone()
+				}
+			));
+
+			let get_definition_hashes = |params: GetDefinitionsParams<_, Vec<u8>>| {
+				serde_json::from_slice::<Map<String, Value>>(
+					&FragmentsPallet::get_definitions(params).unwrap(),
+				)
+				.unwrap()
+				.keys()
+				.cloned()
+				.sorted()
+				.collect::<Vec<String>>()
+			};
+			let sorted_hashes = |definitions: &[&Definition]| {
+				definitions
+					.iter()
+					.map(|definition| hex::encode(definition.get_definition_id()))
+					.sorted()
+					.collect::<Vec<String>>()
+			};
+
+			assert_eq!(
+				get_definition_hashes(GetDefinitionsParams {
+					limit: u64::MAX,
+					currency: Some(b"native".to_vec()),
+					..Default::default()
+				}),
+				sorted_hashes(&[&cheap, &expensive, &sold_out, &unpublished])
+			);
+			assert_eq!(
+				get_definition_hashes(GetDefinitionsParams {
+					limit: u64::MAX,
+					currency: Some(hex::encode(1u64.encode()).into_bytes()),
+					..Default::default()
+				}),
+				Vec::<String>::new()
+			);
+			assert_eq!(
+				get_definition_hashes(GetDefinitionsParams {
+					limit: u64::MAX,
+					published: Some(false),
+					..Default::default()
+				}),
+				sorted_hashes(&[&unpublished])
+			);
+			assert_eq!(
+				get_definition_hashes(GetDefinitionsParams {
+					limit: u64::MAX,
+					published: Some(true),
+					..Default::default()
+				}),
+				sorted_hashes(&[&cheap, &expensive, &sold_out])
+			);
+			assert_eq!(
+				get_definition_hashes(GetDefinitionsParams {
+					limit: u64::MAX,
+					min_price: Some(100),
+					..Default::default()
+				}),
+				sorted_hashes(&[&expensive])
+			);
+			assert_eq!(
+				get_definition_hashes(GetDefinitionsParams {
+					limit: u64::MAX,
+					max_price: Some(100),
+					sold_out: Some(false),
+					..Default::default()
+				}),
+				sorted_hashes(&[&cheap])
+			);
+			assert_eq!(
+				get_definition_hashes(GetDefinitionsParams {
+					limit: u64::MAX,
+					owner: Some(dd.account_id),
+					sold_out: Some(true),
+					..Default::default()
+				}),
+				sorted_hashes(&[&sold_out])
+			);
+		});
+	}
+
+	#[test]
+	fn get_definitions_should_return_the_publishing_data() {
+		new_test_ext().execute_with(|| {
+			let dd = DummyData::new();
+
+			let publish = Publish {
+				quantity: Some(5),
+				expires: Some(100),
+				stack_amount: Some(3),
+				phases: vec![SalePhase {
+					start: 10,
+					price: Compact(7),
+					allowlist: None,
+					max_per_account: Some(Compact(2)),
+				}],
+				..dd.publish
+			};
+
+			assert_ok!(upload(dd.account_id, &publish.definition.proto_fragment));
+			assert_ok!(create(dd.account_id, &publish.definition));
+			assert_ok!(publish_(dd.account_id, &publish));
+
+			assert_eq!(
+				serde_json::from_slice::<Value>(
+					&FragmentsPallet::get_definitions(GetDefinitionsParams {
+						limit: u64::MAX,
+						..Default::default()
+					})
+					.unwrap()
+				)
+				.unwrap(),
+				json!({
+					hex::encode(publish.definition.get_definition_id()): {
+						"name": String::from_utf8(publish.definition.metadata.name).unwrap(),
+						"num_instances": 0,
+						"currency": "native",
+						"sale": {
+							"price": publish.price.to_string(),
+							"units_left": 5,
+							"expiration": 100,
+							"stack_amount": 3,
+							"phases": [
+								{
+									"start": 10,
+									"price": "7",
+									"allowlist": null,
+									"max_per_account": 2,
+								}
+							],
+						},
+					}
+				})
+			);
+		});
+	}
+}
+
+mod get_instances_tests {
+	use super::*;
+	use serde_json::Map;
+
+	#[test]
+	fn get_instances_should_work() {
+		new_test_ext().execute_with(|| {
+			let dd = DummyData::new();
+			let mint = dd.mint_non_unique;
+			assert_ok!(upload(dd.account_id, &mint.definition.proto_fragment));
+			assert_ok!(create(dd.account_id, &mint.definition));
+			assert_ok!(mint_(dd.account_id, &mint));
+
+			let mut correct_map_instances = Map::new();
+			for edition_id in 1..=mint.get_quantity() {
+				correct_map_instances.insert(format!("{}.1", edition_id), Map::new().into());
+			}
+
+			assert_eq!(
+				serde_json::from_slice::<Value>(
+					&FragmentsPallet::get_instances(GetInstancesParams {
+						definition_hash: hex::encode(mint.definition.get_definition_id())
+							.into_bytes(),
+						limit: u64::MAX,
+						..Default::default()
+					})
+					.unwrap()
+				)
+				.unwrap(),
+				json!(correct_map_instances)
+			)
+		});
+	}
+
+	#[test]
+	fn get_instances_should_work_if_only_return_first_copies_is_true() {
+		new_test_ext().execute_with(|| {
+			let dd = DummyData::new();
+			let give = dd.give_copy_perms;
+			assert_ok!(upload(dd.account_id, &give.mint.definition.proto_fragment));
+			assert_ok!(create(dd.account_id, &give.mint.definition));
+			assert_ok!(mint_(dd.account_id, &give.mint));
+			assert_ok!(give_(dd.account_id, &give));
+
+			assert_eq!(
+				serde_json::from_slice::<Value>(
+					&FragmentsPallet::get_instances(GetInstancesParams {
+						definition_hash: hex::encode(give.mint.definition.get_definition_id())
+							.into_bytes(),
+						limit: u64::MAX,
+						only_return_first_copies: true,
+						..Default::default()
+					})
+					.unwrap()
+				)
+				.unwrap(),
+				json!({
+					"1.1": {},
+				})
+			);
+		});
+	}
+
+	#[test]
+	fn get_instances_should_work_if_only_return_first_copies_is_false() {
+		new_test_ext().execute_with(|| {
+			let dd = DummyData::new();
+			let give = dd.give_copy_perms;
+			assert_ok!(upload(dd.account_id, &give.mint.definition.proto_fragment));
+			assert_ok!(create(dd.account_id, &give.mint.definition));
+			assert_ok!(mint_(dd.account_id, &give.mint));
+			assert_ok!(give_(dd.account_id, &give));
+
+			assert_eq!(
+				serde_json::from_slice::<Value>(
+					&FragmentsPallet::get_instances(GetInstancesParams {
+						definition_hash: hex::encode(give.mint.definition.get_definition_id())
+							.into(),
+						limit: u64::MAX,
+						only_return_first_copies: false,
+						..Default::default()
+					})
+					.unwrap()
+				)
+				.unwrap(),
+				json!({
+					"1.1": {},
+					"1.2": {},
+				})
+			);
+		});
+	}
+
+	#[test]
+	fn get_instances_should_work_if_owner_owns_instances() {
+		new_test_ext().execute_with(|| {
 			let dd = DummyData::new();
 			let give = dd.give_no_copy_perms;
 			assert_ok!(upload(dd.account_id, &give.mint.definition.proto_fragment));
@@ -2898,3 +3404,4047 @@ mod get_instance_owner_tests {
 		});
 	}
 }
+
+mod withdraw_proceeds_tests {
+	use super::*;
+
+	fn withdraw_proceeds_(
+		signer: <Test as frame_system::Config>::AccountId,
+		definition: &Definition,
+		amount: u128,
+		to: <Test as frame_system::Config>::AccountId,
+	) -> DispatchResult {
+		FragmentsPallet::withdraw_proceeds(
+			RuntimeOrigin::signed(signer),
+			definition.get_definition_id(),
+			amount,
+			to,
+		)
+	}
+
+	fn fund_vault(definition: &Definition, amount: u128) {
+		let minimum_balance = <Balances as fungible::Inspect<
+			<Test as frame_system::Config>::AccountId,
+		>>::minimum_balance();
+		_ = <Balances as fungible::Mutate<<Test as frame_system::Config>::AccountId>>::mint_into(
+			&definition.get_vault_account_id(),
+			amount + minimum_balance,
+		);
+	}
+
+	#[test]
+	fn withdraw_proceeds_should_work() {
+		new_test_ext().execute_with(|| {
+			let dd = DummyData::new();
+			let definition = dd.definition;
+			assert_ok!(upload(dd.account_id, &definition.proto_fragment));
+			assert_ok!(create(dd.account_id, &definition));
+			fund_vault(&definition, 1_000);
+
+			let balance_before = Balances::free_balance(dd.account_id_second);
+
+			assert_ok!(withdraw_proceeds_(dd.account_id, &definition, 1_000, dd.account_id_second));
+
+			assert_eq!(Balances::free_balance(dd.account_id_second), balance_before + 1_000);
+			System::assert_last_event(
+				FragmentsEvent::ProceedsWithdrawn {
+					definition_hash: definition.get_definition_id(),
+					amount: 1_000,
+					to: dd.account_id_second,
+				}
+				.into(),
+			);
+		});
+	}
+
+	#[test]
+	fn withdraw_proceeds_should_not_work_if_user_does_not_own_the_proto() {
+		new_test_ext().execute_with(|| {
+			let dd = DummyData::new();
+			let definition = dd.definition;
+			assert_ok!(upload(dd.account_id, &definition.proto_fragment));
+			assert_ok!(create(dd.account_id, &definition));
+			fund_vault(&definition, 1_000);
+
+			assert_noop!(
+				withdraw_proceeds_(dd.account_id_second, &definition, 1_000, dd.account_id_second),
+				Error::<Test>::NoPermission
+			);
+		});
+	}
+
+	#[test]
+	fn withdraw_proceeds_should_not_work_if_fragment_definition_does_not_exist() {
+		new_test_ext().execute_with(|| {
+			let dd = DummyData::new();
+			let definition = dd.definition;
+
+			assert_noop!(
+				withdraw_proceeds_(dd.account_id, &definition, 1_000, dd.account_id_second),
+				Error::<Test>::NotFound
+			);
+		});
+	}
+
+	#[test]
+	fn withdraw_proceeds_should_not_work_if_the_vault_would_not_stay_alive() {
+		new_test_ext().execute_with(|| {
+			let dd = DummyData::new();
+			let definition = dd.definition;
+			assert_ok!(upload(dd.account_id, &definition.proto_fragment));
+			assert_ok!(create(dd.account_id, &definition));
+			fund_vault(&definition, 1_000);
+
+			assert_noop!(
+				withdraw_proceeds_(dd.account_id, &definition, 1_001, dd.account_id_second),
+				Error::<Test>::InsufficientBalance
+			);
+		});
+	}
+
+	#[test]
+	fn get_vault_balance_should_work() {
+		new_test_ext().execute_with(|| {
+			let dd = DummyData::new();
+			let definition = dd.definition;
+			assert_ok!(upload(dd.account_id, &definition.proto_fragment));
+			assert_ok!(create(dd.account_id, &definition));
+			fund_vault(&definition, 1_000);
+
+			let result: serde_json::Value = serde_json::from_slice(
+				&FragmentsPallet::get_vault_balance(
+					hex::encode(definition.get_definition_id()).into_bytes(),
+				)
+				.unwrap(),
+			)
+			.unwrap();
+			assert_eq!(result["currency"], "native");
+			assert_eq!(result["withdrawable"], "1000");
+		});
+	}
+}
+
+mod burn_tests {
+	use super::*;
+
+	fn burn_(
+		signer: <Test as frame_system::Config>::AccountId,
+		definition: &Definition,
+		edition_id: InstanceUnit,
+		copy_id: InstanceUnit,
+	) -> DispatchResult {
+		FragmentsPallet::burn(
+			RuntimeOrigin::signed(signer),
+			definition.get_definition_id(),
+			edition_id,
+			copy_id,
+		)
+	}
+
+	fn set_burns_replenish_supply_(
+		signer: <Test as frame_system::Config>::AccountId,
+		definition: &Definition,
+		replenish: bool,
+	) -> DispatchResult {
+		FragmentsPallet::set_burns_replenish_supply(
+			RuntimeOrigin::signed(signer),
+			definition.get_definition_id(),
+			replenish,
+		)
+	}
+
+	#[test]
+	fn burn_should_work() {
+		new_test_ext().execute_with(|| {
+			let dd = DummyData::new();
+			let mint = dd.mint_unique;
+			let definition_hash = mint.definition.get_definition_id();
+			assert_ok!(upload(dd.account_id, &mint.definition.proto_fragment));
+			assert_ok!(create(dd.account_id, &mint.definition));
+			assert_ok!(mint_(dd.account_id, &mint));
+
+			assert_ok!(burn_(dd.account_id, &mint.definition, 1, 1));
+
+			assert!(<Fragments<Test>>::get((definition_hash, 1, 1)).is_none());
+			assert!(!<Inventory<Test>>::get(dd.account_id, definition_hash)
+				.unwrap()
+				.contains(&(Compact(1), Compact(1))));
+			assert!(!<Owners<Test>>::get(definition_hash, dd.account_id)
+				.unwrap()
+				.contains(&(Compact(1), Compact(1))));
+			assert_eq!(<UniqueData2Edition<Test>>::iter_prefix(definition_hash).count(), 0);
+			assert_eq!(<BurnedCount<Test>>::get(definition_hash), Some(Compact(1)));
+			assert_eq!(<BurnedEditionsCount<Test>>::get(definition_hash), Some(Compact(1)));
+
+			System::assert_last_event(
+				FragmentsEvent::Burned {
+					account_id: dd.account_id,
+					definition_hash,
+					fragment_id: (1, 1),
+				}
+				.into(),
+			);
+		});
+	}
+
+	#[test]
+	fn burn_should_not_work_if_user_does_not_own_the_fragment_instance() {
+		new_test_ext().execute_with(|| {
+			let dd = DummyData::new();
+			let mint = dd.mint_non_unique;
+			assert_ok!(upload(dd.account_id, &mint.definition.proto_fragment));
+			assert_ok!(create(dd.account_id, &mint.definition));
+			assert_ok!(mint_(dd.account_id, &mint));
+
+			assert_noop!(
+				burn_(dd.account_id_second, &mint.definition, 1, 1),
+				Error::<Test>::NoPermission
+			);
+		});
+	}
+
+	#[test]
+	fn burn_should_not_work_if_the_fragment_instance_does_not_have_edit_permissions() {
+		new_test_ext().execute_with(|| {
+			let dd = DummyData::new();
+			let mint = dd.give_no_copy_perms.mint; // only has `FragmentPerms::TRANSFER`
+			assert_ok!(upload(dd.account_id, &mint.definition.proto_fragment));
+			assert_ok!(create(dd.account_id, &mint.definition));
+			assert_ok!(mint_(dd.account_id, &mint));
+
+			assert_noop!(burn_(dd.account_id, &mint.definition, 1, 1), Error::<Test>::NoPermission);
+		});
+	}
+
+	#[test]
+	fn burned_editions_should_count_towards_max_supply_by_default() {
+		new_test_ext().execute_with(|| {
+			let dd = DummyData::new();
+			let mut mint = dd.mint_non_unique_with_max_supply;
+			mint.definition.max_supply = Some(1);
+			assert_ok!(upload(dd.account_id, &mint.definition.proto_fragment));
+			assert_ok!(create(dd.account_id, &mint.definition));
+			assert_ok!(mint_(dd.account_id, &mint));
+
+			assert_ok!(burn_(dd.account_id, &mint.definition, 1, 1));
+
+			assert_noop!(mint_(dd.account_id, &mint), Error::<Test>::MaxSupplyReached);
+		});
+	}
+
+	#[test]
+	fn burned_editions_should_free_up_supply_if_the_fragment_definition_allows_it() {
+		new_test_ext().execute_with(|| {
+			let dd = DummyData::new();
+			let mut mint = dd.mint_non_unique_with_max_supply;
+			mint.definition.max_supply = Some(1);
+			assert_ok!(upload(dd.account_id, &mint.definition.proto_fragment));
+			assert_ok!(create(dd.account_id, &mint.definition));
+			assert_ok!(set_burns_replenish_supply_(dd.account_id, &mint.definition, true));
+			assert_ok!(mint_(dd.account_id, &mint));
+			assert_noop!(mint_(dd.account_id, &mint), Error::<Test>::MaxSupplyReached);
+
+			assert_ok!(burn_(dd.account_id, &mint.definition, 1, 1));
+
+			assert_ok!(mint_(dd.account_id, &mint));
+			assert!(<Fragments<Test>>::contains_key((mint.definition.get_definition_id(), 2, 1)));
+		});
+	}
+
+	#[test]
+	fn get_instances_and_get_definitions_should_not_return_burned_fragment_instances() {
+		new_test_ext().execute_with(|| {
+			let dd = DummyData::new();
+			let mint = dd.mint_non_unique;
+			let definition_hash = mint.definition.get_definition_id();
+			assert_ok!(upload(dd.account_id, &mint.definition.proto_fragment));
+			assert_ok!(create(dd.account_id, &mint.definition));
+			assert_ok!(mint_(dd.account_id, &mint));
+			assert_ok!(mint_(dd.account_id, &mint));
+
+			assert_ok!(burn_(dd.account_id, &mint.definition, 1, 1));
+
+			assert_eq!(
+				serde_json::from_slice::<Value>(
+					&FragmentsPallet::get_instances(GetInstancesParams {
+						definition_hash: hex::encode(definition_hash).into_bytes(),
+						limit: u64::MAX,
+						..Default::default()
+					})
+					.unwrap()
+				)
+				.unwrap(),
+				json!({
+					"2.1": {},
+				})
+			);
+			assert_eq!(
+				serde_json::from_slice::<Value>(
+					&FragmentsPallet::get_definitions(GetDefinitionsParams {
+						limit: u64::MAX,
+						..Default::default()
+					})
+					.unwrap()
+				)
+				.unwrap()[hex::encode(definition_hash)]["num_instances"],
+				json!(1)
+			);
+		});
+	}
+
+	#[test]
+	fn set_burns_replenish_supply_should_not_work_if_fragment_instances_were_already_created() {
+		new_test_ext().execute_with(|| {
+			let dd = DummyData::new();
+			let mint = dd.mint_non_unique_with_max_supply;
+			assert_ok!(upload(dd.account_id, &mint.definition.proto_fragment));
+			assert_ok!(create(dd.account_id, &mint.definition));
+			assert_ok!(mint_(dd.account_id, &mint));
+
+			assert_noop!(
+				set_burns_replenish_supply_(dd.account_id, &mint.definition, true),
+				Error::<Test>::InstancesAlreadyCreated
+			);
+		});
+	}
+}
+
+mod stack_tests {
+	use super::*;
+
+	fn mint_stack(dd: &DummyData, stack_amount: u64) -> Mint {
+		let mint = Mint { amount: Some(stack_amount), ..dd.mint_non_unique.clone() };
+		assert_ok!(upload(dd.account_id, &mint.definition.proto_fragment));
+		assert_ok!(create(dd.account_id, &mint.definition));
+		assert_ok!(mint_(dd.account_id, &mint));
+		mint
+	}
+
+	fn split_stack_(
+		signer: <Test as frame_system::Config>::AccountId,
+		definition: &Definition,
+		copy_id: InstanceUnit,
+		amount: InstanceUnit,
+		to: <Test as frame_system::Config>::AccountId,
+	) -> DispatchResult {
+		FragmentsPallet::split_stack(
+			RuntimeOrigin::signed(signer),
+			definition.get_definition_id(),
+			1,
+			copy_id,
+			amount,
+			to,
+		)
+	}
+
+	fn stack_amount_of(definition: &Definition, copy_id: InstanceUnit) -> Option<u64> {
+		<Fragments<Test>>::get((definition.get_definition_id(), 1, copy_id))
+			.and_then(|instance| instance.stack_amount.map(|amount| amount.into()))
+	}
+
+	#[test]
+	fn split_stack_should_work() {
+		new_test_ext().execute_with(|| {
+			let dd = DummyData::new();
+			let mint = mint_stack(&dd, 10);
+
+			assert_ok!(split_stack_(dd.account_id, &mint.definition, 1, 4, dd.account_id_second));
+
+			assert_eq!(stack_amount_of(&mint.definition, 1), Some(6));
+			assert_eq!(stack_amount_of(&mint.definition, 2), Some(4));
+			assert_eq!(
+				<CopiesCount<Test>>::get((mint.definition.get_definition_id(), 1)),
+				Some(Compact(2))
+			);
+			assert!(<Inventory<Test>>::get(
+				dd.account_id_second,
+				mint.definition.get_definition_id()
+			)
+			.unwrap()
+			.contains(&(Compact(1), Compact(2))));
+			assert!(<Owners<Test>>::get(mint.definition.get_definition_id(), dd.account_id_second)
+				.unwrap()
+				.contains(&(Compact(1), Compact(2))));
+		});
+	}
+
+	#[test]
+	fn split_stack_should_not_work_if_amount_is_not_smaller_than_the_stack_amount() {
+		new_test_ext().execute_with(|| {
+			let dd = DummyData::new();
+			let mint = mint_stack(&dd, 10);
+
+			assert_noop!(
+				split_stack_(dd.account_id, &mint.definition, 1, 10, dd.account_id_second),
+				Error::<Test>::InsufficientStackAmount
+			);
+		});
+	}
+
+	#[test]
+	fn split_stack_should_not_work_if_fragment_instance_is_not_stackable() {
+		new_test_ext().execute_with(|| {
+			let dd = DummyData::new();
+			let mint = dd.mint_non_unique;
+			assert_ok!(upload(dd.account_id, &mint.definition.proto_fragment));
+			assert_ok!(create(dd.account_id, &mint.definition));
+			assert_ok!(mint_(dd.account_id, &mint));
+
+			assert_noop!(
+				split_stack_(dd.account_id, &mint.definition, 1, 1, dd.account_id_second),
+				Error::<Test>::NotStackable
+			);
+		});
+	}
+
+	#[test]
+	fn merge_stacks_should_work() {
+		new_test_ext().execute_with(|| {
+			let dd = DummyData::new();
+			let mint = mint_stack(&dd, 10);
+			assert_ok!(split_stack_(dd.account_id, &mint.definition, 1, 4, dd.account_id));
+
+			assert_ok!(FragmentsPallet::merge_stacks(
+				RuntimeOrigin::signed(dd.account_id),
+				mint.definition.get_definition_id(),
+				1,
+				1,
+				2
+			));
+
+			assert_eq!(stack_amount_of(&mint.definition, 1), Some(10));
+			assert!(!<Fragments<Test>>::contains_key((mint.definition.get_definition_id(), 1, 2)));
+			assert!(!<Inventory<Test>>::get(dd.account_id, mint.definition.get_definition_id())
+				.unwrap()
+				.contains(&(Compact(1), Compact(2))));
+		});
+	}
+
+	#[test]
+	fn merge_stacks_should_not_work_if_user_does_not_own_both_fragment_instances() {
+		new_test_ext().execute_with(|| {
+			let dd = DummyData::new();
+			let mint = mint_stack(&dd, 10);
+			assert_ok!(split_stack_(dd.account_id, &mint.definition, 1, 4, dd.account_id_second));
+
+			assert_noop!(
+				FragmentsPallet::merge_stacks(
+					RuntimeOrigin::signed(dd.account_id),
+					mint.definition.get_definition_id(),
+					1,
+					1,
+					2
+				),
+				Error::<Test>::NoPermission
+			);
+		});
+	}
+
+	#[test]
+	fn consume_should_work() {
+		new_test_ext().execute_with(|| {
+			let dd = DummyData::new();
+			let mint = mint_stack(&dd, 10);
+
+			assert_ok!(FragmentsPallet::consume(
+				RuntimeOrigin::signed(dd.account_id),
+				mint.definition.get_definition_id(),
+				1,
+				1,
+				3
+			));
+
+			assert_eq!(stack_amount_of(&mint.definition, 1), Some(7));
+			System::assert_last_event(
+				FragmentsEvent::StackConsumed {
+					definition_hash: mint.definition.get_definition_id(),
+					fragment_id: (1, 1),
+					amount: 3,
+					left: 7,
+				}
+				.into(),
+			);
+		});
+	}
+
+	#[test]
+	fn consume_should_burn_the_fragment_instance_if_the_stack_becomes_empty() {
+		new_test_ext().execute_with(|| {
+			let dd = DummyData::new();
+			let mint = mint_stack(&dd, 10);
+
+			assert_ok!(FragmentsPallet::consume(
+				RuntimeOrigin::signed(dd.account_id),
+				mint.definition.get_definition_id(),
+				1,
+				1,
+				10
+			));
+
+			assert!(!<Fragments<Test>>::contains_key((mint.definition.get_definition_id(), 1, 1)));
+			assert_eq!(
+				<BurnedCount<Test>>::get(mint.definition.get_definition_id()),
+				Some(Compact(1))
+			);
+		});
+	}
+
+	#[test]
+	fn consume_should_not_work_if_amount_is_greater_than_the_stack_amount() {
+		new_test_ext().execute_with(|| {
+			let dd = DummyData::new();
+			let mint = mint_stack(&dd, 10);
+
+			assert_noop!(
+				FragmentsPallet::consume(
+					RuntimeOrigin::signed(dd.account_id),
+					mint.definition.get_definition_id(),
+					1,
+					1,
+					11
+				),
+				Error::<Test>::InsufficientStackAmount
+			);
+		});
+	}
+}
+
+mod sale_phase_tests {
+	use super::*;
+
+	fn publish_with_phases(
+		signer: <Test as frame_system::Config>::AccountId,
+		buy: &Buy,
+		phases: Vec<SalePhase<u64>>,
+	) {
+		assert_ok!(upload(signer, &buy.publish.definition.proto_fragment));
+		assert_ok!(create(signer, &buy.publish.definition));
+		assert_ok!(publish_(signer, &Publish { phases, ..buy.publish.clone() }));
+	}
+
+	fn buy_with_proof(
+		signer: <Test as frame_system::Config>::AccountId,
+		buy: &Buy,
+		quantity: u64,
+		allowlist_proof: Option<AllowlistProof>,
+	) -> DispatchResultWithPostInfo {
+		FragmentsPallet::buy(
+			RuntimeOrigin::signed(signer),
+			buy.publish.definition.get_definition_id(),
+			FragmentBuyOptions::Quantity(quantity),
+			allowlist_proof,
+		)
+	}
+
+	fn leaf(account_id: <Test as frame_system::Config>::AccountId, allowance: u64) -> Hash256 {
+		blake2_256(&(account_id, allowance).encode())
+	}
+
+	/// Merkle Root of an allowlist with two leaves
+	fn root(leaf_1: Hash256, leaf_2: Hash256) -> Hash256 {
+		sp_fragnova::merkle::merkle_root(&[leaf_1, leaf_2])
+	}
+
+	/// Merkle Proof of the first leaf of an allowlist with two leaves
+	fn proof(leaf_1: Hash256, leaf_2: Hash256) -> MerkleProof {
+		sp_fragnova::merkle::merkle_proof(&[leaf_1, leaf_2], 0).unwrap()
+	}
+
+	fn phase(start: u64, price: u128) -> SalePhase<u64> {
+		SalePhase { start, price: Compact(price), allowlist: None, max_per_account: None }
+	}
+
+	#[test]
+	fn publish_should_not_work_if_sale_phases_are_not_sorted() {
+		new_test_ext().execute_with(|| {
+			let dd = DummyData::new();
+			let publish = dd.publish;
+			assert_ok!(upload(dd.account_id, &publish.definition.proto_fragment));
+			assert_ok!(create(dd.account_id, &publish.definition));
+
+			assert_noop!(
+				publish_(
+					dd.account_id,
+					&Publish { phases: vec![phase(10, 1), phase(5, 1)], ..publish.clone() }
+				),
+				Error::<Test>::InvalidSalePhases
+			);
+		});
+	}
+
+	#[test]
+	fn publish_should_not_work_if_a_sale_phase_starts_after_the_sale_expires() {
+		new_test_ext().execute_with(|| {
+			let dd = DummyData::new();
+			let publish = dd.publish;
+			assert_ok!(upload(dd.account_id, &publish.definition.proto_fragment));
+			assert_ok!(create(dd.account_id, &publish.definition));
+
+			let expires = System::block_number() + 10;
+			assert_noop!(
+				publish_(
+					dd.account_id,
+					&Publish {
+						expires: Some(expires),
+						phases: vec![phase(expires - 5, 1), phase(expires, 1)],
+						..publish.clone()
+					}
+				),
+				Error::<Test>::InvalidSalePhases
+			);
+			assert_ok!(publish_(
+				dd.account_id,
+				&Publish {
+					expires: Some(expires),
+					phases: vec![phase(expires - 5, 1), phase(expires - 1, 1)],
+					..publish.clone()
+				}
+			));
+		});
+	}
+
+	#[test]
+	fn buy_should_not_work_if_the_first_sale_phase_has_not_started() {
+		new_test_ext().execute_with(|| {
+			let dd = DummyData::new();
+			let buy = dd.buy_non_unique;
+			let start = System::block_number() + 10;
+			publish_with_phases(dd.account_id, &buy, vec![phase(start, 1)]);
+			fund(dd.account_id_second, 1_000_000);
+
+			assert_noop!(
+				buy_with_proof(dd.account_id_second, &buy, 1, None),
+				Error::<Test>::SaleNotStarted
+			);
+		});
+	}
+
+	#[test]
+	fn buy_should_use_the_price_of_the_current_sale_phase() {
+		new_test_ext().execute_with(|| {
+			let dd = DummyData::new();
+			let buy = dd.buy_non_unique;
+			let current_block_number = System::block_number();
+			publish_with_phases(
+				dd.account_id,
+				&buy,
+				vec![phase(current_block_number, 5), phase(current_block_number + 10, 9)],
+			);
+			fund(dd.account_id_second, 1_000_000);
+
+			assert_ok!(buy_with_proof(dd.account_id_second, &buy, 1, None));
+			System::assert_has_event(
+				pallet_balances::Event::Transfer {
+					from: dd.account_id_second,
+					to: buy.publish.definition.get_vault_account_id(),
+					amount: 5,
+				}
+				.into(),
+			);
+
+			System::set_block_number(current_block_number + 10);
+
+			assert_ok!(buy_with_proof(dd.account_id_second, &buy, 1, None));
+			System::assert_has_event(
+				pallet_balances::Event::Transfer {
+					from: dd.account_id_second,
+					to: buy.publish.definition.get_vault_account_id(),
+					amount: 9,
+				}
+				.into(),
+			);
+		});
+	}
+
+	#[test]
+	fn buy_should_enforce_the_allowlist_of_the_current_sale_phase() {
+		new_test_ext().execute_with(|| {
+			let dd = DummyData::new();
+			let buy = dd.buy_non_unique;
+			let leaf_second = leaf(dd.account_id_second, 2);
+			let leaf_third = leaf(dd.account_id_third, 1);
+			publish_with_phases(
+				dd.account_id,
+				&buy,
+				vec![SalePhase {
+					allowlist: Some(root(leaf_second, leaf_third)),
+					..phase(System::block_number(), 1)
+				}],
+			);
+			fund(dd.account_id_second, 1_000_000);
+			fund(dd.account_id, 1_000_000);
+
+			// no proof
+			assert_noop!(
+				buy_with_proof(dd.account_id_second, &buy, 1, None),
+				Error::<Test>::NotAllowlisted
+			);
+			// proof of a different allowance
+			assert_noop!(
+				buy_with_proof(
+					dd.account_id_second,
+					&buy,
+					1,
+					Some(AllowlistProof { allowance: 3, proof: proof(leaf_second, leaf_third) })
+				),
+				Error::<Test>::NotAllowlisted
+			);
+			// account that is not in the allowlist
+			assert_noop!(
+				buy_with_proof(
+					dd.account_id,
+					&buy,
+					1,
+					Some(AllowlistProof { allowance: 2, proof: proof(leaf_second, leaf_third) })
+				),
+				Error::<Test>::NotAllowlisted
+			);
+
+			let allowlist_proof =
+				AllowlistProof { allowance: 2, proof: proof(leaf_second, leaf_third) };
+			assert_ok!(buy_with_proof(
+				dd.account_id_second,
+				&buy,
+				2,
+				Some(allowlist_proof.clone())
+			));
+			assert_eq!(
+				<PhaseMints<Test>>::get((
+					buy.publish.definition.get_definition_id(),
+					0,
+					0,
+					dd.account_id_second
+				)),
+				Some(Compact(2))
+			);
+			assert_noop!(
+				buy_with_proof(dd.account_id_second, &buy, 1, Some(allowlist_proof)),
+				Error::<Test>::PhaseAllowanceExceeded
+			);
+		});
+	}
+
+	#[test]
+	fn buy_should_enforce_the_max_per_account_of_the_current_sale_phase() {
+		new_test_ext().execute_with(|| {
+			let dd = DummyData::new();
+			let buy = dd.buy_non_unique;
+			publish_with_phases(
+				dd.account_id,
+				&buy,
+				vec![SalePhase {
+					max_per_account: Some(Compact(2)),
+					..phase(System::block_number(), 1)
+				}],
+			);
+			fund(dd.account_id_second, 1_000_000);
+
+			assert_ok!(buy_with_proof(dd.account_id_second, &buy, 1, None));
+			assert_noop!(
+				buy_with_proof(dd.account_id_second, &buy, 2, None),
+				Error::<Test>::PhaseAllowanceExceeded
+			);
+			assert_ok!(buy_with_proof(dd.account_id_second, &buy, 1, None));
+		});
+	}
+
+	#[test]
+	fn buy_should_forget_the_phase_mints_of_a_previous_sale() {
+		new_test_ext().execute_with(|| {
+			let dd = DummyData::new();
+			let buy = dd.buy_non_unique;
+			let phases = vec![SalePhase {
+				max_per_account: Some(Compact(2)),
+				..phase(System::block_number(), 1)
+			}];
+			publish_with_phases(dd.account_id, &buy, phases.clone());
+			fund(dd.account_id_second, 1_000_000);
+
+			assert_ok!(buy_with_proof(dd.account_id_second, &buy, 2, None));
+			assert_noop!(
+				buy_with_proof(dd.account_id_second, &buy, 1, None),
+				Error::<Test>::PhaseAllowanceExceeded
+			);
+
+			assert_ok!(FragmentsPallet::unpublish(
+				RuntimeOrigin::signed(dd.account_id),
+				buy.publish.definition.get_definition_id()
+			));
+			assert_ok!(publish_(dd.account_id, &Publish { phases, ..buy.publish.clone() }));
+
+			assert_ok!(buy_with_proof(dd.account_id_second, &buy, 2, None));
+		});
+	}
+
+	#[test]
+	fn get_sale_phase_should_work() {
+		new_test_ext().execute_with(|| {
+			let dd = DummyData::new();
+			let buy = dd.buy_non_unique;
+			let current_block_number = System::block_number();
+			publish_with_phases(
+				dd.account_id,
+				&buy,
+				vec![
+					SalePhase {
+						max_per_account: Some(Compact(3)),
+						..phase(current_block_number, 5)
+					},
+					phase(current_block_number + 10, 9),
+				],
+			);
+			fund(dd.account_id_second, 1_000_000);
+			assert_ok!(buy_with_proof(dd.account_id_second, &buy, 1, None));
+
+			let result: serde_json::Value = serde_json::from_slice(
+				&FragmentsPallet::get_sale_phase(GetSalePhaseParams {
+					definition_hash: hex::encode(buy.publish.definition.get_definition_id())
+						.into_bytes(),
+					account: Some(dd.account_id_second),
+					allowance: None,
+				})
+				.unwrap(),
+			)
+			.unwrap();
+
+			assert_eq!(result["phase"], 0);
+			assert_eq!(result["started"], true);
+			assert_eq!(result["price"], "5");
+			assert_eq!(result["next_phase_start"], current_block_number + 10);
+			assert_eq!(result["bought"], 1);
+			assert_eq!(result["remaining"], 2);
+		});
+	}
+}
+
+mod mint_to_many_tests {
+	use super::*;
+
+	#[test]
+	fn mint_to_many_should_work() {
+		new_test_ext().execute_with(|| {
+			let dd = DummyData::new();
+			let mint = dd.mint_non_unique;
+			let definition_hash = mint.definition.get_definition_id();
+			assert_ok!(upload(dd.account_id, &mint.definition.proto_fragment));
+			assert_ok!(create(dd.account_id, &mint.definition));
+
+			assert_ok!(FragmentsPallet::mint_to_many(
+				RuntimeOrigin::signed(dd.account_id),
+				definition_hash,
+				vec![(dd.account_id_second, 2), (dd.account_id_third, 1)],
+				Some(5),
+				None,
+			));
+
+			for (account_id, edition_id) in
+				[(dd.account_id_second, 1), (dd.account_id_second, 2), (dd.account_id_third, 3)]
+			{
+				System::assert_has_event(
+					FragmentsEvent::InventoryAdded {
+						account_id,
+						definition_hash,
+						fragment_id: (edition_id, 1),
+					}
+					.into(),
+				);
+				assert_eq!(
+					<Fragments<Test>>::get((definition_hash, edition_id, 1)).unwrap().stack_amount,
+					Some(Compact(5))
+				);
+			}
+			assert_eq!(
+				<Inventory<Test>>::get(dd.account_id_second, definition_hash).unwrap(),
+				vec![Compact(1), Compact(2)]
+			);
+		});
+	}
+
+	#[test]
+	fn mint_to_many_should_not_work_if_the_user_is_not_the_proto_owner() {
+		new_test_ext().execute_with(|| {
+			let dd = DummyData::new();
+			let mint = dd.mint_non_unique;
+			assert_ok!(upload(dd.account_id, &mint.definition.proto_fragment));
+			assert_ok!(create(dd.account_id, &mint.definition));
+
+			assert_noop!(
+				FragmentsPallet::mint_to_many(
+					RuntimeOrigin::signed(dd.account_id_second),
+					mint.definition.get_definition_id(),
+					vec![(dd.account_id_second, 1)],
+					None,
+					None,
+				),
+				Error::<Test>::NoPermission
+			);
+		});
+	}
+
+	#[test]
+	fn mint_to_many_should_not_work_if_the_fragment_definition_is_unique() {
+		new_test_ext().execute_with(|| {
+			let dd = DummyData::new();
+			let mint = dd.mint_unique;
+			assert_ok!(upload(dd.account_id, &mint.definition.proto_fragment));
+			assert_ok!(create(dd.account_id, &mint.definition));
+
+			assert_noop!(
+				FragmentsPallet::mint_to_many(
+					RuntimeOrigin::signed(dd.account_id),
+					mint.definition.get_definition_id(),
+					vec![(dd.account_id_second, 1)],
+					None,
+					None,
+				),
+				Error::<Test>::ParamsNotValid
+			);
+		});
+	}
+
+	#[test]
+	fn mint_to_many_should_not_work_if_there_are_too_many_recipients() {
+		new_test_ext().execute_with(|| {
+			let dd = DummyData::new();
+			let mint = dd.mint_non_unique;
+			assert_ok!(upload(dd.account_id, &mint.definition.proto_fragment));
+			assert_ok!(create(dd.account_id, &mint.definition));
+
+			assert_noop!(
+				FragmentsPallet::mint_to_many(
+					RuntimeOrigin::signed(dd.account_id),
+					mint.definition.get_definition_id(),
+					vec![(dd.account_id_second, 1); MAX_MINT_RECIPIENTS + 1],
+					None,
+					None,
+				),
+				Error::<Test>::TooManyRecipients
+			);
+		});
+	}
+
+	#[test]
+	fn mint_to_many_should_not_work_if_the_max_supply_is_exceeded() {
+		new_test_ext().execute_with(|| {
+			let dd = DummyData::new();
+			let mint = dd.mint_non_unique_with_max_supply;
+			assert_ok!(upload(dd.account_id, &mint.definition.proto_fragment));
+			assert_ok!(create(dd.account_id, &mint.definition));
+
+			assert_noop!(
+				FragmentsPallet::mint_to_many(
+					RuntimeOrigin::signed(dd.account_id),
+					mint.definition.get_definition_id(),
+					vec![(dd.account_id_second, 1235)],
+					None,
+					None,
+				),
+				Error::<Test>::MaxSupplyReached
+			);
+		});
+	}
+}
+
+mod claim_tests {
+	use super::*;
+
+	fn leaf(account_id: <Test as frame_system::Config>::AccountId, quantity: u64) -> Hash256 {
+		blake2_256(&(account_id, quantity).encode())
+	}
+
+	/// Merkle Root of a claim campaign with two leaves
+	fn root(leaf_1: Hash256, leaf_2: Hash256) -> Hash256 {
+		sp_fragnova::merkle::merkle_root(&[leaf_1, leaf_2])
+	}
+
+	/// Merkle Proof of the first leaf of a claim campaign with two leaves
+	fn proof(leaf_1: Hash256, leaf_2: Hash256) -> MerkleProof {
+		sp_fragnova::merkle::merkle_proof(&[leaf_1, leaf_2], 0).unwrap()
+	}
+
+	fn start_claim_(
+		signer: <Test as frame_system::Config>::AccountId,
+		mint: &Mint,
+		merkle_root: Hash256,
+		expiration: Option<u64>,
+	) -> DispatchResult {
+		FragmentsPallet::start_claim(
+			RuntimeOrigin::signed(signer),
+			mint.definition.get_definition_id(),
+			merkle_root,
+			expiration,
+			None,
+			None,
+		)
+	}
+
+	fn claim_(
+		signer: <Test as frame_system::Config>::AccountId,
+		mint: &Mint,
+		options: FragmentBuyOptions,
+		proof: MerkleProof,
+	) -> DispatchResultWithPostInfo {
+		FragmentsPallet::claim(
+			RuntimeOrigin::signed(signer),
+			mint.definition.get_definition_id(),
+			options,
+			proof,
+		)
+	}
+
+	#[test]
+	fn claim_should_work() {
+		new_test_ext().execute_with(|| {
+			let dd = DummyData::new();
+			let mint = dd.mint_non_unique;
+			let definition_hash = mint.definition.get_definition_id();
+			assert_ok!(upload(dd.account_id, &mint.definition.proto_fragment));
+			assert_ok!(create(dd.account_id, &mint.definition));
+
+			let leaf_second = leaf(dd.account_id_second, 2);
+			let leaf_third = leaf(dd.account_id_third, 1);
+			assert_ok!(start_claim_(dd.account_id, &mint, root(leaf_second, leaf_third), None));
+
+			assert_ok!(claim_(
+				dd.account_id_second,
+				&mint,
+				FragmentBuyOptions::Quantity(2),
+				proof(leaf_second, leaf_third)
+			));
+
+			System::assert_has_event(
+				FragmentsEvent::InventoryAdded {
+					account_id: dd.account_id_second,
+					definition_hash,
+					fragment_id: (2, 1),
+				}
+				.into(),
+			);
+			System::assert_last_event(
+				FragmentsEvent::Claimed {
+					definition_hash,
+					account_id: dd.account_id_second,
+					quantity: 2,
+				}
+				.into(),
+			);
+			assert_eq!(
+				<Inventory<Test>>::get(dd.account_id_second, definition_hash).unwrap(),
+				vec![Compact(1), Compact(2)]
+			);
+		});
+	}
+
+	#[test]
+	fn claim_should_not_work_twice() {
+		new_test_ext().execute_with(|| {
+			let dd = DummyData::new();
+			let mint = dd.mint_non_unique;
+			assert_ok!(upload(dd.account_id, &mint.definition.proto_fragment));
+			assert_ok!(create(dd.account_id, &mint.definition));
+
+			let leaf_second = leaf(dd.account_id_second, 1);
+			let leaf_third = leaf(dd.account_id_third, 1);
+			assert_ok!(start_claim_(dd.account_id, &mint, root(leaf_second, leaf_third), None));
+
+			assert_ok!(claim_(
+				dd.account_id_second,
+				&mint,
+				FragmentBuyOptions::Quantity(1),
+				proof(leaf_second, leaf_third)
+			));
+			assert_noop!(
+				claim_(
+					dd.account_id_second,
+					&mint,
+					FragmentBuyOptions::Quantity(1),
+					proof(leaf_second, leaf_third)
+				),
+				Error::<Test>::AlreadyClaimed
+			);
+		});
+	}
+
+	#[test]
+	fn claim_should_not_work_if_the_quantity_is_not_in_the_merkle_tree() {
+		new_test_ext().execute_with(|| {
+			let dd = DummyData::new();
+			let mint = dd.mint_non_unique;
+			assert_ok!(upload(dd.account_id, &mint.definition.proto_fragment));
+			assert_ok!(create(dd.account_id, &mint.definition));
+
+			let leaf_second = leaf(dd.account_id_second, 1);
+			let leaf_third = leaf(dd.account_id_third, 1);
+			assert_ok!(start_claim_(dd.account_id, &mint, root(leaf_second, leaf_third), None));
+
+			assert_noop!(
+				claim_(
+					dd.account_id_second,
+					&mint,
+					FragmentBuyOptions::Quantity(2),
+					proof(leaf_second, leaf_third)
+				),
+				Error::<Test>::NotClaimable
+			);
+		});
+	}
+
+	#[test]
+	fn claim_should_not_work_if_the_claim_campaign_has_expired() {
+		new_test_ext().execute_with(|| {
+			let dd = DummyData::new();
+			let mint = dd.mint_non_unique;
+			assert_ok!(upload(dd.account_id, &mint.definition.proto_fragment));
+			assert_ok!(create(dd.account_id, &mint.definition));
+
+			let leaf_second = leaf(dd.account_id_second, 1);
+			let leaf_third = leaf(dd.account_id_third, 1);
+			let expiration = System::block_number() + 10;
+			assert_ok!(start_claim_(
+				dd.account_id,
+				&mint,
+				root(leaf_second, leaf_third),
+				Some(expiration)
+			));
+
+			System::set_block_number(expiration);
+
+			assert_noop!(
+				claim_(
+					dd.account_id_second,
+					&mint,
+					FragmentBuyOptions::Quantity(1),
+					proof(leaf_second, leaf_third)
+				),
+				Error::<Test>::Expired
+			);
+		});
+	}
+
+	#[test]
+	fn claim_should_not_work_if_the_claim_campaign_has_ended() {
+		new_test_ext().execute_with(|| {
+			let dd = DummyData::new();
+			let mint = dd.mint_non_unique;
+			assert_ok!(upload(dd.account_id, &mint.definition.proto_fragment));
+			assert_ok!(create(dd.account_id, &mint.definition));
+
+			let leaf_second = leaf(dd.account_id_second, 1);
+			let leaf_third = leaf(dd.account_id_third, 1);
+			assert_ok!(start_claim_(dd.account_id, &mint, root(leaf_second, leaf_third), None));
+			assert_ok!(FragmentsPallet::end_claim(
+				RuntimeOrigin::signed(dd.account_id),
+				mint.definition.get_definition_id()
+			));
+
+			assert_noop!(
+				claim_(
+					dd.account_id_second,
+					&mint,
+					FragmentBuyOptions::Quantity(1),
+					proof(leaf_second, leaf_third)
+				),
+				Error::<Test>::NotFound
+			);
+		});
+	}
+
+	#[test]
+	fn claim_should_not_work_if_the_unique_data_already_exists() {
+		new_test_ext().execute_with(|| {
+			let dd = DummyData::new();
+			let mint = dd.mint_unique;
+			assert_ok!(upload(dd.account_id, &mint.definition.proto_fragment));
+			assert_ok!(create(dd.account_id, &mint.definition));
+			assert_ok!(mint_(dd.account_id, &mint));
+
+			let leaf_second = leaf(dd.account_id_second, 1);
+			let leaf_third = leaf(dd.account_id_third, 1);
+			assert_ok!(start_claim_(dd.account_id, &mint, root(leaf_second, leaf_third), None));
+
+			assert_noop!(
+				claim_(
+					dd.account_id_second,
+					&mint,
+					mint.buy_options.clone(),
+					proof(leaf_second, leaf_third)
+				),
+				Error::<Test>::UniqueDataExists
+			);
+		});
+	}
+
+	#[test]
+	fn start_claim_should_not_work_if_the_user_is_not_the_proto_owner() {
+		new_test_ext().execute_with(|| {
+			let dd = DummyData::new();
+			let mint = dd.mint_non_unique;
+			assert_ok!(upload(dd.account_id, &mint.definition.proto_fragment));
+			assert_ok!(create(dd.account_id, &mint.definition));
+
+			assert_noop!(
+				start_claim_(dd.account_id_second, &mint, [7u8; 32], None),
+				Error::<Test>::NoPermission
+			);
+		});
+	}
+}
+
+mod instance_owner_tests {
+	use super::*;
+	use frame_support::traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion};
+
+	#[test]
+	fn mint_should_index_the_owner_of_the_fragment_instances() {
+		new_test_ext().execute_with(|| {
+			let dd = DummyData::new();
+			let mint = dd.mint_non_unique;
+			let definition_hash = mint.definition.get_definition_id();
+			assert_ok!(upload(dd.account_id, &mint.definition.proto_fragment));
+			assert_ok!(create(dd.account_id, &mint.definition));
+			assert_ok!(mint_(dd.account_id, &mint));
+
+			for edition_id in 1..=mint.get_quantity() {
+				assert_eq!(
+					<InstanceOwner<Test>>::get((definition_hash, edition_id, 1)),
+					Some(dd.account_id)
+				);
+			}
+		});
+	}
+
+	#[test]
+	fn give_should_update_the_owner_of_the_fragment_instance() {
+		new_test_ext().execute_with(|| {
+			let dd = DummyData::new();
+			let give = dd.give_no_copy_perms;
+			let definition_hash = give.mint.definition.get_definition_id();
+			mint_give_instance(dd.account_id, &give);
+			assert_ok!(give_(dd.account_id, &give));
+
+			assert_eq!(
+				<InstanceOwner<Test>>::get((definition_hash, give.edition_id, give.copy_id)),
+				Some(give.to)
+			);
+		});
+	}
+
+	#[test]
+	fn give_should_index_the_owner_of_the_copied_fragment_instance() {
+		new_test_ext().execute_with(|| {
+			let dd = DummyData::new();
+			let give = dd.give_copy_perms;
+			let definition_hash = give.mint.definition.get_definition_id();
+			mint_give_instance(dd.account_id, &give);
+			assert_ok!(give_(dd.account_id, &give));
+
+			assert_eq!(
+				<InstanceOwner<Test>>::get((definition_hash, give.edition_id, give.copy_id)),
+				Some(dd.account_id)
+			);
+			assert_eq!(
+				<InstanceOwner<Test>>::get((definition_hash, give.edition_id, give.copy_id + 1)),
+				Some(give.to)
+			);
+		});
+	}
+
+	#[test]
+	fn burn_should_remove_the_owner_of_the_fragment_instance() {
+		new_test_ext().execute_with(|| {
+			let dd = DummyData::new();
+			let mint = dd.mint_unique;
+			let definition_hash = mint.definition.get_definition_id();
+			assert_ok!(upload(dd.account_id, &mint.definition.proto_fragment));
+			assert_ok!(create(dd.account_id, &mint.definition));
+			assert_ok!(mint_(dd.account_id, &mint));
+
+			assert_ok!(FragmentsPallet::burn(
+				RuntimeOrigin::signed(dd.account_id),
+				definition_hash,
+				1,
+				1
+			));
+
+			assert!(<InstanceOwner<Test>>::get((definition_hash, 1, 1)).is_none());
+		});
+	}
+
+	#[test]
+	fn expiration_should_remove_the_owner_of_the_fragment_instance() {
+		new_test_ext().execute_with(|| {
+			let dd = DummyData::new();
+			let give = dd.give_copy_perms;
+			let definition_hash = give.mint.definition.get_definition_id();
+			assert!(give.expiration.is_some());
+			mint_give_instance(dd.account_id, &give);
+			assert_ok!(give_(dd.account_id, &give));
+
+			run_to_block(give.expiration.unwrap());
+
+			assert!(<InstanceOwner<Test>>::get((
+				definition_hash,
+				give.edition_id,
+				give.copy_id + 1
+			))
+			.is_none());
+			assert!(!<Owners<Test>>::get(definition_hash, give.to)
+				.unwrap()
+				.contains(&(Compact(give.edition_id), Compact(give.copy_id + 1))));
+			assert!(!<Inventory<Test>>::get(give.to, definition_hash)
+				.unwrap()
+				.contains(&(Compact(give.edition_id), Compact(give.copy_id + 1))));
+			System::assert_has_event(
+				FragmentsEvent::Expired {
+					account_id: give.to,
+					definition_hash,
+					fragment_id: (give.edition_id, give.copy_id + 1),
+				}
+				.into(),
+			);
+			// the original Fragment Instance does not expire
+			assert_eq!(
+				<InstanceOwner<Test>>::get((definition_hash, give.edition_id, give.copy_id)),
+				Some(dd.account_id)
+			);
+		});
+	}
+
+	#[test]
+	fn migrate_to_v1_should_index_the_owners_of_existing_fragment_instances() {
+		new_test_ext().execute_with(|| {
+			let dd = DummyData::new();
+			let mint = dd.mint_non_unique;
+			let definition_hash = mint.definition.get_definition_id();
+			assert_ok!(upload(dd.account_id, &mint.definition.proto_fragment));
+			assert_ok!(create(dd.account_id, &mint.definition));
+			assert_ok!(mint_(dd.account_id, &mint));
+
+			// simulate a chain whose Fragment Instances were created before `InstanceOwner` existed
+			let _ = <InstanceOwner<Test>>::clear(u32::MAX, None);
+			StorageVersion::new(0).put::<FragmentsPallet>();
+
+			crate::migration::v1::MigrateToV1::<Test>::on_runtime_upgrade();
+
+			assert_eq!(FragmentsPallet::on_chain_storage_version(), StorageVersion::new(1));
+			for edition_id in 1..=mint.get_quantity() {
+				assert_eq!(
+					<InstanceOwner<Test>>::get((definition_hash, edition_id, 1)),
+					Some(dd.account_id)
+				);
+			}
+		});
+	}
+}
+
+mod expiration_tests {
+	use super::*;
+	use frame_support::{
+		traits::{Get, GetStorageVersion, OnRuntimeUpgrade, StorageVersion},
+		weights::Weight,
+	};
+
+	/// Mint one Fragment Instance (that expires at `expiring_at`) to each account of `recipients`
+	fn mint_expiring(
+		signer: <Test as frame_system::Config>::AccountId,
+		mint: &Mint,
+		recipients: Vec<<Test as frame_system::Config>::AccountId>,
+		expiring_at: u64,
+	) {
+		assert_ok!(upload(signer, &mint.definition.proto_fragment));
+		assert_ok!(create(signer, &mint.definition));
+		assert_ok!(FragmentsPallet::mint_to_many(
+			RuntimeOrigin::signed(signer),
+			mint.definition.get_definition_id(),
+			recipients.into_iter().map(|recipient| (recipient, 1)).collect(),
+			None,
+			Some(expiring_at),
+		));
+	}
+
+	#[test]
+	fn sweep_expirations_should_not_exceed_the_weight_budget() {
+		new_test_ext().execute_with(|| {
+			let dd = DummyData::new();
+			let mint = dd.mint_non_unique;
+			let definition_hash = mint.definition.get_definition_id();
+			let expiring_at = System::block_number() + 1;
+			mint_expiring(
+				dd.account_id,
+				&mint,
+				vec![dd.account_id, dd.account_id_second, dd.account_id_third],
+				expiring_at,
+			);
+
+			let base_weight = <() as WeightInfo>::sweep_expirations(0);
+			let instance_weight =
+				<() as WeightInfo>::sweep_expirations(1).saturating_sub(base_weight);
+			let block_weight = <Test as frame_system::Config>::DbWeight::get().reads(1);
+
+			// enough weight to sweep only 2 of the 3 expired Fragment Instances
+			let max_weight = base_weight
+				.saturating_add(block_weight)
+				.saturating_add(instance_weight.saturating_mul(2));
+			let weight = FragmentsPallet::sweep_expirations(expiring_at, max_weight);
+
+			assert!(weight.all_lte(max_weight));
+			assert_eq!(<ExpirationQueue<Test>>::iter_key_prefix(expiring_at).count(), 1);
+			assert_eq!(<ExpirationCursor<Test>>::get(), expiring_at);
+
+			FragmentsPallet::sweep_expirations(expiring_at, Weight::MAX);
+
+			assert_eq!(<ExpirationQueue<Test>>::iter_key_prefix(expiring_at).count(), 0);
+			assert_eq!(<ExpirationCursor<Test>>::get(), expiring_at + 1);
+			for edition_id in 1..=3 {
+				assert!(<Fragments<Test>>::get((definition_hash, edition_id, 1)).is_none());
+				assert!(<InstanceOwner<Test>>::get((definition_hash, edition_id, 1)).is_none());
+			}
+		});
+	}
+
+	#[test]
+	fn sweep_expirations_should_not_sweep_fragment_instances_that_have_not_expired() {
+		new_test_ext().execute_with(|| {
+			let dd = DummyData::new();
+			let mint = dd.mint_non_unique;
+			let definition_hash = mint.definition.get_definition_id();
+			let expiring_at = System::block_number() + 10;
+			mint_expiring(dd.account_id, &mint, vec![dd.account_id], expiring_at);
+
+			FragmentsPallet::sweep_expirations(expiring_at - 1, Weight::MAX);
+
+			assert!(<Fragments<Test>>::get((definition_hash, 1, 1)).is_some());
+			assert!(<ExpirationQueue<Test>>::contains_key(expiring_at, (definition_hash, 1, 1)));
+			assert_eq!(<ExpirationCursor<Test>>::get(), expiring_at);
+		});
+	}
+
+	#[test]
+	fn give_should_not_work_if_the_fragment_instance_has_expired_but_has_not_been_swept() {
+		new_test_ext().execute_with(|| {
+			let dd = DummyData::new();
+			let mint = dd.mint_non_unique;
+			let definition_hash = mint.definition.get_definition_id();
+			let expiring_at = System::block_number() + 1;
+			mint_expiring(dd.account_id, &mint, vec![dd.account_id], expiring_at);
+
+			// no sweep happens
+			System::set_block_number(expiring_at);
+			assert!(<Fragments<Test>>::get((definition_hash, 1, 1)).is_some());
+
+			assert_noop!(
+				FragmentsPallet::give(
+					RuntimeOrigin::signed(dd.account_id),
+					definition_hash,
+					1,
+					1,
+					dd.account_id_second,
+					None,
+					None,
+				),
+				Error::<Test>::NotFound
+			);
+		});
+	}
+
+	#[test]
+	fn get_instances_should_not_return_fragment_instances_that_have_expired_but_have_not_been_swept(
+	) {
+		new_test_ext().execute_with(|| {
+			let dd = DummyData::new();
+			let mint = dd.mint_non_unique;
+			let definition_hash = mint.definition.get_definition_id();
+			let expiring_at = System::block_number() + 1;
+			mint_expiring(dd.account_id, &mint, vec![dd.account_id], expiring_at);
+
+			// no sweep happens
+			System::set_block_number(expiring_at);
+
+			assert_eq!(
+				serde_json::from_slice::<Value>(
+					&FragmentsPallet::get_instances(GetInstancesParams {
+						definition_hash: hex::encode(definition_hash).into_bytes(),
+						limit: u64::MAX,
+						..Default::default()
+					})
+					.unwrap()
+				)
+				.unwrap(),
+				json!(Map::new())
+			);
+		});
+	}
+
+	#[test]
+	fn migrate_to_v2_should_move_the_expirations_into_the_expiration_queue() {
+		new_test_ext().execute_with(|| {
+			let dd = DummyData::new();
+			let definition_hash = dd.mint_non_unique.definition.get_definition_id();
+			let expiring_at = System::block_number() + 10;
+
+			crate::migration::v2::Expirations::<Test>::insert(
+				expiring_at,
+				vec![
+					(definition_hash, Compact(1), Compact(1)),
+					(definition_hash, Compact(2), Compact(1)),
+				],
+			);
+			StorageVersion::new(1).put::<FragmentsPallet>();
+
+			crate::migration::v2::MigrateToV2::<Test>::on_runtime_upgrade();
+
+			assert_eq!(FragmentsPallet::on_chain_storage_version(), StorageVersion::new(2));
+			assert!(!crate::migration::v2::Expirations::<Test>::contains_key(expiring_at));
+			assert!(<ExpirationQueue<Test>>::contains_key(expiring_at, (definition_hash, 1, 1)));
+			assert!(<ExpirationQueue<Test>>::contains_key(expiring_at, (definition_hash, 2, 1)));
+			assert_eq!(<ExpirationCursor<Test>>::get(), System::block_number());
+		});
+	}
+}
+
+mod renewal_tests {
+	use super::*;
+
+	/// Publish the Fragment Definition of `buy` and mint one Fragment Instance (that expires at `expiring_at`) to `signer`
+	fn mint_expiring_published(
+		signer: <Test as frame_system::Config>::AccountId,
+		buy: &Buy,
+		expiring_at: u64,
+	) {
+		assert_ok!(upload(signer, &buy.publish.definition.proto_fragment));
+		assert_ok!(create(signer, &buy.publish.definition));
+		assert_ok!(publish_(signer, &buy.publish));
+		assert_ok!(FragmentsPallet::mint_to_many(
+			RuntimeOrigin::signed(signer),
+			buy.publish.definition.get_definition_id(),
+			vec![(signer, 1)],
+			None,
+			Some(expiring_at),
+		));
+	}
+
+	#[test]
+	fn renew_should_work() {
+		new_test_ext().execute_with(|| {
+			let dd = DummyData::new();
+			let buy = dd.buy_non_unique;
+			let definition_hash = buy.publish.definition.get_definition_id();
+			let created_at = System::block_number();
+			let expiring_at = created_at + 10;
+			mint_expiring_published(dd.account_id, &buy, expiring_at);
+			fund(dd.account_id, 1_000_000);
+
+			let vault: <Test as frame_system::Config>::AccountId =
+				sp_fragnova::get_account_id(b"pallet-fragments-create", &definition_hash);
+			let vault_balance = Balances::free_balance(vault);
+
+			assert_ok!(FragmentsPallet::renew(
+				RuntimeOrigin::signed(dd.account_id),
+				definition_hash,
+				1,
+				1
+			));
+
+			let new_expiring_at = expiring_at + (expiring_at - created_at);
+			assert_eq!(
+				<Fragments<Test>>::get((definition_hash, 1, 1)).unwrap().expiring_at,
+				Some(new_expiring_at)
+			);
+			assert!(!<ExpirationQueue<Test>>::contains_key(expiring_at, (definition_hash, 1, 1)));
+			assert!(<ExpirationQueue<Test>>::contains_key(
+				new_expiring_at,
+				(definition_hash, 1, 1)
+			));
+			assert_eq!(Balances::free_balance(vault), vault_balance + buy.publish.price);
+
+			System::assert_last_event(
+				FragmentsEvent::Renewed {
+					definition_hash,
+					fragment_id: (1, 1),
+					expiring_at: new_expiring_at,
+				}
+				.into(),
+			);
+		});
+	}
+
+	#[test]
+	fn renew_should_not_work_if_the_fragment_instance_does_not_expire() {
+		new_test_ext().execute_with(|| {
+			let dd = DummyData::new();
+			let buy = dd.buy_non_unique;
+			let definition_hash = buy.publish.definition.get_definition_id();
+			assert_ok!(upload(dd.account_id, &buy.publish.definition.proto_fragment));
+			assert_ok!(create(dd.account_id, &buy.publish.definition));
+			assert_ok!(publish_(dd.account_id, &buy.publish));
+			assert_ok!(FragmentsPallet::mint_to_many(
+				RuntimeOrigin::signed(dd.account_id),
+				definition_hash,
+				vec![(dd.account_id, 1)],
+				None,
+				None,
+			));
+			fund(dd.account_id, 1_000_000);
+
+			assert_noop!(
+				FragmentsPallet::renew(RuntimeOrigin::signed(dd.account_id), definition_hash, 1, 1),
+				Error::<Test>::NotRenewable
+			);
+		});
+	}
+
+	#[test]
+	fn renew_should_not_work_if_the_fragment_definition_is_not_published() {
+		new_test_ext().execute_with(|| {
+			let dd = DummyData::new();
+			let buy = dd.buy_non_unique;
+			let definition_hash = buy.publish.definition.get_definition_id();
+			mint_expiring_published(dd.account_id, &buy, System::block_number() + 10);
+			assert_ok!(FragmentsPallet::unpublish(
+				RuntimeOrigin::signed(dd.account_id),
+				definition_hash
+			));
+			fund(dd.account_id, 1_000_000);
+
+			assert_noop!(
+				FragmentsPallet::renew(RuntimeOrigin::signed(dd.account_id), definition_hash, 1, 1),
+				Error::<Test>::NotRenewable
+			);
+		});
+	}
+
+	#[test]
+	fn renew_should_not_work_if_the_user_does_not_own_the_fragment_instance() {
+		new_test_ext().execute_with(|| {
+			let dd = DummyData::new();
+			let buy = dd.buy_non_unique;
+			let definition_hash = buy.publish.definition.get_definition_id();
+			mint_expiring_published(dd.account_id, &buy, System::block_number() + 10);
+			fund(dd.account_id_second, 1_000_000);
+
+			assert_noop!(
+				FragmentsPallet::renew(
+					RuntimeOrigin::signed(dd.account_id_second),
+					definition_hash,
+					1,
+					1
+				),
+				Error::<Test>::NoPermission
+			);
+		});
+	}
+}
+
+mod rental_tests {
+	use super::*;
+
+	fn rent_(
+		signer: <Test as frame_system::Config>::AccountId,
+		definition_hash: Hash128,
+		to: <Test as frame_system::Config>::AccountId,
+		until: u64,
+	) -> DispatchResult {
+		FragmentsPallet::rent(RuntimeOrigin::signed(signer), definition_hash, 1, 1, to, until)
+	}
+
+	fn give_to(
+		signer: <Test as frame_system::Config>::AccountId,
+		definition_hash: Hash128,
+		to: <Test as frame_system::Config>::AccountId,
+	) -> DispatchResult {
+		FragmentsPallet::give(RuntimeOrigin::signed(signer), definition_hash, 1, 1, to, None, None)
+	}
+
+	#[test]
+	fn rent_should_work() {
+		new_test_ext().execute_with(|| {
+			let dd = DummyData::new();
+			let mint = dd.mint_non_unique;
+			let definition_hash = mint.definition.get_definition_id();
+			assert_ok!(upload(dd.account_id, &mint.definition.proto_fragment));
+			assert_ok!(create(dd.account_id, &mint.definition));
+			assert_ok!(mint_(dd.account_id, &mint));
+
+			let until = System::block_number() + 10;
+			assert_ok!(rent_(dd.account_id, definition_hash, dd.account_id_second, until));
+
+			assert_eq!(
+				<Rentals<Test>>::get((definition_hash, 1, 1)),
+				Some(Rental { renter: dd.account_id_second, until })
+			);
+			assert_eq!(
+				FragmentsPallet::get_instance_user(&definition_hash, 1, 1),
+				Some(dd.account_id_second)
+			);
+			// the owner is unchanged
+			assert_eq!(<InstanceOwner<Test>>::get((definition_hash, 1, 1)), Some(dd.account_id));
+
+			System::assert_last_event(
+				FragmentsEvent::Rented {
+					definition_hash,
+					fragment_id: (1, 1),
+					renter: dd.account_id_second,
+					until,
+				}
+				.into(),
+			);
+		});
+	}
+
+	#[test]
+	fn give_should_not_work_if_the_fragment_instance_is_rented_out() {
+		new_test_ext().execute_with(|| {
+			let dd = DummyData::new();
+			let mint = dd.mint_non_unique;
+			let definition_hash = mint.definition.get_definition_id();
+			assert_ok!(upload(dd.account_id, &mint.definition.proto_fragment));
+			assert_ok!(create(dd.account_id, &mint.definition));
+			assert_ok!(mint_(dd.account_id, &mint));
+			assert_ok!(rent_(
+				dd.account_id,
+				definition_hash,
+				dd.account_id_second,
+				System::block_number() + 10
+			));
+
+			assert_noop!(
+				give_to(dd.account_id, definition_hash, dd.account_id_third),
+				Error::<Test>::Rented
+			);
+			assert_noop!(
+				give_to(dd.account_id_second, definition_hash, dd.account_id_third),
+				Error::<Test>::NoPermission
+			);
+		});
+	}
+
+	#[test]
+	fn use_rights_should_return_to_the_owner_once_the_rental_ends() {
+		new_test_ext().execute_with(|| {
+			let dd = DummyData::new();
+			let mint = dd.mint_non_unique;
+			let definition_hash = mint.definition.get_definition_id();
+			assert_ok!(upload(dd.account_id, &mint.definition.proto_fragment));
+			assert_ok!(create(dd.account_id, &mint.definition));
+			assert_ok!(mint_(dd.account_id, &mint));
+
+			let until = System::block_number() + 10;
+			assert_ok!(rent_(dd.account_id, definition_hash, dd.account_id_second, until));
+
+			System::set_block_number(until);
+
+			assert_eq!(
+				FragmentsPallet::get_instance_user(&definition_hash, 1, 1),
+				Some(dd.account_id)
+			);
+			assert_ok!(give_to(dd.account_id, definition_hash, dd.account_id_third));
+			// the stale rental is cleaned up on transfer
+			assert!(!<Rentals<Test>>::contains_key((definition_hash, 1, 1)));
+		});
+	}
+
+	#[test]
+	fn end_rental_should_work() {
+		new_test_ext().execute_with(|| {
+			let dd = DummyData::new();
+			let mint = dd.mint_non_unique;
+			let definition_hash = mint.definition.get_definition_id();
+			assert_ok!(upload(dd.account_id, &mint.definition.proto_fragment));
+			assert_ok!(create(dd.account_id, &mint.definition));
+			assert_ok!(mint_(dd.account_id, &mint));
+			assert_ok!(rent_(
+				dd.account_id,
+				definition_hash,
+				dd.account_id_second,
+				System::block_number() + 10
+			));
+
+			assert_noop!(
+				FragmentsPallet::end_rental(
+					RuntimeOrigin::signed(dd.account_id),
+					definition_hash,
+					1,
+					1
+				),
+				Error::<Test>::NoPermission
+			);
+			assert_ok!(FragmentsPallet::end_rental(
+				RuntimeOrigin::signed(dd.account_id_second),
+				definition_hash,
+				1,
+				1
+			));
+
+			assert!(!<Rentals<Test>>::contains_key((definition_hash, 1, 1)));
+			assert_eq!(
+				FragmentsPallet::get_instance_user(&definition_hash, 1, 1),
+				Some(dd.account_id)
+			);
+			assert_ok!(give_to(dd.account_id, definition_hash, dd.account_id_third));
+		});
+	}
+
+	#[test]
+	fn end_rental_should_not_work_if_the_fragment_instance_is_not_rented_out() {
+		new_test_ext().execute_with(|| {
+			let dd = DummyData::new();
+			let mint = dd.mint_non_unique;
+			let definition_hash = mint.definition.get_definition_id();
+			assert_ok!(upload(dd.account_id, &mint.definition.proto_fragment));
+			assert_ok!(create(dd.account_id, &mint.definition));
+			assert_ok!(mint_(dd.account_id, &mint));
+
+			assert_noop!(
+				FragmentsPallet::end_rental(
+					RuntimeOrigin::signed(dd.account_id),
+					definition_hash,
+					1,
+					1
+				),
+				Error::<Test>::NotRented
+			);
+		});
+	}
+}
+
+mod approval_tests {
+	use super::*;
+
+	fn mint_one(signer: <Test as frame_system::Config>::AccountId, mint: &Mint) -> Hash128 {
+		assert_ok!(upload(signer, &mint.definition.proto_fragment));
+		assert_ok!(create(signer, &mint.definition));
+		assert_ok!(mint_(signer, &mint));
+		mint.definition.get_definition_id()
+	}
+
+	fn approve_(
+		signer: <Test as frame_system::Config>::AccountId,
+		definition_hash: Hash128,
+		operator: Option<<Test as frame_system::Config>::AccountId>,
+	) -> DispatchResult {
+		FragmentsPallet::approve(RuntimeOrigin::signed(signer), definition_hash, 1, 1, operator)
+	}
+
+	fn give_to(
+		signer: <Test as frame_system::Config>::AccountId,
+		definition_hash: Hash128,
+		to: <Test as frame_system::Config>::AccountId,
+	) -> DispatchResult {
+		FragmentsPallet::give(RuntimeOrigin::signed(signer), definition_hash, 1, 1, to, None, None)
+	}
+
+	#[test]
+	fn approve_should_work() {
+		new_test_ext().execute_with(|| {
+			let dd = DummyData::new();
+			let definition_hash = mint_one(dd.account_id, &dd.mint_non_unique);
+
+			assert_ok!(approve_(dd.account_id, definition_hash, Some(dd.account_id_second)));
+
+			assert_eq!(
+				<InstanceApprovals<Test>>::get((definition_hash, 1, 1)),
+				Some(dd.account_id_second)
+			);
+			System::assert_last_event(
+				FragmentsEvent::Approval {
+					definition_hash,
+					fragment_id: (1, 1),
+					owner: dd.account_id,
+					operator: Some(dd.account_id_second),
+				}
+				.into(),
+			);
+
+			// revoke the approval
+			assert_ok!(approve_(dd.account_id, definition_hash, None));
+			assert!(!<InstanceApprovals<Test>>::contains_key((definition_hash, 1, 1)));
+		});
+	}
+
+	#[test]
+	fn approve_should_not_work_if_the_user_does_not_own_the_fragment_instance() {
+		new_test_ext().execute_with(|| {
+			let dd = DummyData::new();
+			let definition_hash = mint_one(dd.account_id, &dd.mint_non_unique);
+
+			assert_noop!(
+				approve_(dd.account_id_second, definition_hash, Some(dd.account_id_second)),
+				Error::<Test>::NoPermission
+			);
+		});
+	}
+
+	#[test]
+	fn give_should_work_if_the_user_is_approved() {
+		new_test_ext().execute_with(|| {
+			let dd = DummyData::new();
+			let definition_hash = mint_one(dd.account_id, &dd.mint_non_unique);
+			assert_ok!(approve_(dd.account_id, definition_hash, Some(dd.account_id_second)));
+
+			assert_ok!(give_to(dd.account_id_second, definition_hash, dd.account_id_third));
+
+			assert_eq!(
+				<InstanceOwner<Test>>::get((definition_hash, 1, 1)),
+				Some(dd.account_id_third)
+			);
+			assert!(<Inventory<Test>>::get(dd.account_id_third, definition_hash)
+				.unwrap()
+				.contains(&(Compact(1), Compact(1))));
+			assert!(!<Inventory<Test>>::get(dd.account_id, definition_hash)
+				.unwrap()
+				.contains(&(Compact(1), Compact(1))));
+			// the approval is cleared on transfer
+			assert!(!<InstanceApprovals<Test>>::contains_key((definition_hash, 1, 1)));
+			assert_noop!(
+				give_to(dd.account_id_second, definition_hash, dd.account_id),
+				Error::<Test>::NoPermission
+			);
+		});
+	}
+
+	#[test]
+	fn give_should_work_if_the_user_is_approved_for_all() {
+		new_test_ext().execute_with(|| {
+			let dd = DummyData::new();
+			let definition_hash = mint_one(dd.account_id, &dd.mint_non_unique);
+			assert_ok!(FragmentsPallet::set_approval_for_all(
+				RuntimeOrigin::signed(dd.account_id),
+				definition_hash,
+				dd.account_id_second,
+				true
+			));
+			System::assert_last_event(
+				FragmentsEvent::ApprovalForAll {
+					definition_hash,
+					owner: dd.account_id,
+					operator: dd.account_id_second,
+					approved: true,
+				}
+				.into(),
+			);
+
+			assert_ok!(give_to(dd.account_id_second, definition_hash, dd.account_id_third));
+
+			assert_eq!(
+				<InstanceOwner<Test>>::get((definition_hash, 1, 1)),
+				Some(dd.account_id_third)
+			);
+			// the approval for all is only valid for the Fragment Instances of the owner
+			assert_noop!(
+				give_to(dd.account_id_second, definition_hash, dd.account_id),
+				Error::<Test>::NoPermission
+			);
+		});
+	}
+
+	#[test]
+	fn give_should_not_work_if_the_approval_for_all_was_revoked() {
+		new_test_ext().execute_with(|| {
+			let dd = DummyData::new();
+			let definition_hash = mint_one(dd.account_id, &dd.mint_non_unique);
+			assert_ok!(FragmentsPallet::set_approval_for_all(
+				RuntimeOrigin::signed(dd.account_id),
+				definition_hash,
+				dd.account_id_second,
+				true
+			));
+			assert_ok!(FragmentsPallet::set_approval_for_all(
+				RuntimeOrigin::signed(dd.account_id),
+				definition_hash,
+				dd.account_id_second,
+				false
+			));
+
+			assert_noop!(
+				give_to(dd.account_id_second, definition_hash, dd.account_id_third),
+				Error::<Test>::NoPermission
+			);
+		});
+	}
+
+	#[test]
+	fn give_should_not_work_if_the_user_is_not_approved() {
+		new_test_ext().execute_with(|| {
+			let dd = DummyData::new();
+			let definition_hash = mint_one(dd.account_id, &dd.mint_non_unique);
+			assert_ok!(approve_(dd.account_id, definition_hash, Some(dd.account_id_second)));
+
+			assert_noop!(
+				give_to(dd.account_id_third, definition_hash, dd.account_id_third),
+				Error::<Test>::NoPermission
+			);
+		});
+	}
+
+	#[test]
+	fn burn_should_clear_the_approval_of_the_fragment_instance() {
+		new_test_ext().execute_with(|| {
+			let dd = DummyData::new();
+			let definition_hash = mint_one(dd.account_id, &dd.mint_non_unique);
+			assert_ok!(approve_(dd.account_id, definition_hash, Some(dd.account_id_second)));
+
+			assert_ok!(FragmentsPallet::burn(
+				RuntimeOrigin::signed(dd.account_id),
+				definition_hash,
+				1,
+				1
+			));
+
+			assert!(!<InstanceApprovals<Test>>::contains_key((definition_hash, 1, 1)));
+		});
+	}
+}
+
+mod swap_tests {
+	use super::*;
+
+	/// Mint the Fragment Instances `(1, 1)` and `(2, 1)` of `mint`'s Fragment Definition,
+	/// and give the second one to `taker`
+	fn mint_two(
+		maker: <Test as frame_system::Config>::AccountId,
+		taker: <Test as frame_system::Config>::AccountId,
+		mint: &Mint,
+	) -> Hash128 {
+		let definition_hash = mint.definition.get_definition_id();
+		assert_ok!(upload(maker, &mint.definition.proto_fragment));
+		assert_ok!(create(maker, &mint.definition));
+		assert_ok!(mint_(
+			maker,
+			&Mint { buy_options: FragmentBuyOptions::Quantity(2), ..mint.clone() }
+		));
+		assert_ok!(FragmentsPallet::give(
+			RuntimeOrigin::signed(maker),
+			definition_hash,
+			2,
+			1,
+			taker,
+			None,
+			None
+		));
+		definition_hash
+	}
+
+	fn bundle(
+		instances: Vec<(Hash128, InstanceUnit, InstanceUnit)>,
+		protos: Vec<Hash256>,
+		amount: Option<u128>,
+	) -> SwapBundle<u64> {
+		SwapBundle { instances, protos, amount: amount.map(|amount| (Currency::Native, amount)) }
+	}
+
+	fn propose_swap_(
+		maker: <Test as frame_system::Config>::AccountId,
+		taker: <Test as frame_system::Config>::AccountId,
+		offer: SwapBundle<u64>,
+		ask: SwapBundle<u64>,
+		expiration: u64,
+	) -> DispatchResult {
+		FragmentsPallet::propose_swap(RuntimeOrigin::signed(maker), taker, offer, ask, expiration)
+	}
+
+	fn give_to(
+		signer: <Test as frame_system::Config>::AccountId,
+		definition_hash: Hash128,
+		edition_id: InstanceUnit,
+		to: <Test as frame_system::Config>::AccountId,
+	) -> DispatchResult {
+		FragmentsPallet::give(
+			RuntimeOrigin::signed(signer),
+			definition_hash,
+			edition_id,
+			1,
+			to,
+			None,
+			None,
+		)
+	}
+
+	#[test]
+	fn propose_swap_should_work() {
+		new_test_ext().execute_with(|| {
+			let dd = DummyData::new();
+			let (maker, taker) = (dd.account_id, dd.account_id_second);
+			let definition_hash = mint_two(maker, taker, &dd.mint_non_unique);
+			let expiration = System::block_number() + 10;
+
+			assert_ok!(propose_swap_(
+				maker,
+				taker,
+				bundle(vec![(definition_hash, 1, 1)], vec![], None),
+				bundle(vec![(definition_hash, 2, 1)], vec![], None),
+				expiration,
+			));
+
+			assert_eq!(
+				<Swaps<Test>>::get(0),
+				Some(Swap {
+					maker,
+					taker,
+					offer: bundle(vec![(definition_hash, 1, 1)], vec![], None),
+					ask: bundle(vec![(definition_hash, 2, 1)], vec![], None),
+					expiration,
+				})
+			);
+			assert_eq!(<NextSwapId<Test>>::get(), 1);
+			assert_eq!(<LockedInstances<Test>>::get((definition_hash, 1, 1)), Some(expiration));
+			System::assert_last_event(
+				FragmentsEvent::SwapProposed { swap_id: 0, maker, taker }.into(),
+			);
+
+			// the offered Fragment Instance is locked
+			assert_noop!(
+				give_to(maker, definition_hash, 1, dd.account_id_third),
+				Error::<Test>::Locked
+			);
+			// and it cannot be offered in another swap
+			assert_noop!(
+				propose_swap_(
+					maker,
+					dd.account_id_third,
+					bundle(vec![(definition_hash, 1, 1)], vec![], None),
+					bundle(vec![], vec![], None),
+					expiration,
+				),
+				Error::<Test>::Locked
+			);
+		});
+	}
+
+	#[test]
+	fn propose_swap_should_not_work_if_the_user_does_not_own_the_offered_fragment_instance() {
+		new_test_ext().execute_with(|| {
+			let dd = DummyData::new();
+			let (maker, taker) = (dd.account_id, dd.account_id_second);
+			let definition_hash = mint_two(maker, taker, &dd.mint_non_unique);
+
+			assert_noop!(
+				propose_swap_(
+					maker,
+					taker,
+					bundle(vec![(definition_hash, 2, 1)], vec![], None),
+					bundle(vec![], vec![], None),
+					System::block_number() + 10,
+				),
+				Error::<Test>::NoPermission
+			);
+		});
+	}
+
+	#[test]
+	fn propose_swap_should_not_work_if_a_side_has_too_many_items() {
+		new_test_ext().execute_with(|| {
+			let dd = DummyData::new();
+			let (maker, taker) = (dd.account_id, dd.account_id_second);
+			let definition_hash = mint_two(maker, taker, &dd.mint_non_unique);
+
+			assert_noop!(
+				propose_swap_(
+					maker,
+					taker,
+					bundle(vec![(definition_hash, 1, 1)], vec![], None),
+					bundle(
+						(1..=MAX_SWAP_ITEMS as u64 + 1)
+							.map(|edition_id| (definition_hash, edition_id, 1))
+							.collect(),
+						vec![],
+						None
+					),
+					System::block_number() + 10,
+				),
+				Error::<Test>::TooManySwapItems
+			);
+		});
+	}
+
+	#[test]
+	fn accept_swap_should_work() {
+		new_test_ext().execute_with(|| {
+			let dd = DummyData::new();
+			let (maker, taker) = (dd.account_id, dd.account_id_second);
+			let mint = dd.mint_non_unique;
+			let definition_hash = mint_two(maker, taker, &mint);
+			let proto_hash = mint.definition.proto_fragment.get_proto_hash();
+			fund(maker, 1_000_000);
+			fund(taker, 1_000_000);
+
+			assert_ok!(propose_swap_(
+				maker,
+				taker,
+				bundle(vec![(definition_hash, 1, 1)], vec![proto_hash], None),
+				bundle(vec![(definition_hash, 2, 1)], vec![], Some(100)),
+				System::block_number() + 10,
+			));
+			assert!(pallet_protos::Pallet::<Test>::is_locked(&proto_hash));
+
+			let maker_balance = Balances::free_balance(maker);
+			let taker_balance = Balances::free_balance(taker);
+
+			assert_ok!(FragmentsPallet::accept_swap(RuntimeOrigin::signed(taker), 0));
+
+			assert_eq!(<InstanceOwner<Test>>::get((definition_hash, 1, 1)), Some(taker));
+			assert_eq!(<InstanceOwner<Test>>::get((definition_hash, 2, 1)), Some(maker));
+			assert_eq!(
+				pallet_protos::Protos::<Test>::get(proto_hash).unwrap().owner,
+				pallet_protos::ProtoOwner::User(taker)
+			);
+			assert_eq!(Balances::free_balance(maker), maker_balance + 100);
+			assert_eq!(Balances::free_balance(taker), taker_balance - 100);
+
+			assert!(!<Swaps<Test>>::contains_key(0));
+			assert!(!<LockedInstances<Test>>::contains_key((definition_hash, 1, 1)));
+			assert!(!pallet_protos::Pallet::<Test>::is_locked(&proto_hash));
+			System::assert_last_event(FragmentsEvent::SwapAccepted { swap_id: 0 }.into());
+		});
+	}
+
+	#[test]
+	fn accept_swap_should_not_work_if_the_user_is_not_the_taker() {
+		new_test_ext().execute_with(|| {
+			let dd = DummyData::new();
+			let (maker, taker) = (dd.account_id, dd.account_id_second);
+			let definition_hash = mint_two(maker, taker, &dd.mint_non_unique);
+			assert_ok!(propose_swap_(
+				maker,
+				taker,
+				bundle(vec![(definition_hash, 1, 1)], vec![], None),
+				bundle(vec![(definition_hash, 2, 1)], vec![], None),
+				System::block_number() + 10,
+			));
+
+			assert_noop!(
+				FragmentsPallet::accept_swap(RuntimeOrigin::signed(dd.account_id_third), 0),
+				Error::<Test>::NoPermission
+			);
+		});
+	}
+
+	#[test]
+	fn accept_swap_should_not_work_if_the_taker_does_not_own_the_asked_fragment_instance() {
+		new_test_ext().execute_with(|| {
+			let dd = DummyData::new();
+			let (maker, taker) = (dd.account_id, dd.account_id_second);
+			let definition_hash = mint_two(maker, taker, &dd.mint_non_unique);
+			assert_ok!(propose_swap_(
+				maker,
+				taker,
+				bundle(vec![(definition_hash, 1, 1)], vec![], None),
+				bundle(vec![(definition_hash, 2, 1)], vec![], None),
+				System::block_number() + 10,
+			));
+			// the taker gives away the asked Fragment Instance in the meantime
+			assert_ok!(give_to(taker, definition_hash, 2, dd.account_id_third));
+
+			assert_noop!(
+				FragmentsPallet::accept_swap(RuntimeOrigin::signed(taker), 0),
+				Error::<Test>::NoPermission
+			);
+		});
+	}
+
+	#[test]
+	fn accept_swap_should_not_work_if_the_swap_has_expired() {
+		new_test_ext().execute_with(|| {
+			let dd = DummyData::new();
+			let (maker, taker) = (dd.account_id, dd.account_id_second);
+			let definition_hash = mint_two(maker, taker, &dd.mint_non_unique);
+			let expiration = System::block_number() + 10;
+			assert_ok!(propose_swap_(
+				maker,
+				taker,
+				bundle(vec![(definition_hash, 1, 1)], vec![], None),
+				bundle(vec![(definition_hash, 2, 1)], vec![], None),
+				expiration,
+			));
+
+			System::set_block_number(expiration);
+
+			assert_noop!(
+				FragmentsPallet::accept_swap(RuntimeOrigin::signed(taker), 0),
+				Error::<Test>::Expired
+			);
+			// the lock of the offered Fragment Instance ends with the swap
+			assert_ok!(give_to(maker, definition_hash, 1, dd.account_id_third));
+			// anyone can cancel an expired swap
+			assert_ok!(FragmentsPallet::cancel_swap(RuntimeOrigin::signed(taker), 0));
+			assert!(!<Swaps<Test>>::contains_key(0));
+		});
+	}
+
+	#[test]
+	fn burn_and_consume_should_not_work_if_the_fragment_instance_is_offered_in_a_pending_swap() {
+		new_test_ext().execute_with(|| {
+			let dd = DummyData::new();
+			let (maker, taker) = (dd.account_id, dd.account_id_second);
+			let definition_hash = mint_two(maker, taker, &dd.mint_non_unique);
+			assert_ok!(propose_swap_(
+				maker,
+				taker,
+				bundle(vec![(definition_hash, 1, 1)], vec![], None),
+				bundle(vec![(definition_hash, 2, 1)], vec![], None),
+				System::block_number() + 10,
+			));
+
+			assert_noop!(
+				FragmentsPallet::burn(RuntimeOrigin::signed(maker), definition_hash, 1, 1),
+				Error::<Test>::Locked
+			);
+			assert_noop!(
+				FragmentsPallet::consume(RuntimeOrigin::signed(maker), definition_hash, 1, 1, 1),
+				Error::<Test>::Locked
+			);
+
+			// the offered Fragment Instance can be edited again once the swap is cancelled
+			assert_ok!(FragmentsPallet::cancel_swap(RuntimeOrigin::signed(maker), 0));
+			assert_ok!(FragmentsPallet::burn(RuntimeOrigin::signed(maker), definition_hash, 1, 1));
+		});
+	}
+
+	#[test]
+	fn cancel_swap_should_work() {
+		new_test_ext().execute_with(|| {
+			let dd = DummyData::new();
+			let (maker, taker) = (dd.account_id, dd.account_id_second);
+			let definition_hash = mint_two(maker, taker, &dd.mint_non_unique);
+			assert_ok!(propose_swap_(
+				maker,
+				taker,
+				bundle(vec![(definition_hash, 1, 1)], vec![], None),
+				bundle(vec![(definition_hash, 2, 1)], vec![], None),
+				System::block_number() + 10,
+			));
+
+			assert_noop!(
+				FragmentsPallet::cancel_swap(RuntimeOrigin::signed(taker), 0),
+				Error::<Test>::NoPermission
+			);
+			assert_ok!(FragmentsPallet::cancel_swap(RuntimeOrigin::signed(maker), 0));
+
+			assert!(!<Swaps<Test>>::contains_key(0));
+			assert!(!<LockedInstances<Test>>::contains_key((definition_hash, 1, 1)));
+			System::assert_last_event(FragmentsEvent::SwapCancelled { swap_id: 0 }.into());
+			assert_ok!(give_to(maker, definition_hash, 1, dd.account_id_third));
+		});
+	}
+}
+
+mod nesting_tests {
+	use super::*;
+
+	/// Mint the Fragment Instances `(1, 1)`, ..., `(quantity, 1)` of `mint`'s Fragment Definition
+	fn mint_many(
+		signer: <Test as frame_system::Config>::AccountId,
+		mint: &Mint,
+		quantity: u64,
+	) -> Hash128 {
+		assert_ok!(upload(signer, &mint.definition.proto_fragment));
+		assert_ok!(create(signer, &mint.definition));
+		assert_ok!(mint_(
+			signer,
+			&Mint { buy_options: FragmentBuyOptions::Quantity(quantity), ..mint.clone() }
+		));
+		mint.definition.get_definition_id()
+	}
+
+	fn nest_(
+		signer: <Test as frame_system::Config>::AccountId,
+		definition_hash: Hash128,
+		edition_id: InstanceUnit,
+		parent_edition_id: InstanceUnit,
+	) -> DispatchResult {
+		FragmentsPallet::nest(
+			RuntimeOrigin::signed(signer),
+			definition_hash,
+			edition_id,
+			1,
+			(definition_hash, parent_edition_id, 1),
+		)
+	}
+
+	fn unnest_(
+		signer: <Test as frame_system::Config>::AccountId,
+		definition_hash: Hash128,
+		edition_id: InstanceUnit,
+	) -> DispatchResult {
+		FragmentsPallet::unnest(RuntimeOrigin::signed(signer), definition_hash, edition_id, 1)
+	}
+
+	#[test]
+	fn nest_should_work() {
+		new_test_ext().execute_with(|| {
+			let dd = DummyData::new();
+			let definition_hash = mint_many(dd.account_id, &dd.mint_non_unique, 2);
+
+			assert_ok!(nest_(dd.account_id, definition_hash, 1, 2));
+
+			let parent_account = FragmentsPallet::get_fragment_account_id(definition_hash, 2, 1);
+			assert_eq!(<InstanceOwner<Test>>::get((definition_hash, 1, 1)), Some(parent_account));
+			assert_eq!(
+				<Inventory<Test>>::get(parent_account, definition_hash).unwrap(),
+				vec![(Compact(1), Compact(1))]
+			);
+			assert!(!<Inventory<Test>>::get(dd.account_id, definition_hash)
+				.unwrap()
+				.contains(&(Compact(1), Compact(1))));
+			assert_eq!(
+				<InstanceParent<Test>>::get((definition_hash, 1, 1)),
+				Some((definition_hash, 2, 1))
+			);
+			assert_eq!(
+				<InstanceChildren<Test>>::get((definition_hash, 2, 1)),
+				Some(vec![(definition_hash, 1, 1)])
+			);
+			System::assert_last_event(
+				FragmentsEvent::Nested {
+					definition_hash,
+					fragment_id: (1, 1),
+					parent: (definition_hash, 2, 1),
+				}
+				.into(),
+			);
+		});
+	}
+
+	#[test]
+	fn nest_should_not_work_if_the_user_does_not_own_the_parent() {
+		new_test_ext().execute_with(|| {
+			let dd = DummyData::new();
+			let definition_hash = mint_many(dd.account_id, &dd.mint_non_unique, 2);
+			assert_ok!(FragmentsPallet::give(
+				RuntimeOrigin::signed(dd.account_id),
+				definition_hash,
+				2,
+				1,
+				dd.account_id_second,
+				None,
+				None
+			));
+
+			assert_noop!(nest_(dd.account_id, definition_hash, 1, 2), Error::<Test>::NoPermission);
+		});
+	}
+
+	#[test]
+	fn nest_should_not_work_if_it_creates_a_cycle() {
+		new_test_ext().execute_with(|| {
+			let dd = DummyData::new();
+			let definition_hash = mint_many(dd.account_id, &dd.mint_non_unique, 2);
+
+			assert_noop!(nest_(dd.account_id, definition_hash, 1, 1), Error::<Test>::NestingCycle);
+
+			assert_ok!(nest_(dd.account_id, definition_hash, 1, 2));
+			assert_noop!(nest_(dd.account_id, definition_hash, 2, 1), Error::<Test>::NestingCycle);
+		});
+	}
+
+	#[test]
+	fn nest_should_not_work_if_the_nesting_is_too_deep() {
+		new_test_ext().execute_with(|| {
+			let dd = DummyData::new();
+			let quantity = MAX_NESTING_DEPTH as u64 + 2;
+			let definition_hash = mint_many(dd.account_id, &dd.mint_non_unique, quantity);
+
+			// nest `(1, 1)` in `(2, 1)`, then `(2, 1)` in `(3, 1)`, and so on
+			for edition_id in 1..quantity - 1 {
+				assert_ok!(nest_(dd.account_id, definition_hash, edition_id, edition_id + 1));
+			}
+			assert_eq!(
+				FragmentsPallet::get_nesting_height(&definition_hash, quantity - 1, 1),
+				MAX_NESTING_DEPTH
+			);
+
+			assert_noop!(
+				nest_(dd.account_id, definition_hash, quantity - 1, quantity),
+				Error::<Test>::NestingTooDeep
+			);
+		});
+	}
+
+	#[test]
+	fn nest_should_not_work_if_the_parent_is_copyable() {
+		new_test_ext().execute_with(|| {
+			let dd = DummyData::new();
+			let mut mint = dd.mint_non_unique;
+			mint.definition.permissions = FragmentPerms::TRANSFER | FragmentPerms::COPY;
+			let definition_hash = mint_many(dd.account_id, &mint, 2);
+
+			assert_noop!(nest_(dd.account_id, definition_hash, 1, 2), Error::<Test>::NotNestable);
+		});
+	}
+
+	#[test]
+	fn transferring_the_parent_should_carry_the_nested_instances() {
+		new_test_ext().execute_with(|| {
+			let dd = DummyData::new();
+			let definition_hash = mint_many(dd.account_id, &dd.mint_non_unique, 2);
+			assert_ok!(nest_(dd.account_id, definition_hash, 1, 2));
+
+			assert_ok!(FragmentsPallet::give(
+				RuntimeOrigin::signed(dd.account_id),
+				definition_hash,
+				2,
+				1,
+				dd.account_id_second,
+				None,
+				None
+			));
+
+			// the nested Fragment Instance is still owned by the account of its parent
+			assert_eq!(
+				<InstanceOwner<Test>>::get((definition_hash, 1, 1)),
+				Some(FragmentsPallet::get_fragment_account_id(definition_hash, 2, 1))
+			);
+			assert_noop!(unnest_(dd.account_id, definition_hash, 1), Error::<Test>::NoPermission);
+			assert_ok!(unnest_(dd.account_id_second, definition_hash, 1));
+			assert_eq!(
+				<InstanceOwner<Test>>::get((definition_hash, 1, 1)),
+				Some(dd.account_id_second)
+			);
+		});
+	}
+
+	#[test]
+	fn unnest_should_work() {
+		new_test_ext().execute_with(|| {
+			let dd = DummyData::new();
+			let definition_hash = mint_many(dd.account_id, &dd.mint_non_unique, 2);
+			assert_ok!(nest_(dd.account_id, definition_hash, 1, 2));
+
+			assert_ok!(unnest_(dd.account_id, definition_hash, 1));
+
+			assert_eq!(<InstanceOwner<Test>>::get((definition_hash, 1, 1)), Some(dd.account_id));
+			assert!(<Inventory<Test>>::get(dd.account_id, definition_hash)
+				.unwrap()
+				.contains(&(Compact(1), Compact(1))));
+			assert!(!<InstanceParent<Test>>::contains_key((definition_hash, 1, 1)));
+			assert!(!<InstanceChildren<Test>>::contains_key((definition_hash, 2, 1)));
+			System::assert_last_event(
+				FragmentsEvent::Unnested {
+					definition_hash,
+					fragment_id: (1, 1),
+					owner: dd.account_id,
+				}
+				.into(),
+			);
+		});
+	}
+
+	#[test]
+	fn unnest_should_not_work_if_the_fragment_instance_is_not_nested() {
+		new_test_ext().execute_with(|| {
+			let dd = DummyData::new();
+			let definition_hash = mint_many(dd.account_id, &dd.mint_non_unique, 1);
+
+			assert_noop!(unnest_(dd.account_id, definition_hash, 1), Error::<Test>::NotNested);
+		});
+	}
+
+	#[test]
+	fn burn_should_not_work_if_the_fragment_instance_has_nested_instances() {
+		new_test_ext().execute_with(|| {
+			let dd = DummyData::new();
+			let definition_hash = mint_many(dd.account_id, &dd.mint_non_unique, 2);
+			assert_ok!(nest_(dd.account_id, definition_hash, 1, 2));
+
+			assert_noop!(
+				FragmentsPallet::burn(RuntimeOrigin::signed(dd.account_id), definition_hash, 2, 1),
+				Error::<Test>::HasNestedInstances
+			);
+		});
+	}
+
+	#[test]
+	fn get_instances_should_work_if_return_nested_is_true() {
+		new_test_ext().execute_with(|| {
+			let dd = DummyData::new();
+			let definition_hash = mint_many(dd.account_id, &dd.mint_non_unique, 3);
+			assert_ok!(nest_(dd.account_id, definition_hash, 1, 2));
+			assert_ok!(nest_(dd.account_id, definition_hash, 2, 3));
+
+			assert_eq!(
+				serde_json::from_slice::<Value>(
+					&FragmentsPallet::get_instances(GetInstancesParams {
+						definition_hash: hex::encode(definition_hash).into_bytes(),
+						limit: u64::MAX,
+						owner: Some(dd.account_id),
+						return_nested: true,
+						..Default::default()
+					})
+					.unwrap()
+				)
+				.unwrap(),
+				json!({
+					"3.1": {
+						"nested": {
+							format!("{}.2.1", hex::encode(definition_hash)): {
+								"nested": {
+									format!("{}.1.1", hex::encode(definition_hash)): {
+										"nested": {}
+									}
+								}
+							}
+						}
+					}
+				})
+			);
+		});
+	}
+}
+
+mod recipe_tests {
+	use super::*;
+
+	/// Create the Fragment Definitions of the inputs and of the outputs of the recipes,
+	/// and give the Fragment Instances `(1, 1)`, ..., `(quantity, 1)` of the input Fragment Definition to `player`
+	fn setup(
+		dd: &DummyData,
+		quantity: u64,
+		stack_amount: Option<u64>,
+		player: <Test as frame_system::Config>::AccountId,
+	) -> (Hash128, Hash128) {
+		let mint = Mint { amount: stack_amount, ..dd.mint_non_unique.clone() };
+		let output_definition = Definition {
+			metadata: DefinitionMetadata {
+				name: b"Je suis un autre Nom".to_vec(),
+				..mint.definition.metadata.clone()
+			},
+			..mint.definition.clone()
+		};
+
+		assert_ok!(upload(dd.account_id, &mint.definition.proto_fragment));
+		assert_ok!(create(dd.account_id, &mint.definition));
+		assert_ok!(create(dd.account_id, &output_definition));
+		assert_ok!(mint_(
+			dd.account_id,
+			&Mint { buy_options: FragmentBuyOptions::Quantity(quantity), ..mint.clone() }
+		));
+
+		let input_hash = mint.definition.get_definition_id();
+		if player != dd.account_id {
+			for edition_id in 1..=quantity {
+				assert_ok!(FragmentsPallet::give(
+					RuntimeOrigin::signed(dd.account_id),
+					input_hash,
+					edition_id,
+					1,
+					player,
+					None,
+					None,
+				));
+			}
+		}
+
+		(input_hash, output_definition.get_definition_id())
+	}
+
+	fn register_recipe_(
+		signer: <Test as frame_system::Config>::AccountId,
+		input: RecipeInput,
+		output: RecipeOutput,
+		fee: Option<(Currency<u64>, u128)>,
+	) -> DispatchResult {
+		FragmentsPallet::register_recipe(
+			RuntimeOrigin::signed(signer),
+			vec![input],
+			vec![output],
+			fee,
+		)
+	}
+
+	fn craft_(
+		signer: <Test as frame_system::Config>::AccountId,
+		input_hash: Hash128,
+		edition_ids: Vec<InstanceUnit>,
+	) -> DispatchResult {
+		FragmentsPallet::craft(
+			RuntimeOrigin::signed(signer),
+			0,
+			edition_ids.into_iter().map(|edition_id| (input_hash, edition_id, 1)).collect(),
+		)
+	}
+
+	#[test]
+	fn register_recipe_should_work() {
+		new_test_ext().execute_with(|| {
+			let dd = DummyData::new();
+			let (input_hash, output_hash) = setup(&dd, 2, None, dd.account_id);
+
+			let input =
+				RecipeInput { definition_hash: input_hash, amount: RecipeInputAmount::Quantity(2) };
+			let output =
+				RecipeOutput { definition_hash: output_hash, quantity: 1, stack_amount: None };
+			assert_ok!(register_recipe_(
+				dd.account_id,
+				input.clone(),
+				output.clone(),
+				Some((Currency::Native, 1_000))
+			));
+
+			assert_eq!(
+				<Recipes<Test>>::get(0),
+				Some(Recipe {
+					owner: dd.account_id,
+					inputs: vec![input],
+					outputs: vec![output],
+					fee: Some((Currency::Native, 1_000)),
+					enabled: true,
+				})
+			);
+			assert_eq!(<NextRecipeId<Test>>::get(), 1);
+			System::assert_last_event(
+				FragmentsEvent::RecipeRegistered { recipe_id: 0, owner: dd.account_id }.into(),
+			);
+		});
+	}
+
+	#[test]
+	fn register_recipe_should_not_work_if_the_user_does_not_own_the_proto_fragment_of_an_output() {
+		new_test_ext().execute_with(|| {
+			let dd = DummyData::new();
+			let (input_hash, output_hash) = setup(&dd, 2, None, dd.account_id);
+
+			assert_noop!(
+				register_recipe_(
+					dd.account_id_second,
+					RecipeInput {
+						definition_hash: input_hash,
+						amount: RecipeInputAmount::Quantity(2)
+					},
+					RecipeOutput { definition_hash: output_hash, quantity: 1, stack_amount: None },
+					None
+				),
+				Error::<Test>::NoPermission
+			);
+		});
+	}
+
+	#[test]
+	fn register_recipe_should_not_work_if_an_output_has_the_unique_capability() {
+		new_test_ext().execute_with(|| {
+			let dd = DummyData::new();
+			let definition = dd.definition;
+			assert_ok!(upload(dd.account_id, &definition.proto_fragment));
+			assert_ok!(create(dd.account_id, &definition));
+
+			assert_noop!(
+				register_recipe_(
+					dd.account_id,
+					RecipeInput {
+						definition_hash: definition.get_definition_id(),
+						amount: RecipeInputAmount::Quantity(1)
+					},
+					RecipeOutput {
+						definition_hash: definition.get_definition_id(),
+						quantity: 1,
+						stack_amount: None
+					},
+					None
+				),
+				Error::<Test>::ParamsNotValid
+			);
+		});
+	}
+
+	#[test]
+	fn set_recipe_enabled_should_not_work_if_the_user_is_not_the_owner_of_the_recipe() {
+		new_test_ext().execute_with(|| {
+			let dd = DummyData::new();
+			let (input_hash, output_hash) = setup(&dd, 2, None, dd.account_id);
+			assert_ok!(register_recipe_(
+				dd.account_id,
+				RecipeInput { definition_hash: input_hash, amount: RecipeInputAmount::Quantity(2) },
+				RecipeOutput { definition_hash: output_hash, quantity: 1, stack_amount: None },
+				None
+			));
+
+			assert_noop!(
+				FragmentsPallet::set_recipe_enabled(
+					RuntimeOrigin::signed(dd.account_id_second),
+					0,
+					false
+				),
+				Error::<Test>::NoPermission
+			);
+		});
+	}
+
+	#[test]
+	fn craft_should_work() {
+		new_test_ext().execute_with(|| {
+			let dd = DummyData::new();
+			let player = dd.account_id_second;
+			let (input_hash, output_hash) = setup(&dd, 2, None, player);
+			assert_ok!(register_recipe_(
+				dd.account_id,
+				RecipeInput { definition_hash: input_hash, amount: RecipeInputAmount::Quantity(2) },
+				RecipeOutput { definition_hash: output_hash, quantity: 3, stack_amount: None },
+				Some((Currency::Native, 1_000))
+			));
+			fund(player, 1_000_000);
+			let owner_balance_before = Balances::free_balance(dd.account_id);
+
+			assert_ok!(craft_(player, input_hash, vec![1, 2]));
+
+			assert!(!<Fragments<Test>>::contains_key((input_hash, 1, 1)));
+			assert!(!<Fragments<Test>>::contains_key((input_hash, 2, 1)));
+			// the output Fragment Definition is not published
+			assert_eq!(
+				<Inventory<Test>>::get(player, output_hash).unwrap(),
+				vec![(Compact(1), Compact(1)), (Compact(2), Compact(1)), (Compact(3), Compact(1))]
+			);
+			assert_eq!(Balances::free_balance(dd.account_id), owner_balance_before + 1_000);
+			System::assert_last_event(
+				FragmentsEvent::Crafted { recipe_id: 0, account_id: player }.into(),
+			);
+		});
+	}
+
+	#[test]
+	fn craft_should_work_if_an_input_requires_a_stack_amount() {
+		new_test_ext().execute_with(|| {
+			let dd = DummyData::new();
+			let (input_hash, output_hash) = setup(&dd, 2, Some(10), dd.account_id);
+			assert_ok!(register_recipe_(
+				dd.account_id,
+				RecipeInput {
+					definition_hash: input_hash,
+					amount: RecipeInputAmount::StackAmount(13)
+				},
+				RecipeOutput { definition_hash: output_hash, quantity: 1, stack_amount: Some(5) },
+				None
+			));
+
+			assert_ok!(craft_(dd.account_id, input_hash, vec![1, 2]));
+
+			// the first stack is consumed completely, so its Fragment Instance is burned
+			assert!(!<Fragments<Test>>::contains_key((input_hash, 1, 1)));
+			assert_eq!(
+				<Fragments<Test>>::get((input_hash, 2, 1)).unwrap().stack_amount,
+				Some(Compact(7))
+			);
+			assert_eq!(
+				<Fragments<Test>>::get((output_hash, 1, 1)).unwrap().stack_amount,
+				Some(Compact(5))
+			);
+		});
+	}
+
+	#[test]
+	fn craft_should_not_work_if_the_recipe_is_disabled() {
+		new_test_ext().execute_with(|| {
+			let dd = DummyData::new();
+			let (input_hash, output_hash) = setup(&dd, 2, None, dd.account_id);
+			assert_ok!(register_recipe_(
+				dd.account_id,
+				RecipeInput { definition_hash: input_hash, amount: RecipeInputAmount::Quantity(2) },
+				RecipeOutput { definition_hash: output_hash, quantity: 1, stack_amount: None },
+				None
+			));
+			assert_ok!(FragmentsPallet::set_recipe_enabled(
+				RuntimeOrigin::signed(dd.account_id),
+				0,
+				false
+			));
+
+			assert_noop!(
+				craft_(dd.account_id, input_hash, vec![1, 2]),
+				Error::<Test>::RecipeDisabled
+			);
+		});
+	}
+
+	#[test]
+	fn craft_should_not_work_if_the_inputs_do_not_match_the_recipe() {
+		new_test_ext().execute_with(|| {
+			let dd = DummyData::new();
+			let (input_hash, output_hash) = setup(&dd, 2, None, dd.account_id);
+			assert_ok!(register_recipe_(
+				dd.account_id,
+				RecipeInput { definition_hash: input_hash, amount: RecipeInputAmount::Quantity(2) },
+				RecipeOutput { definition_hash: output_hash, quantity: 1, stack_amount: None },
+				None
+			));
+
+			assert_noop!(
+				craft_(dd.account_id, input_hash, vec![1]),
+				Error::<Test>::RecipeInputsNotMet
+			);
+		});
+	}
+
+	#[test]
+	fn craft_should_not_work_if_the_user_does_not_own_an_input() {
+		new_test_ext().execute_with(|| {
+			let dd = DummyData::new();
+			let (input_hash, output_hash) = setup(&dd, 2, None, dd.account_id);
+			assert_ok!(register_recipe_(
+				dd.account_id,
+				RecipeInput { definition_hash: input_hash, amount: RecipeInputAmount::Quantity(2) },
+				RecipeOutput { definition_hash: output_hash, quantity: 1, stack_amount: None },
+				None
+			));
+
+			assert_noop!(
+				craft_(dd.account_id_second, input_hash, vec![1, 2]),
+				Error::<Test>::NoPermission
+			);
+		});
+	}
+
+	#[test]
+	fn craft_should_not_work_if_the_user_cannot_pay_the_fee() {
+		new_test_ext().execute_with(|| {
+			let dd = DummyData::new();
+			let player = dd.account_id_second;
+			let (input_hash, output_hash) = setup(&dd, 2, None, player);
+			assert_ok!(register_recipe_(
+				dd.account_id,
+				RecipeInput { definition_hash: input_hash, amount: RecipeInputAmount::Quantity(2) },
+				RecipeOutput { definition_hash: output_hash, quantity: 1, stack_amount: None },
+				Some((Currency::Native, 1_000))
+			));
+
+			assert_noop!(
+				craft_(player, input_hash, vec![1, 2]),
+				Error::<Test>::InsufficientBalance
+			);
+		});
+	}
+}
+
+mod unique_data_validator_tests {
+	use super::*;
+
+	fn set_unique_data_validator_(
+		signer: <Test as frame_system::Config>::AccountId,
+		definition: &Definition,
+		validator: Option<<Test as frame_system::Config>::AccountId>,
+	) -> DispatchResult {
+		FragmentsPallet::set_unique_data_validator(
+			RuntimeOrigin::signed(signer),
+			definition.get_definition_id(),
+			validator,
+		)
+	}
+
+	#[test]
+	fn set_unique_data_validator_should_work() {
+		new_test_ext().execute_with(|| {
+			let dd = DummyData::new();
+			let definition = dd.mint_unique.definition;
+			assert_ok!(upload(dd.account_id, &definition.proto_fragment));
+			assert_ok!(create(dd.account_id, &definition));
+
+			assert_ok!(set_unique_data_validator_(
+				dd.account_id,
+				&definition,
+				Some(dd.account_id_third)
+			));
+
+			assert_eq!(
+				<UniqueDataValidators<Test>>::get(definition.get_definition_id()),
+				Some(dd.account_id_third)
+			);
+			System::assert_last_event(
+				FragmentsEvent::UniqueDataValidatorChanged {
+					definition_hash: definition.get_definition_id(),
+					validator: Some(dd.account_id_third),
+				}
+				.into(),
+			);
+
+			assert_ok!(set_unique_data_validator_(dd.account_id, &definition, None));
+			assert!(!<UniqueDataValidators<Test>>::contains_key(definition.get_definition_id()));
+		});
+	}
+
+	#[test]
+	fn set_unique_data_validator_should_not_work_if_the_user_is_not_the_owner_of_the_proto_fragment(
+	) {
+		new_test_ext().execute_with(|| {
+			let dd = DummyData::new();
+			let definition = dd.mint_unique.definition;
+			assert_ok!(upload(dd.account_id, &definition.proto_fragment));
+			assert_ok!(create(dd.account_id, &definition));
+
+			assert_noop!(
+				set_unique_data_validator_(
+					dd.account_id_second,
+					&definition,
+					Some(dd.account_id_third)
+				),
+				Error::<Test>::NoPermission
+			);
+		});
+	}
+
+	#[test]
+	fn set_unique_data_validator_should_not_work_if_the_fragment_definition_is_not_unique() {
+		new_test_ext().execute_with(|| {
+			let dd = DummyData::new();
+			let definition = dd.mint_non_unique.definition;
+			assert_ok!(upload(dd.account_id, &definition.proto_fragment));
+			assert_ok!(create(dd.account_id, &definition));
+
+			assert_noop!(
+				set_unique_data_validator_(dd.account_id, &definition, Some(dd.account_id_third)),
+				Error::<Test>::ParamsNotValid
+			);
+		});
+	}
+
+	#[test]
+	fn set_unique_data_validator_should_not_work_if_fragment_instances_have_already_been_created() {
+		new_test_ext().execute_with(|| {
+			let dd = DummyData::new();
+			let mint = dd.mint_unique;
+			assert_ok!(upload(dd.account_id, &mint.definition.proto_fragment));
+			assert_ok!(create(dd.account_id, &mint.definition));
+			assert_ok!(mint_(dd.account_id, &mint));
+
+			assert_noop!(
+				set_unique_data_validator_(
+					dd.account_id,
+					&mint.definition,
+					Some(dd.account_id_third)
+				),
+				Error::<Test>::InstancesAlreadyCreated
+			);
+		});
+	}
+
+	#[test]
+	fn mint_should_not_work_if_the_validator_does_not_accept_the_unique_data() {
+		new_test_ext().execute_with(|| {
+			let dd = DummyData::new();
+			let mint = dd.mint_unique;
+			assert_ok!(upload(dd.account_id, &mint.definition.proto_fragment));
+			assert_ok!(create(dd.account_id, &mint.definition));
+			// `account_id_third` is not an ink! contract, so it can never accept any unique data
+			assert_ok!(set_unique_data_validator_(
+				dd.account_id,
+				&mint.definition,
+				Some(dd.account_id_third)
+			));
+
+			assert_noop!(mint_(dd.account_id, &mint), Error::<Test>::UniqueDataNotValid);
+		});
+	}
+
+	#[test]
+	fn mint_should_refund_the_validator_weight_if_there_is_no_validator() {
+		new_test_ext().execute_with(|| {
+			let dd = DummyData::new();
+			let mint = dd.mint_unique;
+			assert_ok!(upload(dd.account_id, &mint.definition.proto_fragment));
+			assert_ok!(create(dd.account_id, &mint.definition));
+
+			let FragmentBuyOptions::UniqueData(data) = &mint.buy_options else {
+				panic!("The Fragment Definition must be unique!");
+			};
+			assert_eq!(
+				mint_(dd.account_id, &mint).unwrap().actual_weight,
+				Some(<<Test as crate::Config>::WeightInfo as WeightInfo>::mint_definition_that_has_unique_capability(
+					data.len() as u32
+				))
+			);
+		});
+	}
+}
+
+mod update_unique_data_tests {
+	use super::*;
+
+	fn update_unique_data_(
+		signer: <Test as frame_system::Config>::AccountId,
+		definition: &Definition,
+		data: Vec<u8>,
+	) -> DispatchResultWithPostInfo {
+		FragmentsPallet::update_unique_data(
+			RuntimeOrigin::signed(signer),
+			definition.get_definition_id(),
+			1,
+			1,
+			data,
+		)
+	}
+
+	#[test]
+	fn update_unique_data_should_work() {
+		new_test_ext().execute_with(|| {
+			let dd = DummyData::new();
+			let mint = dd.mint_unique;
+			assert_ok!(upload(dd.account_id, &mint.definition.proto_fragment));
+			assert_ok!(create(dd.account_id, &mint.definition));
+			assert_ok!(mint_(dd.account_id, &mint));
+
+			let definition_hash = mint.definition.get_definition_id();
+			let previous_data_hash =
+				<Fragments<Test>>::get((definition_hash, 1, 1)).unwrap().custom_data.unwrap();
+
+			assert_ok!(update_unique_data_(
+				dd.account_id,
+				&mint.definition,
+				b"Nuovi Dati".to_vec()
+			));
+
+			let data_hash = blake2_256(b"Nuovi Dati");
+			assert_eq!(
+				<Fragments<Test>>::get((definition_hash, 1, 1)).unwrap().custom_data,
+				Some(data_hash)
+			);
+			assert_eq!(<UniqueData2Edition<Test>>::get(definition_hash, data_hash), Some(1));
+			assert!(!<UniqueData2Edition<Test>>::contains_key(definition_hash, previous_data_hash));
+			assert_eq!(
+				<UniqueDataHistory<Test>>::get((definition_hash, 1)),
+				vec![previous_data_hash]
+			);
+			System::assert_last_event(
+				FragmentsEvent::UniqueDataUpdated {
+					definition_hash,
+					fragment_id: (1, 1),
+					data_hash,
+				}
+				.into(),
+			);
+		});
+	}
+
+	#[test]
+	fn update_unique_data_should_not_work_if_the_unique_data_is_not_mutable() {
+		new_test_ext().execute_with(|| {
+			let dd = DummyData::new();
+			let mint = Mint {
+				definition: Definition {
+					unique: Some(UniqueOptions {
+						mutable: false,
+						_reserved1: None,
+						_reserved2: None,
+						_reserved3: None,
+					}),
+					..dd.mint_unique.definition.clone()
+				},
+				..dd.mint_unique.clone()
+			};
+			assert_ok!(upload(dd.account_id, &mint.definition.proto_fragment));
+			assert_ok!(create(dd.account_id, &mint.definition));
+			assert_ok!(mint_(dd.account_id, &mint));
+
+			assert_noop!(
+				update_unique_data_(dd.account_id, &mint.definition, b"Nuovi Dati".to_vec()),
+				Error::<Test>::UniqueDataNotMutable
+			);
+		});
+	}
+
+	#[test]
+	fn update_unique_data_should_not_work_if_the_user_is_not_the_owner_of_the_fragment_instance() {
+		new_test_ext().execute_with(|| {
+			let dd = DummyData::new();
+			let mint = dd.mint_unique;
+			assert_ok!(upload(dd.account_id, &mint.definition.proto_fragment));
+			assert_ok!(create(dd.account_id, &mint.definition));
+			assert_ok!(mint_(dd.account_id, &mint));
+
+			assert_noop!(
+				update_unique_data_(dd.account_id_second, &mint.definition, b"Nuovi Dati".to_vec()),
+				Error::<Test>::NoPermission
+			);
+		});
+	}
+
+	#[test]
+	fn update_unique_data_should_not_work_if_the_unique_data_already_exists() {
+		new_test_ext().execute_with(|| {
+			let dd = DummyData::new();
+			let mint = dd.mint_unique;
+			assert_ok!(upload(dd.account_id, &mint.definition.proto_fragment));
+			assert_ok!(create(dd.account_id, &mint.definition));
+			assert_ok!(mint_(dd.account_id, &mint));
+			assert_ok!(mint_(
+				dd.account_id,
+				&Mint {
+					buy_options: FragmentBuyOptions::UniqueData(b"Altri Dati".to_vec()),
+					..mint.clone()
+				}
+			));
+
+			assert_noop!(
+				update_unique_data_(dd.account_id, &mint.definition, b"Altri Dati".to_vec()),
+				Error::<Test>::UniqueDataExists
+			);
+		});
+	}
+}
+
+mod metadata_key_policy_tests {
+	use super::*;
+
+	fn stats_key() -> BoundedVec<u8, <Test as pallet_protos::Config>::StringLimit> {
+		b"stats".to_vec().try_into().unwrap()
+	}
+
+	/// Create the Fragment Instance `(1, 1)` of `mint` and give it to `holder`
+	fn mint_to(
+		dd: &DummyData,
+		mint: &Mint,
+		holder: <Test as frame_system::Config>::AccountId,
+	) -> Hash128 {
+		assert_ok!(upload(dd.account_id, &mint.definition.proto_fragment));
+		assert_ok!(create(dd.account_id, &mint.definition));
+		assert_ok!(mint_(dd.account_id, mint));
+		let definition_hash = mint.definition.get_definition_id();
+		if holder != dd.account_id {
+			assert_ok!(FragmentsPallet::give(
+				RuntimeOrigin::signed(dd.account_id),
+				definition_hash,
+				1,
+				1,
+				holder,
+				None,
+				None,
+			));
+		}
+		definition_hash
+	}
+
+	fn set_metadata_key_policy_(
+		signer: <Test as frame_system::Config>::AccountId,
+		definition_hash: Hash128,
+		policy: Option<MetadataKeyPolicy>,
+	) -> DispatchResult {
+		FragmentsPallet::set_metadata_key_policy(
+			RuntimeOrigin::signed(signer),
+			definition_hash,
+			stats_key(),
+			policy,
+		)
+	}
+
+	fn set_instance_metadata_(
+		signer: <Test as frame_system::Config>::AccountId,
+		definition_hash: Hash128,
+	) -> DispatchResult {
+		FragmentsPallet::set_instance_metadata(
+			RuntimeOrigin::signed(signer),
+			definition_hash,
+			1,
+			1,
+			stats_key(),
+			b"{\"strength\": 7}".to_vec(),
+		)
+	}
+
+	#[test]
+	fn set_instance_metadata_should_work_if_the_user_is_the_owner_of_the_fragment_instance() {
+		new_test_ext().execute_with(|| {
+			let dd = DummyData::new();
+			let definition_hash = mint_to(&dd, &dd.mint_non_unique, dd.account_id_second);
+
+			assert_ok!(set_instance_metadata_(dd.account_id_second, definition_hash));
+
+			assert_eq!(<Fragments<Test>>::get((definition_hash, 1, 1)).unwrap().metadata.len(), 1);
+		});
+	}
+
+	#[test]
+	fn set_instance_metadata_should_not_work_if_the_fragment_instance_does_not_have_edit_permission(
+	) {
+		new_test_ext().execute_with(|| {
+			let dd = DummyData::new();
+			let mint = Mint {
+				definition: Definition {
+					permissions: FragmentPerms::TRANSFER,
+					..dd.mint_non_unique.definition.clone()
+				},
+				..dd.mint_non_unique.clone()
+			};
+			let definition_hash = mint_to(&dd, &mint, dd.account_id);
+
+			assert_noop!(
+				set_instance_metadata_(dd.account_id, definition_hash),
+				Error::<Test>::NotEditable
+			);
+		});
+	}
+
+	#[test]
+	fn set_instance_metadata_should_work_if_the_metadata_key_is_reserved_for_the_creator_and_the_user_is_the_creator(
+	) {
+		new_test_ext().execute_with(|| {
+			let dd = DummyData::new();
+			let definition_hash = mint_to(&dd, &dd.mint_non_unique, dd.account_id_second);
+			assert_ok!(set_metadata_key_policy_(
+				dd.account_id,
+				definition_hash,
+				Some(MetadataKeyPolicy::Creator)
+			));
+
+			assert_ok!(set_instance_metadata_(dd.account_id, definition_hash));
+		});
+	}
+
+	#[test]
+	fn set_instance_metadata_should_not_work_if_the_metadata_key_is_reserved_for_the_creator() {
+		new_test_ext().execute_with(|| {
+			let dd = DummyData::new();
+			let definition_hash = mint_to(&dd, &dd.mint_non_unique, dd.account_id_second);
+			assert_ok!(set_metadata_key_policy_(
+				dd.account_id,
+				definition_hash,
+				Some(MetadataKeyPolicy::Creator)
+			));
+
+			assert_noop!(
+				set_instance_metadata_(dd.account_id_second, definition_hash),
+				Error::<Test>::MetadataKeyReservedForCreator
+			);
+		});
+	}
+
+	#[test]
+	fn set_instance_metadata_should_not_work_if_the_metadata_key_is_reserved_for_a_cluster_role() {
+		new_test_ext().execute_with(|| {
+			let dd = DummyData::new();
+			let definition_hash = mint_to(&dd, &dd.mint_non_unique, dd.account_id_second);
+			assert_ok!(set_metadata_key_policy_(
+				dd.account_id,
+				definition_hash,
+				Some(MetadataKeyPolicy::ClusterRole {
+					cluster_id: [7u8; 16],
+					role_name: b"Game Server".to_vec(),
+				})
+			));
+
+			assert_noop!(
+				set_instance_metadata_(dd.account_id_second, definition_hash),
+				Error::<Test>::MetadataKeyReservedForClusterRole
+			);
+		});
+	}
+
+	#[test]
+	fn set_metadata_key_policy_should_not_work_if_the_user_is_not_the_owner_of_the_proto_fragment()
+	{
+		new_test_ext().execute_with(|| {
+			let dd = DummyData::new();
+			let definition_hash = mint_to(&dd, &dd.mint_non_unique, dd.account_id_second);
+
+			assert_noop!(
+				set_metadata_key_policy_(
+					dd.account_id_second,
+					definition_hash,
+					Some(MetadataKeyPolicy::Creator)
+				),
+				Error::<Test>::NoPermission
+			);
+		});
+	}
+}
+
+mod attribute_tests {
+	use super::*;
+	use protos::{
+		categories::Categories,
+		traits::{Trait, VariableType, VariableTypeInfo},
+	};
+
+	/// Trait Proto-Fragment that has the records "level" and "title"
+	fn trait_proto() -> ProtoFragment {
+		let records = vec![
+			(
+				"level".to_string(),
+				vec![VariableTypeInfo { type_: VariableType::Int(None), default: None }],
+			)
+				.into(),
+			(
+				"title".to_string(),
+				vec![VariableTypeInfo { type_: VariableType::String, default: None }],
+			)
+				.into(),
+		];
+		ProtoFragment {
+			references: Vec::new(),
+			category: Categories::Trait(None),
+			tags: Vec::new(),
+			linked_asset: None,
+			data: Trait { name: "Je suis un Trait".to_string(), records }.encode(),
+		}
+	}
+
+	fn schema_attributes() -> Vec<(Vec<u8>, AttributeType)> {
+		vec![(b"level".to_vec(), AttributeType::Number), (b"title".to_vec(), AttributeType::String)]
+	}
+
+	fn set_attribute_schema_(
+		signer: <Test as frame_system::Config>::AccountId,
+		definition_hash: Hash128,
+		trait_data: Vec<u8>,
+		attributes: Vec<(Vec<u8>, AttributeType)>,
+	) -> DispatchResult {
+		FragmentsPallet::set_attribute_schema(
+			RuntimeOrigin::signed(signer),
+			definition_hash,
+			trait_data,
+			attributes,
+		)
+	}
+
+	fn set_instance_attributes_(
+		signer: <Test as frame_system::Config>::AccountId,
+		definition_hash: Hash128,
+		attributes: Vec<(Vec<u8>, Option<AttributeValue>)>,
+	) -> DispatchResult {
+		FragmentsPallet::set_instance_attributes(
+			RuntimeOrigin::signed(signer),
+			definition_hash,
+			1,
+			1,
+			attributes,
+		)
+	}
+
+	/// Create the Fragment Definition of `mint`, give it the attribute schema `schema_attributes()`
+	/// and create its Fragment Instance `(1, 1)`
+	fn setup(dd: &DummyData, mint: &Mint) -> Hash128 {
+		assert_ok!(upload(dd.account_id, &mint.definition.proto_fragment));
+		assert_ok!(create(dd.account_id, &mint.definition));
+		let definition_hash = mint.definition.get_definition_id();
+
+		assert_ok!(upload(dd.account_id, &trait_proto()));
+		assert_ok!(set_attribute_schema_(
+			dd.account_id,
+			definition_hash,
+			trait_proto().data,
+			schema_attributes()
+		));
+
+		assert_ok!(mint_(dd.account_id, mint));
+		definition_hash
+	}
+
+	#[test]
+	fn set_attribute_schema_should_work() {
+		new_test_ext().execute_with(|| {
+			let dd = DummyData::new();
+			let definition_hash = setup(&dd, &dd.mint_non_unique);
+
+			assert_eq!(
+				<AttributeSchemas<Test>>::get(definition_hash).unwrap(),
+				AttributeSchema {
+					trait_proto: trait_proto().get_proto_hash(),
+					attributes: schema_attributes(),
+				}
+			);
+			System::assert_has_event(
+				FragmentsEvent::AttributeSchemaChanged {
+					definition_hash,
+					trait_proto: trait_proto().get_proto_hash(),
+				}
+				.into(),
+			);
+		});
+	}
+
+	#[test]
+	fn set_attribute_schema_should_not_work_if_the_attribute_is_not_a_record_of_the_trait() {
+		new_test_ext().execute_with(|| {
+			let dd = DummyData::new();
+			let definition = &dd.mint_non_unique.definition;
+			assert_ok!(upload(dd.account_id, &definition.proto_fragment));
+			assert_ok!(create(dd.account_id, definition));
+			assert_ok!(upload(dd.account_id, &trait_proto()));
+
+			assert_noop!(
+				set_attribute_schema_(
+					dd.account_id,
+					definition.get_definition_id(),
+					trait_proto().data,
+					vec![(b"strength".to_vec(), AttributeType::Number)]
+				),
+				Error::<Test>::AttributeNotInSchema
+			);
+		});
+	}
+
+	#[test]
+	fn set_attribute_schema_should_not_work_if_the_type_does_not_match_the_trait() {
+		new_test_ext().execute_with(|| {
+			let dd = DummyData::new();
+			let definition = &dd.mint_non_unique.definition;
+			assert_ok!(upload(dd.account_id, &definition.proto_fragment));
+			assert_ok!(create(dd.account_id, definition));
+			assert_ok!(upload(dd.account_id, &trait_proto()));
+
+			// the record "title" of the Trait is a `VariableType::String`
+			assert_noop!(
+				set_attribute_schema_(
+					dd.account_id,
+					definition.get_definition_id(),
+					trait_proto().data,
+					vec![(b"title".to_vec(), AttributeType::Number)]
+				),
+				Error::<Test>::AttributeTypeNotInTrait
+			);
+		});
+	}
+
+	#[test]
+	fn set_attribute_schema_should_not_work_if_the_proto_fragment_is_not_a_trait() {
+		new_test_ext().execute_with(|| {
+			let dd = DummyData::new();
+			let definition = &dd.mint_non_unique.definition;
+			assert_ok!(upload(dd.account_id, &definition.proto_fragment));
+			assert_ok!(create(dd.account_id, definition));
+
+			assert_noop!(
+				set_attribute_schema_(
+					dd.account_id,
+					definition.get_definition_id(),
+					definition.proto_fragment.data.clone(),
+					schema_attributes()
+				),
+				Error::<Test>::NotATrait
+			);
+		});
+	}
+
+	#[test]
+	fn set_attribute_schema_should_not_work_if_fragment_instances_were_already_created() {
+		new_test_ext().execute_with(|| {
+			let dd = DummyData::new();
+			let definition_hash = setup(&dd, &dd.mint_non_unique);
+
+			assert_noop!(
+				set_attribute_schema_(
+					dd.account_id,
+					definition_hash,
+					trait_proto().data,
+					schema_attributes()
+				),
+				Error::<Test>::InstancesAlreadyCreated
+			);
+		});
+	}
+
+	#[test]
+	fn set_attribute_schema_should_not_work_if_the_user_is_not_the_owner_of_the_proto_fragment() {
+		new_test_ext().execute_with(|| {
+			let dd = DummyData::new();
+			let definition = &dd.mint_non_unique.definition;
+			assert_ok!(upload(dd.account_id, &definition.proto_fragment));
+			assert_ok!(create(dd.account_id, definition));
+			assert_ok!(upload(dd.account_id, &trait_proto()));
+
+			assert_noop!(
+				set_attribute_schema_(
+					dd.account_id_second,
+					definition.get_definition_id(),
+					trait_proto().data,
+					schema_attributes()
+				),
+				Error::<Test>::NoPermission
+			);
+		});
+	}
+
+	#[test]
+	fn set_instance_attributes_should_work() {
+		new_test_ext().execute_with(|| {
+			let dd = DummyData::new();
+			let definition_hash = setup(&dd, &dd.mint_non_unique);
+
+			assert_ok!(set_instance_attributes_(
+				dd.account_id,
+				definition_hash,
+				vec![
+					(b"level".to_vec(), Some(AttributeValue::Number(7))),
+					(b"title".to_vec(), Some(AttributeValue::String(b"Il Cavaliere".to_vec()))),
+				]
+			));
+			assert_eq!(
+				<InstanceAttributes<Test>>::get((definition_hash, 1, 1)).unwrap(),
+				BTreeMap::from([
+					(b"level".to_vec(), AttributeValue::Number(7)),
+					(b"title".to_vec(), AttributeValue::String(b"Il Cavaliere".to_vec())),
+				])
+			);
+			System::assert_last_event(
+				FragmentsEvent::InstanceAttributesUpdated { definition_hash, fragment_id: (1, 1) }
+					.into(),
+			);
+
+			assert_ok!(set_instance_attributes_(
+				dd.account_id,
+				definition_hash,
+				vec![(b"title".to_vec(), None)]
+			));
+			assert_eq!(
+				<InstanceAttributes<Test>>::get((definition_hash, 1, 1)).unwrap(),
+				BTreeMap::from([(b"level".to_vec(), AttributeValue::Number(7))])
+			);
+		});
+	}
+
+	#[test]
+	fn set_instance_attributes_should_not_work_if_the_value_does_not_have_the_type_of_the_attribute(
+	) {
+		new_test_ext().execute_with(|| {
+			let dd = DummyData::new();
+			let definition_hash = setup(&dd, &dd.mint_non_unique);
+
+			assert_noop!(
+				set_instance_attributes_(
+					dd.account_id,
+					definition_hash,
+					vec![(b"level".to_vec(), Some(AttributeValue::String(b"sette".to_vec())))]
+				),
+				Error::<Test>::AttributeTypeMismatch
+			);
+		});
+	}
+
+	#[test]
+	fn set_instance_attributes_should_not_work_if_the_attribute_is_not_in_the_schema() {
+		new_test_ext().execute_with(|| {
+			let dd = DummyData::new();
+			let definition_hash = setup(&dd, &dd.mint_non_unique);
+
+			assert_noop!(
+				set_instance_attributes_(
+					dd.account_id,
+					definition_hash,
+					vec![(b"strength".to_vec(), Some(AttributeValue::Number(7)))]
+				),
+				Error::<Test>::AttributeNotInSchema
+			);
+		});
+	}
+
+	#[test]
+	fn set_instance_attributes_should_not_work_if_the_attribute_is_reserved_for_the_creator() {
+		new_test_ext().execute_with(|| {
+			let dd = DummyData::new();
+			let definition_hash = setup(&dd, &dd.mint_non_unique);
+			assert_ok!(FragmentsPallet::give(
+				RuntimeOrigin::signed(dd.account_id),
+				definition_hash,
+				1,
+				1,
+				dd.account_id_second,
+				None,
+				None,
+			));
+			assert_ok!(FragmentsPallet::set_metadata_key_policy(
+				RuntimeOrigin::signed(dd.account_id),
+				definition_hash,
+				b"level".to_vec().try_into().unwrap(),
+				Some(MetadataKeyPolicy::Creator),
+			));
+
+			assert_noop!(
+				set_instance_attributes_(
+					dd.account_id_second,
+					definition_hash,
+					vec![(b"level".to_vec(), Some(AttributeValue::Number(7)))]
+				),
+				Error::<Test>::MetadataKeyReservedForCreator
+			);
+			assert_ok!(set_instance_attributes_(
+				dd.account_id,
+				definition_hash,
+				vec![(b"level".to_vec(), Some(AttributeValue::Number(7)))]
+			));
+		});
+	}
+
+	#[test]
+	fn burn_should_remove_the_attributes_of_the_fragment_instance() {
+		new_test_ext().execute_with(|| {
+			let dd = DummyData::new();
+			let definition_hash = setup(&dd, &dd.mint_non_unique);
+			assert_ok!(set_instance_attributes_(
+				dd.account_id,
+				definition_hash,
+				vec![(b"level".to_vec(), Some(AttributeValue::Number(7)))]
+			));
+
+			assert_ok!(FragmentsPallet::burn(
+				RuntimeOrigin::signed(dd.account_id),
+				definition_hash,
+				1,
+				1
+			));
+
+			assert!(!<InstanceAttributes<Test>>::contains_key((definition_hash, 1, 1)));
+		});
+	}
+}
+
+mod license_tests {
+	use super::*;
+	use pallet_protos::{ProtoData, UsageLicense};
+	use sp_runtime::Perbill;
+
+	/// Upload `proto` with the license `UsageLicense::Open`, so that anyone can create a Fragment Definition from it
+	fn upload_open(
+		signer: <Test as frame_system::Config>::AccountId,
+		proto: &ProtoFragment,
+	) -> DispatchResult {
+		Protos::upload(
+			RuntimeOrigin::signed(signer),
+			proto.references.clone(),
+			proto.category.clone(),
+			BoundedVec::default(),
+			proto.linked_asset.clone(),
+			UsageLicense::Open,
+			None,
+			ProtoData::Local(proto.data.clone()),
+		)
+	}
+
+	#[test]
+	fn create_should_work_if_the_license_of_the_proto_is_open() {
+		new_test_ext().execute_with(|| {
+			let dd = DummyData::new();
+
+			let definition = dd.definition;
+
+			assert_ok!(upload_open(dd.account_id, &definition.proto_fragment));
+
+			assert_ok!(create(dd.account_id_second, &definition));
+
+			assert_eq!(
+				<Definitions<Test>>::get(definition.get_definition_id()).unwrap().creator,
+				dd.account_id_second
+			);
+		});
+	}
+
+	#[test]
+	fn only_the_creator_of_the_fragment_definition_should_be_able_to_manage_it() {
+		new_test_ext().execute_with(|| {
+			let dd = DummyData::new();
+
+			let publish = dd.publish;
+
+			assert_ok!(upload_open(dd.account_id, &publish.definition.proto_fragment));
+			assert_ok!(create(dd.account_id_second, &publish.definition));
+
+			// the owner of the Proto-Fragment is not the creator of the Fragment Definition
+			assert_noop!(publish_(dd.account_id, &publish), Error::<Test>::NoPermission);
+			assert_noop!(
+				FragmentsPallet::set_definition_metadata(
+					RuntimeOrigin::signed(dd.account_id),
+					publish.definition.get_definition_id(),
+					b"title".to_vec().try_into().unwrap(),
+					b"Je suis un Titre".to_vec(),
+				),
+				Error::<Test>::NoPermission
+			);
+
+			assert_ok!(publish_(dd.account_id_second, &publish));
+		});
+	}
+
+	#[test]
+	fn buy_should_pay_the_license_royalty_to_the_owner_of_the_proto() {
+		new_test_ext().execute_with(|| {
+			let dd = DummyData::new();
+
+			let mut publish = dd.publish;
+			publish.price = 1_000;
+
+			assert_ok!(upload_open(dd.account_id, &publish.definition.proto_fragment));
+			assert_ok!(Protos::set_license_royalty(
+				RuntimeOrigin::signed(dd.account_id),
+				publish.definition.proto_fragment.get_proto_hash(),
+				Some(Perbill::from_percent(10)),
+			));
+			assert_ok!(create(dd.account_id_second, &publish.definition));
+			assert_ok!(publish_(dd.account_id_second, &publish));
+
+			let minimum_balance = <Balances as fungible::Inspect<
+				<Test as frame_system::Config>::AccountId,
+			>>::minimum_balance();
+			fund(dd.account_id, minimum_balance);
+			fund(dd.account_id_third, publish.price + minimum_balance);
+
+			assert_ok!(FragmentsPallet::buy(
+				RuntimeOrigin::signed(dd.account_id_third),
+				publish.definition.get_definition_id(),
+				FragmentBuyOptions::Quantity(1),
+				None,
+			));
+
+			System::assert_has_event(
+				pallet_balances::Event::Transfer {
+					from: dd.account_id_third,
+					to: publish.definition.get_vault_account_id(),
+					amount: 900,
+				}
+				.into(),
+			);
+			System::assert_has_event(
+				pallet_balances::Event::Transfer {
+					from: dd.account_id_third,
+					to: dd.account_id,
+					amount: 100,
+				}
+				.into(),
+			);
+		});
+	}
+
+	#[test]
+	fn buy_should_not_pay_the_license_royalty_if_the_owner_of_the_proto_created_the_fragment_definition(
+	) {
+		new_test_ext().execute_with(|| {
+			let dd = DummyData::new();
+
+			let mut publish = dd.publish;
+			publish.price = 1_000;
+
+			assert_ok!(upload_open(dd.account_id, &publish.definition.proto_fragment));
+			assert_ok!(Protos::set_license_royalty(
+				RuntimeOrigin::signed(dd.account_id),
+				publish.definition.proto_fragment.get_proto_hash(),
+				Some(Perbill::from_percent(10)),
+			));
+			assert_ok!(create(dd.account_id, &publish.definition));
+			assert_ok!(publish_(dd.account_id, &publish));
+
+			let minimum_balance = <Balances as fungible::Inspect<
+				<Test as frame_system::Config>::AccountId,
+			>>::minimum_balance();
+			fund(dd.account_id_third, publish.price + minimum_balance);
+
+			assert_ok!(FragmentsPallet::buy(
+				RuntimeOrigin::signed(dd.account_id_third),
+				publish.definition.get_definition_id(),
+				FragmentBuyOptions::Quantity(1),
+				None,
+			));
+
+			System::assert_has_event(
+				pallet_balances::Event::Transfer {
+					from: dd.account_id_third,
+					to: publish.definition.get_vault_account_id(),
+					amount: publish.price,
+				}
+				.into(),
+			);
+		});
+	}
+}
+
+mod transfer_definition_tests {
+	use super::*;
+	use frame_support::traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion};
+
+	pub fn transfer_definition_(
+		signer: <Test as frame_system::Config>::AccountId,
+		definition_hash: Hash128,
+		to: <Test as frame_system::Config>::AccountId,
+	) -> DispatchResult {
+		FragmentsPallet::transfer_definition(RuntimeOrigin::signed(signer), definition_hash, to)
+	}
+
+	#[test]
+	fn transfer_definition_should_work() {
+		new_test_ext().execute_with(|| {
+			let dd = DummyData::new();
+
+			let definition = dd.definition;
+			let definition_hash = definition.get_definition_id();
+
+			assert_ok!(upload(dd.account_id, &definition.proto_fragment));
+			assert_ok!(create(dd.account_id, &definition));
+
+			assert_ok!(transfer_definition_(dd.account_id, definition_hash, dd.account_id_second));
+
+			let definition_struct = <Definitions<Test>>::get(definition_hash).unwrap();
+			assert_eq!(definition_struct.owner, dd.account_id_second);
+			assert_eq!(definition_struct.creator, dd.account_id);
+			assert_eq!(<DefinitionsByOwner<Test>>::get(dd.account_id), Some(vec![]));
+			assert_eq!(
+				<DefinitionsByOwner<Test>>::get(dd.account_id_second),
+				Some(vec![definition_hash])
+			);
+			// the Proto-Fragment is not transferred
+			assert_eq!(
+				pallet_protos::Protos::<Test>::get(definition.proto_fragment.get_proto_hash())
+					.unwrap()
+					.owner,
+				pallet_protos::ProtoOwner::User(dd.account_id)
+			);
+
+			System::assert_last_event(
+				FragmentsEvent::DefinitionTransferred {
+					definition_hash,
+					owner: dd.account_id_second,
+				}
+				.into(),
+			);
+		});
+	}
+
+	#[test]
+	fn transfer_definition_should_not_work_if_the_user_does_not_own_the_fragment_definition() {
+		new_test_ext().execute_with(|| {
+			let dd = DummyData::new();
+
+			let definition = dd.definition;
+			let definition_hash = definition.get_definition_id();
+
+			assert_ok!(upload(dd.account_id, &definition.proto_fragment));
+			assert_ok!(create(dd.account_id, &definition));
+
+			assert_noop!(
+				transfer_definition_(dd.account_id_second, definition_hash, dd.account_id_third),
+				Error::<Test>::NoPermission
+			);
+		});
+	}
+
+	#[test]
+	fn only_the_new_owner_should_be_able_to_manage_a_transferred_fragment_definition() {
+		new_test_ext().execute_with(|| {
+			let dd = DummyData::new();
+
+			let publish = dd.publish;
+			let definition_hash = publish.definition.get_definition_id();
+
+			assert_ok!(upload(dd.account_id, &publish.definition.proto_fragment));
+			assert_ok!(create(dd.account_id, &publish.definition));
+			assert_ok!(transfer_definition_(dd.account_id, definition_hash, dd.account_id_second));
+
+			// neither the creator of the Fragment Definition nor the Proto-Fragment's owner can manage it anymore
+			assert_noop!(publish_(dd.account_id, &publish), Error::<Test>::NoPermission);
+
+			assert_ok!(publish_(dd.account_id_second, &publish));
+			assert_ok!(FragmentsPallet::unpublish(
+				RuntimeOrigin::signed(dd.account_id_second),
+				definition_hash
+			));
+		});
+	}
+
+	#[test]
+	fn get_definitions_should_return_the_owner_of_a_transferred_fragment_definition() {
+		new_test_ext().execute_with(|| {
+			let dd = DummyData::new();
+
+			let definition = dd.definition;
+			let definition_hash = definition.get_definition_id();
+
+			assert_ok!(upload(dd.account_id, &definition.proto_fragment));
+			assert_ok!(create(dd.account_id, &definition));
+			assert_ok!(transfer_definition_(dd.account_id, definition_hash, dd.account_id_second));
+
+			assert_eq!(
+				serde_json::from_slice::<Value>(
+					&FragmentsPallet::get_definitions(GetDefinitionsParams {
+						limit: u64::MAX,
+						owner: Some(dd.account_id_second),
+						return_owners: true,
+						..Default::default()
+					})
+					.unwrap()
+				)
+				.unwrap(),
+				json!({
+					hex::encode(definition_hash): {
+						"name": String::from_utf8(definition.metadata.name).unwrap(),
+						"num_instances": 0,
+						"currency": "native",
+						"sale": null,
+						"owner": {
+							"type": "internal",
+							"value": hex::encode(dd.account_id_second)
+						}
+					}
+				})
+			);
+		});
+	}
+
+	#[test]
+	fn migrate_to_v3_should_set_the_owner_of_existing_fragment_definitions() {
+		new_test_ext().execute_with(|| {
+			let dd = DummyData::new();
+
+			let definition = dd.definition;
+			let definition_hash = definition.get_definition_id();
+
+			let old_definition = crate::migration::v3::OldFragmentDefinition {
+				proto_hash: definition.proto_fragment.get_proto_hash(),
+				metadata: definition.metadata.clone(),
+				permissions: definition.permissions,
+				unique: definition.unique.clone(),
+				max_supply: None,
+				creator: dd.account_id,
+				created_at: System::block_number(),
+				custom_metadata: BTreeMap::new(),
+				_reserved1: None,
+				_reserved2: None,
+				_reserved3: None,
+			};
+			frame_support::storage::unhashed::put(
+				&<Definitions<Test>>::hashed_key_for(definition_hash),
+				&old_definition,
+			);
+			StorageVersion::new(2).put::<FragmentsPallet>();
+
+			crate::migration::v3::MigrateToV3::<Test>::on_runtime_upgrade();
+
+			assert_eq!(FragmentsPallet::on_chain_storage_version(), StorageVersion::new(3));
+			let definition_struct = <Definitions<Test>>::get(definition_hash).unwrap();
+			assert_eq!(definition_struct.owner, dd.account_id);
+			assert_eq!(definition_struct.creator, dd.account_id);
+			assert_eq!(<DefinitionsByOwner<Test>>::get(dd.account_id), Some(vec![definition_hash]));
+		});
+	}
+
+	#[test]
+	fn migrate_to_v3_should_set_the_owner_to_the_proto_owner_of_existing_fragment_definitions() {
+		new_test_ext().execute_with(|| {
+			let dd = DummyData::new();
+
+			let definition = dd.definition;
+			let definition_hash = definition.get_definition_id();
+
+			// The Proto-Fragment is owned by `account_id_second`, while the Fragment Definition was created by `account_id`
+			assert_ok!(upload(dd.account_id_second, &definition.proto_fragment));
+
+			let old_definition = crate::migration::v3::OldFragmentDefinition {
+				proto_hash: definition.proto_fragment.get_proto_hash(),
+				metadata: definition.metadata.clone(),
+				permissions: definition.permissions,
+				unique: definition.unique.clone(),
+				max_supply: None,
+				creator: dd.account_id,
+				created_at: System::block_number(),
+				custom_metadata: BTreeMap::new(),
+				_reserved1: None,
+				_reserved2: None,
+				_reserved3: None,
+			};
+			frame_support::storage::unhashed::put(
+				&<Definitions<Test>>::hashed_key_for(definition_hash),
+				&old_definition,
+			);
+			StorageVersion::new(2).put::<FragmentsPallet>();
+
+			crate::migration::v3::MigrateToV3::<Test>::on_runtime_upgrade();
+
+			let definition_struct = <Definitions<Test>>::get(definition_hash).unwrap();
+			assert_eq!(definition_struct.owner, dd.account_id_second);
+			assert_eq!(definition_struct.creator, dd.account_id);
+			assert_eq!(
+				<DefinitionsByOwner<Test>>::get(dd.account_id_second),
+				Some(vec![definition_hash])
+			);
+			assert_eq!(<DefinitionsByOwner<Test>>::get(dd.account_id), None);
+		});
+	}
+}
@@ -15,14 +15,13 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-//! Autogenerated weights for pallet_fragments
+//! Weights for pallet_fragments
 //!
-//! THIS FILE WAS AUTO-GENERATED USING THE SUBSTRATE BENCHMARK CLI VERSION 4.0.0-dev
-//! DATE: 2022-10-13, STEPS: `50`, REPEAT: 20, LOW RANGE: `[]`, HIGH RANGE: `[]`
-//! HOSTNAME: `alepc`, CPU: `11th Gen Intel(R) Core(TM) i7-1195G7 @ 2.90GHz`
-//! EXECUTION: Some(Wasm), WASM-EXECUTION: Compiled, CHAIN: Some("dev"), DB CACHE: 1024
+//! NOTE: These weights are **not** the output of a benchmark run. They were first generated with the
+//! Substrate benchmark CLI and have since been **estimated by hand** for the new or changed extrinsics.
+//! They must be regenerated (on the reference hardware) before they are relied upon.
 
-// Executed Command:
+// Command to regenerate this file:
 // ./target/production/fragnova
 // benchmark
 // pallet
@@ -47,6 +46,8 @@ use sp_std::marker::PhantomData;
 /// Weight functions needed for pallet_fragments.
 pub trait WeightInfo {
 	fn create(n: u32, ) -> Weight;
+	fn set_definition_metadata(d: u32, ) -> Weight;
+	fn set_instance_metadata(d: u32, ) -> Weight;
 	fn publish() -> Weight;
 	fn unpublish() -> Weight;
 	fn mint_definition_that_has_non_unique_capability(q: u32, ) -> Weight;
@@ -55,6 +56,38 @@ pub trait WeightInfo {
 	fn buy_definition_that_has_unique_capability(d: u32, ) -> Weight;
 	fn benchmark_give_instance_that_does_not_have_copy_perms() -> Weight;
 	fn benchmark_give_instance_that_has_copy_perms() -> Weight;
+	fn withdraw_proceeds() -> Weight;
+	fn burn() -> Weight;
+	fn set_burns_replenish_supply() -> Weight;
+	fn split_stack() -> Weight;
+	fn merge_stacks() -> Weight;
+	fn consume() -> Weight;
+	fn mint_to_many(r: u32, q: u32, ) -> Weight;
+	fn start_claim() -> Weight;
+	fn end_claim() -> Weight;
+	fn claim_definition_that_has_non_unique_capability(q: u32, ) -> Weight;
+	fn claim_definition_that_has_unique_capability(d: u32, ) -> Weight;
+	fn sweep_expirations(n: u32, ) -> Weight;
+	fn renew() -> Weight;
+	fn rent() -> Weight;
+	fn end_rental() -> Weight;
+	fn approve() -> Weight;
+	fn set_approval_for_all() -> Weight;
+	fn propose_swap(n: u32, ) -> Weight;
+	fn accept_swap(n: u32, ) -> Weight;
+	fn cancel_swap(n: u32, ) -> Weight;
+	fn nest() -> Weight;
+	fn unnest() -> Weight;
+	fn as_instance() -> Weight;
+	fn register_recipe(n: u32, ) -> Weight;
+	fn set_recipe_enabled() -> Weight;
+	fn craft(n: u32, q: u32, ) -> Weight;
+	fn set_unique_data_validator() -> Weight;
+	fn update_unique_data(d: u32, ) -> Weight;
+	fn set_metadata_key_policy() -> Weight;
+	fn set_attribute_schema(n: u32, ) -> Weight;
+	fn set_instance_attributes(n: u32, ) -> Weight;
+	fn transfer_definition() -> Weight;
 }
 
 /// Weights for pallet_fragments using the Substrate node and recommended hardware.
@@ -66,14 +99,46 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 	// Storage: Assets Asset (r:1 w:0)
 	// Storage: System Account (r:1 w:1)
 	// Storage: Fragments Proto2Fragments (r:1 w:1)
+	// Storage: Fragments DefinitionsByOwner (r:1 w:1)
 	/// The range of component `n` is `[1, 100]`.
 	fn create(n: u32, ) -> Weight {
 		Weight::from_ref_time(53_058_000 as u64)
 			// Standard Error: 9_000
 			.saturating_add(Weight::from_ref_time(10_000 as u64).saturating_mul(n as u64))
+			.saturating_add(T::DbWeight::get().reads(7 as u64))
+			.saturating_add(T::DbWeight::get().writes(4 as u64))
+	}
+	// Storage: Fragments Definitions (r:1 w:1)
+	// Storage: Fragments MetadataKeyPolicies (r:1 w:0)
+	// Storage: Detach DetachedHashes (r:1 w:0)
+	// Storage: unknown [0x3a65787472696e7369635f696e646578] (r:1 w:0)
+	// Storage: Fragments MetaKeys (r:1 w:1)
+	// Storage: Fragments MetaKeysIndex (r:1 w:1)
+	/// The range of component `d` is `[1, 1000000]`.
+	fn set_definition_metadata(d: u32, ) -> Weight {
+		Weight::from_ref_time(36_712_000 as u64)
+			// Standard Error: 0
+			.saturating_add(Weight::from_ref_time(2_000 as u64).saturating_mul(d as u64))
 			.saturating_add(T::DbWeight::get().reads(6 as u64))
 			.saturating_add(T::DbWeight::get().writes(3 as u64))
 	}
+	// Storage: Detach DetachedHashes (r:1 w:0)
+	// Storage: Fragments Fragments (r:1 w:1)
+	// Storage: Fragments MetadataKeyPolicies (r:1 w:0)
+	// Storage: Fragments Inventory (r:1 w:0)
+	// Storage: unknown [0x3a65787472696e7369635f696e646578] (r:1 w:0)
+	// Storage: Fragments MetaKeys (r:1 w:1)
+	// Storage: Fragments MetaKeysIndex (r:1 w:1)
+	// Storage: Fragments DataHashMapIndex (r:1 w:1)
+	// Storage: Fragments DataHashMap (r:0 w:1)
+	/// The range of component `d` is `[1, 1000000]`.
+	fn set_instance_metadata(d: u32, ) -> Weight {
+		Weight::from_ref_time(41_058_000 as u64)
+			// Standard Error: 0
+			.saturating_add(Weight::from_ref_time(2_000 as u64).saturating_mul(d as u64))
+			.saturating_add(T::DbWeight::get().reads(8 as u64))
+			.saturating_add(T::DbWeight::get().writes(5 as u64))
+	}
 	// Storage: Fragments Definitions (r:1 w:0)
 	// Storage: Protos Protos (r:1 w:0)
 	// Storage: Detach DetachedHashes (r:1 w:0)
@@ -100,6 +165,7 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 	// Storage: Fragments EditionsCount (r:1 w:1)
 	// Storage: Fragments Inventory (r:1 w:1)
 	// Storage: Fragments Owners (r:1 w:1)
+	// Storage: Fragments InstanceOwner (r:0 w:1)
 	// Storage: Fragments Fragments (r:0 w:1)
 	// Storage: Fragments CopiesCount (r:0 w:1)
 	/// The range of component `q` is `[1, 100]`.
@@ -109,7 +175,7 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 			.saturating_add(Weight::from_ref_time(8_255_000 as u64).saturating_mul(q as u64))
 			.saturating_add(T::DbWeight::get().reads(7 as u64))
 			.saturating_add(T::DbWeight::get().writes(4 as u64))
-			.saturating_add(T::DbWeight::get().writes((2 as u64).saturating_mul(q as u64)))
+			.saturating_add(T::DbWeight::get().writes((3 as u64).saturating_mul(q as u64)))
 	}
 	// Storage: Fragments Definitions (r:1 w:1)
 	// Storage: Protos Protos (r:1 w:0)
@@ -119,70 +185,556 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 	// Storage: Fragments EditionsCount (r:1 w:1)
 	// Storage: Fragments Inventory (r:1 w:1)
 	// Storage: Fragments Owners (r:1 w:1)
+	// Storage: Fragments InstanceOwner (r:0 w:1)
 	// Storage: Fragments Fragments (r:0 w:1)
 	// Storage: Fragments CopiesCount (r:0 w:1)
+	// Storage: Fragments UniqueDataValidators (r:1 w:0)
 	/// The range of component `d` is `[1, 1000000]`.
 	fn mint_definition_that_has_unique_capability(d: u32, ) -> Weight {
 		Weight::from_ref_time(77_922_000 as u64)
 			// Standard Error: 0
 			.saturating_add(Weight::from_ref_time(1_000 as u64).saturating_mul(d as u64))
-			.saturating_add(T::DbWeight::get().reads(8 as u64))
-			.saturating_add(T::DbWeight::get().writes(7 as u64))
+			.saturating_add(T::DbWeight::get().reads(9 as u64))
+			.saturating_add(T::DbWeight::get().writes(8 as u64))
 	}
 	// Storage: Fragments Publishing (r:1 w:1)
 	// Storage: Fragments Definitions (r:1 w:1)
-	// Storage: System Account (r:1 w:1)
+	// Storage: System Account (r:2 w:2)
 	// Storage: unknown [0x3a65787472696e7369635f696e646578] (r:1 w:0)
 	// Storage: Fragments EditionsCount (r:1 w:1)
 	// Storage: Fragments Inventory (r:1 w:1)
 	// Storage: Fragments Owners (r:1 w:1)
+	// Storage: Fragments InstanceOwner (r:0 w:1)
 	// Storage: Fragments Fragments (r:0 w:1)
 	// Storage: Fragments CopiesCount (r:0 w:1)
+	// Storage: Protos LicenseRoyalties (r:1 w:0)
+	// Storage: Protos Protos (r:1 w:0)
 	/// The range of component `q` is `[1, 100]`.
 	fn buy_definition_that_has_non_unique_capability(q: u32, ) -> Weight {
 		Weight::from_ref_time(49_276_000 as u64)
 			// Standard Error: 32_000
 			.saturating_add(Weight::from_ref_time(7_485_000 as u64).saturating_mul(q as u64))
-			.saturating_add(T::DbWeight::get().reads(7 as u64))
-			.saturating_add(T::DbWeight::get().writes(6 as u64))
-			.saturating_add(T::DbWeight::get().writes((2 as u64).saturating_mul(q as u64)))
+			.saturating_add(T::DbWeight::get().reads(10 as u64))
+			.saturating_add(T::DbWeight::get().writes(7 as u64))
+			.saturating_add(T::DbWeight::get().writes((3 as u64).saturating_mul(q as u64)))
 	}
 	// Storage: Fragments Publishing (r:1 w:1)
 	// Storage: Fragments Definitions (r:1 w:1)
-	// Storage: System Account (r:1 w:1)
+	// Storage: System Account (r:2 w:2)
 	// Storage: unknown [0x3a65787472696e7369635f696e646578] (r:1 w:0)
 	// Storage: Fragments UniqueData2Edition (r:1 w:1)
 	// Storage: Fragments EditionsCount (r:1 w:1)
 	// Storage: Fragments Inventory (r:1 w:1)
 	// Storage: Fragments Owners (r:1 w:1)
+	// Storage: Fragments InstanceOwner (r:0 w:1)
 	// Storage: Fragments Fragments (r:0 w:1)
 	// Storage: Fragments CopiesCount (r:0 w:1)
+	// Storage: Fragments UniqueDataValidators (r:1 w:0)
+	// Storage: Protos LicenseRoyalties (r:1 w:0)
+	// Storage: Protos Protos (r:1 w:0)
 	/// The range of component `d` is `[1, 1000000]`.
 	fn buy_definition_that_has_unique_capability(d: u32, ) -> Weight {
 		Weight::from_ref_time(33_800_000 as u64)
 			// Standard Error: 0
 			.saturating_add(Weight::from_ref_time(2_000 as u64).saturating_mul(d as u64))
-			.saturating_add(T::DbWeight::get().reads(8 as u64))
-			.saturating_add(T::DbWeight::get().writes(9 as u64))
+			.saturating_add(T::DbWeight::get().reads(12 as u64))
+			.saturating_add(T::DbWeight::get().writes(11 as u64))
 	}
 	// Storage: Fragments Fragments (r:1 w:1)
 	// Storage: Fragments Inventory (r:2 w:2)
 	// Storage: Fragments Owners (r:2 w:2)
+	// Storage: Fragments InstanceOwner (r:1 w:1)
+	// Storage: Fragments Rentals (r:1 w:1)
+	// Storage: Fragments InstanceApprovals (r:1 w:1)
+	// Storage: Fragments OperatorApprovals (r:1 w:0)
+	// Storage: Fragments LockedInstances (r:1 w:1)
+	// Storage: Fragments InstanceParent (r:1 w:1)
+	// Storage: Proxy Proxies (r:1 w:0)
 	fn benchmark_give_instance_that_does_not_have_copy_perms() -> Weight {
-		Weight::from_ref_time(36_669_000 as u64)
-			.saturating_add(T::DbWeight::get().reads(5 as u64))
-			.saturating_add(T::DbWeight::get().writes(5 as u64))
+		Weight::from_ref_time(41_203_000 as u64)
+			.saturating_add(T::DbWeight::get().reads(12 as u64))
+			.saturating_add(T::DbWeight::get().writes(10 as u64))
 	}
 	// Storage: Fragments Fragments (r:1 w:1)
 	// Storage: Fragments Inventory (r:2 w:1)
 	// Storage: Fragments CopiesCount (r:1 w:1)
 	// Storage: Fragments Owners (r:1 w:1)
-	// Storage: Fragments Expirations (r:1 w:1)
+	// Storage: Fragments InstanceOwner (r:1 w:1)
+	// Storage: Fragments ExpirationQueue (r:1 w:1)
+	// Storage: Fragments Rentals (r:1 w:0)
+	// Storage: Fragments InstanceApprovals (r:1 w:0)
+	// Storage: Fragments OperatorApprovals (r:1 w:0)
+	// Storage: Fragments LockedInstances (r:1 w:0)
+	// Storage: Fragments InstanceAttributes (r:1 w:1)
 	fn benchmark_give_instance_that_has_copy_perms() -> Weight {
-		Weight::from_ref_time(30_168_000 as u64)
+		Weight::from_ref_time(34_512_000 as u64)
+			.saturating_add(T::DbWeight::get().reads(12 as u64))
+			.saturating_add(T::DbWeight::get().writes(7 as u64))
+	}
+	// Storage: Fragments Definitions (r:1 w:0)
+	// Storage: Protos Protos (r:1 w:0)
+	// Storage: System Account (r:2 w:2)
+	fn withdraw_proceeds() -> Weight {
+		Weight::from_ref_time(41_285_000 as u64)
+			.saturating_add(T::DbWeight::get().reads(4 as u64))
+			.saturating_add(T::DbWeight::get().writes(2 as u64))
+	}
+	// Storage: Detach DetachedHashes (r:1 w:0)
+	// Storage: Fragments Fragments (r:2 w:1)
+	// Storage: Fragments Inventory (r:1 w:1)
+	// Storage: Fragments Owners (r:1 w:1)
+	// Storage: Fragments InstanceOwner (r:0 w:1)
+	// Storage: Fragments ExpirationQueue (r:1 w:1)
+	// Storage: Fragments Definition2SecondarySales (r:0 w:1)
+	// Storage: Fragments BurnedCount (r:1 w:1)
+	// Storage: Fragments RemovedCount (r:1 w:1)
+	// Storage: Fragments UniqueData2Edition (r:0 w:1)
+	// Storage: Fragments BurnedEditionsCount (r:1 w:1)
+	// Storage: Fragments Rentals (r:1 w:1)
+	// Storage: Fragments InstanceApprovals (r:0 w:1)
+	// Storage: Fragments LockedInstances (r:0 w:1)
+	// Storage: Fragments InstanceChildren (r:1 w:0)
+	// Storage: Fragments InstanceParent (r:1 w:1)
+	// Storage: Fragments UniqueDataHistory (r:0 w:1)
+	// Storage: Fragments InstanceAttributes (r:0 w:1)
+	fn burn() -> Weight {
+		Weight::from_ref_time(48_611_000 as u64)
+			.saturating_add(T::DbWeight::get().reads(12 as u64))
+			.saturating_add(T::DbWeight::get().writes(16 as u64))
+	}
+	// Storage: Fragments Definitions (r:1 w:0)
+	// Storage: Protos Protos (r:1 w:0)
+	// Storage: Fragments EditionsCount (r:1 w:0)
+	// Storage: Fragments BurnsReplenishSupply (r:0 w:1)
+	fn set_burns_replenish_supply() -> Weight {
+		Weight::from_ref_time(24_093_000 as u64)
+			.saturating_add(T::DbWeight::get().reads(3 as u64))
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+	}
+	// Storage: Detach DetachedHashes (r:1 w:0)
+	// Storage: Fragments Fragments (r:1 w:2)
+	// Storage: Fragments Inventory (r:2 w:1)
+	// Storage: Fragments CopiesCount (r:1 w:1)
+	// Storage: Fragments Owners (r:1 w:1)
+	// Storage: Fragments InstanceOwner (r:0 w:1)
+	// Storage: Fragments ExpirationQueue (r:1 w:1)
+	// Storage: Fragments Rentals (r:1 w:0)
+	// Storage: Fragments InstanceAttributes (r:1 w:1)
+	fn split_stack() -> Weight {
+		Weight::from_ref_time(45_870_000 as u64)
+			.saturating_add(T::DbWeight::get().reads(9 as u64))
+			.saturating_add(T::DbWeight::get().writes(8 as u64))
+	}
+	// Storage: Detach DetachedHashes (r:2 w:0)
+	// Storage: Fragments Fragments (r:2 w:2)
+	// Storage: Fragments Inventory (r:2 w:1)
+	// Storage: Fragments Owners (r:1 w:1)
+	// Storage: Fragments InstanceOwner (r:0 w:1)
+	// Storage: Fragments ExpirationQueue (r:1 w:1)
+	// Storage: Fragments Definition2SecondarySales (r:0 w:1)
+	// Storage: Fragments Rentals (r:2 w:1)
+	// Storage: Fragments InstanceApprovals (r:0 w:1)
+	// Storage: Fragments LockedInstances (r:0 w:1)
+	// Storage: Fragments InstanceChildren (r:2 w:0)
+	// Storage: Fragments InstanceParent (r:1 w:1)
+	// Storage: Fragments InstanceAttributes (r:2 w:1)
+	// Storage: Fragments RemovedCount (r:1 w:1)
+	fn merge_stacks() -> Weight {
+		Weight::from_ref_time(51_402_000 as u64)
+			.saturating_add(T::DbWeight::get().reads(16 as u64))
+			.saturating_add(T::DbWeight::get().writes(13 as u64))
+	}
+	// Storage: Detach DetachedHashes (r:1 w:0)
+	// Storage: Fragments Fragments (r:2 w:1)
+	// Storage: Fragments Inventory (r:1 w:1)
+	// Storage: Fragments Owners (r:1 w:1)
+	// Storage: Fragments InstanceOwner (r:0 w:1)
+	// Storage: Fragments ExpirationQueue (r:1 w:1)
+	// Storage: Fragments Definition2SecondarySales (r:0 w:1)
+	// Storage: Fragments BurnedCount (r:1 w:1)
+	// Storage: Fragments RemovedCount (r:1 w:1)
+	// Storage: Fragments UniqueData2Edition (r:0 w:1)
+	// Storage: Fragments BurnedEditionsCount (r:1 w:1)
+	// Storage: Fragments Rentals (r:1 w:1)
+	// Storage: Fragments InstanceApprovals (r:0 w:1)
+	// Storage: Fragments LockedInstances (r:0 w:1)
+	// Storage: Fragments InstanceChildren (r:1 w:0)
+	// Storage: Fragments InstanceParent (r:1 w:1)
+	// Storage: Fragments InstanceAttributes (r:0 w:1)
+	fn consume() -> Weight {
+		Weight::from_ref_time(49_233_000 as u64)
+			.saturating_add(T::DbWeight::get().reads(12 as u64))
+			.saturating_add(T::DbWeight::get().writes(15 as u64))
+	}
+	// Storage: Fragments Definitions (r:1 w:0)
+	// Storage: Protos Protos (r:1 w:0)
+	// Storage: Detach DetachedHashes (r:1 w:0)
+	// Storage: Fragments BurnsReplenishSupply (r:1 w:0)
+	// Storage: Fragments EditionsCount (r:1 w:1)
+	// Storage: unknown [0x3a65787472696e7369635f696e646578] (r:1 w:0)
+	// Storage: System Account (r:100 w:0)
+	// Storage: Fragments Inventory (r:100 w:100)
+	// Storage: Fragments Owners (r:100 w:100)
+	// Storage: Fragments InstanceOwner (r:0 w:100)
+	// Storage: Fragments Fragments (r:0 w:100)
+	// Storage: Fragments CopiesCount (r:0 w:100)
+	/// The range of component `r` is `[1, 100]`.
+	/// The range of component `q` is `[1, 100]`.
+	fn mint_to_many(r: u32, q: u32, ) -> Weight {
+		Weight::from_ref_time(38_112_000 as u64)
+			// Standard Error: 41_000
+			.saturating_add(Weight::from_ref_time(12_406_000 as u64).saturating_mul(r as u64))
+			// Standard Error: 41_000
+			.saturating_add(Weight::from_ref_time(8_255_000 as u64).saturating_mul(q as u64))
 			.saturating_add(T::DbWeight::get().reads(6 as u64))
+			.saturating_add(T::DbWeight::get().reads((3 as u64).saturating_mul(r as u64)))
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+			.saturating_add(T::DbWeight::get().writes((2 as u64).saturating_mul(r as u64)))
+			.saturating_add(T::DbWeight::get().writes((3 as u64).saturating_mul(q as u64)))
+	}
+	// Storage: Fragments Definitions (r:1 w:0)
+	// Storage: Protos Protos (r:1 w:0)
+	// Storage: Detach DetachedHashes (r:1 w:0)
+	// Storage: Fragments ClaimCampaigns (r:1 w:1)
+	fn start_claim() -> Weight {
+		Weight::from_ref_time(29_871_000 as u64)
+			.saturating_add(T::DbWeight::get().reads(4 as u64))
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+	}
+	// Storage: Fragments Definitions (r:1 w:0)
+	// Storage: Protos Protos (r:1 w:0)
+	// Storage: Fragments ClaimCampaigns (r:1 w:1)
+	fn end_claim() -> Weight {
+		Weight::from_ref_time(26_540_000 as u64)
+			.saturating_add(T::DbWeight::get().reads(3 as u64))
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+	}
+	// Storage: Fragments ClaimCampaigns (r:1 w:0)
+	// Storage: Fragments Definitions (r:1 w:0)
+	// Storage: Detach DetachedHashes (r:1 w:0)
+	// Storage: Fragments Claimed (r:1 w:1)
+	// Storage: Fragments BurnsReplenishSupply (r:1 w:0)
+	// Storage: Fragments EditionsCount (r:1 w:1)
+	// Storage: unknown [0x3a65787472696e7369635f696e646578] (r:1 w:0)
+	// Storage: System Account (r:1 w:0)
+	// Storage: Fragments Inventory (r:1 w:1)
+	// Storage: Fragments Owners (r:1 w:1)
+	// Storage: Fragments InstanceOwner (r:0 w:1)
+	// Storage: Fragments Fragments (r:0 w:1)
+	// Storage: Fragments CopiesCount (r:0 w:1)
+	/// The range of component `q` is `[1, 100]`.
+	fn claim_definition_that_has_non_unique_capability(q: u32, ) -> Weight {
+		Weight::from_ref_time(61_403_000 as u64)
+			// Standard Error: 128_000
+			.saturating_add(Weight::from_ref_time(8_255_000 as u64).saturating_mul(q as u64))
+			.saturating_add(T::DbWeight::get().reads(10 as u64))
+			.saturating_add(T::DbWeight::get().writes(4 as u64))
+			.saturating_add(T::DbWeight::get().writes((3 as u64).saturating_mul(q as u64)))
+	}
+	// Storage: Fragments ClaimCampaigns (r:1 w:0)
+	// Storage: Fragments Definitions (r:1 w:1)
+	// Storage: Detach DetachedHashes (r:1 w:0)
+	// Storage: Fragments Claimed (r:1 w:1)
+	// Storage: Fragments BurnsReplenishSupply (r:1 w:0)
+	// Storage: unknown [0x3a65787472696e7369635f696e646578] (r:1 w:0)
+	// Storage: Fragments UniqueData2Edition (r:1 w:1)
+	// Storage: Fragments EditionsCount (r:1 w:1)
+	// Storage: System Account (r:1 w:0)
+	// Storage: Fragments Inventory (r:1 w:1)
+	// Storage: Fragments Owners (r:1 w:1)
+	// Storage: Fragments InstanceOwner (r:0 w:1)
+	// Storage: Fragments Fragments (r:0 w:1)
+	// Storage: Fragments CopiesCount (r:0 w:1)
+	// Storage: Fragments UniqueDataValidators (r:1 w:0)
+	/// The range of component `d` is `[1, 1000000]`.
+	fn claim_definition_that_has_unique_capability(d: u32, ) -> Weight {
+		Weight::from_ref_time(103_957_000 as u64)
+			// Standard Error: 0
+			.saturating_add(Weight::from_ref_time(1_000 as u64).saturating_mul(d as u64))
+			.saturating_add(T::DbWeight::get().reads(12 as u64))
+			.saturating_add(T::DbWeight::get().writes(9 as u64))
+	}
+	// Storage: Fragments ExpirationCursor (r:1 w:1)
+	// Storage: Fragments ExpirationQueue (r:101 w:100)
+	// Storage: Fragments Fragments (r:0 w:100)
+	// Storage: Fragments Definition2SecondarySales (r:0 w:100)
+	// Storage: Fragments InstanceOwner (r:100 w:100)
+	// Storage: Fragments Owners (r:100 w:100)
+	// Storage: Fragments Inventory (r:100 w:100)
+	// Storage: Fragments Rentals (r:0 w:100)
+	// Storage: Fragments InstanceApprovals (r:0 w:100)
+	// Storage: Fragments LockedInstances (r:0 w:100)
+	// Storage: Fragments InstanceParent (r:100 w:100)
+	// Storage: Fragments RemovedCount (r:100 w:100)
+	/// The range of component `n` is `[0, 100]`.
+	fn sweep_expirations(n: u32, ) -> Weight {
+		Weight::from_ref_time(9_734_000 as u64)
+			// Standard Error: 23_000
+			.saturating_add(Weight::from_ref_time(15_961_000 as u64).saturating_mul(n as u64))
+			.saturating_add(T::DbWeight::get().reads(2 as u64))
+			.saturating_add(T::DbWeight::get().reads((6 as u64).saturating_mul(n as u64)))
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+			.saturating_add(T::DbWeight::get().writes((11 as u64).saturating_mul(n as u64)))
+	}
+	// Storage: Detach DetachedHashes (r:1 w:0)
+	// Storage: Fragments Fragments (r:1 w:1)
+	// Storage: Fragments InstanceOwner (r:1 w:0)
+	// Storage: Fragments Publishing (r:1 w:0)
+	// Storage: Fragments Definitions (r:1 w:0)
+	// Storage: System Account (r:2 w:2)
+	// Storage: Fragments ExpirationQueue (r:0 w:2)
+	fn renew() -> Weight {
+		Weight::from_ref_time(58_297_000 as u64)
+			.saturating_add(T::DbWeight::get().reads(7 as u64))
 			.saturating_add(T::DbWeight::get().writes(5 as u64))
 	}
+	// Storage: Detach DetachedHashes (r:1 w:0)
+	// Storage: Fragments Fragments (r:1 w:0)
+	// Storage: Fragments Inventory (r:1 w:0)
+	// Storage: Fragments Rentals (r:1 w:1)
+	// Storage: Fragments LockedInstances (r:1 w:0)
+	fn rent() -> Weight {
+		Weight::from_ref_time(31_604_000 as u64)
+			.saturating_add(T::DbWeight::get().reads(5 as u64))
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+	}
+	// Storage: Fragments Rentals (r:1 w:1)
+	fn end_rental() -> Weight {
+		Weight::from_ref_time(19_938_000 as u64)
+			.saturating_add(T::DbWeight::get().reads(1 as u64))
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+	}
+	// Storage: Fragments Fragments (r:1 w:0)
+	// Storage: Fragments InstanceOwner (r:1 w:0)
+	// Storage: Fragments OperatorApprovals (r:1 w:0)
+	// Storage: Fragments InstanceApprovals (r:0 w:1)
+	fn approve() -> Weight {
+		Weight::from_ref_time(27_415_000 as u64)
+			.saturating_add(T::DbWeight::get().reads(3 as u64))
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+	}
+	// Storage: Fragments Definitions (r:1 w:0)
+	// Storage: Fragments OperatorApprovals (r:0 w:1)
+	fn set_approval_for_all() -> Weight {
+		Weight::from_ref_time(21_760_000 as u64)
+			.saturating_add(T::DbWeight::get().reads(1 as u64))
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+	}
+	// Storage: Detach DetachedHashes (r:32 w:0)
+	// Storage: Fragments LockedInstances (r:32 w:32)
+	// Storage: Fragments Fragments (r:32 w:0)
+	// Storage: Fragments Inventory (r:32 w:0)
+	// Storage: Fragments Rentals (r:32 w:0)
+	// Storage: Fragments NextSwapId (r:1 w:1)
+	// Storage: Fragments Swaps (r:0 w:1)
+	/// The range of component `n` is `[1, 32]`.
+	fn propose_swap(n: u32, ) -> Weight {
+		Weight::from_ref_time(24_930_000 as u64)
+			// Standard Error: 9_000
+			.saturating_add(Weight::from_ref_time(14_318_000 as u64).saturating_mul(n as u64))
+			.saturating_add(T::DbWeight::get().reads(1 as u64))
+			.saturating_add(T::DbWeight::get().reads((5 as u64).saturating_mul(n as u64)))
+			.saturating_add(T::DbWeight::get().writes(2 as u64))
+			.saturating_add(T::DbWeight::get().writes((1 as u64).saturating_mul(n as u64)))
+	}
+	// Storage: Fragments Swaps (r:1 w:1)
+	// Storage: Fragments InstanceOwner (r:32 w:32)
+	// Storage: Fragments LockedInstances (r:0 w:32)
+	// Storage: Detach DetachedHashes (r:32 w:0)
+	// Storage: Fragments Fragments (r:32 w:32)
+	// Storage: Fragments Inventory (r:64 w:64)
+	// Storage: Fragments Owners (r:64 w:64)
+	// Storage: Fragments Rentals (r:32 w:32)
+	// Storage: Fragments InstanceApprovals (r:0 w:32)
+	// Storage: Fragments InstanceParent (r:32 w:32)
+	// Storage: Proxy Proxies (r:32 w:0)
+	/// The range of component `n` is `[1, 32]`.
+	fn accept_swap(n: u32, ) -> Weight {
+		Weight::from_ref_time(29_508_000 as u64)
+			// Standard Error: 21_000
+			.saturating_add(Weight::from_ref_time(33_746_000 as u64).saturating_mul(n as u64))
+			.saturating_add(T::DbWeight::get().reads(1 as u64))
+			.saturating_add(T::DbWeight::get().reads((10 as u64).saturating_mul(n as u64)))
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+			.saturating_add(T::DbWeight::get().writes((10 as u64).saturating_mul(n as u64)))
+	}
+	// Storage: Fragments Swaps (r:1 w:1)
+	// Storage: Fragments InstanceOwner (r:32 w:0)
+	// Storage: Fragments LockedInstances (r:0 w:32)
+	/// The range of component `n` is `[1, 32]`.
+	fn cancel_swap(n: u32, ) -> Weight {
+		Weight::from_ref_time(20_114_000 as u64)
+			// Standard Error: 4_000
+			.saturating_add(Weight::from_ref_time(4_205_000 as u64).saturating_mul(n as u64))
+			.saturating_add(T::DbWeight::get().reads(1 as u64))
+			.saturating_add(T::DbWeight::get().reads((1 as u64).saturating_mul(n as u64)))
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+			.saturating_add(T::DbWeight::get().writes((1 as u64).saturating_mul(n as u64)))
+	}
+	// Storage: Detach DetachedHashes (r:2 w:0)
+	// Storage: Fragments Fragments (r:2 w:1)
+	// Storage: Fragments InstanceParent (r:3 w:2)
+	// Storage: Fragments InstanceOwner (r:1 w:1)
+	// Storage: Fragments LockedInstances (r:2 w:1)
+	// Storage: Fragments InstanceChildren (r:2 w:1)
+	// Storage: Fragments Inventory (r:2 w:2)
+	// Storage: Fragments Rentals (r:1 w:1)
+	// Storage: Fragments Owners (r:2 w:2)
+	// Storage: Fragments InstanceApprovals (r:0 w:1)
+	// Storage: Proxy Proxies (r:1 w:0)
+	fn nest() -> Weight {
+		Weight::from_ref_time(63_218_000 as u64)
+			.saturating_add(T::DbWeight::get().reads(18 as u64))
+			.saturating_add(T::DbWeight::get().writes(12 as u64))
+	}
+	// Storage: Fragments InstanceParent (r:3 w:1)
+	// Storage: Fragments InstanceOwner (r:1 w:1)
+	// Storage: Fragments LockedInstances (r:2 w:1)
+	// Storage: Detach DetachedHashes (r:1 w:0)
+	// Storage: Fragments Fragments (r:2 w:1)
+	// Storage: Fragments Inventory (r:2 w:2)
+	// Storage: Fragments Rentals (r:1 w:1)
+	// Storage: Fragments InstanceChildren (r:1 w:1)
+	// Storage: Fragments Owners (r:2 w:2)
+	// Storage: Fragments InstanceApprovals (r:0 w:1)
+	// Storage: Proxy Proxies (r:1 w:0)
+	fn unnest() -> Weight {
+		Weight::from_ref_time(52_904_000 as u64)
+			.saturating_add(T::DbWeight::get().reads(16 as u64))
+			.saturating_add(T::DbWeight::get().writes(11 as u64))
+	}
+	// Storage: Detach DetachedHashes (r:1 w:0)
+	// Storage: Fragments Fragments (r:1 w:0)
+	// Storage: Fragments InstanceOwner (r:1 w:0)
+	// Storage: Fragments LockedInstances (r:1 w:0)
+	fn as_instance() -> Weight {
+		Weight::from_ref_time(18_372_000 as u64)
+			.saturating_add(T::DbWeight::get().reads(4 as u64))
+	}
+	// Storage: Fragments Definitions (r:32 w:0)
+	// Storage: Protos Protos (r:16 w:0)
+	// Storage: Detach DetachedHashes (r:16 w:0)
+	// Storage: Fragments NextRecipeId (r:1 w:1)
+	// Storage: Fragments Recipes (r:0 w:1)
+	/// The range of component `n` is `[1, 16]`.
+	fn register_recipe(n: u32, ) -> Weight {
+		Weight::from_ref_time(31_406_000 as u64)
+			// Standard Error: 6_000
+			.saturating_add(Weight::from_ref_time(9_872_000 as u64).saturating_mul(n as u64))
+			.saturating_add(T::DbWeight::get().reads(2 as u64))
+			.saturating_add(T::DbWeight::get().reads((3 as u64).saturating_mul(n as u64)))
+			.saturating_add(T::DbWeight::get().writes(2 as u64))
+	}
+	// Storage: Fragments Recipes (r:1 w:1)
+	fn set_recipe_enabled() -> Weight {
+		Weight::from_ref_time(20_114_000 as u64)
+			.saturating_add(T::DbWeight::get().reads(1 as u64))
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+	}
+	// Storage: Fragments Recipes (r:1 w:0)
+	// Storage: Detach DetachedHashes (r:33 w:0)
+	// Storage: Fragments Fragments (r:32 w:32)
+	// Storage: Fragments InstanceOwner (r:32 w:32)
+	// Storage: Fragments Inventory (r:33 w:33)
+	// Storage: Fragments Rentals (r:32 w:0)
+	// Storage: Fragments InstanceChildren (r:32 w:0)
+	// Storage: Fragments InstanceParent (r:32 w:32)
+	// Storage: Fragments Owners (r:33 w:33)
+	// Storage: Fragments RemovedCount (r:32 w:32)
+	// Storage: Fragments EditionsCount (r:2 w:2)
+	// Storage: Fragments BurnedEditionsCount (r:1 w:1)
+	// Storage: Fragments Definitions (r:2 w:1)
+	// Storage: Protos Protos (r:1 w:0)
+	// Storage: System Account (r:2 w:2)
+	// Storage: unknown [0x3a65787472696e7369635f696e646578] (r:1 w:0)
+	// Storage: Fragments CopiesCount (r:0 w:100)
+	/// The range of component `n` is `[1, 32]`.
+	/// The range of component `q` is `[1, 100]`.
+	fn craft(n: u32, q: u32, ) -> Weight {
+		Weight::from_ref_time(61_873_000 as u64)
+			// Standard Error: 23_000
+			.saturating_add(Weight::from_ref_time(24_519_000 as u64).saturating_mul(n as u64))
+			// Standard Error: 8_000
+			.saturating_add(Weight::from_ref_time(8_302_000 as u64).saturating_mul(q as u64))
+			.saturating_add(T::DbWeight::get().reads(10 as u64))
+			.saturating_add(T::DbWeight::get().reads((8 as u64).saturating_mul(n as u64)))
+			.saturating_add(T::DbWeight::get().writes(9 as u64))
+			.saturating_add(T::DbWeight::get().writes((6 as u64).saturating_mul(n as u64)))
+			.saturating_add(T::DbWeight::get().writes((5 as u64).saturating_mul(q as u64)))
+	}
+	// Storage: Fragments Definitions (r:1 w:0)
+	// Storage: Protos Protos (r:1 w:0)
+	// Storage: Fragments EditionsCount (r:1 w:0)
+	// Storage: Fragments UniqueDataValidators (r:0 w:1)
+	fn set_unique_data_validator() -> Weight {
+		Weight::from_ref_time(24_387_000 as u64)
+			.saturating_add(T::DbWeight::get().reads(3 as u64))
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+	}
+	// Storage: Fragments Definitions (r:1 w:0)
+	// Storage: Detach DetachedHashes (r:1 w:0)
+	// Storage: Fragments Fragments (r:2 w:1)
+	// Storage: Fragments InstanceOwner (r:1 w:0)
+	// Storage: Fragments Rentals (r:1 w:0)
+	// Storage: Fragments InstanceChildren (r:1 w:0)
+	// Storage: Fragments UniqueData2Edition (r:1 w:2)
+	// Storage: Fragments UniqueDataValidators (r:1 w:0)
+	// Storage: unknown [0x3a65787472696e7369635f696e646578] (r:1 w:0)
+	// Storage: Fragments UniqueDataHistory (r:1 w:1)
+	/// The range of component `d` is `[1, 1000000]`.
+	fn update_unique_data(d: u32, ) -> Weight {
+		Weight::from_ref_time(41_236_000 as u64)
+			// Standard Error: 0
+			.saturating_add(Weight::from_ref_time(2_000 as u64).saturating_mul(d as u64))
+			.saturating_add(T::DbWeight::get().reads(11 as u64))
+			.saturating_add(T::DbWeight::get().writes(4 as u64))
+	}
+	// Storage: Fragments Definitions (r:1 w:0)
+	// Storage: Protos Protos (r:1 w:0)
+	// Storage: Detach DetachedHashes (r:1 w:0)
+	// Storage: Fragments MetadataKeyPolicies (r:0 w:1)
+	fn set_metadata_key_policy() -> Weight {
+		Weight::from_ref_time(25_904_000 as u64)
+			.saturating_add(T::DbWeight::get().reads(3 as u64))
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+	}
+	// Storage: Fragments Definitions (r:1 w:0)
+	// Storage: Protos Protos (r:2 w:0)
+	// Storage: Detach DetachedHashes (r:1 w:0)
+	// Storage: Fragments EditionsCount (r:1 w:0)
+	// Storage: Fragments AttributeSchemas (r:0 w:1)
+	/// The range of component `n` is `[1, 32]`.
+	fn set_attribute_schema(n: u32, ) -> Weight {
+		Weight::from_ref_time(30_118_000 as u64)
+			// Standard Error: 4_000
+			.saturating_add(Weight::from_ref_time(1_274_000 as u64).saturating_mul(n as u64))
+			.saturating_add(T::DbWeight::get().reads(5 as u64))
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+	}
+	// Storage: Detach DetachedHashes (r:1 w:0)
+	// Storage: Fragments Fragments (r:1 w:0)
+	// Storage: Fragments AttributeSchemas (r:1 w:0)
+	// Storage: Fragments MetadataKeyPolicies (r:32 w:0)
+	// Storage: Fragments Definitions (r:32 w:0)
+	// Storage: Protos Protos (r:32 w:0)
+	// Storage: Fragments InstanceAttributes (r:1 w:1)
+	/// The range of component `n` is `[1, 32]`.
+	fn set_instance_attributes(n: u32, ) -> Weight {
+		Weight::from_ref_time(27_635_000 as u64)
+			// Standard Error: 7_000
+			.saturating_add(Weight::from_ref_time(8_412_000 as u64).saturating_mul(n as u64))
+			.saturating_add(T::DbWeight::get().reads(4 as u64))
+			.saturating_add(T::DbWeight::get().reads((3 as u64).saturating_mul(n as u64)))
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+	}
+	// Storage: Fragments Definitions (r:1 w:1)
+	// Storage: Detach DetachedHashes (r:1 w:0)
+	// Storage: Fragments DefinitionsByOwner (r:2 w:2)
+	fn transfer_definition() -> Weight {
+		Weight::from_ref_time(28_903_000 as u64)
+			.saturating_add(T::DbWeight::get().reads(4 as u64))
+			.saturating_add(T::DbWeight::get().writes(3 as u64))
+	}
 }
 
 // For backwards compatibility and tests
@@ -193,14 +745,46 @@ impl WeightInfo for () {
 	// Storage: Assets Asset (r:1 w:0)
 	// Storage: System Account (r:1 w:1)
 	// Storage: Fragments Proto2Fragments (r:1 w:1)
+	// Storage: Fragments DefinitionsByOwner (r:1 w:1)
 	/// The range of component `n` is `[1, 100]`.
 	fn create(n: u32, ) -> Weight {
 		Weight::from_ref_time(53_058_000 as u64)
 			// Standard Error: 9_000
 			.saturating_add(Weight::from_ref_time(10_000 as u64).saturating_mul(n as u64))
+			.saturating_add(RocksDbWeight::get().reads(7 as u64))
+			.saturating_add(RocksDbWeight::get().writes(4 as u64))
+	}
+	// Storage: Fragments Definitions (r:1 w:1)
+	// Storage: Fragments MetadataKeyPolicies (r:1 w:0)
+	// Storage: Detach DetachedHashes (r:1 w:0)
+	// Storage: unknown [0x3a65787472696e7369635f696e646578] (r:1 w:0)
+	// Storage: Fragments MetaKeys (r:1 w:1)
+	// Storage: Fragments MetaKeysIndex (r:1 w:1)
+	/// The range of component `d` is `[1, 1000000]`.
+	fn set_definition_metadata(d: u32, ) -> Weight {
+		Weight::from_ref_time(36_712_000 as u64)
+			// Standard Error: 0
+			.saturating_add(Weight::from_ref_time(2_000 as u64).saturating_mul(d as u64))
 			.saturating_add(RocksDbWeight::get().reads(6 as u64))
 			.saturating_add(RocksDbWeight::get().writes(3 as u64))
 	}
+	// Storage: Detach DetachedHashes (r:1 w:0)
+	// Storage: Fragments Fragments (r:1 w:1)
+	// Storage: Fragments MetadataKeyPolicies (r:1 w:0)
+	// Storage: Fragments Inventory (r:1 w:0)
+	// Storage: unknown [0x3a65787472696e7369635f696e646578] (r:1 w:0)
+	// Storage: Fragments MetaKeys (r:1 w:1)
+	// Storage: Fragments MetaKeysIndex (r:1 w:1)
+	// Storage: Fragments DataHashMapIndex (r:1 w:1)
+	// Storage: Fragments DataHashMap (r:0 w:1)
+	/// The range of component `d` is `[1, 1000000]`.
+	fn set_instance_metadata(d: u32, ) -> Weight {
+		Weight::from_ref_time(41_058_000 as u64)
+			// Standard Error: 0
+			.saturating_add(Weight::from_ref_time(2_000 as u64).saturating_mul(d as u64))
+			.saturating_add(RocksDbWeight::get().reads(8 as u64))
+			.saturating_add(RocksDbWeight::get().writes(5 as u64))
+	}
 	// Storage: Fragments Definitions (r:1 w:0)
 	// Storage: Protos Protos (r:1 w:0)
 	// Storage: Detach DetachedHashes (r:1 w:0)
@@ -227,6 +811,7 @@ impl WeightInfo for () {
 	// Storage: Fragments EditionsCount (r:1 w:1)
 	// Storage: Fragments Inventory (r:1 w:1)
 	// Storage: Fragments Owners (r:1 w:1)
+	// Storage: Fragments InstanceOwner (r:0 w:1)
 	// Storage: Fragments Fragments (r:0 w:1)
 	// Storage: Fragments CopiesCount (r:0 w:1)
 	/// The range of component `q` is `[1, 100]`.
@@ -236,7 +821,7 @@ impl WeightInfo for () {
 			.saturating_add(Weight::from_ref_time(8_255_000 as u64).saturating_mul(q as u64))
 			.saturating_add(RocksDbWeight::get().reads(7 as u64))
 			.saturating_add(RocksDbWeight::get().writes(4 as u64))
-			.saturating_add(RocksDbWeight::get().writes((2 as u64).saturating_mul(q as u64)))
+			.saturating_add(RocksDbWeight::get().writes((3 as u64).saturating_mul(q as u64)))
 	}
 	// Storage: Fragments Definitions (r:1 w:1)
 	// Storage: Protos Protos (r:1 w:0)
@@ -246,68 +831,554 @@ impl WeightInfo for () {
 	// Storage: Fragments EditionsCount (r:1 w:1)
 	// Storage: Fragments Inventory (r:1 w:1)
 	// Storage: Fragments Owners (r:1 w:1)
+	// Storage: Fragments InstanceOwner (r:0 w:1)
 	// Storage: Fragments Fragments (r:0 w:1)
 	// Storage: Fragments CopiesCount (r:0 w:1)
+	// Storage: Fragments UniqueDataValidators (r:1 w:0)
 	/// The range of component `d` is `[1, 1000000]`.
 	fn mint_definition_that_has_unique_capability(d: u32, ) -> Weight {
 		Weight::from_ref_time(77_922_000 as u64)
 			// Standard Error: 0
 			.saturating_add(Weight::from_ref_time(1_000 as u64).saturating_mul(d as u64))
-			.saturating_add(RocksDbWeight::get().reads(8 as u64))
-			.saturating_add(RocksDbWeight::get().writes(7 as u64))
+			.saturating_add(RocksDbWeight::get().reads(9 as u64))
+			.saturating_add(RocksDbWeight::get().writes(8 as u64))
 	}
 	// Storage: Fragments Publishing (r:1 w:1)
 	// Storage: Fragments Definitions (r:1 w:1)
-	// Storage: System Account (r:1 w:1)
+	// Storage: System Account (r:2 w:2)
 	// Storage: unknown [0x3a65787472696e7369635f696e646578] (r:1 w:0)
 	// Storage: Fragments EditionsCount (r:1 w:1)
 	// Storage: Fragments Inventory (r:1 w:1)
 	// Storage: Fragments Owners (r:1 w:1)
+	// Storage: Fragments InstanceOwner (r:0 w:1)
 	// Storage: Fragments Fragments (r:0 w:1)
 	// Storage: Fragments CopiesCount (r:0 w:1)
+	// Storage: Protos LicenseRoyalties (r:1 w:0)
+	// Storage: Protos Protos (r:1 w:0)
 	/// The range of component `q` is `[1, 100]`.
 	fn buy_definition_that_has_non_unique_capability(q: u32, ) -> Weight {
 		Weight::from_ref_time(49_276_000 as u64)
 			// Standard Error: 32_000
 			.saturating_add(Weight::from_ref_time(7_485_000 as u64).saturating_mul(q as u64))
-			.saturating_add(RocksDbWeight::get().reads(7 as u64))
-			.saturating_add(RocksDbWeight::get().writes(6 as u64))
-			.saturating_add(RocksDbWeight::get().writes((2 as u64).saturating_mul(q as u64)))
+			.saturating_add(RocksDbWeight::get().reads(10 as u64))
+			.saturating_add(RocksDbWeight::get().writes(7 as u64))
+			.saturating_add(RocksDbWeight::get().writes((3 as u64).saturating_mul(q as u64)))
 	}
 	// Storage: Fragments Publishing (r:1 w:1)
 	// Storage: Fragments Definitions (r:1 w:1)
-	// Storage: System Account (r:1 w:1)
+	// Storage: System Account (r:2 w:2)
 	// Storage: unknown [0x3a65787472696e7369635f696e646578] (r:1 w:0)
 	// Storage: Fragments UniqueData2Edition (r:1 w:1)
 	// Storage: Fragments EditionsCount (r:1 w:1)
 	// Storage: Fragments Inventory (r:1 w:1)
 	// Storage: Fragments Owners (r:1 w:1)
+	// Storage: Fragments InstanceOwner (r:0 w:1)
 	// Storage: Fragments Fragments (r:0 w:1)
 	// Storage: Fragments CopiesCount (r:0 w:1)
+	// Storage: Fragments UniqueDataValidators (r:1 w:0)
+	// Storage: Protos LicenseRoyalties (r:1 w:0)
+	// Storage: Protos Protos (r:1 w:0)
 	/// The range of component `d` is `[1, 1000000]`.
 	fn buy_definition_that_has_unique_capability(d: u32, ) -> Weight {
 		Weight::from_ref_time(33_800_000 as u64)
 			// Standard Error: 0
 			.saturating_add(Weight::from_ref_time(2_000 as u64).saturating_mul(d as u64))
-			.saturating_add(RocksDbWeight::get().reads(8 as u64))
-			.saturating_add(RocksDbWeight::get().writes(9 as u64))
+			.saturating_add(RocksDbWeight::get().reads(12 as u64))
+			.saturating_add(RocksDbWeight::get().writes(11 as u64))
 	}
 	// Storage: Fragments Fragments (r:1 w:1)
 	// Storage: Fragments Inventory (r:2 w:2)
 	// Storage: Fragments Owners (r:2 w:2)
+	// Storage: Fragments InstanceOwner (r:1 w:1)
+	// Storage: Fragments Rentals (r:1 w:1)
+	// Storage: Fragments InstanceApprovals (r:1 w:1)
+	// Storage: Fragments OperatorApprovals (r:1 w:0)
+	// Storage: Fragments LockedInstances (r:1 w:1)
+	// Storage: Fragments InstanceParent (r:1 w:1)
+	// Storage: Proxy Proxies (r:1 w:0)
 	fn benchmark_give_instance_that_does_not_have_copy_perms() -> Weight {
-		Weight::from_ref_time(36_669_000 as u64)
-			.saturating_add(RocksDbWeight::get().reads(5 as u64))
-			.saturating_add(RocksDbWeight::get().writes(5 as u64))
+		Weight::from_ref_time(41_203_000 as u64)
+			.saturating_add(RocksDbWeight::get().reads(12 as u64))
+			.saturating_add(RocksDbWeight::get().writes(10 as u64))
 	}
 	// Storage: Fragments Fragments (r:1 w:1)
 	// Storage: Fragments Inventory (r:2 w:1)
 	// Storage: Fragments CopiesCount (r:1 w:1)
 	// Storage: Fragments Owners (r:1 w:1)
-	// Storage: Fragments Expirations (r:1 w:1)
+	// Storage: Fragments InstanceOwner (r:1 w:1)
+	// Storage: Fragments ExpirationQueue (r:1 w:1)
+	// Storage: Fragments Rentals (r:1 w:0)
+	// Storage: Fragments InstanceApprovals (r:1 w:0)
+	// Storage: Fragments OperatorApprovals (r:1 w:0)
+	// Storage: Fragments LockedInstances (r:1 w:0)
+	// Storage: Fragments InstanceAttributes (r:1 w:1)
 	fn benchmark_give_instance_that_has_copy_perms() -> Weight {
-		Weight::from_ref_time(30_168_000 as u64)
+		Weight::from_ref_time(34_512_000 as u64)
+			.saturating_add(RocksDbWeight::get().reads(12 as u64))
+			.saturating_add(RocksDbWeight::get().writes(7 as u64))
+	}
+	// Storage: Fragments Definitions (r:1 w:0)
+	// Storage: Protos Protos (r:1 w:0)
+	// Storage: System Account (r:2 w:2)
+	fn withdraw_proceeds() -> Weight {
+		Weight::from_ref_time(41_285_000 as u64)
+			.saturating_add(RocksDbWeight::get().reads(4 as u64))
+			.saturating_add(RocksDbWeight::get().writes(2 as u64))
+	}
+	// Storage: Detach DetachedHashes (r:1 w:0)
+	// Storage: Fragments Fragments (r:2 w:1)
+	// Storage: Fragments Inventory (r:1 w:1)
+	// Storage: Fragments Owners (r:1 w:1)
+	// Storage: Fragments InstanceOwner (r:0 w:1)
+	// Storage: Fragments ExpirationQueue (r:1 w:1)
+	// Storage: Fragments Definition2SecondarySales (r:0 w:1)
+	// Storage: Fragments BurnedCount (r:1 w:1)
+	// Storage: Fragments RemovedCount (r:1 w:1)
+	// Storage: Fragments UniqueData2Edition (r:0 w:1)
+	// Storage: Fragments BurnedEditionsCount (r:1 w:1)
+	// Storage: Fragments Rentals (r:1 w:1)
+	// Storage: Fragments InstanceApprovals (r:0 w:1)
+	// Storage: Fragments LockedInstances (r:0 w:1)
+	// Storage: Fragments InstanceChildren (r:1 w:0)
+	// Storage: Fragments InstanceParent (r:1 w:1)
+	// Storage: Fragments UniqueDataHistory (r:0 w:1)
+	// Storage: Fragments InstanceAttributes (r:0 w:1)
+	fn burn() -> Weight {
+		Weight::from_ref_time(48_611_000 as u64)
+			.saturating_add(RocksDbWeight::get().reads(12 as u64))
+			.saturating_add(RocksDbWeight::get().writes(16 as u64))
+	}
+	// Storage: Fragments Definitions (r:1 w:0)
+	// Storage: Protos Protos (r:1 w:0)
+	// Storage: Fragments EditionsCount (r:1 w:0)
+	// Storage: Fragments BurnsReplenishSupply (r:0 w:1)
+	fn set_burns_replenish_supply() -> Weight {
+		Weight::from_ref_time(24_093_000 as u64)
+			.saturating_add(RocksDbWeight::get().reads(3 as u64))
+			.saturating_add(RocksDbWeight::get().writes(1 as u64))
+	}
+	// Storage: Detach DetachedHashes (r:1 w:0)
+	// Storage: Fragments Fragments (r:1 w:2)
+	// Storage: Fragments Inventory (r:2 w:1)
+	// Storage: Fragments CopiesCount (r:1 w:1)
+	// Storage: Fragments Owners (r:1 w:1)
+	// Storage: Fragments InstanceOwner (r:0 w:1)
+	// Storage: Fragments ExpirationQueue (r:1 w:1)
+	// Storage: Fragments Rentals (r:1 w:0)
+	// Storage: Fragments InstanceAttributes (r:1 w:1)
+	fn split_stack() -> Weight {
+		Weight::from_ref_time(45_870_000 as u64)
+			.saturating_add(RocksDbWeight::get().reads(9 as u64))
+			.saturating_add(RocksDbWeight::get().writes(8 as u64))
+	}
+	// Storage: Detach DetachedHashes (r:2 w:0)
+	// Storage: Fragments Fragments (r:2 w:2)
+	// Storage: Fragments Inventory (r:2 w:1)
+	// Storage: Fragments Owners (r:1 w:1)
+	// Storage: Fragments InstanceOwner (r:0 w:1)
+	// Storage: Fragments ExpirationQueue (r:1 w:1)
+	// Storage: Fragments Definition2SecondarySales (r:0 w:1)
+	// Storage: Fragments Rentals (r:2 w:1)
+	// Storage: Fragments InstanceApprovals (r:0 w:1)
+	// Storage: Fragments LockedInstances (r:0 w:1)
+	// Storage: Fragments InstanceChildren (r:2 w:0)
+	// Storage: Fragments InstanceParent (r:1 w:1)
+	// Storage: Fragments InstanceAttributes (r:2 w:1)
+	// Storage: Fragments RemovedCount (r:1 w:1)
+	fn merge_stacks() -> Weight {
+		Weight::from_ref_time(51_402_000 as u64)
+			.saturating_add(RocksDbWeight::get().reads(16 as u64))
+			.saturating_add(RocksDbWeight::get().writes(13 as u64))
+	}
+	// Storage: Detach DetachedHashes (r:1 w:0)
+	// Storage: Fragments Fragments (r:2 w:1)
+	// Storage: Fragments Inventory (r:1 w:1)
+	// Storage: Fragments Owners (r:1 w:1)
+	// Storage: Fragments InstanceOwner (r:0 w:1)
+	// Storage: Fragments ExpirationQueue (r:1 w:1)
+	// Storage: Fragments Definition2SecondarySales (r:0 w:1)
+	// Storage: Fragments BurnedCount (r:1 w:1)
+	// Storage: Fragments RemovedCount (r:1 w:1)
+	// Storage: Fragments UniqueData2Edition (r:0 w:1)
+	// Storage: Fragments BurnedEditionsCount (r:1 w:1)
+	// Storage: Fragments Rentals (r:1 w:1)
+	// Storage: Fragments InstanceApprovals (r:0 w:1)
+	// Storage: Fragments LockedInstances (r:0 w:1)
+	// Storage: Fragments InstanceChildren (r:1 w:0)
+	// Storage: Fragments InstanceParent (r:1 w:1)
+	// Storage: Fragments InstanceAttributes (r:0 w:1)
+	fn consume() -> Weight {
+		Weight::from_ref_time(49_233_000 as u64)
+			.saturating_add(RocksDbWeight::get().reads(12 as u64))
+			.saturating_add(RocksDbWeight::get().writes(15 as u64))
+	}
+	// Storage: Fragments Definitions (r:1 w:0)
+	// Storage: Protos Protos (r:1 w:0)
+	// Storage: Detach DetachedHashes (r:1 w:0)
+	// Storage: Fragments BurnsReplenishSupply (r:1 w:0)
+	// Storage: Fragments EditionsCount (r:1 w:1)
+	// Storage: unknown [0x3a65787472696e7369635f696e646578] (r:1 w:0)
+	// Storage: System Account (r:100 w:0)
+	// Storage: Fragments Inventory (r:100 w:100)
+	// Storage: Fragments Owners (r:100 w:100)
+	// Storage: Fragments InstanceOwner (r:0 w:100)
+	// Storage: Fragments Fragments (r:0 w:100)
+	// Storage: Fragments CopiesCount (r:0 w:100)
+	/// The range of component `r` is `[1, 100]`.
+	/// The range of component `q` is `[1, 100]`.
+	fn mint_to_many(r: u32, q: u32, ) -> Weight {
+		Weight::from_ref_time(38_112_000 as u64)
+			// Standard Error: 41_000
+			.saturating_add(Weight::from_ref_time(12_406_000 as u64).saturating_mul(r as u64))
+			// Standard Error: 41_000
+			.saturating_add(Weight::from_ref_time(8_255_000 as u64).saturating_mul(q as u64))
 			.saturating_add(RocksDbWeight::get().reads(6 as u64))
+			.saturating_add(RocksDbWeight::get().reads((3 as u64).saturating_mul(r as u64)))
+			.saturating_add(RocksDbWeight::get().writes(1 as u64))
+			.saturating_add(RocksDbWeight::get().writes((2 as u64).saturating_mul(r as u64)))
+			.saturating_add(RocksDbWeight::get().writes((3 as u64).saturating_mul(q as u64)))
+	}
+	// Storage: Fragments Definitions (r:1 w:0)
+	// Storage: Protos Protos (r:1 w:0)
+	// Storage: Detach DetachedHashes (r:1 w:0)
+	// Storage: Fragments ClaimCampaigns (r:1 w:1)
+	fn start_claim() -> Weight {
+		Weight::from_ref_time(29_871_000 as u64)
+			.saturating_add(RocksDbWeight::get().reads(4 as u64))
+			.saturating_add(RocksDbWeight::get().writes(1 as u64))
+	}
+	// Storage: Fragments Definitions (r:1 w:0)
+	// Storage: Protos Protos (r:1 w:0)
+	// Storage: Fragments ClaimCampaigns (r:1 w:1)
+	fn end_claim() -> Weight {
+		Weight::from_ref_time(26_540_000 as u64)
+			.saturating_add(RocksDbWeight::get().reads(3 as u64))
+			.saturating_add(RocksDbWeight::get().writes(1 as u64))
+	}
+	// Storage: Fragments ClaimCampaigns (r:1 w:0)
+	// Storage: Fragments Definitions (r:1 w:0)
+	// Storage: Detach DetachedHashes (r:1 w:0)
+	// Storage: Fragments Claimed (r:1 w:1)
+	// Storage: Fragments BurnsReplenishSupply (r:1 w:0)
+	// Storage: Fragments EditionsCount (r:1 w:1)
+	// Storage: unknown [0x3a65787472696e7369635f696e646578] (r:1 w:0)
+	// Storage: System Account (r:1 w:0)
+	// Storage: Fragments Inventory (r:1 w:1)
+	// Storage: Fragments Owners (r:1 w:1)
+	// Storage: Fragments InstanceOwner (r:0 w:1)
+	// Storage: Fragments Fragments (r:0 w:1)
+	// Storage: Fragments CopiesCount (r:0 w:1)
+	/// The range of component `q` is `[1, 100]`.
+	fn claim_definition_that_has_non_unique_capability(q: u32, ) -> Weight {
+		Weight::from_ref_time(61_403_000 as u64)
+			// Standard Error: 128_000
+			.saturating_add(Weight::from_ref_time(8_255_000 as u64).saturating_mul(q as u64))
+			.saturating_add(RocksDbWeight::get().reads(10 as u64))
+			.saturating_add(RocksDbWeight::get().writes(4 as u64))
+			.saturating_add(RocksDbWeight::get().writes((3 as u64).saturating_mul(q as u64)))
+	}
+	// Storage: Fragments ClaimCampaigns (r:1 w:0)
+	// Storage: Fragments Definitions (r:1 w:1)
+	// Storage: Detach DetachedHashes (r:1 w:0)
+	// Storage: Fragments Claimed (r:1 w:1)
+	// Storage: Fragments BurnsReplenishSupply (r:1 w:0)
+	// Storage: unknown [0x3a65787472696e7369635f696e646578] (r:1 w:0)
+	// Storage: Fragments UniqueData2Edition (r:1 w:1)
+	// Storage: Fragments EditionsCount (r:1 w:1)
+	// Storage: System Account (r:1 w:0)
+	// Storage: Fragments Inventory (r:1 w:1)
+	// Storage: Fragments Owners (r:1 w:1)
+	// Storage: Fragments InstanceOwner (r:0 w:1)
+	// Storage: Fragments Fragments (r:0 w:1)
+	// Storage: Fragments CopiesCount (r:0 w:1)
+	// Storage: Fragments UniqueDataValidators (r:1 w:0)
+	/// The range of component `d` is `[1, 1000000]`.
+	fn claim_definition_that_has_unique_capability(d: u32, ) -> Weight {
+		Weight::from_ref_time(103_957_000 as u64)
+			// Standard Error: 0
+			.saturating_add(Weight::from_ref_time(1_000 as u64).saturating_mul(d as u64))
+			.saturating_add(RocksDbWeight::get().reads(12 as u64))
+			.saturating_add(RocksDbWeight::get().writes(9 as u64))
+	}
+	// Storage: Fragments ExpirationCursor (r:1 w:1)
+	// Storage: Fragments ExpirationQueue (r:101 w:100)
+	// Storage: Fragments Fragments (r:0 w:100)
+	// Storage: Fragments Definition2SecondarySales (r:0 w:100)
+	// Storage: Fragments InstanceOwner (r:100 w:100)
+	// Storage: Fragments Owners (r:100 w:100)
+	// Storage: Fragments Inventory (r:100 w:100)
+	// Storage: Fragments Rentals (r:0 w:100)
+	// Storage: Fragments InstanceApprovals (r:0 w:100)
+	// Storage: Fragments LockedInstances (r:0 w:100)
+	// Storage: Fragments InstanceParent (r:100 w:100)
+	// Storage: Fragments RemovedCount (r:100 w:100)
+	/// The range of component `n` is `[0, 100]`.
+	fn sweep_expirations(n: u32, ) -> Weight {
+		Weight::from_ref_time(9_734_000 as u64)
+			// Standard Error: 23_000
+			.saturating_add(Weight::from_ref_time(15_961_000 as u64).saturating_mul(n as u64))
+			.saturating_add(RocksDbWeight::get().reads(2 as u64))
+			.saturating_add(RocksDbWeight::get().reads((6 as u64).saturating_mul(n as u64)))
+			.saturating_add(RocksDbWeight::get().writes(1 as u64))
+			.saturating_add(RocksDbWeight::get().writes((11 as u64).saturating_mul(n as u64)))
+	}
+	// Storage: Detach DetachedHashes (r:1 w:0)
+	// Storage: Fragments Fragments (r:1 w:1)
+	// Storage: Fragments InstanceOwner (r:1 w:0)
+	// Storage: Fragments Publishing (r:1 w:0)
+	// Storage: Fragments Definitions (r:1 w:0)
+	// Storage: System Account (r:2 w:2)
+	// Storage: Fragments ExpirationQueue (r:0 w:2)
+	fn renew() -> Weight {
+		Weight::from_ref_time(58_297_000 as u64)
+			.saturating_add(RocksDbWeight::get().reads(7 as u64))
 			.saturating_add(RocksDbWeight::get().writes(5 as u64))
 	}
+	// Storage: Detach DetachedHashes (r:1 w:0)
+	// Storage: Fragments Fragments (r:1 w:0)
+	// Storage: Fragments Inventory (r:1 w:0)
+	// Storage: Fragments Rentals (r:1 w:1)
+	// Storage: Fragments LockedInstances (r:1 w:0)
+	fn rent() -> Weight {
+		Weight::from_ref_time(31_604_000 as u64)
+			.saturating_add(RocksDbWeight::get().reads(5 as u64))
+			.saturating_add(RocksDbWeight::get().writes(1 as u64))
+	}
+	// Storage: Fragments Rentals (r:1 w:1)
+	fn end_rental() -> Weight {
+		Weight::from_ref_time(19_938_000 as u64)
+			.saturating_add(RocksDbWeight::get().reads(1 as u64))
+			.saturating_add(RocksDbWeight::get().writes(1 as u64))
+	}
+	// Storage: Fragments Fragments (r:1 w:0)
+	// Storage: Fragments InstanceOwner (r:1 w:0)
+	// Storage: Fragments OperatorApprovals (r:1 w:0)
+	// Storage: Fragments InstanceApprovals (r:0 w:1)
+	fn approve() -> Weight {
+		Weight::from_ref_time(27_415_000 as u64)
+			.saturating_add(RocksDbWeight::get().reads(3 as u64))
+			.saturating_add(RocksDbWeight::get().writes(1 as u64))
+	}
+	// Storage: Fragments Definitions (r:1 w:0)
+	// Storage: Fragments OperatorApprovals (r:0 w:1)
+	fn set_approval_for_all() -> Weight {
+		Weight::from_ref_time(21_760_000 as u64)
+			.saturating_add(RocksDbWeight::get().reads(1 as u64))
+			.saturating_add(RocksDbWeight::get().writes(1 as u64))
+	}
+	// Storage: Detach DetachedHashes (r:32 w:0)
+	// Storage: Fragments LockedInstances (r:32 w:32)
+	// Storage: Fragments Fragments (r:32 w:0)
+	// Storage: Fragments Inventory (r:32 w:0)
+	// Storage: Fragments Rentals (r:32 w:0)
+	// Storage: Fragments NextSwapId (r:1 w:1)
+	// Storage: Fragments Swaps (r:0 w:1)
+	/// The range of component `n` is `[1, 32]`.
+	fn propose_swap(n: u32, ) -> Weight {
+		Weight::from_ref_time(24_930_000 as u64)
+			// Standard Error: 9_000
+			.saturating_add(Weight::from_ref_time(14_318_000 as u64).saturating_mul(n as u64))
+			.saturating_add(RocksDbWeight::get().reads(1 as u64))
+			.saturating_add(RocksDbWeight::get().reads((5 as u64).saturating_mul(n as u64)))
+			.saturating_add(RocksDbWeight::get().writes(2 as u64))
+			.saturating_add(RocksDbWeight::get().writes((1 as u64).saturating_mul(n as u64)))
+	}
+	// Storage: Fragments Swaps (r:1 w:1)
+	// Storage: Fragments InstanceOwner (r:32 w:32)
+	// Storage: Fragments LockedInstances (r:0 w:32)
+	// Storage: Detach DetachedHashes (r:32 w:0)
+	// Storage: Fragments Fragments (r:32 w:32)
+	// Storage: Fragments Inventory (r:64 w:64)
+	// Storage: Fragments Owners (r:64 w:64)
+	// Storage: Fragments Rentals (r:32 w:32)
+	// Storage: Fragments InstanceApprovals (r:0 w:32)
+	// Storage: Fragments InstanceParent (r:32 w:32)
+	// Storage: Proxy Proxies (r:32 w:0)
+	/// The range of component `n` is `[1, 32]`.
+	fn accept_swap(n: u32, ) -> Weight {
+		Weight::from_ref_time(29_508_000 as u64)
+			// Standard Error: 21_000
+			.saturating_add(Weight::from_ref_time(33_746_000 as u64).saturating_mul(n as u64))
+			.saturating_add(RocksDbWeight::get().reads(1 as u64))
+			.saturating_add(RocksDbWeight::get().reads((10 as u64).saturating_mul(n as u64)))
+			.saturating_add(RocksDbWeight::get().writes(1 as u64))
+			.saturating_add(RocksDbWeight::get().writes((10 as u64).saturating_mul(n as u64)))
+	}
+	// Storage: Fragments Swaps (r:1 w:1)
+	// Storage: Fragments InstanceOwner (r:32 w:0)
+	// Storage: Fragments LockedInstances (r:0 w:32)
+	/// The range of component `n` is `[1, 32]`.
+	fn cancel_swap(n: u32, ) -> Weight {
+		Weight::from_ref_time(20_114_000 as u64)
+			// Standard Error: 4_000
+			.saturating_add(Weight::from_ref_time(4_205_000 as u64).saturating_mul(n as u64))
+			.saturating_add(RocksDbWeight::get().reads(1 as u64))
+			.saturating_add(RocksDbWeight::get().reads((1 as u64).saturating_mul(n as u64)))
+			.saturating_add(RocksDbWeight::get().writes(1 as u64))
+			.saturating_add(RocksDbWeight::get().writes((1 as u64).saturating_mul(n as u64)))
+	}
+	// Storage: Detach DetachedHashes (r:2 w:0)
+	// Storage: Fragments Fragments (r:2 w:1)
+	// Storage: Fragments InstanceParent (r:3 w:2)
+	// Storage: Fragments InstanceOwner (r:1 w:1)
+	// Storage: Fragments LockedInstances (r:2 w:1)
+	// Storage: Fragments InstanceChildren (r:2 w:1)
+	// Storage: Fragments Inventory (r:2 w:2)
+	// Storage: Fragments Rentals (r:1 w:1)
+	// Storage: Fragments Owners (r:2 w:2)
+	// Storage: Fragments InstanceApprovals (r:0 w:1)
+	// Storage: Proxy Proxies (r:1 w:0)
+	fn nest() -> Weight {
+		Weight::from_ref_time(63_218_000 as u64)
+			.saturating_add(RocksDbWeight::get().reads(18 as u64))
+			.saturating_add(RocksDbWeight::get().writes(12 as u64))
+	}
+	// Storage: Fragments InstanceParent (r:3 w:1)
+	// Storage: Fragments InstanceOwner (r:1 w:1)
+	// Storage: Fragments LockedInstances (r:2 w:1)
+	// Storage: Detach DetachedHashes (r:1 w:0)
+	// Storage: Fragments Fragments (r:2 w:1)
+	// Storage: Fragments Inventory (r:2 w:2)
+	// Storage: Fragments Rentals (r:1 w:1)
+	// Storage: Fragments InstanceChildren (r:1 w:1)
+	// Storage: Fragments Owners (r:2 w:2)
+	// Storage: Fragments InstanceApprovals (r:0 w:1)
+	// Storage: Proxy Proxies (r:1 w:0)
+	fn unnest() -> Weight {
+		Weight::from_ref_time(52_904_000 as u64)
+			.saturating_add(RocksDbWeight::get().reads(16 as u64))
+			.saturating_add(RocksDbWeight::get().writes(11 as u64))
+	}
+	// Storage: Detach DetachedHashes (r:1 w:0)
+	// Storage: Fragments Fragments (r:1 w:0)
+	// Storage: Fragments InstanceOwner (r:1 w:0)
+	// Storage: Fragments LockedInstances (r:1 w:0)
+	fn as_instance() -> Weight {
+		Weight::from_ref_time(18_372_000 as u64)
+			.saturating_add(RocksDbWeight::get().reads(4 as u64))
+	}
+	// Storage: Fragments Definitions (r:32 w:0)
+	// Storage: Protos Protos (r:16 w:0)
+	// Storage: Detach DetachedHashes (r:16 w:0)
+	// Storage: Fragments NextRecipeId (r:1 w:1)
+	// Storage: Fragments Recipes (r:0 w:1)
+	/// The range of component `n` is `[1, 16]`.
+	fn register_recipe(n: u32, ) -> Weight {
+		Weight::from_ref_time(31_406_000 as u64)
+			// Standard Error: 6_000
+			.saturating_add(Weight::from_ref_time(9_872_000 as u64).saturating_mul(n as u64))
+			.saturating_add(RocksDbWeight::get().reads(2 as u64))
+			.saturating_add(RocksDbWeight::get().reads((3 as u64).saturating_mul(n as u64)))
+			.saturating_add(RocksDbWeight::get().writes(2 as u64))
+	}
+	// Storage: Fragments Recipes (r:1 w:1)
+	fn set_recipe_enabled() -> Weight {
+		Weight::from_ref_time(20_114_000 as u64)
+			.saturating_add(RocksDbWeight::get().reads(1 as u64))
+			.saturating_add(RocksDbWeight::get().writes(1 as u64))
+	}
+	// Storage: Fragments Recipes (r:1 w:0)
+	// Storage: Detach DetachedHashes (r:33 w:0)
+	// Storage: Fragments Fragments (r:32 w:32)
+	// Storage: Fragments InstanceOwner (r:32 w:32)
+	// Storage: Fragments Inventory (r:33 w:33)
+	// Storage: Fragments Rentals (r:32 w:0)
+	// Storage: Fragments InstanceChildren (r:32 w:0)
+	// Storage: Fragments InstanceParent (r:32 w:32)
+	// Storage: Fragments Owners (r:33 w:33)
+	// Storage: Fragments RemovedCount (r:32 w:32)
+	// Storage: Fragments EditionsCount (r:2 w:2)
+	// Storage: Fragments BurnedEditionsCount (r:1 w:1)
+	// Storage: Fragments Definitions (r:2 w:1)
+	// Storage: Protos Protos (r:1 w:0)
+	// Storage: System Account (r:2 w:2)
+	// Storage: unknown [0x3a65787472696e7369635f696e646578] (r:1 w:0)
+	// Storage: Fragments CopiesCount (r:0 w:100)
+	/// The range of component `n` is `[1, 32]`.
+	/// The range of component `q` is `[1, 100]`.
+	fn craft(n: u32, q: u32, ) -> Weight {
+		Weight::from_ref_time(61_873_000 as u64)
+			// Standard Error: 23_000
+			.saturating_add(Weight::from_ref_time(24_519_000 as u64).saturating_mul(n as u64))
+			// Standard Error: 8_000
+			.saturating_add(Weight::from_ref_time(8_302_000 as u64).saturating_mul(q as u64))
+			.saturating_add(RocksDbWeight::get().reads(10 as u64))
+			.saturating_add(RocksDbWeight::get().reads((8 as u64).saturating_mul(n as u64)))
+			.saturating_add(RocksDbWeight::get().writes(9 as u64))
+			.saturating_add(RocksDbWeight::get().writes((6 as u64).saturating_mul(n as u64)))
+			.saturating_add(RocksDbWeight::get().writes((5 as u64).saturating_mul(q as u64)))
+	}
+	// Storage: Fragments Definitions (r:1 w:0)
+	// Storage: Protos Protos (r:1 w:0)
+	// Storage: Fragments EditionsCount (r:1 w:0)
+	// Storage: Fragments UniqueDataValidators (r:0 w:1)
+	fn set_unique_data_validator() -> Weight {
+		Weight::from_ref_time(24_387_000 as u64)
+			.saturating_add(RocksDbWeight::get().reads(3 as u64))
+			.saturating_add(RocksDbWeight::get().writes(1 as u64))
+	}
+	// Storage: Fragments Definitions (r:1 w:0)
+	// Storage: Detach DetachedHashes (r:1 w:0)
+	// Storage: Fragments Fragments (r:2 w:1)
+	// Storage: Fragments InstanceOwner (r:1 w:0)
+	// Storage: Fragments Rentals (r:1 w:0)
+	// Storage: Fragments InstanceChildren (r:1 w:0)
+	// Storage: Fragments UniqueData2Edition (r:1 w:2)
+	// Storage: Fragments UniqueDataValidators (r:1 w:0)
+	// Storage: unknown [0x3a65787472696e7369635f696e646578] (r:1 w:0)
+	// Storage: Fragments UniqueDataHistory (r:1 w:1)
+	/// The range of component `d` is `[1, 1000000]`.
+	fn update_unique_data(d: u32, ) -> Weight {
+		Weight::from_ref_time(41_236_000 as u64)
+			// Standard Error: 0
+			.saturating_add(Weight::from_ref_time(2_000 as u64).saturating_mul(d as u64))
+			.saturating_add(RocksDbWeight::get().reads(11 as u64))
+			.saturating_add(RocksDbWeight::get().writes(4 as u64))
+	}
+	// Storage: Fragments Definitions (r:1 w:0)
+	// Storage: Protos Protos (r:1 w:0)
+	// Storage: Detach DetachedHashes (r:1 w:0)
+	// Storage: Fragments MetadataKeyPolicies (r:0 w:1)
+	fn set_metadata_key_policy() -> Weight {
+		Weight::from_ref_time(25_904_000 as u64)
+			.saturating_add(RocksDbWeight::get().reads(3 as u64))
+			.saturating_add(RocksDbWeight::get().writes(1 as u64))
+	}
+	// Storage: Fragments Definitions (r:1 w:0)
+	// Storage: Protos Protos (r:2 w:0)
+	// Storage: Detach DetachedHashes (r:1 w:0)
+	// Storage: Fragments EditionsCount (r:1 w:0)
+	// Storage: Fragments AttributeSchemas (r:0 w:1)
+	/// The range of component `n` is `[1, 32]`.
+	fn set_attribute_schema(n: u32, ) -> Weight {
+		Weight::from_ref_time(30_118_000 as u64)
+			// Standard Error: 4_000
+			.saturating_add(Weight::from_ref_time(1_274_000 as u64).saturating_mul(n as u64))
+			.saturating_add(RocksDbWeight::get().reads(5 as u64))
+			.saturating_add(RocksDbWeight::get().writes(1 as u64))
+	}
+	// Storage: Detach DetachedHashes (r:1 w:0)
+	// Storage: Fragments Fragments (r:1 w:0)
+	// Storage: Fragments AttributeSchemas (r:1 w:0)
+	// Storage: Fragments MetadataKeyPolicies (r:32 w:0)
+	// Storage: Fragments Definitions (r:32 w:0)
+	// Storage: Protos Protos (r:32 w:0)
+	// Storage: Fragments InstanceAttributes (r:1 w:1)
+	/// The range of component `n` is `[1, 32]`.
+	fn set_instance_attributes(n: u32, ) -> Weight {
+		Weight::from_ref_time(27_635_000 as u64)
+			// Standard Error: 7_000
+			.saturating_add(Weight::from_ref_time(8_412_000 as u64).saturating_mul(n as u64))
+			.saturating_add(RocksDbWeight::get().reads(4 as u64))
+			.saturating_add(RocksDbWeight::get().reads((3 as u64).saturating_mul(n as u64)))
+			.saturating_add(RocksDbWeight::get().writes(1 as u64))
+	}
+	// Storage: Fragments Definitions (r:1 w:1)
+	// Storage: Detach DetachedHashes (r:1 w:0)
+	// Storage: Fragments DefinitionsByOwner (r:2 w:2)
+	fn transfer_definition() -> Weight {
+		Weight::from_ref_time(28_903_000 as u64)
+			.saturating_add(RocksDbWeight::get().reads(4 as u64))
+			.saturating_add(RocksDbWeight::get().writes(3 as u64))
+	}
 }
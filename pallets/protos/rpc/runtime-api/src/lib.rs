@@ -32,5 +32,9 @@ sp_api::decl_runtime_apis! {
 		fn get_protos(params: GetProtosParams<AccountId, Vec<u8>>) -> Result<Vec<u8>, Vec<u8>>;
 		/// **Query** the Genealogy of a Proto-Fragment based on **`params`**
 		fn get_genealogy(params: GetGenealogyParams<Vec<u8>>) -> Result<Vec<u8>, Vec<u8>>;
+		/// Get the **hashes of the chunks** (in order) of a **Proto-Fragment created by a Multi-Part Upload**
+		fn get_data_chunks(proto_hash: [u8; 32]) -> Option<Vec<[u8; 32]>>;
+		/// Get the **binary CID** of the **current data** of a **Proto-Fragment**
+		fn get_cid(proto_hash: [u8; 32]) -> Option<Vec<u8>>;
 	}
 }
@@ -3,7 +3,7 @@
 use std::sync::Arc;
 
 use base64::{engine::general_purpose::STANDARD, Engine};
-use codec::Codec;
+use codec::{Codec, Decode};
 use jsonrpsee::{
 	core::{Error as JsonRpseeError, RpcResult},
 	proc_macros::rpc,
@@ -32,7 +32,7 @@ pub trait ProtosRpc<BlockHash, AccountId> {
 	) -> RpcResult<String>;
 
 	/// **Query** the Genealogy of a Proto-Fragment based on **`params`**.
-	/// The **return type** is a **JSON string** that represents an Adjacency List.
+	/// The **return type** is a **string** in the format `param.format` (i.e a JSON Adjacency List, a DOT digraph or a GraphML document).
 	#[method(name = "getGenealogy")]
 	fn get_genealogy(
 		&self,
@@ -44,6 +44,23 @@ pub trait ProtosRpc<BlockHash, AccountId> {
 	/// The **return type** is base64 encoded **bytes**.
 	#[method(name = "getData")]
 	fn get_data(&self, proto_hash: BlockHash, at: Option<BlockHash>) -> RpcResult<String>;
+
+	/// **Query** and **Return** the data of a **Proto-Fragment** that was **uploaded in multiple parts**,
+	/// by reassembling its chunks from the indexed transactions.
+	/// The **return type** is base64 encoded **bytes**.
+	#[method(name = "getChunkedData")]
+	fn get_chunked_data(&self, proto_hash: BlockHash, at: Option<BlockHash>) -> RpcResult<String>;
+
+	/// **Query** and **Return** the **CID** of the **current data** of a **Proto-Fragment**.
+	/// The **return type** is the **string representation** of the CID (i.e `"z" + base58btc` for a CIDv1).
+	#[method(name = "getCid")]
+	fn get_cid(&self, proto_hash: BlockHash, at: Option<BlockHash>) -> RpcResult<String>;
+
+	/// **Query** and **Return** the data that is **indexed on the Fragnova Blockchain** based on its **CID** `cid`
+	/// (i.e a CIDv1 whose codec is `raw` and whose multihash is `blake2b-256`).
+	/// The **return type** is base64 encoded **bytes**.
+	#[method(name = "getDataByCid")]
+	fn get_data_by_cid(&self, cid: String, at: Option<BlockHash>) -> RpcResult<String>;
 }
 
 /// An implementation of protos specific RPC methods.
@@ -66,7 +83,7 @@ where
 	C: Send + Sync + 'static,
 	C: ProvideRuntimeApi<Block>,
 	C: HeaderBackend<Block>,
-	C: BlockBackend<Block>, // used to call the function `BlockBackend::indexed_transaction()` in the RPC method `protos_getData`
+	C: BlockBackend<Block>, // used to call the function `BlockBackend::indexed_transaction()` in the RPC methods `protos_getData`, `protos_getChunkedData` and `protos_getDataByCid`
 	C::Api: ProtosRuntimeApi<Block, AccountId>,
 	AccountId: Codec,
 {
@@ -92,6 +109,7 @@ where
 			tags: param.tags.into_iter().map(|s| s.into_bytes()).collect(),
 			exclude_tags: param.exclude_tags.into_iter().map(|s| s.into_bytes()).collect(),
 			available: param.available,
+			data_availability: param.data_availability,
 		};
 
 		let result_outer = api.get_protos(at_hash, param_no_std).map(|list_bytes| {
@@ -119,6 +137,11 @@ where
 		let param_no_std = GetGenealogyParams::<Vec<u8>> {
 			proto_hash: param.proto_hash.into_bytes(),
 			get_ancestors: param.get_ancestors,
+			max_depth: param.max_depth,
+			max_nodes: param.max_nodes,
+			include_patches: param.include_patches,
+			annotate_edges: param.annotate_edges,
+			format: param.format,
 		};
 
 		let result = api.get_genealogy(at_hash, param_no_std).map(|list_bytes| {
@@ -150,6 +173,87 @@ where
 			Err(e) => Err(runtime_error_into_rpc_err(e)),
 		}
 	}
+
+	fn get_chunked_data(
+		&self,
+		proto_hash: <Block as BlockT>::Hash,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<String> {
+		let api = self.client.runtime_api();
+
+		// If the block hash is not supplied in `at`, use the best block's hash
+		let at_hash = at.unwrap_or_else(|| self.client.info().best_hash);
+
+		let proto_hash: [u8; 32] = proto_hash
+			.as_ref()
+			.try_into()
+			.map_err(|_| runtime_error_into_rpc_err("Invalid Proto-Fragment hash"))?;
+
+		let chunks = api
+			.get_data_chunks(at_hash, proto_hash)
+			.map_err(runtime_error_into_rpc_err)?
+			.ok_or_else(|| runtime_error_into_rpc_err("No chunks found for this Proto-Fragment"))?;
+
+		let mut data = Vec::new();
+		for chunk_hash in chunks.into_iter() {
+			let chunk_hash = <Block as BlockT>::Hash::decode(&mut &chunk_hash[..])
+				.map_err(runtime_error_into_rpc_err)?;
+			let chunk = self
+				.client
+				.indexed_transaction(chunk_hash)
+				.map_err(runtime_error_into_rpc_err)?
+				.ok_or_else(|| runtime_error_into_rpc_err("No indexed transaction found"))?;
+			data.extend(chunk);
+		}
+
+		Ok(STANDARD.encode(data))
+	}
+
+	fn get_cid(
+		&self,
+		proto_hash: <Block as BlockT>::Hash,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<String> {
+		let api = self.client.runtime_api();
+
+		// If the block hash is not supplied in `at`, use the best block's hash
+		let at_hash = at.unwrap_or_else(|| self.client.info().best_hash);
+
+		let proto_hash: [u8; 32] = proto_hash
+			.as_ref()
+			.try_into()
+			.map_err(|_| runtime_error_into_rpc_err("Invalid Proto-Fragment hash"))?;
+
+		let cid = api
+			.get_cid(at_hash, proto_hash)
+			.map_err(runtime_error_into_rpc_err)?
+			.ok_or_else(|| runtime_error_into_rpc_err("No CID found for this Proto-Fragment"))?;
+
+		sp_fragnova::cid::cid_to_string(&cid).map_err(runtime_error_into_rpc_err)
+	}
+
+	fn get_data_by_cid(
+		&self,
+		cid: String,
+		_at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<String> {
+		let cid = sp_fragnova::cid::cid_from_string(&cid)
+			.and_then(|cid| sp_fragnova::cid::decode_cid(&cid))
+			.map_err(runtime_error_into_rpc_err)?;
+		let data_hash = cid.get_fragnova_hash().ok_or_else(|| {
+			runtime_error_into_rpc_err("The CID does not point to data indexed on Fragnova")
+		})?;
+		let data_hash = <Block as BlockT>::Hash::decode(&mut &data_hash[..])
+			.map_err(runtime_error_into_rpc_err)?;
+
+		let data = self
+			.client
+			.indexed_transaction(data_hash)
+			.map_err(runtime_error_into_rpc_err)?
+			.ok_or_else(|| runtime_error_into_rpc_err("No indexed transaction found"))?;
+
+		Ok(STANDARD.encode(data))
+	}
 }
 
 fn runtime_error_into_rpc_err(err: impl std::fmt::Debug) -> JsonRpseeError {
@@ -6,10 +6,14 @@
 
 use super::*;
 use frame_benchmarking::{account, benchmarks, vec, whitelisted_caller};
-use frame_support::{traits::Get, BoundedVec};
+use frame_support::{
+	traits::{Currency, Get, ReservableCurrency},
+	BoundedVec,
+};
 use frame_system::RawOrigin;
-use protos::categories::{Categories, TextCategories};
+use protos::categories::{BinaryCategories, Categories, TextCategories};
 use sp_io::hashing::blake2_256;
+use sp_runtime::Perbill;
 
 use crate::Pallet as Protos;
 
@@ -19,13 +23,57 @@ fn assert_last_event<T: Config>(generic_event: <T as Config>::RuntimeEvent) {
 	frame_system::Pallet::<T>::assert_last_event(generic_event.into());
 }
 
+/// Give `who` enough balance to pay the deposit of the biggest Multi-Part Upload Session
+fn fund_account<T: Config>(who: &T::AccountId) {
+	let balance = T::UploadChunkDeposit::get()
+		.saturating_mul(T::MaxUploadChunks::get().into())
+		.saturating_mul(2u32.into())
+		.saturating_add(<T as pallet_balances::Config>::ExistentialDeposit::get());
+	<pallet_balances::Pallet<T> as Currency<T::AccountId>>::make_free_balance_be(who, balance);
+}
+
+/// Insert a Multi-Part Upload Session of `c` chunks (whose deposit is reserved from `caller`) directly in the storage
+fn insert_upload_session<T: Config>(
+	caller: &T::AccountId,
+	session_id: Hash256,
+	merkle_root: Hash256,
+	c: u32,
+) -> Result<(), sp_runtime::DispatchError> {
+	fund_account::<T>(caller);
+	let deposit = T::UploadChunkDeposit::get().saturating_mul(c.into());
+	<pallet_balances::Pallet<T> as ReservableCurrency<T::AccountId>>::reserve(caller, deposit)?;
+	let total_size = T::UploadChunkSize::get() as u64 * c as u64;
+	// the prefix must be valid for the category, since it is validated when the upload is finalized
+	let mut prefix = b"BLENDER-".to_vec();
+	prefix.resize(total_size.min(UPLOAD_VALIDATION_PREFIX_LENGTH) as usize, 0);
+	<UploadSessions<T>>::insert(
+		session_id,
+		UploadSession {
+			owner: caller.clone(),
+			category: Categories::Binary(BinaryCategories::BlendFile),
+			total_size,
+			merkle_root,
+			chunk_size: T::UploadChunkSize::get(),
+			chunk_count: c,
+			uploaded_chunks: c,
+			created_at: frame_system::Pallet::<T>::block_number(),
+			deposit,
+			prefix,
+		},
+	);
+	Ok(())
+}
+
 const MAX_REFERENCES_LENGTH: u32 = 100;
 const MAX_DATA_LENGTH: u32 = 1_000_000; // 1 MegaByte
 
 benchmarks! {
 
+	// `T::Public` and `T::Signature` bounds are needed to create a dummy `T::Public` value and a dummy `T::Signature` value
 	where_clause { where
-		T::AccountId: UncheckedFrom<T::Hash> + AsRef<[u8]>
+		T::AccountId: UncheckedFrom<T::Hash> + AsRef<[u8]>,
+		T::Public: From<sp_core::ed25519::Public>,
+		T::Signature: From<sp_core::ed25519::Signature>
 	}
 
 	upload {
@@ -143,11 +191,12 @@ benchmarks! {
 
 	transfer {
 		let caller: T::AccountId = whitelisted_caller();
+		let owner: T::AccountId = account("Sample", 101, SEED);
 		let new_owner: T::AccountId = account("Sample", 100, SEED);
 
 		let proto_data = b"Je suis Data".to_vec();
 		Protos::<T>::upload(
-			RawOrigin::Signed(caller.clone()).into(),
+			RawOrigin::Signed(owner.clone()).into(),
 			Vec::<Hash256>::new(),
 			Categories::Text(TextCategories::Plain),
 			Vec::<BoundedVec<u8, _>>::new().try_into().unwrap(),
@@ -158,6 +207,9 @@ benchmarks! {
 		)?;
 		let proto_hash = blake2_256(&proto_data);
 
+		// the caller is an operator approved by the owner, so that the approvals are also read
+		Protos::<T>::set_approval_for_all(RawOrigin::Signed(owner).into(), caller.clone(), true)?;
+
 	}: transfer(RawOrigin::Signed(caller), proto_hash, new_owner.clone())
 	verify {
 		assert_last_event::<T>(Event::<T>::Transferred { proto_hash: proto_hash, owner_id: new_owner }.into())
@@ -190,5 +242,190 @@ benchmarks! {
 		assert_last_event::<T>(Event::<T>::MetadataChanged { proto_hash: proto_hash, metadata_key: metadata_key.into() }.into())
 	}
 
+	begin_upload {
+		let caller: T::AccountId = whitelisted_caller();
+		fund_account::<T>(&caller);
+		let total_size = T::UploadChunkSize::get() as u64 * T::MaxUploadChunks::get() as u64;
+		let merkle_root = [7u8; 32];
+		let session_id = blake2_256(&(b"proto-upload-session", &caller, merkle_root).encode());
+	}: _(RawOrigin::Signed(caller.clone()), Categories::Binary(BinaryCategories::BlendFile), total_size, merkle_root)
+	verify {
+		assert_last_event::<T>(Event::<T>::UploadBegan { session_id: session_id, owner: caller }.into())
+	}
+
+	upload_chunk {
+		let d in 1 .. T::UploadChunkSize::get(); // `data` length
+		let caller: T::AccountId = whitelisted_caller();
+		fund_account::<T>(&caller);
+
+		let data = vec![7u8; d as usize];
+		// `data` is the last chunk, so that it can be smaller than `T::UploadChunkSize`
+		let total_size = T::UploadChunkSize::get() as u64 + d as u64;
+		let merkle_root = [7u8; 32];
+		Protos::<T>::begin_upload(
+			RawOrigin::Signed(caller.clone()).into(),
+			Categories::Binary(BinaryCategories::BlendFile),
+			total_size,
+			merkle_root,
+		)?;
+		let session_id = blake2_256(&(b"proto-upload-session", &caller, merkle_root).encode());
+
+	}: _(RawOrigin::Signed(caller), session_id, 1, data)
+	verify {
+		assert_last_event::<T>(Event::<T>::ChunkUploaded { session_id: session_id, index: 1 }.into())
+	}
+
+	finalize_upload {
+		let r in 1 .. MAX_REFERENCES_LENGTH; // `references` length
+		let t in 1 .. T::MaxTags::get(); // `tags` length
+		let c in 1 .. T::MaxUploadChunks::get(); // number of chunks
+		let caller: T::AccountId = whitelisted_caller();
+
+		let references: Vec<Hash256> = (0 .. r).into_iter().map(|i| -> Result<Hash256, sp_runtime::DispatchError> {
+			let proto_data = format!("{}", i).into_bytes();
+			Protos::<T>::upload(
+				RawOrigin::Signed(caller.clone()).into(),
+				Vec::<Hash256>::new(),
+				Categories::Text(TextCategories::Plain),
+				Vec::<BoundedVec<u8, _>>::new().try_into().unwrap(),
+				None,
+				UsageLicense::Closed,
+				None,
+				ProtoData::Local(proto_data.clone()),
+			)?;
+			let proto_hash = blake2_256(&proto_data);
+			Ok(proto_hash)
+		}).collect::<Result::<Vec<Hash256>, _>>()?;
+		let tags: BoundedVec::<BoundedVec<u8, _>, _> = (0 .. t).into_iter().map(|i| {
+			format!("{}", i).repeat(<T as pallet::Config>::StringLimit::get() as usize).into_bytes()[0..<T as pallet::Config>::StringLimit::get() as usize].to_vec().try_into().unwrap()
+		}).collect::<Vec<BoundedVec<u8, _>>>().try_into().unwrap();
+
+		// The chunks are written directly in the storage, since uploading `c` chunks of `T::UploadChunkSize` bytes is way too slow
+		let chunks: Vec<Hash256> = (0 .. c).into_iter().map(|i| blake2_256(&i.encode())).collect();
+		let merkle_root = sp_fragnova::merkle::merkle_root(&chunks);
+		let session_id = blake2_256(&(b"proto-upload-session", &caller, merkle_root).encode());
+		insert_upload_session::<T>(&caller, session_id, merkle_root, c)?;
+		for (index, chunk_hash) in chunks.into_iter().enumerate() {
+			<UploadChunks<T>>::insert(session_id, index as u32, chunk_hash);
+		}
+
+	}: _(RawOrigin::Signed(caller), session_id, references, tags, None, UsageLicense::Closed, None)
+	verify {
+		assert_last_event::<T>(Event::<T>::Uploaded { proto_hash: merkle_root }.into())
+	}
+
+	cancel_upload {
+		let c in 1 .. T::MaxUploadChunks::get(); // number of chunks
+		let caller: T::AccountId = whitelisted_caller();
+
+		let merkle_root = [7u8; 32];
+		let session_id = blake2_256(&(b"proto-upload-session", &caller, merkle_root).encode());
+		insert_upload_session::<T>(&caller, session_id, merkle_root, c)?;
+		for index in 0 .. c {
+			<UploadChunks<T>>::insert(session_id, index, blake2_256(&index.encode()));
+		}
+
+	}: _(RawOrigin::Signed(caller), session_id)
+	verify {
+		assert_last_event::<T>(Event::<T>::UploadCancelled { session_id: session_id }.into())
+	}
+
+	internal_attest_data {
+		let caller: T::AccountId = whitelisted_caller();
+
+		let arweave_tx = [7u8; 32];
+		Protos::<T>::upload(
+			RawOrigin::Signed(caller.clone()).into(),
+			Vec::<Hash256>::new(),
+			Categories::Binary(BinaryCategories::BlendFile),
+			Vec::<BoundedVec<u8, _>>::new().try_into().unwrap(),
+			None,
+			UsageLicense::Closed,
+			None,
+			ProtoData::Arweave(arweave_tx),
+		)?;
+		let proto_hash = blake2_256(&arweave_tx);
+
+		let block_number = frame_system::Pallet::<T>::block_number() + 1u32.into();
+		frame_system::Pallet::<T>::set_block_number(block_number);
+
+		// worst case scenario: the vote is the one that passes the threshold
+		let threshold = <T as pallet_accounts::Config>::Threshold::get();
+		if threshold > 1 {
+			let data_hash = (proto_hash, DataAvailability::Available, block_number).using_encoded(blake2_256);
+			<DataAvailabilityVoting<T>>::insert(data_hash, threshold - 1);
+		}
+
+		let data = DataAvailabilityVote::<T::Public, T::BlockNumber> {
+			public: sp_core::ed25519::Public([7u8; 32]).into(),
+			proto_hash,
+			status: DataAvailability::Available,
+			block_number,
+			_reserved1: None,
+			_reserved2: None,
+			_reserved3: None,
+		};
+		let signature: T::Signature = sp_core::ed25519::Signature([69u8; 64]).into(); // this can be anything since the signature is checked in `validate_unsigned()`
+	}: _(RawOrigin::None, data, signature)
+	verify {
+		assert_last_event::<T>(Event::<T>::DataAttested { proto_hash: proto_hash, status: DataAvailability::Available }.into())
+	}
+
+	approve {
+		let caller: T::AccountId = whitelisted_caller();
+		let owner: T::AccountId = account("Sample", 101, SEED);
+		let operator: T::AccountId = account("Sample", 100, SEED);
+
+		let proto_data = b"Je suis Data".to_vec();
+		Protos::<T>::upload(
+			RawOrigin::Signed(owner.clone()).into(),
+			Vec::<Hash256>::new(),
+			Categories::Text(TextCategories::Plain),
+			Vec::<BoundedVec<u8, _>>::new().try_into().unwrap(),
+			None,
+			UsageLicense::Closed,
+			None,
+			ProtoData::Local(proto_data.clone()),
+		)?;
+		let proto_hash = blake2_256(&proto_data);
+
+		// the caller is approved for all of the owner's protos, so that `OperatorApprovals` is also read
+		Protos::<T>::set_approval_for_all(RawOrigin::Signed(owner).into(), caller.clone(), true)?;
+
+	}: approve(RawOrigin::Signed(caller), proto_hash, Some(operator.clone()))
+	verify {
+		assert_last_event::<T>(Event::<T>::Approval { proto_hash, operator: Some(operator) }.into())
+	}
+
+	set_approval_for_all {
+		let caller: T::AccountId = whitelisted_caller();
+		let operator: T::AccountId = account("Sample", 100, SEED);
+	}: set_approval_for_all(RawOrigin::Signed(caller.clone()), operator.clone(), true)
+	verify {
+		assert_last_event::<T>(Event::<T>::ApprovalForAll { owner: caller, operator, approved: true }.into())
+	}
+
+	set_license_royalty {
+		let caller: T::AccountId = whitelisted_caller();
+
+		let proto_data = b"Je suis Data".to_vec();
+		Protos::<T>::upload(
+			RawOrigin::Signed(caller.clone()).into(),
+			Vec::<Hash256>::new(),
+			Categories::Text(TextCategories::Plain),
+			Vec::<BoundedVec<u8, _>>::new().try_into().unwrap(),
+			None,
+			UsageLicense::Open,
+			None,
+			ProtoData::Local(proto_data.clone()),
+		)?;
+		let proto_hash = blake2_256(&proto_data);
+
+		let royalty = Perbill::from_percent(10);
+	}: set_license_royalty(RawOrigin::Signed(caller), proto_hash, Some(royalty))
+	verify {
+		assert_last_event::<T>(Event::<T>::LicenseRoyaltyChanged { proto_hash, royalty: Some(royalty) }.into())
+	}
+
 	impl_benchmark_test_suite!(Protos, crate::mock::new_test_ext(), crate::mock::Test);
 }
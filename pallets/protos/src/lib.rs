@@ -29,6 +29,9 @@ use protos::{
 
 use sp_core::crypto::UncheckedFrom;
 
+use frame_support::traits::ReservableCurrency;
+use sp_runtime::traits::Saturating;
+
 use codec::{Compact, Decode, Encode};
 
 pub use pallet::*;
@@ -41,7 +44,7 @@ use sp_io::{
 	transaction_index,
 };
 use sp_std::{
-	collections::{btree_map::BTreeMap, vec_deque::VecDeque},
+	collections::{btree_map::BTreeMap, btree_set::BTreeSet, vec_deque::VecDeque},
 	ops::Deref,
 	vec,
 	vec::Vec,
@@ -50,9 +53,13 @@ use sp_std::{
 pub use weights::WeightInfo;
 
 pub use sp_fragnova::protos::{
-	LinkSource, LinkedAsset, Proto, ProtoData, ProtoOwner, ProtoPatch, UsageLicense,
+	LinkSource, LinkedAsset, Proto, ProtoData, ProtoOwner, ProtoPatch, UploadSession, UsageLicense,
+};
+use sp_fragnova::{http_get, merkle::merkle_root, Hash128, Hash256, Hash64};
+
+use frame_system::offchain::{
+	CreateSignedTransaction, SendUnsignedTransaction, SignedPayload, Signer, SigningTypes,
 };
-use sp_fragnova::{Hash128, Hash256, Hash64};
 
 use scale_info::prelude::{
 	format,
@@ -60,6 +67,8 @@ use scale_info::prelude::{
 };
 use serde_json::{json, Map, Value};
 
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+
 /// **Data Type** used to **Query and Filter for Proto-Fragments**
 #[derive(Encode, Decode, Clone, scale_info::TypeInfo)]
 #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
@@ -84,6 +93,10 @@ pub struct GetProtosParams<TAccountId, TString> {
 	pub exclude_tags: Vec<TString>,
 	/// Whether the Proto-Fragments should be available or not
 	pub available: Option<bool>,
+	/// The availability of the data of the returned Proto-Fragments (*optional*).
+	/// Proto-Fragments whose data is stored on the Fragnova Blockchain are considered `Available`,
+	/// while Proto-Fragments whose data is stored on Arweave or IPFS must have a matching ***DataAttestation***.
+	pub data_availability: Option<DataAvailability>,
 }
 #[cfg(test)]
 impl<TAccountId, TString> Default for GetProtosParams<TAccountId, TString> {
@@ -99,10 +112,24 @@ impl<TAccountId, TString> Default for GetProtosParams<TAccountId, TString> {
 			tags: Default::default(),
 			exclude_tags: Default::default(),
 			available: Default::default(),
+			data_availability: None,
 		}
 	}
 }
 
+/// **Enum** of the **formats** in which the **Genealogy of a Proto-Fragment** can be **returned**
+#[derive(Encode, Decode, Clone, PartialEq, Debug, Default, scale_info::TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum GenealogyFormat {
+	/// A JSON object that represents an Adjacency List
+	#[default]
+	Json,
+	/// A Graphviz DOT digraph (https://graphviz.org/doc/info/lang.html)
+	Dot,
+	/// A GraphML document (http://graphml.graphdrawing.org/)
+	GraphMl,
+}
+
 /// **Data Type** used to **Query the Genealogy of a Proto-Fragment**
 #[derive(Encode, Decode, Clone, scale_info::TypeInfo)]
 #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
@@ -111,6 +138,138 @@ pub struct GetGenealogyParams<TString> {
 	pub proto_hash: TString,
 	/// Whether to retrieve the ancestors of the Proto-Fragment. If `false`, the descendants are retrieved instead
 	pub get_ancestors: bool,
+	/// Maximum number of hops to walk away from `proto_hash` (*optional*).
+	/// The Proto-Fragments found at this depth are returned but not expanded.
+	#[cfg_attr(feature = "std", serde(default))]
+	pub max_depth: Option<u32>,
+	/// Maximum number of Proto-Fragments that the returned graph can contain (*optional*)
+	#[cfg_attr(feature = "std", serde(default))]
+	pub max_nodes: Option<u32>,
+	/// Whether to follow the references that were introduced by a patch of a Proto-Fragment (*optional*).
+	/// If `None`, the references introduced by patches are followed (like they were before the field was added).
+	#[cfg_attr(feature = "std", serde(default))]
+	pub include_patches: Option<bool>,
+	/// Whether every edge should be annotated with the block number and the patch index that introduced it
+	#[cfg_attr(feature = "std", serde(default))]
+	pub annotate_edges: bool,
+	/// The format of the returned graph
+	#[cfg_attr(feature = "std", serde(default))]
+	pub format: GenealogyFormat,
+}
+
+/// **Edge** of the **Genealogy graph of a Proto-Fragment**
+struct GenealogyEdge {
+	/// The Proto-Fragment at the other end of the edge
+	proto_hash: Hash256,
+	/// Block number in which the reference was introduced
+	block: u64,
+	/// Index of the patch that introduced the reference (`None` if it was introduced when the Proto-Fragment was uploaded)
+	patch: Option<u32>,
+}
+
+/// The timeout (in milliseconds) of the HTTP Requests made to fetch the data of Proto-Fragments that is not stored on the Fragnova Blockchain
+const DATA_FETCH_TIMEOUT_PERIOD: u64 = 5000;
+/// The number of blocks after which the availability of the data of a Proto-Fragment (that is not stored on the Fragnova Blockchain) is checked again
+const DATA_CHECK_INTERVAL: u32 = 14400;
+/// The maximum number of Proto-Fragments whose data availability is checked by an Offchain Worker in a single block
+const MAX_DATA_CHECKS_PER_BLOCK: usize = 4;
+/// The maximum number of Proto-Fragments that an Offchain Worker visits in a single block when looking for data to check
+const MAX_DATA_CHECK_SCANS_PER_BLOCK: usize = 256;
+
+/// **Traits** that **validate** the **data of a Proto-Fragment** against **its Category**
+pub trait ProtoDataValidator {
+	/// Whether `data` is valid data for a Proto-Fragment of the category `category`
+	fn is_valid(_category: &Categories, _data: &[u8]) -> bool {
+		true
+	}
+}
+
+impl ProtoDataValidator for () {}
+
+/// Whether a **Proto-Fragment** of the category `category` can be **uploaded in multiple parts** (see `Pallet::begin_upload()`).
+///
+/// The data of a Multi-Part Upload is never available as a whole on-chain, so the categories whose data must be processed
+/// as a whole when it is uploaded (Traits, Shards Scripts and Bundles) or validated as a whole (Text) cannot be uploaded in multiple parts.
+///
+/// The data of every other category is validated (see `ProtoDataValidator`) by checking the file signature at the start of the data,
+/// so validating the first `UPLOAD_VALIDATION_PREFIX_LENGTH` bytes of a Multi-Part Upload (see `finalize_upload()`)
+/// is the same as validating the reassembled data.
+/// A category whose validator inspects more than the start of the data must be added to this list.
+pub fn can_upload_in_chunks(category: &Categories) -> bool {
+	!matches!(
+		category,
+		Categories::Trait(_) | Categories::Shards(_) | Categories::Bundle | Categories::Text(_)
+	)
+}
+
+/// **Number of bytes** at the **start of the data** of a **Multi-Part Upload** that are **kept on-chain** until the upload is finalized,
+/// so that they can be validated (see `can_upload_in_chunks()`)
+pub const UPLOAD_VALIDATION_PREFIX_LENGTH: u64 = 1024;
+
+/// **Enum** that indicates **whether the data of a Proto-Fragment** (that is not stored on the Fragnova Blockchain) **is available**
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, Debug, scale_info::TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum DataAvailability {
+	/// The data was fetched and it is valid for the category of the Proto-Fragment
+	Available,
+	/// The data could not be fetched
+	Missing,
+	/// The data was fetched but it is not valid for the category of the Proto-Fragment
+	Invalid,
+}
+
+/// **Struct** of an **Attestation** (voted by the Fragnova Authorities) of the **availability of the data of a Proto-Fragment**
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Debug, scale_info::TypeInfo)]
+pub struct DataAttestation<TBlockNumber> {
+	/// Whether the data of the Proto-Fragment is available
+	pub status: DataAvailability,
+	/// The block number in which the data of the Proto-Fragment was last checked
+	pub checked_at: TBlockNumber,
+}
+
+/// **Struct** of a **vote** (sent by an Offchain Worker) on the **availability of the data of a Proto-Fragment**
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Debug, scale_info::TypeInfo)]
+pub struct DataAvailabilityVote<TPublic, TBlockNumber> {
+	/// Fragnova Public Account Address (the account address should be in FragKeys, otherwise it fails)
+	pub public: TPublic,
+	/// The Proto-Fragment whose data was checked
+	pub proto_hash: Hash256,
+	/// Whether the data of the Proto-Fragment is available
+	pub status: DataAvailability,
+	/// The block number in which the data of the Proto-Fragment was checked
+	pub block_number: TBlockNumber,
+	/// Reserved for future use
+	pub _reserved1: Option<()>,
+	/// Reserved for future use
+	pub _reserved2: Option<()>,
+	/// Reserved for future use
+	pub _reserved3: Option<()>,
+}
+
+/// **Struct** of a **Proto-Fragment** that is **added to the Genesis Block** (see `GenesisConfig`).
+///
+/// The tags and the metadata keys of `proto` are indexes that are only valid on the chain the Proto-Fragment was exported from,
+/// so they are overwritten using the **names** of the tags and the metadata keys (i.e `tags` and `metadata`).
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Debug, scale_info::TypeInfo)]
+pub struct GenesisProto<TAccountId, TBlockNumber> {
+	/// The hash of the Proto-Fragment
+	pub proto_hash: Hash256,
+	/// The *Proto* struct of the Proto-Fragment
+	pub proto: Proto<TAccountId, TBlockNumber>,
+	/// The names of the tags of the Proto-Fragment
+	pub tags: Vec<Vec<u8>>,
+	/// The Metadata of the Proto-Fragment (i.e a list of Metadata Keys and the hashes of their Metadata Objects)
+	pub metadata: Vec<(Vec<u8>, Hash256)>,
+	/// The name of the Trait, if the Proto-Fragment is a Trait
+	pub trait_name: Option<Vec<u8>>,
+	/// The hashes of the chunks of the data, if the Proto-Fragment was created by a Multi-Part Upload
+	pub chunks: Option<Vec<Hash256>>,
+}
+
+impl<T: SigningTypes> SignedPayload<T> for DataAvailabilityVote<T::Public, T::BlockNumber> {
+	fn public(&self) -> T::Public {
+		self.public.clone()
+	}
 }
 
 #[frame_support::pallet]
@@ -123,7 +282,14 @@ pub mod pallet {
 		DetachCollection, DetachHash, DetachRequest, DetachRequests, DetachedHashes,
 		SupportedChains,
 	};
-	use sp_runtime::SaturatedConversion;
+	use sp_core::{ed25519, offchain::Timestamp};
+	use sp_runtime::{
+		offchain::storage::StorageValueRef,
+		transaction_validity::{
+			InvalidTransaction, TransactionSource, TransactionValidity, ValidTransaction,
+		},
+		MultiSigner, Perbill, SaturatedConversion,
+	};
 
 	/// Configure the pallet by specifying the parameters and types on which it depends.
 	#[pallet::config]
@@ -133,6 +299,7 @@ pub mod pallet {
 		+ pallet_accounts::Config
 		+ pallet_contracts::Config
 		+ pallet_clusters::Config
+		+ CreateSignedTransaction<Call<Self>>
 	{
 		/// Because this pallet emits events, it depends on the runtime's definition of an event.
 		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
@@ -147,6 +314,38 @@ pub mod pallet {
 		/// The **maximum number of tags** that a **single Proto-Fragment** can be **tagged with**.
 		#[pallet::constant]
 		type MaxTags: Get<u32>;
+		/// The **size (in bytes)** of **every chunk** of a **Multi-Part Upload** (except the last one, which can be smaller).
+		#[pallet::constant]
+		type UploadChunkSize: Get<u32>;
+		/// The **maximum number of chunks** that the **data of a Multi-Part Upload** can be **split into**.
+		#[pallet::constant]
+		type MaxUploadChunks: Get<u32>;
+		/// The **amount** that is **reserved** from the **owner** of a **Multi-Part Upload Session** **for every chunk** of it.
+		/// The deposit is returned once the session is finalized or cancelled.
+		#[pallet::constant]
+		type UploadChunkDeposit: Get<<Self as pallet_balances::Config>::Balance>;
+		/// The **number of blocks** after which a **Multi-Part Upload Session** **expires**.
+		/// An expired session can no longer be uploaded to or finalized, and anyone can cancel it (see `cancel_upload()`).
+		#[pallet::constant]
+		type UploadSessionTimeout: Get<Self::BlockNumber>;
+		/// **Traits** that **validate** the **data of a Proto-Fragment** (that is not stored on the Fragnova Blockchain) **against its Category**
+		type DataValidator: ProtoDataValidator;
+	}
+
+	/// The Genesis Configuration for the Pallet.
+	#[pallet::genesis_config]
+	#[derive(Default)]
+	pub struct GenesisConfig {
+		/// **List of SCALE-encoded *GenesisProto* structs** of the **Proto-Fragments** that exist **in the Genesis Block**
+		/// (e.g the Proto-Fragments imported from a CAR archive using the node subcommand `import-protos`)
+		pub protos: Vec<Vec<u8>>,
+	}
+
+	#[pallet::genesis_build]
+	impl<T: Config> GenesisBuild<T> for GenesisConfig {
+		fn build(&self) {
+			Pallet::<T>::initialize_protos(&self.protos);
+		}
 	}
 
 	#[pallet::pallet]
@@ -203,6 +402,65 @@ pub mod pallet {
 	#[pallet::storage]
 	pub type ProtosByTrait<T: Config> = StorageMap<_, Identity, Hash64, Vec<Hash256>>;
 
+	/// **StorageMap** that maps a **Multi-Part Upload Session ID** to an ***UploadSession* struct**
+	#[pallet::storage]
+	pub type UploadSessions<T: Config> = StorageMap<
+		_,
+		Identity,
+		Hash256,
+		UploadSession<T::AccountId, T::BlockNumber, <T as pallet_balances::Config>::Balance>,
+	>;
+
+	/// **StorageDoubleMap** that maps a **Multi-Part Upload Session ID** and a **chunk index**
+	/// to the **hash of the chunk's data**
+	#[pallet::storage]
+	pub type UploadChunks<T: Config> =
+		StorageDoubleMap<_, Identity, Hash256, Twox64Concat, u32, Hash256>;
+
+	/// **StorageMap** that maps a **Proto-Fragment created by a Multi-Part Upload** to the
+	/// **list of hashes of its chunks** (in order)
+	#[pallet::storage]
+	pub type ProtoChunks<T: Config> = StorageMap<_, Identity, Hash256, Vec<Hash256>>;
+
+	/// **StorageMap** that maps a **Proto-Fragment whose data is stored on Arweave or IPFS** to the
+	/// **latest *DataAttestation* struct of its data** (voted by the Fragnova Authorities)
+	#[pallet::storage]
+	pub type DataAttestations<T: Config> =
+		StorageMap<_, Identity, Hash256, DataAttestation<T::BlockNumber>>;
+
+	/// **StorageMap** that maps a **vote on the availability of the data of a Proto-Fragment** to the **number of votes it received**.
+	/// The key for this map is:
+	/// `blake2_256(encoded(<Proto-Fragment hash, Data Availability, Block Number of the check>))`
+	#[pallet::storage]
+	pub type DataAvailabilityVoting<T: Config> = StorageMap<_, Identity, Hash256, u64>;
+
+	/// **StorageMap** that maps a **Proto-Fragment** to the **Account ID** that is **approved to transfer it on behalf of its owner**
+	/// (see `approve()`). The approval is cleared whenever the Proto-Fragment is transferred.
+	#[pallet::storage]
+	pub type ProtoApprovals<T: Config> = StorageMap<_, Identity, Hash256, T::AccountId>;
+
+	/// **StorageDoubleMap** that maps an **owner's Account ID** and an **operator's Account ID** to `()`,
+	/// if the operator is **approved to transfer every Proto-Fragment** that the owner owns (see `set_approval_for_all()`)
+	#[pallet::storage]
+	pub type OperatorApprovals<T: Config> =
+		StorageDoubleMap<_, Blake2_128Concat, T::AccountId, Blake2_128Concat, T::AccountId, ()>;
+
+	/// **StorageMap** that maps a **Proto-Fragment** to the **block number until which it cannot be transferred**
+	/// (e.g because it is offered in a pending swap of `pallet-fragments`).
+	///
+	/// Footnotes:
+	///
+	/// A lock ends by itself once its block number is reached (see `is_locked()`),
+	/// its entry is removed when the Proto-Fragment is unlocked or transferred.
+	#[pallet::storage]
+	pub type LockedProtos<T: Config> = StorageMap<_, Identity, Hash256, T::BlockNumber>;
+
+	/// **StorageMap** that maps a **Proto-Fragment** to the **share of the sale price** of the Fragment Definitions created from it
+	/// that is **paid to its owner**, when the Fragment Definition was created by another account under the Proto-Fragment's license
+	/// (see `set_license_royalty()`)
+	#[pallet::storage]
+	pub type LicenseRoyalties<T: Config> = StorageMap<_, Identity, Hash256, Perbill>;
+
 	#[allow(missing_docs)]
 	#[pallet::event]
 	#[pallet::generate_deposit(pub(super) fn deposit_event)]
@@ -217,6 +475,20 @@ pub mod pallet {
 		Detached { proto_hash: Hash256, cid: Vec<u8> },
 		/// A Proto-Fragment was transferred
 		Transferred { proto_hash: Hash256, owner_id: T::AccountId },
+		/// A Multi-Part Upload Session began
+		UploadBegan { session_id: Hash256, owner: T::AccountId },
+		/// A chunk of a Multi-Part Upload Session was uploaded
+		ChunkUploaded { session_id: Hash256, index: u32 },
+		/// A Multi-Part Upload Session was cancelled
+		UploadCancelled { session_id: Hash256 },
+		/// The availability of the data of a Proto-Fragment was attested by the Fragnova Authorities
+		DataAttested { proto_hash: Hash256, status: DataAvailability },
+		/// The approved operator of a Proto-Fragment has changed (`None` if the approval was revoked)
+		Approval { proto_hash: Hash256, operator: Option<T::AccountId> },
+		/// An operator has been approved (or disapproved) to transfer every Proto-Fragment owned by `owner`
+		ApprovalForAll { owner: T::AccountId, operator: T::AccountId, approved: bool },
+		/// The license royalty of a Proto-Fragment has changed (`None` if the royalty was removed)
+		LicenseRoyaltyChanged { proto_hash: Hash256, royalty: Option<Perbill> },
 	}
 
 	// Errors inform users that something went wrong.
@@ -254,6 +526,36 @@ pub mod pallet {
 		RequiredTraitsMissing,
 		/// All the implementing traits of the Shards Script that you want to upload have not been implemented
 		TraitsNotImplemented,
+		/// Proto-Fragments of this category cannot be uploaded in multiple parts
+		CategoryNotChunkable,
+		/// The data is split into more chunks than allowed
+		UploadTooLarge,
+		/// Multi-Part Upload Session already exists
+		UploadSessionExists,
+		/// Multi-Part Upload Session not found
+		UploadSessionNotFound,
+		/// The chunk index is out of the range of the Multi-Part Upload Session
+		InvalidChunkIndex,
+		/// The chunk does not have the size expected at its index
+		InvalidChunkSize,
+		/// The chunk was already uploaded
+		ChunkAlreadyUploaded,
+		/// Not all the chunks of the Multi-Part Upload Session were uploaded
+		UploadIncomplete,
+		/// The Merkle Root of the uploaded chunks does not match the one declared when the upload began
+		MerkleRootMismatch,
+		/// The Multi-Part Upload Session has expired
+		UploadSessionExpired,
+		/// The data of the Proto-Fragment is stored on the Fragnova Blockchain
+		DataNotExternal,
+		/// The data of the Proto-Fragment was already checked in a more recent block, or it was patched after the check
+		StaleDataAttestation,
+		/// The IPFS data of the Proto-Fragment is not a valid binary CID
+		InvalidCid,
+		/// The Proto-Fragment is locked (e.g in a pending swap)
+		Locked,
+		/// The data of the Proto-Fragment is not valid for its category
+		ProtoDataNotValid,
 	}
 
 	// Dispatchable functions allows users to interact with the pallet and invoke state changes.
@@ -306,11 +608,8 @@ pub mod pallet {
 				Error::<T>::DuplicateProtoTagExists
 			); // TODO Review - Is `O(n ^ 2)` good? (Alternatively we can **use HashMap** or **sort the tags then check for equal consecutive elements** -  but I don't think it's worth it since `T::MaxTags` is small
 
-			let current_block_number = <frame_system::Pallet<T>>::block_number();
-
 			// hash the immutable data, this is also the unique proto id
-			// to compose the V1 Cid add this prefix to the hash: (str "z" (base58
-			// "0x0155a0e40220"))
+			// to compose the V1 Cid of a local proto use `sp_fragnova::cid::encode_cid()` (see `get_cid()`)
 			let (proto_hash, data_size, data_stored) = match &data {
 				ProtoData::Local(data) =>
 					if category == Categories::Bundle {
@@ -321,7 +620,14 @@ pub mod pallet {
 						(blake2_256(data), data.len(), ProtoData::Local(vec![]))
 					},
 				ProtoData::Arweave(data) => (blake2_256(data), 0usize, ProtoData::Arweave(*data)),
-				ProtoData::Ipfs(cid) => (blake2_256(cid), 0usize, ProtoData::Ipfs(*cid)),
+				ProtoData::Ipfs(cid) => {
+					Self::warn_deprecated_ipfs_data();
+					(blake2_256(cid), 0usize, ProtoData::Ipfs(*cid))
+				},
+				ProtoData::IpfsCid(cid) => {
+					ensure!(sp_fragnova::cid::decode_cid(cid).is_ok(), Error::<T>::InvalidCid);
+					(blake2_256(cid), 0usize, ProtoData::IpfsCid(cid.clone()))
+				},
 			};
 
 			// make sure the proto does not exist already!
@@ -401,62 +707,17 @@ pub mod pallet {
 				_ => category,
 			};
 
-			let owner = if let Some(link) = linked_asset {
-				ProtoOwner::ExternalAsset(link)
-			} else {
-				ProtoOwner::User(who.clone())
-			};
-
-			let tags = tags
-				.iter()
-				.map(|s| {
-					let s = s.deref();
-					let tag_index = <Tags<T>>::get(s);
-					if let Some(tag_index) = tag_index {
-						<Compact<u64>>::from(tag_index)
-					} else {
-						let next_index = <TagsIndex<T>>::try_get().unwrap_or_default() + 1;
-						<Tags<T>>::insert(s, next_index);
-						// storing is dangerous inside a closure
-						// but after this call we start storing..
-						// so it's fine here
-						<TagsIndex<T>>::put(next_index);
-						<Compact<u64>>::from(next_index)
-					}
-				})
-				.collect();
-
-			// store in the state the proto
-			let proto = Proto {
-				block: current_block_number,
-				patches: vec![],
+			Self::store_proto(
+				&who,
+				proto_hash,
+				references,
+				category.clone(),
+				&tags,
+				linked_asset,
 				license,
-				creator: who.clone(),
-				owner: owner.clone(),
-				references: references.clone(),
-				category: category.clone(),
-				tags,
-				metadata: BTreeMap::new(),
-				data: data_stored,
 				cluster,
-				_reserved1: None,
-				_reserved2: None,
-				_reserved3: None,
-			};
-
-			// store proto
-			<Protos<T>>::insert(proto_hash, proto);
-
-			// store by parent
-			for reference in references.into_iter() {
-				<ProtosByParent<T>>::append(reference, proto_hash);
-			}
-
-			// store by category (original)
-			<ProtosByCategory<T>>::append(category.clone(), proto_hash);
-
-			// store by owner
-			<ProtosByOwner<T>>::append(owner, proto_hash);
+				data_stored,
+			);
 
 			match &data {
 				ProtoData::Local(_data) => {
@@ -468,9 +729,6 @@ pub mod pallet {
 				_ => {},
 			};
 
-			// also emit event
-			Self::deposit_event(Event::Uploaded { proto_hash });
-
 			log::debug!("Uploaded proto: {:?}", proto_hash);
 
 			Ok(())
@@ -544,6 +802,10 @@ pub mod pallet {
 					ProtoData::Local(data) => {
 						ensure!(!data.is_empty(), Error::<T>::ProtoDataIsEmpty);
 					},
+					ProtoData::Ipfs(_) => Self::warn_deprecated_ipfs_data(),
+					ProtoData::IpfsCid(cid) => {
+						ensure!(sp_fragnova::cid::decode_cid(cid).is_ok(), Error::<T>::InvalidCid);
+					},
 					_ => {},
 				};
 			}
@@ -579,6 +841,7 @@ pub mod pallet {
 						},
 						ProtoData::Arweave(data) => (blake2_256(data), ProtoData::Arweave(*data)),
 						ProtoData::Ipfs(cid) => (blake2_256(cid), ProtoData::Ipfs(*cid)),
+						ProtoData::IpfsCid(cid) => (blake2_256(cid), ProtoData::IpfsCid(cid.clone())),
 					};
 
 					proto.patches.push(ProtoPatch {
@@ -594,6 +857,9 @@ pub mod pallet {
 					for new_reference in new_references.into_iter() {
 						<ProtosByParent<T>>::append(new_reference, proto_hash);
 					}
+
+					// The attestation was about the previous data of the Proto-Fragment
+					<DataAttestations<T>>::remove(&proto_hash);
 				}
 
 				// Overwrite license if not None
@@ -634,9 +900,12 @@ pub mod pallet {
 			Ok(())
 		}
 
-		/// **Transfer** the **ownership** of a **Proto-Fragment** from **`origin`** to
+		/// **Transfer** the **ownership** of a **Proto-Fragment** from its **owner** to
 		/// **`new_owner`**
 		///
+		/// Note: **Only** the **Proto-Fragment's owner** or an **operator approved by the owner**
+		/// (see `approve()` and `set_approval_for_all()`) is **allowed** to transfer the Proto-Fragment
+		///
 		/// # Arguments
 		///
 		/// * `origin` - The origin of the extrinsic function
@@ -655,9 +924,12 @@ pub mod pallet {
 			let proto: Proto<T::AccountId, T::BlockNumber> =
 				<Protos<T>>::get(&proto_hash).ok_or(Error::<T>::ProtoNotFound)?;
 
-			// make sure the caller is the owner
+			// make sure the caller is the owner (or is approved by the owner)
 			match proto.owner.clone() {
-				ProtoOwner::User(owner) => ensure!(owner == who, Error::<T>::Unauthorized),
+				ProtoOwner::User(owner) => ensure!(
+					owner == who || Self::is_approved(&owner, &who, &proto_hash),
+					Error::<T>::Unauthorized
+				),
 				ProtoOwner::ExternalAsset(_ext_asset) =>
 				// We don't allow updating external assets
 				{
@@ -671,29 +943,12 @@ pub mod pallet {
 				Error::<T>::Detached
 			);
 
-			// collect new owner
-			let new_owner_s = ProtoOwner::User(new_owner.clone());
+			// make sure the proto is not locked (e.g in a pending swap)
+			ensure!(!Self::is_locked(&proto_hash), Error::<T>::Locked);
 
 			// WRITING STATE FROM NOW
 
-			// remove proto from old owner
-			<ProtosByOwner<T>>::mutate(proto.owner, |protos_by_owner| {
-				if let Some(list) = protos_by_owner {
-					list.retain(|current_hash| proto_hash != *current_hash);
-				}
-			});
-
-			// add proto to new owner
-			<ProtosByOwner<T>>::append(new_owner_s.clone(), proto_hash);
-
-			// update proto
-			<Protos<T>>::mutate(&proto_hash, |proto| {
-				let proto = proto.as_mut().unwrap();
-				proto.owner = new_owner_s;
-			});
-
-			// emit event
-			Self::deposit_event(Event::Transferred { proto_hash, owner_id: new_owner });
+			Self::transfer_proto(&proto_hash, proto.owner, new_owner);
 
 			Ok(())
 		}
@@ -874,101 +1129,1110 @@ pub mod pallet {
 
 			Ok(())
 		}
-	}
 
-	impl<T: Config> Pallet<T>
-	where
-		T::AccountId: UncheckedFrom<T::Hash> + AsRef<[u8]>,
-	{
-		fn check_license(references: &[Hash256], who: &T::AccountId) -> DispatchResult {
-			// TODO this is not tested properly
-			for reference in references.iter() {
-				let proto = <Protos<T>>::get(reference);
-				if let Some(proto) = proto {
-					let owner = match proto.owner {
-						ProtoOwner::User(owner) => Some(owner),
-						_ => None,
-					};
+		/// **Begin** a **Multi-Part Upload** of a **Proto-Fragment** whose data is **too big to fit in a single extrinsic**.
+		///
+		/// The data must be split into chunks of `T::UploadChunkSize` bytes (the last chunk can be smaller),
+		/// which are then uploaded with `upload_chunk()`. Once all the chunks are uploaded, `finalize_upload()` creates the Proto-Fragment.
+		///
+		/// The hash of the Proto-Fragment created by a Multi-Part Upload is `merkle_root`.
+		/// Therefore, the same data gets a different Proto-Fragment hash when it is uploaded with `upload()`
+		/// (where the hash is the blake2_256 hash of the data) and when it is uploaded in multiple parts,
+		/// and it can be uploaded once in each way.
+		///
+		/// # Arguments
+		///
+		/// * `origin` - The origin of the extrinsic function
+		/// * `category` - **Category type** of the **Proto-Fragment**
+		/// * `total_size` - **Total size** (in bytes) of the **Proto-Fragment's data**
+		/// * `merkle_root` - **Merkle Root** of the **Binary Merkle Tree** whose leaves are the **blake2_256 hashes of the chunks** (see `sp_fragnova::merkle::merkle_root()`)
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::begin_upload())]
+		#[pallet::call_index(6)]
+		pub fn begin_upload(
+			origin: OriginFor<T>,
+			category: Categories,
+			total_size: u64,
+			merkle_root: Hash256,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
 
-					if let Some(owner) = owner {
-						if owner == *who {
-							// owner can include freely
-							continue
-						}
-					}
+			ensure!(can_upload_in_chunks(&category), Error::<T>::CategoryNotChunkable);
 
-					let license = proto.license;
-					match license {
-						UsageLicense::Closed => return Err(Error::<T>::Unauthorized.into()),
-						UsageLicense::Open => continue,
-						UsageLicense::Contract(contract_address) => {
-							let data = (reference, who.clone()).encode();
-							let res: Result<pallet_contracts_primitives::ExecReturnValue, _> =
-								<pallet_contracts::Pallet<T>>::bare_call(
-									who.clone(),
-									contract_address,
-									0u32.saturated_into(),
-									Weight::from_ref_time(1_000_000), // TODO determine this limit better should not be too high indeed
-									None,
-									data,
-									false,
-									Determinism::Deterministic,
-								)
-								.result
-								.map_err(|e| {
-									log::debug!("UsageLicense::Contract error: {:?}", e);
-									e
-								});
+			ensure!(total_size > 0, Error::<T>::ProtoDataIsEmpty);
+
+			let chunk_size = T::UploadChunkSize::get();
+			ensure!(chunk_size > 0, Error::<T>::SystematicFailure);
+			let chunk_count = (total_size + chunk_size as u64 - 1) / chunk_size as u64;
+			ensure!(chunk_count <= T::MaxUploadChunks::get() as u64, Error::<T>::UploadTooLarge);
+
+			ensure!(!<Protos<T>>::contains_key(&merkle_root), Error::<T>::ProtoExists);
+
+			let session_id = blake2_256(&(b"proto-upload-session", &who, merkle_root).encode());
+			ensure!(
+				!<UploadSessions<T>>::contains_key(&session_id),
+				Error::<T>::UploadSessionExists
+			);
+
+			let deposit = T::UploadChunkDeposit::get().saturating_mul((chunk_count as u32).into());
+
+			// Write STATE from now, ensure no errors from now...
+
+			<pallet_balances::Pallet<T> as ReservableCurrency<T::AccountId>>::reserve(
+				&who, deposit,
+			)?;
+
+			<UploadSessions<T>>::insert(
+				session_id,
+				UploadSession {
+					owner: who.clone(),
+					category,
+					total_size,
+					merkle_root,
+					chunk_size,
+					chunk_count: chunk_count as u32,
+					uploaded_chunks: 0,
+					created_at: <frame_system::Pallet<T>>::block_number(),
+					deposit,
+					prefix: vec![0u8; total_size.min(UPLOAD_VALIDATION_PREFIX_LENGTH) as usize],
+				},
+			);
+
+			Self::deposit_event(Event::UploadBegan { session_id, owner: who });
 
-							if let Ok(res) = res {
-								let allowed = bool::decode(&mut &res.data[..]);
-								if let Ok(allowed) = allowed {
-									if !allowed {
-										return Err(Error::<T>::Unauthorized.into())
-									}
-								} else {
-									return Err(Error::<T>::Unauthorized.into())
-								}
-							} else {
-								return Err(Error::<T>::Unauthorized.into())
-							}
-						},
-					}
-				} else {
-					// Proto not found
-					return Err(Error::<T>::ReferenceNotFound.into())
-				}
-			}
 			Ok(())
 		}
 
-		fn filter_proto(
-			proto_id: &Hash256,
-			tags: &[Vec<u8>],
-			categories: &[Categories],
-			avail: Option<bool>,
-			exclude_tags: &[Vec<u8>],
-		) -> bool {
-			if let Some(struct_proto) = <Protos<T>>::get(proto_id) {
-				if let Some(avail) = avail {
-					if avail && struct_proto.license == UsageLicense::Closed {
-						return false
-					} else if !avail && struct_proto.license != UsageLicense::Closed {
-						return false
-					}
-				}
+		/// **Upload** the **chunk** at index `index` of the **Multi-Part Upload Session** `session_id`.
+		/// Furthermore, this function also indexes `data` in the Blockchain's Database and makes it
+		/// available via bitswap (IPFS) directly from every chain node permanently.
+		///
+		/// # Arguments
+		///
+		/// * `origin` - The origin of the extrinsic function
+		/// * `session_id` - ID of the **Multi-Part Upload Session**
+		/// * `index` - **Index** of the **chunk** in the **Proto-Fragment's data**
+		/// * `data` - **Data** of the **chunk**
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::upload_chunk(data.len() as u32))]
+		#[pallet::call_index(7)]
+		pub fn upload_chunk(
+			origin: OriginFor<T>,
+			session_id: Hash256,
+			index: u32,
+			// let data come last as we record this size in blocks db (storage chain)
+			data: Vec<u8>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
 
-				if categories.len() == 0 {
-					return Self::filter_tags(tags, &struct_proto, exclude_tags)
-				} else {
-					return Self::filter_category(tags, &struct_proto, categories, exclude_tags)
-				}
+			let session =
+				<UploadSessions<T>>::get(&session_id).ok_or(Error::<T>::UploadSessionNotFound)?;
+			ensure!(session.owner == who, Error::<T>::Unauthorized);
+			ensure!(!Self::is_upload_session_expired(&session), Error::<T>::UploadSessionExpired);
+
+			ensure!(index < session.chunk_count, Error::<T>::InvalidChunkIndex);
+			ensure!(
+				!<UploadChunks<T>>::contains_key(&session_id, index),
+				Error::<T>::ChunkAlreadyUploaded
+			);
+
+			let expected_size = if index + 1 == session.chunk_count {
+				session.total_size - index as u64 * session.chunk_size as u64
 			} else {
-				false
-			}
-		}
+				session.chunk_size as u64
+			};
+			ensure!(data.len() as u64 == expected_size, Error::<T>::InvalidChunkSize);
 
-		/// Whether `struct_proto` has all the tags `tags` and categories `categories`
+			// we need this to index transactions
+			let extrinsic_index = <frame_system::Pallet<T>>::extrinsic_index()
+				.ok_or(Error::<T>::SystematicFailure)?;
+
+			let chunk_hash = blake2_256(&data);
+
+			// Write STATE from now, ensure no errors from now...
+
+			<UploadChunks<T>>::insert(session_id, index, chunk_hash);
+			<UploadSessions<T>>::mutate(&session_id, |session| {
+				let session = session.as_mut().expect("Session exists from above check; qed");
+				session.uploaded_chunks += 1;
+				// keep the part of the chunk that overlaps the prefix, so that it can be validated in `finalize_upload()`
+				let start = index as usize * session.chunk_size as usize;
+				if start < session.prefix.len() {
+					let end = session.prefix.len().min(start + data.len());
+					session.prefix[start..end].copy_from_slice(&data[..end - start]);
+				}
+			});
+
+			// index the chunk for fetching
+			transaction_index::index(extrinsic_index, data.len() as u32, chunk_hash);
+
+			Self::deposit_event(Event::ChunkUploaded { session_id, index });
+
+			Ok(())
+		}
+
+		/// **Finalize** the **Multi-Part Upload Session** `session_id` and **create** the **Proto-Fragment**.
+		///
+		/// The Merkle Root of the uploaded chunks must match the one declared in `begin_upload()`.
+		///
+		/// The reassembled data is never available as a whole on-chain, so only its first `UPLOAD_VALIDATION_PREFIX_LENGTH` bytes
+		/// (which are kept in the session as the chunks are uploaded) are validated here. This is only sufficient for the categories
+		/// whose validator checks the file signature at the start of the data. The categories for which it is not
+		/// (Traits, Shards Scripts, Bundles and Text) are rejected by `begin_upload()` (see `can_upload_in_chunks()`).
+		///
+		/// # Arguments
+		///
+		/// * `origin` - The origin of the extrinsic function
+		/// * `session_id` - ID of the **Multi-Part Upload Session**
+		/// * `references` - **List of other Proto-Fragments** used to create the **Proto-Fragment**
+		/// * `tags` - **List of tags** to **tag** the **Proto-Fragment** **with**
+		/// * `linked_asset` (*optional*) - An **asset that is linked with the Proto-Fragment** (e.g
+		///   an ERC-721 Contract)
+		/// * `license` - **Enum** indicating **how the Proto-Fragment can be used**
+		/// * `cluster` - the **Cluster id** the proto belongs to (Optional)
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::finalize_upload(references.len() as u32, tags.len() as u32, T::MaxUploadChunks::get()))]
+		#[pallet::call_index(8)]
+		pub fn finalize_upload(
+			origin: OriginFor<T>,
+			session_id: Hash256,
+			references: Vec<Hash256>,
+			tags: BoundedVec<BoundedVec<u8, <T as pallet::Config>::StringLimit>, T::MaxTags>,
+			linked_asset: Option<LinkedAsset>,
+			license: UsageLicense<T::AccountId>,
+			cluster: Option<Hash128>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			ensure!(
+				!tags.iter().enumerate().any(|(index, tag)| tags
+					.iter()
+					.enumerate()
+					.any(|(i, t)| t == tag && i != index)),
+				Error::<T>::DuplicateProtoTagExists
+			);
+
+			let session =
+				<UploadSessions<T>>::get(&session_id).ok_or(Error::<T>::UploadSessionNotFound)?;
+			ensure!(session.owner == who, Error::<T>::Unauthorized);
+			ensure!(!Self::is_upload_session_expired(&session), Error::<T>::UploadSessionExpired);
+			ensure!(session.uploaded_chunks == session.chunk_count, Error::<T>::UploadIncomplete);
+
+			let chunks = (0..session.chunk_count)
+				.map(|index| {
+					<UploadChunks<T>>::get(&session_id, index).ok_or(Error::<T>::UploadIncomplete)
+				})
+				.collect::<Result<Vec<Hash256>, _>>()?;
+			ensure!(merkle_root(&chunks) == session.merkle_root, Error::<T>::MerkleRootMismatch);
+
+			ensure!(
+				T::DataValidator::is_valid(&session.category, &session.prefix),
+				Error::<T>::ProtoDataNotValid
+			);
+
+			let proto_hash = session.merkle_root;
+
+			// make sure the proto does not exist already!
+			ensure!(!<Protos<T>>::contains_key(&proto_hash), Error::<T>::ProtoExists);
+
+			// proto cannot refer itself!
+			ensure!(!references.contains(&proto_hash), Error::<T>::CircularReference);
+
+			// Check license requirements
+			Self::check_license(&references, &who)?;
+
+			// Write STATE from now, ensure no errors from now...
+
+			Self::remove_upload_session(&session_id, &session);
+
+			<ProtoChunks<T>>::insert(proto_hash, chunks);
+
+			Self::store_proto(
+				&who,
+				proto_hash,
+				references,
+				session.category,
+				&tags,
+				linked_asset,
+				license,
+				cluster,
+				ProtoData::Local(vec![]),
+			);
+
+			log::debug!("Uploaded proto in {} chunks: {:?}", session.chunk_count, proto_hash);
+
+			Ok(())
+		}
+
+		/// **Cancel** the **Multi-Part Upload Session** `session_id`.
+		///
+		/// The chunks that were already uploaded remain indexed, but no Proto-Fragment is created from them.
+		/// The deposit of the session is returned to its owner.
+		///
+		/// Only the owner of the session can cancel it, unless it has expired (see `Config::UploadSessionTimeout`),
+		/// in which case anyone can cancel it.
+		///
+		/// # Arguments
+		///
+		/// * `origin` - The origin of the extrinsic function
+		/// * `session_id` - ID of the **Multi-Part Upload Session**
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::cancel_upload(T::MaxUploadChunks::get()))]
+		#[pallet::call_index(9)]
+		pub fn cancel_upload(origin: OriginFor<T>, session_id: Hash256) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let session =
+				<UploadSessions<T>>::get(&session_id).ok_or(Error::<T>::UploadSessionNotFound)?;
+			// anyone can clean up an expired session
+			ensure!(
+				session.owner == who || Self::is_upload_session_expired(&session),
+				Error::<T>::Unauthorized
+			);
+
+			// Write STATE from now, ensure no errors from now...
+
+			Self::remove_upload_session(&session_id, &session);
+
+			Self::deposit_event(Event::UploadCancelled { session_id });
+
+			Ok(())
+		}
+
+		/// **Vote** on the **availability of the data of a Proto-Fragment** that is stored on Arweave or IPFS.
+		///
+		/// Once the vote `data` receives `Threshold` votes, the ***DataAttestation* struct** of the Proto-Fragment is updated.
+		///
+		/// Note: This is an unsigned transaction with a signed payload, that is sent by the Offchain Worker of a Fragnova Authority (see `check_data_availability()`)
+		///
+		/// # Arguments
+		///
+		/// * `origin` - The origin of the extrinsic function
+		/// * `data` - The vote on the availability of the data of the Proto-Fragment
+		/// * `_signature` - The signature of `data`, written by `data.public`
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::internal_attest_data())]
+		#[pallet::call_index(10)]
+		pub fn internal_attest_data(
+			origin: OriginFor<T>,
+			data: DataAvailabilityVote<T::Public, T::BlockNumber>,
+			_signature: T::Signature,
+		) -> DispatchResult {
+			ensure_none(origin)?;
+
+			log::debug!("Data availability vote: {:?}", data);
+
+			let proto = <Protos<T>>::get(&data.proto_hash).ok_or(Error::<T>::ProtoNotFound)?;
+			ensure!(Self::get_external_data(&proto).is_some(), Error::<T>::DataNotExternal);
+			// The check must have happened after the current data of the Proto-Fragment was set
+			ensure!(
+				data.block_number >= Self::get_data_block_number(&proto),
+				Error::<T>::StaleDataAttestation
+			);
+			if let Some(attestation) = <DataAttestations<T>>::get(&data.proto_hash) {
+				ensure!(
+					attestation.checked_at < data.block_number,
+					Error::<T>::StaleDataAttestation
+				);
+			}
+
+			let data_hash =
+				(data.proto_hash, data.status, data.block_number).using_encoded(blake2_256);
+
+			let threshold = <T as pallet_accounts::Config>::Threshold::get();
+			if threshold > 1 {
+				let current_votes = <DataAvailabilityVoting<T>>::get(&data_hash);
+				if let Some(current_votes) = current_votes {
+					if current_votes + 1u64 < threshold {
+						// Current Votes has not passed the threshold
+						<DataAvailabilityVoting<T>>::insert(&data_hash, current_votes + 1);
+						return Ok(())
+					} else {
+						// Current votes passes the threshold, let's remove the record
+						<DataAvailabilityVoting<T>>::remove(&data_hash);
+					}
+				} else {
+					// If key `data_hash` doesn't exist in DataAvailabilityVoting
+					<DataAvailabilityVoting<T>>::insert(&data_hash, 1);
+					return Ok(())
+				}
+			}
+
+			<DataAttestations<T>>::insert(
+				&data.proto_hash,
+				DataAttestation { status: data.status, checked_at: data.block_number },
+			);
+
+			Self::deposit_event(Event::DataAttested {
+				proto_hash: data.proto_hash,
+				status: data.status,
+			});
+
+			Ok(())
+		}
+
+		/// **Approve** `operator` to **transfer a Proto-Fragment on behalf of its owner** (see `transfer()`).
+		/// If `operator` is `None`, the current approval of the Proto-Fragment is revoked.
+		///
+		/// A Proto-Fragment has at most one approved operator, and its approval is cleared once it is transferred.
+		///
+		/// # Arguments
+		///
+		/// * `origin` - The origin of the extrinsic function
+		/// * `proto_hash` - The **hash of the data of the Proto-Fragment**
+		/// * `operator` (*optional*) - The **Account ID** to approve
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::approve())]
+		#[pallet::call_index(11)]
+		pub fn approve(
+			origin: OriginFor<T>,
+			proto_hash: Hash256,
+			operator: Option<T::AccountId>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let proto = <Protos<T>>::get(&proto_hash).ok_or(Error::<T>::ProtoNotFound)?;
+
+			// make sure the caller is the owner (or is approved for all of the owner's protos)
+			match proto.owner {
+				ProtoOwner::User(owner) => {
+					ensure!(
+						owner == who || <OperatorApprovals<T>>::contains_key(&owner, &who),
+						Error::<T>::Unauthorized
+					);
+					if let Some(operator) = &operator {
+						ensure!(*operator != owner, Error::<T>::Unauthorized);
+					}
+				},
+				ProtoOwner::ExternalAsset(_ext_asset) =>
+				// We don't allow transferring external assets
+				{
+					ensure!(false, Error::<T>::Unauthorized)
+				},
+			};
+
+			// make sure the proto is not detached
+			ensure!(
+				!<DetachedHashes<T>>::contains_key(&DetachHash::Proto(proto_hash)),
+				Error::<T>::Detached
+			);
+
+			// WRITING STATE FROM NOW
+
+			match &operator {
+				Some(operator) => <ProtoApprovals<T>>::insert(&proto_hash, operator),
+				None => <ProtoApprovals<T>>::remove(&proto_hash),
+			}
+
+			Self::deposit_event(Event::Approval { proto_hash, operator });
+
+			Ok(())
+		}
+
+		/// **Approve** (or **disapprove**) `operator` to **transfer every Proto-Fragment that is owned by `origin`**
+		/// (including the ones that `origin` will own in the future).
+		///
+		/// # Arguments
+		///
+		/// * `origin` - The origin of the extrinsic function
+		/// * `operator` - The **Account ID** to approve (or disapprove)
+		/// * `approved` - Whether `operator` is approved
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::set_approval_for_all())]
+		#[pallet::call_index(12)]
+		pub fn set_approval_for_all(
+			origin: OriginFor<T>,
+			operator: T::AccountId,
+			approved: bool,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			ensure!(operator != who, Error::<T>::Unauthorized);
+
+			// WRITING STATE FROM NOW
+
+			if approved {
+				<OperatorApprovals<T>>::insert(&who, &operator, ());
+			} else {
+				<OperatorApprovals<T>>::remove(&who, &operator);
+			}
+
+			Self::deposit_event(Event::ApprovalForAll { owner: who, operator, approved });
+
+			Ok(())
+		}
+
+		/// Set the **license royalty** of the **Proto-Fragment `proto_hash`**, i.e the **share of the sale price** of every
+		/// Fragment Instance bought from a Fragment Definition that **another account created** from the Proto-Fragment
+		/// (under the Proto-Fragment's license) that is **paid to the Proto-Fragment's owner**.
+		/// If `royalty` is `None`, the license royalty of the Proto-Fragment is removed.
+		///
+		/// Note: **Only** the **Proto-Fragment's owner** is **allowed** to call this extrinsic
+		///
+		/// # Arguments
+		///
+		/// * `origin` - The origin of the extrinsic function
+		/// * `proto_hash` - The **hash of the data of the Proto-Fragment**
+		/// * `royalty` (*optional*) - The **share of the sale price** paid to the Proto-Fragment's owner
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::set_license_royalty())]
+		#[pallet::call_index(13)]
+		pub fn set_license_royalty(
+			origin: OriginFor<T>,
+			proto_hash: Hash256,
+			royalty: Option<Perbill>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let proto = <Protos<T>>::get(&proto_hash).ok_or(Error::<T>::ProtoNotFound)?;
+
+			// make sure the caller is the owner
+			match proto.owner {
+				ProtoOwner::User(owner) => ensure!(owner == who, Error::<T>::Unauthorized),
+				ProtoOwner::ExternalAsset(_ext_asset) => ensure!(false, Error::<T>::Unauthorized),
+			};
+
+			// make sure the proto is not detached
+			ensure!(
+				!<DetachedHashes<T>>::contains_key(&DetachHash::Proto(proto_hash)),
+				Error::<T>::Detached
+			);
+
+			// WRITING STATE FROM NOW
+
+			match &royalty {
+				Some(royalty) => <LicenseRoyalties<T>>::insert(&proto_hash, royalty),
+				None => <LicenseRoyalties<T>>::remove(&proto_hash),
+			}
+
+			Self::deposit_event(Event::LicenseRoyaltyChanged { proto_hash, royalty });
+
+			Ok(())
+		}
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T>
+	where
+		T::AccountId: UncheckedFrom<T::Hash> + AsRef<[u8]>,
+	{
+		fn offchain_worker(block_number: T::BlockNumber) {
+			Self::check_data_availability(block_number);
+		}
+	}
+
+	/// By default, all unsigned transactions are rejected in Substrate.
+	/// To enable Substrate to accept certain unsigned transactions, you must implement the ValidateUnsigned trait for the pallet.
+	#[pallet::validate_unsigned]
+	impl<T: Config> ValidateUnsigned for Pallet<T>
+	where
+		T::AccountId: UncheckedFrom<T::Hash> + AsRef<[u8]>,
+	{
+		type Call = Call<T>;
+
+		/// For the call `Call::internal_attest_data` which is an unsigned transaction with a signed payload,
+		/// verify that `data.public` is in `FragKeys` and that `signature` is a valid signature of `data` written by `data.public`.
+		///
+		/// If both the aforementioned, allow the call to execute. Otherwise, do not allow it to.
+		fn validate_unsigned(source: TransactionSource, call: &Self::Call) -> TransactionValidity {
+			// Firstly let's check that we call the right function.
+			if let Call::internal_attest_data { ref data, ref signature } = call {
+				// ensure it's a local transaction sent by an offchain worker
+				match source {
+					TransactionSource::InBlock | TransactionSource::Local => {},
+					_ => {
+						log::debug!("Not a local transaction");
+						return InvalidTransaction::Call.into()
+					},
+				}
+
+				// check public is valid
+				let valid_keys = <pallet_accounts::FragKeys<T>>::get();
+				log::debug!("Valid keys: {:?}", valid_keys);
+				let pub_key = data.public.encode();
+				let pub_key: ed25519::Public = {
+					if let Ok(MultiSigner::Ed25519(pub_key)) =
+						<MultiSigner>::decode(&mut &pub_key[..])
+					{
+						pub_key
+					} else {
+						return InvalidTransaction::BadSigner.into()
+					}
+				};
+				log::debug!("Public key: {:?}", pub_key);
+				if !valid_keys.contains(&pub_key) {
+					return InvalidTransaction::BadSigner.into()
+				}
+
+				// most expensive bit last
+				let signature_valid = SignedPayload::<T>::verify::<
+					<T as pallet_accounts::Config>::AuthorityId,
+				>(data, signature.clone());
+				if !signature_valid {
+					return InvalidTransaction::BadProof.into()
+				}
+
+				log::debug!("Sending data availability vote extrinsic");
+				ValidTransaction::with_tag_prefix("ProtoDataAvailability")
+					.and_provides((data.proto_hash, data.status, data.block_number, pub_key))
+					.propagate(false)
+					.build()
+			} else {
+				InvalidTransaction::Call.into()
+			}
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// **Store** the **Proto-Fragments** of the Genesis Block `protos` (a list of SCALE-encoded *GenesisProto* structs)
+		/// and **index them** by their references, their category, their owner and the Traits they implement.
+		fn initialize_protos(protos: &[Vec<u8>]) {
+			for genesis_proto in protos.iter() {
+				let GenesisProto { proto_hash, mut proto, tags, metadata, trait_name, chunks } =
+					GenesisProto::<T::AccountId, T::BlockNumber>::decode(&mut &genesis_proto[..])
+						.expect("Genesis Proto-Fragments are valid; qed");
+
+				proto.tags = tags
+					.iter()
+					.map(|tag| {
+						let tag_index = <Tags<T>>::get(tag).unwrap_or_else(|| {
+							let next_index = <TagsIndex<T>>::get() + 1;
+							<Tags<T>>::insert(tag, next_index);
+							<TagsIndex<T>>::put(next_index);
+							next_index
+						});
+						<Compact<u64>>::from(tag_index)
+					})
+					.collect();
+
+				proto.metadata = metadata
+					.iter()
+					.map(|(metadata_key, data_hash)| {
+						let metadata_key_index =
+							<MetaKeys<T>>::get(metadata_key).unwrap_or_else(|| {
+								let next_index = <MetaKeysIndex<T>>::get() + 1;
+								<MetaKeys<T>>::insert(metadata_key, next_index);
+								<MetaKeysIndex<T>>::put(next_index);
+								next_index
+							});
+						(<Compact<u64>>::from(metadata_key_index), *data_hash)
+					})
+					.collect();
+
+				let references = proto
+					.references
+					.iter()
+					.chain(proto.patches.iter().flat_map(|patch| patch.references.iter()));
+				for reference in references {
+					<ProtosByParent<T>>::append(reference, proto_hash);
+				}
+
+				match &proto.category {
+					Categories::Trait(Some(trait_id)) =>
+						if let Some(trait_name) = trait_name {
+							<Traits<T>>::insert(trait_id, trait_name);
+						},
+					Categories::Shards(info) =>
+						for implementing in info.implementing.iter() {
+							<ProtosByTrait<T>>::append(implementing, proto_hash);
+						},
+					_ => {},
+				}
+
+				if let Some(chunks) = chunks {
+					<ProtoChunks<T>>::insert(proto_hash, chunks);
+				}
+
+				<ProtosByCategory<T>>::append(&proto.category, proto_hash);
+				<ProtosByOwner<T>>::append(&proto.owner, proto_hash);
+				<Protos<T>>::insert(proto_hash, proto);
+			}
+		}
+
+		/// Whether `operator` is approved to transfer the Proto-Fragment `proto_hash` on behalf of `owner`,
+		/// either specifically (see `approve()`) or for all of `owner`'s Proto-Fragments (see `set_approval_for_all()`)
+		pub fn is_approved(
+			owner: &T::AccountId,
+			operator: &T::AccountId,
+			proto_hash: &Hash256,
+		) -> bool {
+			<ProtoApprovals<T>>::get(proto_hash).as_ref() == Some(operator) ||
+				<OperatorApprovals<T>>::contains_key(owner, operator)
+		}
+
+		/// Whether the Proto-Fragment `proto_hash` is locked, i.e whether its lock has not ended yet (see `lock_proto()`)
+		pub fn is_locked(proto_hash: &Hash256) -> bool {
+			<LockedProtos<T>>::get(proto_hash)
+				.map_or(false, |until| <frame_system::Pallet<T>>::block_number() < until)
+		}
+
+		/// **Lock** the Proto-Fragment `proto_hash` **until the block number `until`**,
+		/// so that it cannot be transferred with `transfer()` in the meantime
+		pub fn lock_proto(proto_hash: &Hash256, until: T::BlockNumber) {
+			<LockedProtos<T>>::insert(proto_hash, until);
+		}
+
+		/// **Unlock** the Proto-Fragment `proto_hash` (see `lock_proto()`)
+		pub fn unlock_proto(proto_hash: &Hash256) {
+			<LockedProtos<T>>::remove(proto_hash);
+		}
+
+		/// Whether the Proto-Fragment `proto_hash` can be transferred by its owner `from`,
+		/// regardless of whether it is locked
+		pub fn can_transfer_proto(from: &T::AccountId, proto_hash: &Hash256) -> DispatchResult {
+			let proto = <Protos<T>>::get(proto_hash).ok_or(Error::<T>::ProtoNotFound)?;
+			ensure!(proto.owner == ProtoOwner::User(from.clone()), Error::<T>::Unauthorized);
+			ensure!(
+				!<DetachedHashes<T>>::contains_key(&DetachHash::Proto(*proto_hash)),
+				Error::<T>::Detached
+			);
+			Ok(())
+		}
+
+		/// **Transfer** the **ownership** of the Proto-Fragment `proto_hash` from `owner` to `new_owner`
+		/// and emit the `Transferred` event.
+		///
+		/// Note: This function does not check anything, see `can_transfer_proto()`
+		pub fn transfer_proto(
+			proto_hash: &Hash256,
+			owner: ProtoOwner<T::AccountId>,
+			new_owner: T::AccountId,
+		) {
+			// collect new owner
+			let new_owner_s = ProtoOwner::User(new_owner.clone());
+
+			// remove proto from old owner
+			<ProtosByOwner<T>>::mutate(owner, |protos_by_owner| {
+				if let Some(list) = protos_by_owner {
+					list.retain(|current_hash| proto_hash != current_hash);
+				}
+			});
+
+			// add proto to new owner
+			<ProtosByOwner<T>>::append(new_owner_s.clone(), proto_hash);
+
+			// update proto
+			<Protos<T>>::mutate(proto_hash, |proto| {
+				if let Some(proto) = proto {
+					proto.owner = new_owner_s;
+				}
+			});
+
+			// the approval and the lock of the proto do not carry over to the new owner
+			<ProtoApprovals<T>>::remove(proto_hash);
+			<LockedProtos<T>>::remove(proto_hash);
+
+			// emit event
+			Self::deposit_event(Event::Transferred {
+				proto_hash: *proto_hash,
+				owner_id: new_owner,
+			});
+		}
+	}
+
+	impl<T: Config> Pallet<T>
+	where
+		T::AccountId: UncheckedFrom<T::Hash> + AsRef<[u8]>,
+	{
+		/// Get the **current data** of the Proto-Fragment `proto` (i.e the data of its latest patch, if it was patched)
+		/// **if it is stored on Arweave or IPFS**
+		pub fn get_external_data(
+			proto: &Proto<T::AccountId, T::BlockNumber>,
+		) -> Option<&ProtoData> {
+			let data = proto.patches.last().map(|patch| &patch.data).unwrap_or(&proto.data);
+			match data {
+				ProtoData::Local(_) => None,
+				ProtoData::Arweave(_) | ProtoData::Ipfs(_) | ProtoData::IpfsCid(_) => Some(data),
+			}
+		}
+
+		/// Get the **block number** in which the **current data** of the Proto-Fragment `proto` was set
+		fn get_data_block_number(proto: &Proto<T::AccountId, T::BlockNumber>) -> T::BlockNumber {
+			proto.patches.last().map(|patch| patch.block).unwrap_or(proto.block)
+		}
+
+		/// Get the **URL** from which the data `data` (that is stored on Arweave or IPFS) **can be fetched**,
+		/// if the URL of the corresponding Gateway was set.
+		fn get_external_data_url(data: &ProtoData) -> Option<String> {
+			let (gateway, path) = match data {
+				ProtoData::Local(_) => return None,
+				ProtoData::Arweave(tx) => (
+					sp_fragnova::fragnova::get_arweave_gateway_url()?,
+					// Arweave Transaction IDs are Base64URL encoded without padding
+					URL_SAFE_NO_PAD.encode(tx),
+				),
+				// the legacy variant does not hold a CID, so there is no way to fetch it
+				ProtoData::Ipfs(_) => return None,
+				ProtoData::IpfsCid(cid) => (
+					sp_fragnova::fragnova::get_ipfs_gateway_url()?,
+					sp_fragnova::cid::cid_to_string(cid).ok()?,
+				),
+			};
+			let gateway = String::from_utf8(gateway).ok()?;
+			Some(format!("{}/{}", gateway.trim_end_matches('/'), path))
+		}
+
+		/// Check the **availability of the data** of the Proto-Fragments whose data is **stored on Arweave or IPFS**,
+		/// and send the result as an unsigned transaction with a signed payload (see `internal_attest_data()`).
+		///
+		/// The data of every Proto-Fragment is checked once every `DATA_CHECK_INTERVAL` blocks.
+		/// At most `MAX_DATA_CHECK_SCANS_PER_BLOCK` Proto-Fragments are visited and at most `MAX_DATA_CHECKS_PER_BLOCK` of them are checked in a single block,
+		/// and the Proto-Fragment where the scan stopped is kept in the offchain local storage so that the next block continues from it.
+		///
+		/// NOTE: `Signer::<T, T::AuthorityId>::any_account()` uses any of the keys that was added using the RPC `author_insertKey` into Fragnova
+		pub fn check_data_availability(block_number: T::BlockNumber) {
+			let signer = Signer::<T, <T as pallet_accounts::Config>::AuthorityId>::any_account();
+			if !signer.can_sign() {
+				log::debug!("No local account available, skipping data availability checks");
+				return
+			}
+
+			if sp_fragnova::fragnova::get_arweave_gateway_url().is_none() &&
+				sp_fragnova::fragnova::get_ipfs_gateway_url().is_none()
+			{
+				log::debug!("No data gateway url found, skipping data availability checks");
+				return // It is fine to have a node not checking the data of Proto-Fragments
+			}
+
+			// All the authorities that check a Proto-Fragment within the same interval must vote on the same block number
+			let interval = T::BlockNumber::from(DATA_CHECK_INTERVAL);
+			let round = block_number - block_number % interval;
+
+			let cursor_ref = StorageValueRef::persistent(b"protos-data-check-cursor");
+			let cursor: Option<Hash256> = cursor_ref.get().unwrap_or_default();
+			let protos = if let Some(cursor) = cursor {
+				<Protos<T>>::iter_from(<Protos<T>>::hashed_key_for(cursor))
+			} else {
+				<Protos<T>>::iter()
+			};
+
+			let mut checks = 0usize;
+			let mut scans = 0usize;
+			let mut last_proto_hash = None;
+			for (proto_hash, proto) in protos {
+				if checks >= MAX_DATA_CHECKS_PER_BLOCK || scans >= MAX_DATA_CHECK_SCANS_PER_BLOCK {
+					break
+				}
+				scans += 1;
+				last_proto_hash = Some(proto_hash);
+
+				let Some(data) = Self::get_external_data(&proto) else {
+					continue;
+				};
+
+				let check_block = round.max(Self::get_data_block_number(&proto));
+				if let Some(attestation) = <DataAttestations<T>>::get(&proto_hash) {
+					if attestation.checked_at >= check_block {
+						continue
+					}
+				}
+
+				// Don't vote twice for the same check
+				let voted_ref =
+					StorageValueRef::persistent(&(b"protos-data-check-voted", proto_hash).encode());
+				if voted_ref.get::<T::BlockNumber>().unwrap_or_default() == Some(check_block) {
+					continue
+				}
+
+				let Some(url) = Self::get_external_data_url(data) else {
+					continue;
+				};
+
+				let wait = Timestamp::from_unix_millis(
+					sp_io::offchain::timestamp().unix_millis() + DATA_FETCH_TIMEOUT_PERIOD,
+				);
+				let status = match http_get(&url, Some(wait)) {
+					Ok(fetched_data) =>
+						if T::DataValidator::is_valid(&proto.category, &fetched_data) {
+							DataAvailability::Available
+						} else {
+							DataAvailability::Invalid
+						},
+					Err(e) => {
+						log::debug!("Failed to fetch the data of proto {:?}: {}", proto_hash, e);
+						DataAvailability::Missing
+					},
+				};
+
+				let result = signer
+					.send_unsigned_transaction(
+						|account| DataAvailabilityVote {
+							public: account.public.clone(),
+							proto_hash,
+							status,
+							block_number: check_block,
+							_reserved1: None,
+							_reserved2: None,
+							_reserved3: None,
+						},
+						|payload, signature| Call::internal_attest_data {
+							data: payload,
+							signature,
+						},
+					)
+					.ok_or("Failed to sign transaction")
+					.and_then(|(_, result)| result.map_err(|_| "Failed to send transaction"));
+				match result {
+					Ok(()) => voted_ref.set(&check_block),
+					Err(e) =>
+						log::error!("Failed to vote on the data of proto {:?}: {}", proto_hash, e),
+				}
+
+				checks += 1;
+			}
+
+			if checks < MAX_DATA_CHECKS_PER_BLOCK && scans < MAX_DATA_CHECK_SCANS_PER_BLOCK {
+				// The end of `Protos` was reached, so the next block starts from the beginning
+				cursor_ref.clear();
+			} else if let Some(last_proto_hash) = last_proto_hash {
+				cursor_ref.set(&last_proto_hash);
+			}
+		}
+
+		/// **Warn** that the **deprecated variant `ProtoData::Ipfs`** was used.
+		///
+		/// It is still accepted (without being validated) for one release, so that clients have time to switch to `ProtoData::IpfsCid`.
+		/// It will then be rejected with `Error::InvalidCid`.
+		fn warn_deprecated_ipfs_data() {
+			log::warn!(
+				"ProtoData::Ipfs is deprecated and will be rejected in the next release, use ProtoData::IpfsCid instead"
+			);
+		}
+
+		/// Whether the **Multi-Part Upload Session** `session` has **expired** (see `Config::UploadSessionTimeout`)
+		fn is_upload_session_expired(
+			session: &UploadSession<
+				T::AccountId,
+				T::BlockNumber,
+				<T as pallet_balances::Config>::Balance,
+			>,
+		) -> bool {
+			<frame_system::Pallet<T>>::block_number() >
+				session.created_at.saturating_add(T::UploadSessionTimeout::get())
+		}
+
+		/// **Remove** the **Multi-Part Upload Session** `session_id` (and its chunks) from storage
+		/// and **return its deposit** to its owner
+		fn remove_upload_session(
+			session_id: &Hash256,
+			session: &UploadSession<
+				T::AccountId,
+				T::BlockNumber,
+				<T as pallet_balances::Config>::Balance,
+			>,
+		) {
+			<UploadSessions<T>>::remove(session_id);
+			let _ = <UploadChunks<T>>::clear_prefix(session_id, session.chunk_count, None);
+			<pallet_balances::Pallet<T> as ReservableCurrency<T::AccountId>>::unreserve(
+				&session.owner,
+				session.deposit,
+			);
+		}
+
+		/// **Store** a **new Proto-Fragment** `proto_hash` and **index it** by its references, its category and its owner.
+		///
+		/// Note: This function does not do any check, so they must be done before calling it.
+		fn store_proto(
+			who: &T::AccountId,
+			proto_hash: Hash256,
+			references: Vec<Hash256>,
+			category: Categories,
+			tags: &[BoundedVec<u8, <T as pallet::Config>::StringLimit>],
+			linked_asset: Option<LinkedAsset>,
+			license: UsageLicense<T::AccountId>,
+			cluster: Option<Hash128>,
+			data_stored: ProtoData,
+		) {
+			let owner = if let Some(link) = linked_asset {
+				ProtoOwner::ExternalAsset(link)
+			} else {
+				ProtoOwner::User(who.clone())
+			};
+
+			let tags = tags
+				.iter()
+				.map(|s| {
+					let s = s.deref();
+					let tag_index = <Tags<T>>::get(s);
+					if let Some(tag_index) = tag_index {
+						<Compact<u64>>::from(tag_index)
+					} else {
+						let next_index = <TagsIndex<T>>::try_get().unwrap_or_default() + 1;
+						<Tags<T>>::insert(s, next_index);
+						// storing is dangerous inside a closure
+						// but after this call we start storing..
+						// so it's fine here
+						<TagsIndex<T>>::put(next_index);
+						<Compact<u64>>::from(next_index)
+					}
+				})
+				.collect();
+
+			// store in the state the proto
+			let proto = Proto {
+				block: <frame_system::Pallet<T>>::block_number(),
+				patches: vec![],
+				license,
+				creator: who.clone(),
+				owner: owner.clone(),
+				references: references.clone(),
+				category: category.clone(),
+				tags,
+				metadata: BTreeMap::new(),
+				data: data_stored,
+				cluster,
+				_reserved1: None,
+				_reserved2: None,
+				_reserved3: None,
+			};
+
+			// store proto
+			<Protos<T>>::insert(proto_hash, proto);
+
+			// store by parent
+			for reference in references.into_iter() {
+				<ProtosByParent<T>>::append(reference, proto_hash);
+			}
+
+			// store by category (original)
+			<ProtosByCategory<T>>::append(category, proto_hash);
+
+			// store by owner
+			<ProtosByOwner<T>>::append(owner, proto_hash);
+
+			// also emit event
+			Self::deposit_event(Event::Uploaded { proto_hash });
+		}
+
+		/// Get the **hashes of the chunks** (in order) of a **Proto-Fragment created by a Multi-Part Upload**.
+		/// The data of every chunk can be fetched using the chunk's hash.
+		pub fn get_data_chunks(proto_hash: Hash256) -> Option<Vec<Hash256>> {
+			<ProtoChunks<T>>::get(proto_hash)
+		}
+
+		/// Get the **binary CID** of the **current data** of the Proto-Fragment `proto_hash`.
+		///
+		/// If the data is stored on the Fragnova Blockchain, this is the CIDv1 with which the data can be fetched via bitswap (IPFS).
+		/// If the data is stored on IPFS, this is the CID that was provided when the data was uploaded (or patched).
+		///
+		/// Returns `None` if the Proto-Fragment does not exist, if it is a Bundle, if its data is stored on Arweave,
+		/// if its data uses the legacy variant `ProtoData::Ipfs` or if its data was uploaded in multiple parts (see `get_data_chunks()`).
+		pub fn get_cid(proto_hash: Hash256) -> Option<Vec<u8>> {
+			let proto = <Protos<T>>::get(proto_hash)?;
+			if proto.category == Categories::Bundle || <ProtoChunks<T>>::contains_key(proto_hash) {
+				return None
+			}
+			match proto.patches.last() {
+				Some(patch) => match &patch.data {
+					ProtoData::Local(_) => Some(sp_fragnova::cid::encode_cid(&patch.data_hash)),
+					ProtoData::Arweave(_) | ProtoData::Ipfs(_) => None,
+					ProtoData::IpfsCid(cid) => Some(cid.clone()),
+				},
+				None => match &proto.data {
+					ProtoData::Local(_) => Some(sp_fragnova::cid::encode_cid(&proto_hash)),
+					ProtoData::Arweave(_) | ProtoData::Ipfs(_) => None,
+					ProtoData::IpfsCid(cid) => Some(cid.clone()),
+				},
+			}
+		}
+
+		/// Ensure that `who` is **allowed** to **use** every Proto-Fragment in `references`,
+		/// i.e that `who` **owns** the Proto-Fragment or that the **license** of the Proto-Fragment permits it (see `UsageLicense`).
+		pub fn check_license(references: &[Hash256], who: &T::AccountId) -> DispatchResult {
+			// TODO this is not tested properly
+			for reference in references.iter() {
+				let proto = <Protos<T>>::get(reference);
+				if let Some(proto) = proto {
+					let owner = match proto.owner {
+						ProtoOwner::User(owner) => Some(owner),
+						_ => None,
+					};
+
+					if let Some(owner) = owner {
+						if owner == *who {
+							// owner can include freely
+							continue
+						}
+					}
+
+					let license = proto.license;
+					match license {
+						UsageLicense::Closed => return Err(Error::<T>::Unauthorized.into()),
+						UsageLicense::Open => continue,
+						UsageLicense::Contract(contract_address) => {
+							let data = (reference, who.clone()).encode();
+							let res: Result<pallet_contracts_primitives::ExecReturnValue, _> =
+								<pallet_contracts::Pallet<T>>::bare_call(
+									who.clone(),
+									contract_address,
+									0u32.saturated_into(),
+									Weight::from_ref_time(1_000_000), // TODO determine this limit better should not be too high indeed
+									None,
+									data,
+									false,
+									Determinism::Deterministic,
+								)
+								.result
+								.map_err(|e| {
+									log::debug!("UsageLicense::Contract error: {:?}", e);
+									e
+								});
+
+							if let Ok(res) = res {
+								let allowed = bool::decode(&mut &res.data[..]);
+								if let Ok(allowed) = allowed {
+									if !allowed {
+										return Err(Error::<T>::Unauthorized.into())
+									}
+								} else {
+									return Err(Error::<T>::Unauthorized.into())
+								}
+							} else {
+								return Err(Error::<T>::Unauthorized.into())
+							}
+						},
+					}
+				} else {
+					// Proto not found
+					return Err(Error::<T>::ReferenceNotFound.into())
+				}
+			}
+			Ok(())
+		}
+
+		/// Whether the Proto-Fragment `proto_id` exists and matches all the given filters
+		/// (i.e `tags`, `categories`, `avail`, `exclude_tags` and `data_availability`)
+		pub fn filter_proto(
+			proto_id: &Hash256,
+			tags: &[Vec<u8>],
+			categories: &[Categories],
+			avail: Option<bool>,
+			exclude_tags: &[Vec<u8>],
+			data_availability: Option<DataAvailability>,
+		) -> bool {
+			if let Some(struct_proto) = <Protos<T>>::get(proto_id) {
+				if let Some(avail) = avail {
+					if avail && struct_proto.license == UsageLicense::Closed {
+						return false
+					} else if !avail && struct_proto.license != UsageLicense::Closed {
+						return false
+					}
+				}
+
+				if let Some(data_availability) = data_availability {
+					let status = if Self::get_external_data(&struct_proto).is_some() {
+						<DataAttestations<T>>::get(proto_id).map(|attestation| attestation.status)
+					} else {
+						Some(DataAvailability::Available)
+					};
+					if status != Some(data_availability) {
+						return false
+					}
+				}
+
+				if categories.len() == 0 {
+					return Self::filter_tags(tags, &struct_proto, exclude_tags)
+				} else {
+					return Self::filter_category(tags, &struct_proto, categories, exclude_tags)
+				}
+			} else {
+				false
+			}
+		}
+
+		/// Whether `struct_proto` has all the tags `tags` and categories `categories`
 		fn filter_category(
 			tags: &[Vec<u8>],
 			struct_proto: &Proto<T::AccountId, T::BlockNumber>,
@@ -1229,6 +2493,7 @@ pub mod pallet {
 								&params.categories,
 								params.available,
 								&params.exclude_tags,
+								params.data_availability,
 							)
 						})
 						.skip(params.from as usize)
@@ -1245,6 +2510,7 @@ pub mod pallet {
 								&params.categories,
 								params.available,
 								&params.exclude_tags,
+								params.data_availability,
 							)
 						})
 						.skip(params.from as usize)
@@ -1285,6 +2551,7 @@ pub mod pallet {
 										&params.categories,
 										params.available,
 										&params.exclude_tags,
+										params.data_availability,
 									)
 								})
 								.collect()
@@ -1299,6 +2566,7 @@ pub mod pallet {
 										&params.categories,
 										params.available,
 										&params.exclude_tags,
+										params.data_availability,
 									)
 								})
 								.collect()
@@ -1376,7 +2644,7 @@ pub mod pallet {
 		}
 
 		/// **Query** the Genealogy of a Proto-Fragment based on **`params`**. The **return
-		/// type** is a **JSON string** that represents an Adjacency List.
+		/// type** is a **string** whose format is `params.format` (by default a JSON Adjacency List).
 		///
 		/// # Arguments
 		///
@@ -1387,43 +2655,250 @@ pub mod pallet {
 				.try_into()
 				.map_err(|_| "Failed to convert u8 slice to Hash256")?;
 
-			let mut adjacency_list = BTreeMap::<String, Vec<String>>::new();
+			let max_nodes = params.max_nodes.map(|max_nodes| max_nodes as usize);
+			let include_patches = params.include_patches.unwrap_or(true);
 
-			let mut queue = VecDeque::<Hash256>::new();
-			queue.push_back(proto_hash);
+			let mut adjacency_list = BTreeMap::<Hash256, Vec<GenealogyEdge>>::new();
+
+			let mut queue = VecDeque::<(Hash256, u32)>::new();
+			queue.push_back((proto_hash, 0));
 
 			let mut visited = BTreeMap::<Hash256, bool>::new();
 			visited.insert(proto_hash, true);
 
-			while let Some(proto) = queue.pop_front() {
+			while let Some((proto, depth)) = queue.pop_front() {
+				// Proto-Fragments at the depth limit are part of the graph, but their neighbors are not
+				if let Some(max_depth) = params.max_depth {
+					if depth >= max_depth {
+						adjacency_list.insert(proto, Vec::new());
+						continue
+					}
+				}
+
 				let neighbors = if params.get_ancestors {
-					let proto_struct =
-						<Protos<T>>::get(proto).ok_or("Proto Hash Does Not Exist!")?;
-					let mut parents = proto_struct.references;
-					let mut references_from_patches = proto_struct
-						.patches
-						.into_iter()
-						.flat_map(|pp: ProtoPatch<_>| pp.references)
-						.collect::<Vec<Hash256>>();
-					parents.append(&mut references_from_patches);
-					parents
+					Self::get_genealogy_parents(&proto, include_patches)?
 				} else {
-					let children = <ProtosByParent<T>>::get(proto).unwrap_or_default();
-					children
+					Self::get_genealogy_children(&proto, include_patches)
 				};
 
-				adjacency_list
-					.insert(hex::encode(proto), neighbors.iter().map(|p| hex::encode(p)).collect());
+				let mut edges = Vec::new();
+				for edge in neighbors.into_iter() {
+					if !visited.contains_key(&edge.proto_hash) {
+						if let Some(max_nodes) = max_nodes {
+							if visited.len() >= max_nodes {
+								continue
+							}
+						}
+						visited.insert(edge.proto_hash, true);
+						queue.push_back((edge.proto_hash, depth + 1));
+					}
+					edges.push(edge);
+				}
+
+				adjacency_list.insert(proto, edges);
+			}
+
+			let result = match params.format {
+				GenealogyFormat::Json =>
+					Self::genealogy_to_json(&adjacency_list, params.annotate_edges).to_string(),
+				GenealogyFormat::Dot =>
+					Self::genealogy_to_dot(&visited, &adjacency_list, params.annotate_edges),
+				GenealogyFormat::GraphMl =>
+					Self::genealogy_to_graphml(&visited, &adjacency_list, params.annotate_edges),
+			};
+
+			Ok(result.into_bytes())
+		}
+
+		/// Get the edges from the Proto-Fragment `proto_hash` to the Proto-Fragments it references.
+		///
+		/// If `include_patches` is `false`, the references introduced by the patches of `proto_hash` are ignored.
+		fn get_genealogy_parents(
+			proto_hash: &Hash256,
+			include_patches: bool,
+		) -> Result<Vec<GenealogyEdge>, Vec<u8>> {
+			let proto_struct = <Protos<T>>::get(proto_hash).ok_or("Proto Hash Does Not Exist!")?;
+
+			let block: u64 = proto_struct.block.saturated_into();
+			let mut parents = proto_struct
+				.references
+				.into_iter()
+				.map(|parent| GenealogyEdge { proto_hash: parent, block, patch: None })
+				.collect::<Vec<GenealogyEdge>>();
+
+			if include_patches {
+				for (index, patch) in proto_struct.patches.into_iter().enumerate() {
+					let block: u64 = patch.block.saturated_into();
+					parents.extend(patch.references.into_iter().map(|parent| GenealogyEdge {
+						proto_hash: parent,
+						block,
+						patch: Some(index as u32),
+					}));
+				}
+			}
+
+			Ok(parents)
+		}
+
+		/// Get the edges from the Proto-Fragment `proto_hash` to the Proto-Fragments that reference it.
+		///
+		/// If `include_patches` is `false`, the references introduced by the patches of the children are ignored.
+		fn get_genealogy_children(
+			proto_hash: &Hash256,
+			include_patches: bool,
+		) -> Vec<GenealogyEdge> {
+			let children = <ProtosByParent<T>>::get(proto_hash).unwrap_or_default();
+
+			let mut visited = BTreeSet::new();
+			let mut edges = Vec::new();
+			for child in children.into_iter() {
+				// a child is appended once for every time it references `proto_hash`
+				if !visited.insert(child) {
+					continue
+				}
+				let Some(child_struct) = <Protos<T>>::get(child) else { continue };
+
+				if child_struct.references.contains(proto_hash) {
+					edges.push(GenealogyEdge {
+						proto_hash: child,
+						block: child_struct.block.saturated_into(),
+						patch: None,
+					});
+				}
+
+				if include_patches {
+					for (index, patch) in child_struct.patches.iter().enumerate() {
+						if patch.references.contains(proto_hash) {
+							edges.push(GenealogyEdge {
+								proto_hash: child,
+								block: patch.block.saturated_into(),
+								patch: Some(index as u32),
+							});
+						}
+					}
+				}
+			}
+
+			edges
+		}
+
+		/// Converts a Genealogy Adjacency List into a JSON object.
+		///
+		/// If `annotate_edges` is `true`, every neighbor is an object with the keys `proto`, `block` and `patch`.
+		/// Otherwise, every neighbor is just the hex-encoded hash of the Proto-Fragment.
+		fn genealogy_to_json(
+			adjacency_list: &BTreeMap<Hash256, Vec<GenealogyEdge>>,
+			annotate_edges: bool,
+		) -> Value {
+			let mut map = Map::new();
+
+			for (proto_hash, edges) in adjacency_list.iter() {
+				let neighbors = edges
+					.iter()
+					.map(|edge| {
+						if annotate_edges {
+							json!({
+								"proto": hex::encode(edge.proto_hash),
+								"block": edge.block,
+								"patch": edge.patch,
+							})
+						} else {
+							Value::String(hex::encode(edge.proto_hash))
+						}
+					})
+					.collect::<Vec<Value>>();
+				map.insert(hex::encode(proto_hash), Value::Array(neighbors));
+			}
+
+			Value::Object(map)
+		}
+
+		/// Converts a Genealogy Adjacency List into a Graphviz DOT digraph
+		fn genealogy_to_dot(
+			nodes: &BTreeMap<Hash256, bool>,
+			adjacency_list: &BTreeMap<Hash256, Vec<GenealogyEdge>>,
+			annotate_edges: bool,
+		) -> String {
+			let mut dot = String::from("digraph genealogy {\n");
+
+			for proto_hash in nodes.keys() {
+				dot.push_str(&format!("\t\"{}\";\n", hex::encode(proto_hash)));
+			}
+
+			for (proto_hash, edges) in adjacency_list.iter() {
+				for edge in edges.iter() {
+					let attributes = match (annotate_edges, edge.patch) {
+						(false, _) => String::new(),
+						(true, None) =>
+							format!(" [label=\"block {}\", block={}]", edge.block, edge.block),
+						(true, Some(patch)) => format!(
+							" [label=\"block {}, patch {}\", block={}, patch={}]",
+							edge.block, patch, edge.block, patch
+						),
+					};
+					dot.push_str(&format!(
+						"\t\"{}\" -> \"{}\"{};\n",
+						hex::encode(proto_hash),
+						hex::encode(edge.proto_hash),
+						attributes
+					));
+				}
+			}
+
+			dot.push_str("}\n");
+			dot
+		}
+
+		/// Converts a Genealogy Adjacency List into a GraphML document
+		fn genealogy_to_graphml(
+			nodes: &BTreeMap<Hash256, bool>,
+			adjacency_list: &BTreeMap<Hash256, Vec<GenealogyEdge>>,
+			annotate_edges: bool,
+		) -> String {
+			let mut graphml = String::from(
+				"<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+				<graphml xmlns=\"http://graphml.graphdrawing.org/xmlns\">\n",
+			);
+
+			if annotate_edges {
+				graphml.push_str(
+					"\t<key id=\"block\" for=\"edge\" attr.name=\"block\" attr.type=\"long\"/>\n\
+					\t<key id=\"patch\" for=\"edge\" attr.name=\"patch\" attr.type=\"long\"/>\n",
+				);
+			}
+
+			graphml.push_str("\t<graph id=\"genealogy\" edgedefault=\"directed\">\n");
 
-				for neighbor in neighbors.into_iter() {
-					if !visited.contains_key(&neighbor) {
-						visited.insert(neighbor, true);
-						queue.push_back(neighbor);
+			for proto_hash in nodes.keys() {
+				graphml.push_str(&format!("\t\t<node id=\"{}\"/>\n", hex::encode(proto_hash)));
+			}
+
+			for (proto_hash, edges) in adjacency_list.iter() {
+				for edge in edges.iter() {
+					let source = hex::encode(proto_hash);
+					let target = hex::encode(edge.proto_hash);
+					if annotate_edges {
+						graphml.push_str(&format!(
+							"\t\t<edge source=\"{}\" target=\"{}\">\n\
+							\t\t\t<data key=\"block\">{}</data>\n",
+							source, target, edge.block
+						));
+						if let Some(patch) = edge.patch {
+							graphml
+								.push_str(&format!("\t\t\t<data key=\"patch\">{}</data>\n", patch));
+						}
+						graphml.push_str("\t\t</edge>\n");
+					} else {
+						graphml.push_str(&format!(
+							"\t\t<edge source=\"{}\" target=\"{}\"/>\n",
+							source, target
+						));
 					}
 				}
 			}
 
-			Ok(json!(adjacency_list).to_string().into_bytes())
+			graphml.push_str("\t</graph>\n</graphml>\n");
+			graphml
 		}
 	}
 }
@@ -2,6 +2,7 @@
 
 use crate as pallet_protos;
 use crate::*;
+use protos::categories::{BinaryCategories, Categories};
 
 use frame_support::{
 	parameter_types,
@@ -10,14 +11,28 @@ use frame_support::{
 };
 use frame_system;
 
-use sp_core::{ed25519::Signature, H256};
+use sp_core::{
+	ed25519::Signature,
+	offchain::{
+		testing::{self, OffchainState, PoolState},
+		OffchainDbExt, OffchainWorkerExt, TransactionPoolExt,
+	},
+	H256,
+};
 
 use sp_runtime::traits::{
 	BlakeTwo256, ConstU128, Extrinsic as ExtrinsicT, IdentifyAccount, IdentityLookup, Verify,
 };
 
 use pallet_oracle::{OracleContract, OracleProvider};
-use sp_runtime::testing::{Header, TestXt};
+use sp_runtime::{
+	testing::{Header, TestXt},
+	RuntimeAppPublic,
+};
+
+use parking_lot::RwLock;
+use sp_keystore::{testing::KeyStore, KeystoreExt, SyncCryptoStore};
+use std::sync::Arc;
 
 type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
 type Block = frame_system::mocking::MockBlock<Test>;
@@ -197,6 +212,23 @@ impl pallet_protos::Config for Test {
 	type StringLimit = StringLimit;
 	type DetachAccountLimit = ConstU32<20>;
 	type MaxTags = ConstU32<10>;
+	type UploadChunkSize = ConstU32<4>;
+	type MaxUploadChunks = ConstU32<16>;
+	type UploadChunkDeposit = ConstU128<1>;
+	type UploadSessionTimeout = ConstU64<100>;
+	type DataValidator = MockDataValidator;
+}
+
+/// Validator that checks the file signature of Blender Files, and accepts the data of every other category
+pub struct MockDataValidator;
+
+impl ProtoDataValidator for MockDataValidator {
+	fn is_valid(category: &Categories, data: &[u8]) -> bool {
+		match category {
+			Categories::Binary(BinaryCategories::BlendFile) => data.starts_with(b"BLENDER-"),
+			_ => true,
+		}
+	}
 }
 
 impl pallet_detach::Config for Test {
@@ -294,3 +326,40 @@ pub fn new_test_ext() -> sp_io::TestExternalities {
 
 	ext
 }
+
+pub fn new_test_ext_with_ocw() -> (
+	sp_io::TestExternalities,
+	Arc<RwLock<PoolState>>,
+	Arc<RwLock<OffchainState>>,
+	sp_core::ed25519::Public,
+) {
+	const PHRASE: &str =
+		"news slush supreme milk chapter athlete soap sausage put clutch what kitten";
+
+	let (offchain, offchain_state) = testing::TestOffchainExt::new();
+	let (pool, pool_state) = testing::TestTransactionPoolExt::new();
+
+	let keystore = KeyStore::new();
+
+	// The Offchain Worker of this pallet signs its transactions with the keys of `pallet_accounts`
+	SyncCryptoStore::ed25519_generate_new(
+		&keystore,
+		<pallet_accounts::crypto::Public as RuntimeAppPublic>::ID,
+		Some(&format!("{}", PHRASE)),
+	)
+	.unwrap();
+
+	let ed25519_public_key =
+		SyncCryptoStore::ed25519_public_keys(&keystore, pallet_accounts::crypto::Public::ID)
+			.get(0)
+			.unwrap()
+			.clone();
+
+	let mut t = new_test_ext();
+	t.register_extension(OffchainDbExt::new(offchain.clone()));
+	t.register_extension(OffchainWorkerExt::new(offchain));
+	t.register_extension(TransactionPoolExt::new(pool));
+	t.register_extension(KeystoreExt(Arc::new(keystore)));
+
+	(t, pool_state, offchain_state, ed25519_public_key)
+}
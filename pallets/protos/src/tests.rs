@@ -294,6 +294,31 @@ mod transfer_tests {
 		});
 	}
 
+	#[test]
+	fn transfer_should_not_work_if_proto_is_locked() {
+		new_test_ext().execute_with(|| {
+			let dd = DummyData::new();
+
+			let proto = dd.proto_fragment;
+
+			assert_ok!(upload(dd.account_id, &proto));
+
+			let until = System::block_number() + 10;
+			ProtosPallet::lock_proto(&proto.get_proto_hash(), until);
+
+			assert_noop!(
+				transfer(dd.account_id, &proto, dd.account_id_second),
+				Error::<Test>::Locked
+			);
+
+			// the lock ends by itself
+			System::set_block_number(until);
+
+			assert_ok!(transfer(dd.account_id, &proto, dd.account_id_second));
+			assert_eq!(<LockedProtos<Test>>::get(proto.get_proto_hash()), None);
+		});
+	}
+
 	#[test]
 	#[ignore]
 	fn transfer_should_not_work_if_proto_is_detached() {
@@ -301,6 +326,188 @@ mod transfer_tests {
 	}
 }
 
+mod approval_tests {
+	use super::*;
+
+	fn operator() -> <Test as frame_system::Config>::AccountId {
+		sp_core::ed25519::Public::from_raw([3u8; 32])
+	}
+
+	#[test]
+	fn transfer_should_work_if_user_is_approved() {
+		new_test_ext().execute_with(|| {
+			let dd = DummyData::new();
+
+			let proto = dd.proto_fragment;
+			let proto_hash = proto.get_proto_hash();
+
+			assert_ok!(upload(dd.account_id, &proto));
+			assert_ok!(ProtosPallet::approve(
+				RuntimeOrigin::signed(dd.account_id),
+				proto_hash,
+				Some(operator())
+			));
+			assert_eq!(<ProtoApprovals<Test>>::get(proto_hash), Some(operator()));
+
+			assert_ok!(ProtosPallet::transfer(
+				RuntimeOrigin::signed(operator()),
+				proto_hash,
+				dd.account_id_second
+			));
+
+			assert_eq!(
+				<Protos<Test>>::get(proto_hash).unwrap().owner,
+				ProtoOwner::User(dd.account_id_second)
+			);
+			// the approval is cleared on transfer
+			assert_eq!(<ProtoApprovals<Test>>::get(proto_hash), None);
+			assert_noop!(
+				ProtosPallet::transfer(
+					RuntimeOrigin::signed(operator()),
+					proto_hash,
+					dd.account_id
+				),
+				Error::<Test>::Unauthorized
+			);
+		});
+	}
+
+	#[test]
+	fn transfer_should_work_if_user_is_approved_for_all() {
+		new_test_ext().execute_with(|| {
+			let dd = DummyData::new();
+
+			let proto = dd.proto_fragment;
+			let proto_hash = proto.get_proto_hash();
+
+			assert_ok!(upload(dd.account_id, &proto));
+			assert_ok!(ProtosPallet::set_approval_for_all(
+				RuntimeOrigin::signed(dd.account_id),
+				operator(),
+				true
+			));
+
+			assert_ok!(ProtosPallet::transfer(
+				RuntimeOrigin::signed(operator()),
+				proto_hash,
+				dd.account_id_second
+			));
+
+			assert_eq!(
+				<Protos<Test>>::get(proto_hash).unwrap().owner,
+				ProtoOwner::User(dd.account_id_second)
+			);
+		});
+	}
+
+	#[test]
+	fn transfer_should_not_work_if_approval_for_all_was_revoked() {
+		new_test_ext().execute_with(|| {
+			let dd = DummyData::new();
+
+			let proto = dd.proto_fragment;
+			let proto_hash = proto.get_proto_hash();
+
+			assert_ok!(upload(dd.account_id, &proto));
+			assert_ok!(ProtosPallet::set_approval_for_all(
+				RuntimeOrigin::signed(dd.account_id),
+				operator(),
+				true
+			));
+			assert_ok!(ProtosPallet::set_approval_for_all(
+				RuntimeOrigin::signed(dd.account_id),
+				operator(),
+				false
+			));
+
+			assert_noop!(
+				ProtosPallet::transfer(
+					RuntimeOrigin::signed(operator()),
+					proto_hash,
+					dd.account_id_second
+				),
+				Error::<Test>::Unauthorized
+			);
+		});
+	}
+
+	#[test]
+	fn approve_should_not_work_if_user_does_not_own_proto() {
+		new_test_ext().execute_with(|| {
+			let dd = DummyData::new();
+
+			let proto = dd.proto_fragment;
+
+			assert_ok!(upload(dd.account_id, &proto));
+
+			assert_noop!(
+				ProtosPallet::approve(
+					RuntimeOrigin::signed(dd.account_id_second),
+					proto.get_proto_hash(),
+					Some(dd.account_id_second)
+				),
+				Error::<Test>::Unauthorized
+			);
+		});
+	}
+}
+
+mod license_royalty_tests {
+	use super::*;
+	use sp_runtime::Perbill;
+
+	#[test]
+	fn set_license_royalty_should_work() {
+		new_test_ext().execute_with(|| {
+			let dd = DummyData::new();
+
+			let proto = dd.proto_fragment;
+			let proto_hash = proto.get_proto_hash();
+
+			assert_ok!(upload(dd.account_id, &proto));
+			assert_ok!(ProtosPallet::set_license_royalty(
+				RuntimeOrigin::signed(dd.account_id),
+				proto_hash,
+				Some(Perbill::from_percent(5))
+			));
+			assert_eq!(<LicenseRoyalties<Test>>::get(proto_hash), Some(Perbill::from_percent(5)));
+			System::assert_last_event(
+				Event::LicenseRoyaltyChanged {
+					proto_hash,
+					royalty: Some(Perbill::from_percent(5)),
+				}
+				.into(),
+			);
+
+			assert_ok!(ProtosPallet::set_license_royalty(
+				RuntimeOrigin::signed(dd.account_id),
+				proto_hash,
+				None
+			));
+			assert_eq!(<LicenseRoyalties<Test>>::get(proto_hash), None);
+		});
+	}
+
+	#[test]
+	fn set_license_royalty_should_not_work_if_user_does_not_own_proto() {
+		new_test_ext().execute_with(|| {
+			let dd = DummyData::new();
+
+			let proto = dd.proto_fragment;
+
+			assert_ok!(upload(dd.account_id, &proto));
+			assert_noop!(
+				ProtosPallet::set_license_royalty(
+					RuntimeOrigin::signed(dd.account_id_second),
+					proto.get_proto_hash(),
+					Some(Perbill::from_percent(5))
+				),
+				Error::<Test>::Unauthorized
+			);
+		});
+	}
+}
+
 mod set_metadata_tests {
 	use super::*;
 
@@ -495,6 +702,7 @@ mod get_protos_tests {
 				tags: Vec::new(),
 				exclude_tags: Vec::new(),
 				available: Some(true),
+				data_availability: None,
 			};
 
 			let result: Result<Vec<u8>, Vec<u8>> = ProtosPallet::get_protos(params);
@@ -525,6 +733,7 @@ mod get_protos_tests {
 				tags: Vec::new(),
 				exclude_tags: Vec::new(),
 				available: Some(true),
+				data_availability: None,
 			};
 
 			let result = ProtosPallet::get_protos(params).ok().unwrap();
@@ -568,6 +777,7 @@ mod get_protos_tests {
 				tags: Vec::new(),
 				exclude_tags: Vec::new(),
 				available: Some(true),
+				data_availability: None,
 			};
 
 			let result = ProtosPallet::get_protos(params).ok().unwrap();
@@ -613,6 +823,7 @@ mod get_protos_tests {
 				tags: Vec::new(),
 				exclude_tags: Vec::new(),
 				available: Some(true),
+				data_availability: None,
 			};
 
 			let result = ProtosPallet::get_protos(params).ok().unwrap();
@@ -659,6 +870,7 @@ mod get_protos_tests {
 				tags: Vec::new(),
 				exclude_tags: Vec::new(),
 				available: Some(true),
+				data_availability: None,
 			};
 
 			let result = ProtosPallet::get_protos(params).ok().unwrap();
@@ -917,6 +1129,7 @@ mod get_protos_tests {
 				tags: Vec::new(),
 				exclude_tags: Vec::new(),
 				available: Some(true),
+				data_availability: None,
 			};
 
 			let result = ProtosPallet::get_protos(params).ok().unwrap();
@@ -1214,6 +1427,11 @@ mod get_genealogy_tests {
 					&ProtosPallet::get_genealogy(GetGenealogyParams {
 						proto_hash: hex::encode(proto_third.get_proto_hash()).into_bytes(),
 						get_ancestors: true,
+						max_depth: None,
+						max_nodes: None,
+						include_patches: Some(true),
+						annotate_edges: false,
+						format: GenealogyFormat::Json,
 					})
 					.unwrap()
 				)
@@ -1252,6 +1470,11 @@ mod get_genealogy_tests {
 					&ProtosPallet::get_genealogy(GetGenealogyParams {
 						proto_hash: hex::encode(proto.get_proto_hash()).into_bytes(),
 						get_ancestors: false,
+						max_depth: None,
+						max_nodes: None,
+						include_patches: Some(true),
+						annotate_edges: false,
+						format: GenealogyFormat::Json,
 					})
 					.unwrap()
 				)
@@ -1269,6 +1492,205 @@ mod get_genealogy_tests {
 		});
 	}
 
+	fn get_genealogy_params(
+		proto: &ProtoFragment,
+		get_ancestors: bool,
+	) -> GetGenealogyParams<Vec<u8>> {
+		GetGenealogyParams {
+			proto_hash: hex::encode(proto.get_proto_hash()).into_bytes(),
+			get_ancestors,
+			max_depth: None,
+			max_nodes: None,
+			include_patches: Some(true),
+			annotate_edges: false,
+			format: GenealogyFormat::Json,
+		}
+	}
+
+	/// Uploads `proto` <- `proto_second` <- `proto_third` and returns them
+	fn upload_chain(dd: DummyData) -> (ProtoFragment, ProtoFragment, ProtoFragment) {
+		let proto = dd.proto_fragment;
+		assert_ok!(upload(dd.account_id, &proto));
+
+		let mut proto_second = dd.proto_fragment_second;
+		proto_second.references = vec![proto.get_proto_hash()];
+		assert_ok!(upload(dd.account_id, &proto_second));
+
+		let mut proto_third = dd.proto_fragment_third;
+		proto_third.references = vec![proto_second.get_proto_hash()];
+		assert_ok!(upload(dd.account_id, &proto_third));
+
+		(proto, proto_second, proto_third)
+	}
+
+	#[test]
+	fn get_genealogy_should_stop_at_max_depth() {
+		new_test_ext().execute_with(|| {
+			let dd = DummyData::new();
+			let (proto, proto_second, _proto_third) = upload_chain(dd);
+
+			let mut params = get_genealogy_params(&proto, false);
+			params.max_depth = Some(1);
+
+			assert_eq!(
+				serde_json::from_slice::<Value>(&ProtosPallet::get_genealogy(params).unwrap())
+					.unwrap(),
+				json!({
+					hex::encode(proto.get_proto_hash()): [
+						hex::encode(proto_second.get_proto_hash())
+					],
+					hex::encode(proto_second.get_proto_hash()): [],
+				})
+			);
+		});
+	}
+
+	#[test]
+	fn get_genealogy_should_return_the_root_at_max_depth_zero() {
+		new_test_ext().execute_with(|| {
+			let dd = DummyData::new();
+			let (proto, _proto_second, _proto_third) = upload_chain(dd);
+
+			let mut params = get_genealogy_params(&proto, false);
+			params.max_depth = Some(0);
+
+			assert_eq!(
+				serde_json::from_slice::<Value>(&ProtosPallet::get_genealogy(params).unwrap())
+					.unwrap(),
+				json!({
+					hex::encode(proto.get_proto_hash()): [],
+				})
+			);
+		});
+	}
+
+	#[test]
+	fn get_genealogy_should_stop_at_max_nodes() {
+		new_test_ext().execute_with(|| {
+			let dd = DummyData::new();
+			let (proto, proto_second, _proto_third) = upload_chain(dd);
+
+			let mut params = get_genealogy_params(&proto, false);
+			params.max_nodes = Some(2);
+
+			assert_eq!(
+				serde_json::from_slice::<Value>(&ProtosPallet::get_genealogy(params).unwrap())
+					.unwrap(),
+				json!({
+					hex::encode(proto.get_proto_hash()): [
+						hex::encode(proto_second.get_proto_hash())
+					],
+					hex::encode(proto_second.get_proto_hash()): [],
+				})
+			);
+		});
+	}
+
+	#[test]
+	fn get_genealogy_should_annotate_and_filter_patch_references() {
+		new_test_ext().execute_with(|| {
+			let dd = DummyData::new();
+
+			let proto = dd.proto_fragment;
+			assert_ok!(upload(dd.account_id, &proto));
+			let proto_second = dd.proto_fragment_second;
+			assert_ok!(upload(dd.account_id, &proto_second));
+
+			System::set_block_number(2);
+			assert_ok!(ProtosPallet::patch(
+				RuntimeOrigin::signed(dd.account_id),
+				proto_second.get_proto_hash(),
+				None,
+				vec![proto.get_proto_hash()],
+				None,
+				Some(ProtoData::Local(b"patch data".to_vec())),
+			));
+
+			let mut params = get_genealogy_params(&proto_second, true);
+			params.annotate_edges = true;
+			assert_eq!(
+				serde_json::from_slice::<Value>(
+					&ProtosPallet::get_genealogy(params.clone()).unwrap()
+				)
+				.unwrap(),
+				json!({
+					hex::encode(proto_second.get_proto_hash()): [
+						{ "proto": hex::encode(proto.get_proto_hash()), "block": 2, "patch": 0 }
+					],
+					hex::encode(proto.get_proto_hash()): [],
+				})
+			);
+
+			params.include_patches = Some(false);
+			assert_eq!(
+				serde_json::from_slice::<Value>(&ProtosPallet::get_genealogy(params).unwrap())
+					.unwrap(),
+				json!({
+					hex::encode(proto_second.get_proto_hash()): [],
+				})
+			);
+
+			let mut params = get_genealogy_params(&proto, false);
+			params.annotate_edges = true;
+			assert_eq!(
+				serde_json::from_slice::<Value>(&ProtosPallet::get_genealogy(params).unwrap())
+					.unwrap(),
+				json!({
+					hex::encode(proto.get_proto_hash()): [
+						{ "proto": hex::encode(proto_second.get_proto_hash()), "block": 2, "patch": 0 }
+					],
+					hex::encode(proto_second.get_proto_hash()): [],
+				})
+			);
+		});
+	}
+
+	#[test]
+	fn get_genealogy_should_work_in_dot_and_graphml_format() {
+		new_test_ext().execute_with(|| {
+			let dd = DummyData::new();
+
+			let proto = dd.proto_fragment;
+			assert_ok!(upload(dd.account_id, &proto));
+			let mut proto_second = dd.proto_fragment_second;
+			proto_second.references = vec![proto.get_proto_hash()];
+			assert_ok!(upload(dd.account_id, &proto_second));
+
+			let parent = hex::encode(proto.get_proto_hash());
+			let child = hex::encode(proto_second.get_proto_hash());
+
+			let mut params = get_genealogy_params(&proto_second, true);
+			params.annotate_edges = true;
+			params.format = GenealogyFormat::Dot;
+			let dot =
+				String::from_utf8(ProtosPallet::get_genealogy(params.clone()).unwrap()).unwrap();
+			assert!(dot.starts_with("digraph genealogy {"));
+			assert!(dot.contains(&format!(
+				"\"{}\" -> \"{}\" [label=\"block 1\", block=1];",
+				child, parent
+			)));
+
+			params.format = GenealogyFormat::GraphMl;
+			let graphml = String::from_utf8(ProtosPallet::get_genealogy(params).unwrap()).unwrap();
+			assert!(graphml.contains(&format!("<node id=\"{}\"/>", parent)));
+			assert!(graphml.contains(&format!("<node id=\"{}\"/>", child)));
+			assert!(graphml.contains(&format!("<edge source=\"{}\" target=\"{}\">", child, parent)));
+			assert!(graphml.contains("<data key=\"block\">1</data>"));
+		});
+	}
+
+	#[test]
+	fn get_genealogy_params_should_deserialize_without_the_new_fields() {
+		let params: GetGenealogyParams<String> =
+			serde_json::from_str(r#"{"proto_hash": "00", "get_ancestors": true}"#).unwrap();
+
+		assert_eq!(params.max_depth, None);
+		assert_eq!(params.max_nodes, None);
+		assert_eq!(params.include_patches, None);
+		assert!(!params.annotate_edges);
+		assert_eq!(params.format, GenealogyFormat::Json);
+	}
+
 	#[test]
 	fn set_metadata_should_not_work_if_user_does_not_own_proto() {
 		new_test_ext().execute_with(|| {
@@ -1338,3 +1760,762 @@ mod ban_tests {
 		});
 	}
 }
+
+mod multi_part_upload_tests {
+	use super::*;
+	use frame_support::traits::{Currency, Get, ReservableCurrency};
+	use protos::categories::BinaryCategories;
+	use sp_fragnova::merkle::merkle_root;
+
+	const DATA: &[u8] = b"BLENDER-v300 data that does not fit in one chunk";
+
+	fn get_chunks() -> Vec<Vec<u8>> {
+		DATA.chunks(<Test as pallet_protos::Config>::UploadChunkSize::get() as usize)
+			.map(|chunk| chunk.to_vec())
+			.collect()
+	}
+
+	fn get_merkle_root() -> Hash256 {
+		merkle_root(&get_chunks().iter().map(|chunk| blake2_256(chunk)).collect::<Vec<_>>())
+	}
+
+	const BALANCE: u128 = 1_000;
+
+	fn begin_upload(
+		signer: <Test as frame_system::Config>::AccountId,
+		merkle_root: Hash256,
+	) -> Result<Hash256, sp_runtime::DispatchError> {
+		if Balances::free_balance(&signer) == 0 {
+			Balances::make_free_balance_be(&signer, BALANCE);
+		}
+		ProtosPallet::begin_upload(
+			RuntimeOrigin::signed(signer),
+			Categories::Binary(BinaryCategories::BlendFile),
+			DATA.len() as u64,
+			merkle_root,
+		)?;
+		Ok(blake2_256(&(b"proto-upload-session", signer, merkle_root).encode()))
+	}
+
+	fn upload_chunks(
+		signer: <Test as frame_system::Config>::AccountId,
+		session_id: Hash256,
+	) -> DispatchResult {
+		for (index, chunk) in get_chunks().into_iter().enumerate() {
+			ProtosPallet::upload_chunk(
+				RuntimeOrigin::signed(signer),
+				session_id,
+				index as u32,
+				chunk,
+			)?;
+		}
+		Ok(())
+	}
+
+	fn finalize_upload(
+		signer: <Test as frame_system::Config>::AccountId,
+		session_id: Hash256,
+	) -> DispatchResult {
+		ProtosPallet::finalize_upload(
+			RuntimeOrigin::signed(signer),
+			session_id,
+			vec![],
+			vec![].try_into().unwrap(),
+			None,
+			UsageLicense::Open,
+			None,
+		)
+	}
+
+	#[test]
+	fn multi_part_upload_should_work() {
+		new_test_ext().execute_with(|| {
+			let dd = DummyData::new();
+
+			let session_id = begin_upload(dd.account_id, get_merkle_root()).unwrap();
+			assert_ok!(upload_chunks(dd.account_id, session_id));
+			assert_ok!(finalize_upload(dd.account_id, session_id));
+
+			let proto_hash = get_merkle_root();
+			let proto_struct = <Protos<Test>>::get(proto_hash).unwrap();
+			assert_eq!(proto_struct.category, Categories::Binary(BinaryCategories::BlendFile));
+			assert_eq!(proto_struct.owner, ProtoOwner::User(dd.account_id));
+			assert_eq!(proto_struct.data, ProtoData::Local(vec![]));
+
+			assert_eq!(
+				ProtosPallet::get_data_chunks(proto_hash).unwrap(),
+				get_chunks().iter().map(|chunk| blake2_256(chunk)).collect::<Vec<_>>()
+			);
+			assert!(!<UploadSessions<Test>>::contains_key(session_id));
+			assert_eq!(<UploadChunks<Test>>::iter_prefix(session_id).count(), 0);
+
+			let event = <frame_system::Pallet<Test>>::events()
+				.pop()
+				.expect("Expected at least one EventRecord to be found")
+				.event;
+			assert_eq!(
+				event,
+				mock::RuntimeEvent::from(pallet_protos::Event::Uploaded { proto_hash })
+			);
+		});
+	}
+
+	#[test]
+	fn upload_chunk_should_not_work_if_chunk_has_wrong_size() {
+		new_test_ext().execute_with(|| {
+			let dd = DummyData::new();
+
+			let session_id = begin_upload(dd.account_id, get_merkle_root()).unwrap();
+			assert_noop!(
+				ProtosPallet::upload_chunk(
+					RuntimeOrigin::signed(dd.account_id),
+					session_id,
+					0,
+					b"BLE".to_vec()
+				),
+				Error::<Test>::InvalidChunkSize
+			);
+			assert_noop!(
+				ProtosPallet::upload_chunk(
+					RuntimeOrigin::signed(dd.account_id),
+					session_id,
+					get_chunks().len() as u32,
+					b"BLEN".to_vec()
+				),
+				Error::<Test>::InvalidChunkIndex
+			);
+		});
+	}
+
+	#[test]
+	fn upload_chunk_should_not_work_if_user_does_not_own_session() {
+		new_test_ext().execute_with(|| {
+			let dd = DummyData::new();
+
+			let session_id = begin_upload(dd.account_id, get_merkle_root()).unwrap();
+			assert_noop!(
+				upload_chunks(dd.account_id_second, session_id),
+				Error::<Test>::Unauthorized
+			);
+		});
+	}
+
+	#[test]
+	fn begin_upload_should_not_work_if_data_has_too_many_chunks() {
+		new_test_ext().execute_with(|| {
+			let dd = DummyData::new();
+
+			let max_size = <Test as pallet_protos::Config>::UploadChunkSize::get() as u64 *
+				<Test as pallet_protos::Config>::MaxUploadChunks::get() as u64;
+			assert_noop!(
+				ProtosPallet::begin_upload(
+					RuntimeOrigin::signed(dd.account_id),
+					Categories::Binary(BinaryCategories::BlendFile),
+					max_size + 1,
+					get_merkle_root(),
+				),
+				Error::<Test>::UploadTooLarge
+			);
+		});
+	}
+
+	#[test]
+	fn finalize_upload_should_not_work_if_upload_is_incomplete() {
+		new_test_ext().execute_with(|| {
+			let dd = DummyData::new();
+
+			let session_id = begin_upload(dd.account_id, get_merkle_root()).unwrap();
+			assert_ok!(ProtosPallet::upload_chunk(
+				RuntimeOrigin::signed(dd.account_id),
+				session_id,
+				0,
+				get_chunks()[0].clone()
+			));
+			assert_noop!(
+				finalize_upload(dd.account_id, session_id),
+				Error::<Test>::UploadIncomplete
+			);
+		});
+	}
+
+	#[test]
+	fn finalize_upload_should_not_work_if_merkle_root_does_not_match() {
+		new_test_ext().execute_with(|| {
+			let dd = DummyData::new();
+
+			let session_id = begin_upload(dd.account_id, [7u8; 32]).unwrap();
+			assert_ok!(upload_chunks(dd.account_id, session_id));
+			assert_noop!(
+				finalize_upload(dd.account_id, session_id),
+				Error::<Test>::MerkleRootMismatch
+			);
+		});
+	}
+
+	#[test]
+	fn finalize_upload_should_not_work_if_a_later_chunk_makes_the_data_invalid() {
+		new_test_ext().execute_with(|| {
+			let dd = DummyData::new();
+
+			// the file signature `BLENDER-` spans the first two chunks, and the second one is corrupted
+			let mut chunks = get_chunks();
+			chunks[1] = b"DER!".to_vec();
+			let merkle_root =
+				merkle_root(&chunks.iter().map(|chunk| blake2_256(chunk)).collect::<Vec<_>>());
+
+			let session_id = begin_upload(dd.account_id, merkle_root).unwrap();
+			for (index, chunk) in chunks.into_iter().enumerate() {
+				assert_ok!(ProtosPallet::upload_chunk(
+					RuntimeOrigin::signed(dd.account_id),
+					session_id,
+					index as u32,
+					chunk,
+				));
+			}
+			assert_noop!(
+				finalize_upload(dd.account_id, session_id),
+				Error::<Test>::ProtoDataNotValid
+			);
+		});
+	}
+
+	#[test]
+	fn cancel_upload_should_work() {
+		new_test_ext().execute_with(|| {
+			let dd = DummyData::new();
+
+			let session_id = begin_upload(dd.account_id, get_merkle_root()).unwrap();
+			assert_ok!(upload_chunks(dd.account_id, session_id));
+			assert_ok!(ProtosPallet::cancel_upload(
+				RuntimeOrigin::signed(dd.account_id),
+				session_id
+			));
+
+			assert!(!<UploadSessions<Test>>::contains_key(session_id));
+			assert_eq!(<UploadChunks<Test>>::iter_prefix(session_id).count(), 0);
+			assert!(!<Protos<Test>>::contains_key(get_merkle_root()));
+		});
+	}
+
+	#[test]
+	fn merkle_root_should_not_be_the_hash_of_the_concatenated_chunk_hashes() {
+		let leaves = [blake2_256(b"BLEN"), blake2_256(b"DER!")];
+		assert_ne!(merkle_root(&leaves), blake2_256(&[&leaves[0][..], &leaves[1][..]].concat()));
+		// an odd node is not promoted as is
+		assert_ne!(merkle_root(&leaves[..1]), leaves[0]);
+		assert_ne!(merkle_root(&[leaves[0], leaves[1], leaves[0]]), merkle_root(&leaves));
+	}
+
+	#[test]
+	fn begin_upload_should_reserve_a_deposit_that_is_returned_when_the_upload_is_finalized() {
+		new_test_ext().execute_with(|| {
+			let dd = DummyData::new();
+
+			let deposit = <Test as pallet_protos::Config>::UploadChunkDeposit::get() *
+				get_chunks().len() as u128;
+
+			let session_id = begin_upload(dd.account_id, get_merkle_root()).unwrap();
+			assert_eq!(Balances::reserved_balance(&dd.account_id), deposit);
+			assert_eq!(<UploadSessions<Test>>::get(session_id).unwrap().deposit, deposit);
+
+			assert_ok!(upload_chunks(dd.account_id, session_id));
+			assert_ok!(finalize_upload(dd.account_id, session_id));
+			assert_eq!(Balances::reserved_balance(&dd.account_id), 0);
+			assert_eq!(Balances::free_balance(&dd.account_id), BALANCE);
+		});
+	}
+
+	#[test]
+	fn begin_upload_should_not_work_if_category_is_not_chunkable() {
+		new_test_ext().execute_with(|| {
+			let dd = DummyData::new();
+
+			assert_noop!(
+				ProtosPallet::begin_upload(
+					RuntimeOrigin::signed(dd.account_id),
+					Categories::Text(TextCategories::Plain),
+					DATA.len() as u64,
+					get_merkle_root(),
+				),
+				Error::<Test>::CategoryNotChunkable
+			);
+		});
+	}
+
+	#[test]
+	fn expired_upload_session_should_be_cancellable_by_anyone() {
+		new_test_ext().execute_with(|| {
+			let dd = DummyData::new();
+
+			let session_id = begin_upload(dd.account_id, get_merkle_root()).unwrap();
+			assert_noop!(
+				ProtosPallet::cancel_upload(
+					RuntimeOrigin::signed(dd.account_id_second),
+					session_id
+				),
+				Error::<Test>::Unauthorized
+			);
+
+			System::set_block_number(
+				System::block_number() +
+					<Test as pallet_protos::Config>::UploadSessionTimeout::get() +
+					1,
+			);
+
+			assert_noop!(
+				upload_chunks(dd.account_id, session_id),
+				Error::<Test>::UploadSessionExpired
+			);
+			assert_ok!(ProtosPallet::cancel_upload(
+				RuntimeOrigin::signed(dd.account_id_second),
+				session_id
+			));
+			assert!(!<UploadSessions<Test>>::contains_key(session_id));
+			assert_eq!(Balances::reserved_balance(&dd.account_id), 0);
+			assert_eq!(Balances::free_balance(&dd.account_id), BALANCE);
+		});
+	}
+}
+
+mod data_availability_tests {
+	use super::*;
+	use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+	use protos::categories::BinaryCategories;
+	use sp_core::offchain::testing;
+
+	const ARWEAVE_TX: [u8; 32] = [7u8; 32];
+	const ARWEAVE_GATEWAY_URL: &str = "http://localhost:1984/";
+
+	fn upload_arweave_proto(signer: <Test as frame_system::Config>::AccountId) -> Hash256 {
+		assert_ok!(ProtosPallet::upload(
+			RuntimeOrigin::signed(signer),
+			Vec::new(),
+			Categories::Binary(BinaryCategories::BlendFile),
+			Vec::new().try_into().unwrap(),
+			None,
+			UsageLicense::Open,
+			None,
+			ProtoData::Arweave(ARWEAVE_TX),
+		));
+		blake2_256(&ARWEAVE_TX)
+	}
+
+	fn attest_data(
+		proto_hash: Hash256,
+		status: DataAvailability,
+		block_number: u64,
+	) -> DispatchResult {
+		ProtosPallet::internal_attest_data(
+			RuntimeOrigin::none(),
+			DataAvailabilityVote {
+				public: sp_core::ed25519::Public([7u8; 32]),
+				proto_hash,
+				status,
+				block_number,
+				_reserved1: None,
+				_reserved2: None,
+				_reserved3: None,
+			},
+			sp_core::ed25519::Signature([69u8; 64]), // this can be anything
+		)
+	}
+
+	#[test]
+	fn internal_attest_data_should_work() {
+		new_test_ext().execute_with(|| {
+			let dd = DummyData::new();
+
+			let proto_hash = upload_arweave_proto(dd.account_id);
+
+			assert_ok!(attest_data(proto_hash, DataAvailability::Missing, 1));
+
+			assert_eq!(
+				<DataAttestations<Test>>::get(proto_hash),
+				Some(DataAttestation { status: DataAvailability::Missing, checked_at: 1 })
+			);
+			System::assert_last_event(
+				Event::DataAttested { proto_hash, status: DataAvailability::Missing }.into(),
+			);
+		});
+	}
+
+	#[test]
+	fn internal_attest_data_should_not_work_if_the_data_is_stored_on_chain() {
+		new_test_ext().execute_with(|| {
+			let dd = DummyData::new();
+
+			let proto = dd.proto_fragment;
+			assert_ok!(upload(dd.account_id, &proto));
+
+			assert_noop!(
+				attest_data(proto.get_proto_hash(), DataAvailability::Available, 1),
+				Error::<Test>::DataNotExternal
+			);
+		});
+	}
+
+	#[test]
+	fn internal_attest_data_should_not_work_if_the_attestation_is_stale() {
+		new_test_ext().execute_with(|| {
+			let dd = DummyData::new();
+
+			let proto_hash = upload_arweave_proto(dd.account_id);
+
+			assert_ok!(attest_data(proto_hash, DataAvailability::Available, 5));
+			assert_noop!(
+				attest_data(proto_hash, DataAvailability::Missing, 5),
+				Error::<Test>::StaleDataAttestation
+			);
+
+			// patching the data resets the attestation, and the checks made before the patch are stale
+			System::set_block_number(10);
+			assert_ok!(ProtosPallet::patch(
+				RuntimeOrigin::signed(dd.account_id),
+				proto_hash,
+				None,
+				Vec::new(),
+				None,
+				Some(ProtoData::Arweave([8u8; 32])),
+			));
+			assert_eq!(<DataAttestations<Test>>::get(proto_hash), None);
+			assert_noop!(
+				attest_data(proto_hash, DataAvailability::Available, 9),
+				Error::<Test>::StaleDataAttestation
+			);
+			assert_ok!(attest_data(proto_hash, DataAvailability::Available, 10));
+		});
+	}
+
+	#[test]
+	fn get_protos_should_filter_by_data_availability() {
+		new_test_ext().execute_with(|| {
+			let dd = DummyData::new();
+
+			let proto = dd.proto_fragment;
+			assert_ok!(upload(dd.account_id, &proto));
+			let arweave_proto_hash = upload_arweave_proto(dd.account_id);
+
+			let get_protos = |data_availability: Option<DataAvailability>| {
+				serde_json::from_slice::<Value>(
+					&ProtosPallet::get_protos(GetProtosParams {
+						limit: u64::MAX,
+						data_availability,
+						..Default::default()
+					})
+					.unwrap(),
+				)
+				.unwrap()
+			};
+
+			// the data of a Proto-Fragment stored on Arweave is not available until it is attested
+			assert_eq!(
+				get_protos(Some(DataAvailability::Available)),
+				json!({ hex::encode(proto.get_proto_hash()): {} })
+			);
+			assert_eq!(get_protos(Some(DataAvailability::Missing)), json!({}));
+
+			assert_ok!(attest_data(arweave_proto_hash, DataAvailability::Missing, 1));
+
+			assert_eq!(
+				get_protos(Some(DataAvailability::Missing)),
+				json!({ hex::encode(arweave_proto_hash): {} })
+			);
+			assert_eq!(
+				get_protos(None),
+				json!({
+					hex::encode(proto.get_proto_hash()): {},
+					hex::encode(arweave_proto_hash): {},
+				})
+			);
+		});
+	}
+
+	#[test]
+	fn check_data_availability_should_work() {
+		let (mut ext, pool_state, offchain_state, ed25519_public_key) = new_test_ext_with_ocw();
+
+		sp_fragnova::init_data_gateways(Some(String::from(ARWEAVE_GATEWAY_URL)), None);
+
+		ext.execute_with(|| {
+			let dd = DummyData::new();
+
+			let proto_hash = upload_arweave_proto(dd.account_id);
+
+			// the local stand-in of the Arweave Gateway
+			offchain_state.write().expect_request(testing::PendingRequest {
+				method: String::from("GET"),
+				uri: format!("http://localhost:1984/{}", URL_SAFE_NO_PAD.encode(ARWEAVE_TX)),
+				response: Some(b"BLENDER-v300".to_vec()),
+				sent: true,
+				..Default::default()
+			});
+
+			ProtosPallet::check_data_availability(System::block_number());
+
+			let tx = pool_state.write().transactions.pop().unwrap();
+			let tx = <Extrinsic as codec::Decode>::decode(&mut &*tx).unwrap();
+			assert_eq!(tx.signature, None); // an unsigned transaction with a signed payload
+
+			let RuntimeCall::ProtosPallet(crate::Call::internal_attest_data { data, signature }) =
+				tx.call
+			else {
+				panic!("The unsigned transaction that was sent is incorrect!");
+			};
+			assert_eq!(
+				data,
+				DataAvailabilityVote {
+					public: ed25519_public_key,
+					proto_hash,
+					status: DataAvailability::Available,
+					block_number: System::block_number(),
+					_reserved1: None,
+					_reserved2: None,
+					_reserved3: None,
+				}
+			);
+			assert!(<DataAvailabilityVote<
+				<Test as frame_system::offchain::SigningTypes>::Public,
+				u64,
+			> as frame_system::offchain::SignedPayload<Test>>::verify::<pallet_accounts::crypto::FragAuthId>(
+				&data, signature
+			));
+
+			// the same check is not voted twice
+			ProtosPallet::check_data_availability(System::block_number());
+			assert!(pool_state.read().transactions.is_empty());
+		});
+	}
+}
+
+mod cid_tests {
+	use super::*;
+	use protos::categories::BinaryCategories;
+	use sp_fragnova::cid::{cid_from_string, cid_to_string, decode_cid, encode_cid};
+
+	// A CIDv0 (i.e a bare `sha2-256` multihash)
+	const CID_V0: &str = "QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbdG";
+
+	fn upload_ipfs_proto(
+		signer: <Test as frame_system::Config>::AccountId,
+		cid: Vec<u8>,
+	) -> DispatchResult {
+		ProtosPallet::upload(
+			RuntimeOrigin::signed(signer),
+			Vec::new(),
+			Categories::Binary(BinaryCategories::BlendFile),
+			Vec::new().try_into().unwrap(),
+			None,
+			UsageLicense::Open,
+			None,
+			ProtoData::IpfsCid(cid),
+		)
+	}
+
+	#[test]
+	fn upload_should_work_if_the_ipfs_cid_is_valid() {
+		new_test_ext().execute_with(|| {
+			let dd = DummyData::new();
+
+			let cid_v0 = cid_from_string(CID_V0).unwrap();
+			assert_ok!(upload_ipfs_proto(dd.account_id, cid_v0.clone()));
+			assert_eq!(ProtosPallet::get_cid(blake2_256(&cid_v0)), Some(cid_v0.clone()));
+			assert_eq!(cid_to_string(&cid_v0).unwrap(), CID_V0);
+
+			let cid_v1 = encode_cid(&[7u8; 32]);
+			assert_ok!(upload_ipfs_proto(dd.account_id, cid_v1.clone()));
+			assert_eq!(ProtosPallet::get_cid(blake2_256(&cid_v1)), Some(cid_v1));
+		});
+	}
+
+	#[test]
+	fn upload_should_not_work_if_the_ipfs_cid_is_invalid() {
+		new_test_ext().execute_with(|| {
+			let dd = DummyData::new();
+
+			// empty CID
+			assert_noop!(upload_ipfs_proto(dd.account_id, vec![]), Error::<Test>::InvalidCid);
+			// unsupported CID version
+			assert_noop!(
+				upload_ipfs_proto(
+					dd.account_id,
+					[&[0x02u8][..], &encode_cid(&[7u8; 32])[1..]].concat()
+				),
+				Error::<Test>::InvalidCid
+			);
+			// truncated digest
+			let cid = encode_cid(&[7u8; 32]);
+			assert_noop!(
+				upload_ipfs_proto(dd.account_id, cid[..cid.len() - 1].to_vec()),
+				Error::<Test>::InvalidCid
+			);
+			// a CID that was right-padded with zeroes
+			assert_noop!(
+				upload_ipfs_proto(dd.account_id, [&cid[..], &[0u8; 26][..]].concat()),
+				Error::<Test>::InvalidCid
+			);
+		});
+	}
+
+	#[test]
+	fn upload_should_still_work_with_the_deprecated_ipfs_variant() {
+		new_test_ext().execute_with(|| {
+			let dd = DummyData::new();
+
+			assert_ok!(ProtosPallet::upload(
+				RuntimeOrigin::signed(dd.account_id),
+				Vec::new(),
+				Categories::Binary(BinaryCategories::BlendFile),
+				Vec::new().try_into().unwrap(),
+				None,
+				UsageLicense::Open,
+				None,
+				ProtoData::Ipfs([7u8; 64]),
+			));
+
+			let proto_hash = blake2_256(&[7u8; 64]);
+			assert_eq!(<Protos<Test>>::get(proto_hash).unwrap().data, ProtoData::Ipfs([7u8; 64]));
+			// it has no CID, since its data is not a binary CID
+			assert_eq!(ProtosPallet::get_cid(proto_hash), None);
+		});
+	}
+
+	#[test]
+	fn patch_should_not_work_if_the_ipfs_cid_is_invalid() {
+		new_test_ext().execute_with(|| {
+			let dd = DummyData::new();
+
+			let cid = encode_cid(&[7u8; 32]);
+			assert_ok!(upload_ipfs_proto(dd.account_id, cid.clone()));
+
+			assert_noop!(
+				ProtosPallet::patch(
+					RuntimeOrigin::signed(dd.account_id),
+					blake2_256(&cid),
+					None,
+					Vec::new(),
+					None,
+					Some(ProtoData::IpfsCid(cid[..8].to_vec())),
+				),
+				Error::<Test>::InvalidCid
+			);
+		});
+	}
+
+	#[test]
+	fn get_cid_should_return_the_cid_of_the_indexed_data_if_the_data_is_local() {
+		new_test_ext().execute_with(|| {
+			let dd = DummyData::new();
+
+			let patch = dd.patch;
+			let proto_hash = patch.proto_fragment.get_proto_hash();
+
+			assert_ok!(upload(dd.account_id, &patch.proto_fragment));
+			let cid = ProtosPallet::get_cid(proto_hash).unwrap();
+			assert_eq!(cid, encode_cid(&proto_hash));
+			assert_eq!(decode_cid(&cid).unwrap().get_fragnova_hash(), Some(proto_hash));
+			assert_eq!(cid_from_string(&cid_to_string(&cid).unwrap()), Ok(cid));
+
+			// the CID of a patched Proto-Fragment is the CID of its current data
+			assert_ok!(ProtosPallet::patch(
+				RuntimeOrigin::signed(dd.account_id),
+				proto_hash,
+				None,
+				Vec::new(),
+				None,
+				Some(ProtoData::Local(patch.new_data.clone())),
+			));
+			assert_eq!(ProtosPallet::get_cid(proto_hash), Some(encode_cid(&patch.get_data_hash())));
+		});
+	}
+
+	#[test]
+	fn get_cid_should_not_return_anything_if_the_data_is_stored_on_arweave() {
+		new_test_ext().execute_with(|| {
+			let dd = DummyData::new();
+
+			assert_ok!(ProtosPallet::upload(
+				RuntimeOrigin::signed(dd.account_id),
+				Vec::new(),
+				Categories::Binary(BinaryCategories::BlendFile),
+				Vec::new().try_into().unwrap(),
+				None,
+				UsageLicense::Open,
+				None,
+				ProtoData::Arweave([7u8; 32]),
+			));
+
+			assert_eq!(ProtosPallet::get_cid(blake2_256(&[7u8; 32])), None);
+		});
+	}
+}
+
+mod genesis_tests {
+	use super::*;
+	use frame_support::traits::GenesisBuild;
+
+	#[test]
+	fn genesis_build_should_store_and_index_protos() {
+		let dd = DummyData::new();
+
+		let parent_hash = [1u8; 32];
+		let proto_hash = [2u8; 32];
+		let genesis_proto = |proto_hash: Hash256, references: Vec<Hash256>| GenesisProto {
+			proto_hash,
+			proto: Proto {
+				category: Categories::Text(TextCategories::Plain),
+				references,
+				patches: vec![],
+				data: ProtoData::Local(vec![]),
+				block: 7,
+				license: UsageLicense::Open,
+				creator: dd.account_id,
+				owner: ProtoOwner::User(dd.account_id),
+				// these indexes are overwritten by `tags` and `metadata`
+				tags: vec![Compact(69)],
+				metadata: BTreeMap::from([(Compact(69), [3u8; 32])]),
+				cluster: None,
+				_reserved1: None,
+				_reserved2: None,
+				_reserved3: None,
+			},
+			tags: vec![b"tag".to_vec()],
+			metadata: vec![(b"image".to_vec(), [3u8; 32])],
+			trait_name: None,
+			chunks: None,
+		};
+
+		let mut storage = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
+		GenesisBuild::<Test>::assimilate_storage(
+			&pallet_protos::GenesisConfig {
+				protos: vec![
+					genesis_proto(parent_hash, vec![]).encode(),
+					genesis_proto(proto_hash, vec![parent_hash]).encode(),
+				],
+			},
+			&mut storage,
+		)
+		.unwrap();
+
+		sp_io::TestExternalities::new(storage).execute_with(|| {
+			let tag_index = <Tags<Test>>::get(b"tag".to_vec()).unwrap();
+			let metadata_key_index = <MetaKeys<Test>>::get(b"image".to_vec()).unwrap();
+			assert_eq!(<TagsIndex<Test>>::get(), tag_index);
+			assert_eq!(<MetaKeysIndex<Test>>::get(), metadata_key_index);
+
+			let proto = <Protos<Test>>::get(proto_hash).unwrap();
+			assert_eq!(proto.block, 7);
+			assert_eq!(proto.tags, vec![Compact(tag_index)]);
+			assert_eq!(proto.metadata, BTreeMap::from([(Compact(metadata_key_index), [3u8; 32])]));
+
+			assert_eq!(<ProtosByParent<Test>>::get(parent_hash), Some(vec![proto_hash]));
+			assert_eq!(
+				<ProtosByOwner<Test>>::get(ProtoOwner::User(dd.account_id)),
+				Some(vec![parent_hash, proto_hash])
+			);
+			assert_eq!(
+				<ProtosByCategory<Test>>::get(Categories::Text(TextCategories::Plain)),
+				Some(vec![parent_hash, proto_hash])
+			);
+		});
+	}
+}
@@ -15,14 +15,13 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-//! Autogenerated weights for pallet_protos
+//! Weights for pallet_protos
 //!
-//! THIS FILE WAS AUTO-GENERATED USING THE SUBSTRATE BENCHMARK CLI VERSION 4.0.0-dev
-//! DATE: 2022-10-13, STEPS: `50`, REPEAT: 20, LOW RANGE: `[]`, HIGH RANGE: `[]`
-//! HOSTNAME: `alepc`, CPU: `11th Gen Intel(R) Core(TM) i7-1195G7 @ 2.90GHz`
-//! EXECUTION: Some(Wasm), WASM-EXECUTION: Compiled, CHAIN: Some("dev"), DB CACHE: 1024
+//! NOTE: These weights are **not** the output of a benchmark run. They were first generated with the
+//! Substrate benchmark CLI and have since been **estimated by hand** for the new or changed extrinsics.
+//! They must be regenerated (on the reference hardware) before they are relied upon.
 
-// Executed Command:
+// Command to regenerate this file:
 // ./target/production/fragnova
 // benchmark
 // pallet
@@ -51,6 +50,14 @@ pub trait WeightInfo {
 	fn detach() -> Weight;
 	fn transfer() -> Weight;
 	fn set_metadata(m: u32, d: u32, ) -> Weight;
+	fn begin_upload() -> Weight;
+	fn upload_chunk(d: u32, ) -> Weight;
+	fn finalize_upload(r: u32, t: u32, c: u32, ) -> Weight;
+	fn cancel_upload(c: u32, ) -> Weight;
+	fn internal_attest_data() -> Weight;
+	fn approve() -> Weight;
+	fn set_approval_for_all() -> Weight;
+	fn set_license_royalty() -> Weight;
 }
 
 /// Weights for pallet_protos using the Substrate node and recommended hardware.
@@ -112,10 +119,13 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 	// Storage: Protos Protos (r:1 w:1)
 	// Storage: Detach DetachedHashes (r:1 w:0)
 	// Storage: Protos ProtosByOwner (r:2 w:2)
+	// Storage: Protos ProtoApprovals (r:1 w:1)
+	// Storage: Protos OperatorApprovals (r:1 w:0)
+	// Storage: Protos LockedProtos (r:1 w:1)
 	fn transfer() -> Weight {
-		Weight::from_ref_time(24_381_000 as u64)
-			.saturating_add(T::DbWeight::get().reads(4 as u64))
-			.saturating_add(T::DbWeight::get().writes(3 as u64))
+		Weight::from_ref_time(29_518_000 as u64)
+			.saturating_add(T::DbWeight::get().reads(7 as u64))
+			.saturating_add(T::DbWeight::get().writes(5 as u64))
 	}
 	// Storage: Protos Protos (r:1 w:1)
 	// Storage: Detach DetachedHashes (r:1 w:0)
@@ -133,6 +143,95 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 			.saturating_add(T::DbWeight::get().reads(5 as u64))
 			.saturating_add(T::DbWeight::get().writes(3 as u64))
 	}
+	// Storage: Protos Protos (r:1 w:0)
+	// Storage: Protos UploadSessions (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	fn begin_upload() -> Weight {
+		Weight::from_ref_time(34_861_000 as u64)
+			.saturating_add(T::DbWeight::get().reads(3 as u64))
+			.saturating_add(T::DbWeight::get().writes(2 as u64))
+	}
+	// Storage: Protos UploadSessions (r:1 w:1)
+	// Storage: Protos UploadChunks (r:1 w:1)
+	// Storage: unknown [0x3a65787472696e7369635f696e646578] (r:1 w:0)
+	/// The range of component `d` is `[1, 1048576]`.
+	fn upload_chunk(d: u32, ) -> Weight {
+		Weight::from_ref_time(26_118_000 as u64)
+			// Standard Error: 0
+			.saturating_add(Weight::from_ref_time(2_000 as u64).saturating_mul(d as u64))
+			.saturating_add(T::DbWeight::get().reads(3 as u64))
+			.saturating_add(T::DbWeight::get().writes(2 as u64))
+	}
+	// Storage: Protos UploadSessions (r:1 w:1)
+	// Storage: Protos UploadChunks (r:1024 w:1024)
+	// Storage: Protos Protos (r:101 w:1)
+	// Storage: Protos Tags (r:10 w:10)
+	// Storage: Protos TagsIndex (r:1 w:1)
+	// Storage: Protos ProtoChunks (r:0 w:1)
+	// Storage: Protos ProtosByCategory (r:1 w:1)
+	// Storage: Protos ProtosByOwner (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	/// The range of component `r` is `[1, 100]`.
+	/// The range of component `t` is `[1, 10]`.
+	/// The range of component `c` is `[1, 1024]`.
+	fn finalize_upload(r: u32, t: u32, c: u32, ) -> Weight {
+		Weight::from_ref_time(64_310_000 as u64)
+			// Standard Error: 65_000
+			.saturating_add(Weight::from_ref_time(2_092_000 as u64).saturating_mul(r as u64))
+			// Standard Error: 65_000
+			.saturating_add(Weight::from_ref_time(2_860_000 as u64).saturating_mul(t as u64))
+			// Standard Error: 4_000
+			.saturating_add(Weight::from_ref_time(1_467_000 as u64).saturating_mul(c as u64))
+			.saturating_add(T::DbWeight::get().reads(6 as u64))
+			.saturating_add(T::DbWeight::get().reads((1 as u64).saturating_mul(r as u64)))
+			.saturating_add(T::DbWeight::get().reads((1 as u64).saturating_mul(t as u64)))
+			.saturating_add(T::DbWeight::get().reads((1 as u64).saturating_mul(c as u64)))
+			.saturating_add(T::DbWeight::get().writes(7 as u64))
+			.saturating_add(T::DbWeight::get().writes((1 as u64).saturating_mul(t as u64)))
+			.saturating_add(T::DbWeight::get().writes((1 as u64).saturating_mul(c as u64)))
+	}
+	// Storage: Protos UploadSessions (r:1 w:1)
+	// Storage: Protos UploadChunks (r:0 w:1024)
+	// Storage: System Account (r:1 w:1)
+	/// The range of component `c` is `[1, 1024]`.
+	fn cancel_upload(c: u32, ) -> Weight {
+		Weight::from_ref_time(18_902_000 as u64)
+			// Standard Error: 2_000
+			.saturating_add(Weight::from_ref_time(611_000 as u64).saturating_mul(c as u64))
+			.saturating_add(T::DbWeight::get().reads(2 as u64))
+			.saturating_add(T::DbWeight::get().writes(2 as u64))
+			.saturating_add(T::DbWeight::get().writes((1 as u64).saturating_mul(c as u64)))
+	}
+	// Storage: Protos Protos (r:1 w:0)
+	// Storage: Protos DataAttestations (r:1 w:1)
+	// Storage: Protos DataAvailabilityVoting (r:1 w:1)
+	fn internal_attest_data() -> Weight {
+		Weight::from_ref_time(29_117_000 as u64)
+			.saturating_add(T::DbWeight::get().reads(3 as u64))
+			.saturating_add(T::DbWeight::get().writes(2 as u64))
+	}
+	// Storage: Protos Protos (r:1 w:0)
+	// Storage: Protos OperatorApprovals (r:1 w:0)
+	// Storage: Detach DetachedHashes (r:1 w:0)
+	// Storage: Protos ProtoApprovals (r:0 w:1)
+	fn approve() -> Weight {
+		Weight::from_ref_time(22_846_000 as u64)
+			.saturating_add(T::DbWeight::get().reads(3 as u64))
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+	}
+	// Storage: Protos OperatorApprovals (r:0 w:1)
+	fn set_approval_for_all() -> Weight {
+		Weight::from_ref_time(14_207_000 as u64)
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+	}
+	// Storage: Protos Protos (r:1 w:0)
+	// Storage: Detach DetachedHashes (r:1 w:0)
+	// Storage: Protos LicenseRoyalties (r:0 w:1)
+	fn set_license_royalty() -> Weight {
+		Weight::from_ref_time(21_534_000 as u64)
+			.saturating_add(T::DbWeight::get().reads(2 as u64))
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+	}
 }
 
 // For backwards compatibility and tests
@@ -193,10 +292,13 @@ impl WeightInfo for () {
 	// Storage: Protos Protos (r:1 w:1)
 	// Storage: Detach DetachedHashes (r:1 w:0)
 	// Storage: Protos ProtosByOwner (r:2 w:2)
+	// Storage: Protos ProtoApprovals (r:1 w:1)
+	// Storage: Protos OperatorApprovals (r:1 w:0)
+	// Storage: Protos LockedProtos (r:1 w:1)
 	fn transfer() -> Weight {
-		Weight::from_ref_time(24_381_000 as u64)
-			.saturating_add(RocksDbWeight::get().reads(4 as u64))
-			.saturating_add(RocksDbWeight::get().writes(3 as u64))
+		Weight::from_ref_time(29_518_000 as u64)
+			.saturating_add(RocksDbWeight::get().reads(7 as u64))
+			.saturating_add(RocksDbWeight::get().writes(5 as u64))
 	}
 	// Storage: Protos Protos (r:1 w:1)
 	// Storage: Detach DetachedHashes (r:1 w:0)
@@ -214,4 +316,93 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().reads(5 as u64))
 			.saturating_add(RocksDbWeight::get().writes(3 as u64))
 	}
+	// Storage: Protos Protos (r:1 w:0)
+	// Storage: Protos UploadSessions (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	fn begin_upload() -> Weight {
+		Weight::from_ref_time(34_861_000 as u64)
+			.saturating_add(RocksDbWeight::get().reads(3 as u64))
+			.saturating_add(RocksDbWeight::get().writes(2 as u64))
+	}
+	// Storage: Protos UploadSessions (r:1 w:1)
+	// Storage: Protos UploadChunks (r:1 w:1)
+	// Storage: unknown [0x3a65787472696e7369635f696e646578] (r:1 w:0)
+	/// The range of component `d` is `[1, 1048576]`.
+	fn upload_chunk(d: u32, ) -> Weight {
+		Weight::from_ref_time(26_118_000 as u64)
+			// Standard Error: 0
+			.saturating_add(Weight::from_ref_time(2_000 as u64).saturating_mul(d as u64))
+			.saturating_add(RocksDbWeight::get().reads(3 as u64))
+			.saturating_add(RocksDbWeight::get().writes(2 as u64))
+	}
+	// Storage: Protos UploadSessions (r:1 w:1)
+	// Storage: Protos UploadChunks (r:1024 w:1024)
+	// Storage: Protos Protos (r:101 w:1)
+	// Storage: Protos Tags (r:10 w:10)
+	// Storage: Protos TagsIndex (r:1 w:1)
+	// Storage: Protos ProtoChunks (r:0 w:1)
+	// Storage: Protos ProtosByCategory (r:1 w:1)
+	// Storage: Protos ProtosByOwner (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	/// The range of component `r` is `[1, 100]`.
+	/// The range of component `t` is `[1, 10]`.
+	/// The range of component `c` is `[1, 1024]`.
+	fn finalize_upload(r: u32, t: u32, c: u32, ) -> Weight {
+		Weight::from_ref_time(64_310_000 as u64)
+			// Standard Error: 65_000
+			.saturating_add(Weight::from_ref_time(2_092_000 as u64).saturating_mul(r as u64))
+			// Standard Error: 65_000
+			.saturating_add(Weight::from_ref_time(2_860_000 as u64).saturating_mul(t as u64))
+			// Standard Error: 4_000
+			.saturating_add(Weight::from_ref_time(1_467_000 as u64).saturating_mul(c as u64))
+			.saturating_add(RocksDbWeight::get().reads(6 as u64))
+			.saturating_add(RocksDbWeight::get().reads((1 as u64).saturating_mul(r as u64)))
+			.saturating_add(RocksDbWeight::get().reads((1 as u64).saturating_mul(t as u64)))
+			.saturating_add(RocksDbWeight::get().reads((1 as u64).saturating_mul(c as u64)))
+			.saturating_add(RocksDbWeight::get().writes(7 as u64))
+			.saturating_add(RocksDbWeight::get().writes((1 as u64).saturating_mul(t as u64)))
+			.saturating_add(RocksDbWeight::get().writes((1 as u64).saturating_mul(c as u64)))
+	}
+	// Storage: Protos UploadSessions (r:1 w:1)
+	// Storage: Protos UploadChunks (r:0 w:1024)
+	// Storage: System Account (r:1 w:1)
+	/// The range of component `c` is `[1, 1024]`.
+	fn cancel_upload(c: u32, ) -> Weight {
+		Weight::from_ref_time(18_902_000 as u64)
+			// Standard Error: 2_000
+			.saturating_add(Weight::from_ref_time(611_000 as u64).saturating_mul(c as u64))
+			.saturating_add(RocksDbWeight::get().reads(2 as u64))
+			.saturating_add(RocksDbWeight::get().writes(2 as u64))
+			.saturating_add(RocksDbWeight::get().writes((1 as u64).saturating_mul(c as u64)))
+	}
+	// Storage: Protos Protos (r:1 w:0)
+	// Storage: Protos DataAttestations (r:1 w:1)
+	// Storage: Protos DataAvailabilityVoting (r:1 w:1)
+	fn internal_attest_data() -> Weight {
+		Weight::from_ref_time(29_117_000 as u64)
+			.saturating_add(RocksDbWeight::get().reads(3 as u64))
+			.saturating_add(RocksDbWeight::get().writes(2 as u64))
+	}
+	// Storage: Protos Protos (r:1 w:0)
+	// Storage: Protos OperatorApprovals (r:1 w:0)
+	// Storage: Detach DetachedHashes (r:1 w:0)
+	// Storage: Protos ProtoApprovals (r:0 w:1)
+	fn approve() -> Weight {
+		Weight::from_ref_time(22_846_000 as u64)
+			.saturating_add(RocksDbWeight::get().reads(3 as u64))
+			.saturating_add(RocksDbWeight::get().writes(1 as u64))
+	}
+	// Storage: Protos OperatorApprovals (r:0 w:1)
+	fn set_approval_for_all() -> Weight {
+		Weight::from_ref_time(14_207_000 as u64)
+			.saturating_add(RocksDbWeight::get().writes(1 as u64))
+	}
+	// Storage: Protos Protos (r:1 w:0)
+	// Storage: Detach DetachedHashes (r:1 w:0)
+	// Storage: Protos LicenseRoyalties (r:0 w:1)
+	fn set_license_royalty() -> Weight {
+		Weight::from_ref_time(21_534_000 as u64)
+			.saturating_add(RocksDbWeight::get().reads(2 as u64))
+			.saturating_add(RocksDbWeight::get().writes(1 as u64))
+	}
 }
@@ -0,0 +1,138 @@
+//! Helper Functions to **encode** and **decode** **Content Identifiers (CIDs)** (https://github.com/multiformats/cid)
+//!
+//! The data that is indexed on the Fragnova Blockchain can be fetched from any node via bitswap (IPFS)
+//! using a CIDv1 whose codec is `raw` and whose multihash is the `blake2b-256` hash of the data (see `CID_PREFIX`).
+
+use crate::{Hash256, CID_PREFIX};
+use base58::{FromBase58, ToBase58};
+use scale_info::prelude::string::String;
+use sp_std::vec::Vec;
+
+/// The version of a CIDv1
+pub const CID_V1: u64 = 0x01;
+/// The Multicodec code of `raw` binary data (https://github.com/multiformats/multicodec)
+pub const RAW_CODEC: u64 = 0x55;
+/// The Multihash code of `blake2b-256` (https://github.com/multiformats/multihash)
+pub const BLAKE2B_256: u64 = 0xb220;
+/// The Multihash code of `sha2-256` (the only hash function allowed in a CIDv0)
+pub const SHA2_256: u64 = 0x12;
+/// The maximum length (in bytes) of a binary CID that can be stored on-chain
+pub const MAX_CID_LENGTH: usize = 128;
+/// The Multibase prefix of `base58btc` (https://github.com/multiformats/multibase)
+const BASE58BTC_PREFIX: char = 'z';
+
+/// **Errors** that can occur while **decoding a CID**
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CidError {
+	/// The CID is empty or longer than `MAX_CID_LENGTH` bytes
+	InvalidLength,
+	/// A varint of the CID is truncated or too big
+	InvalidVarint,
+	/// The CID version is neither 0 nor 1
+	UnsupportedVersion,
+	/// The length of the multihash digest does not match the declared one
+	InvalidDigestLength,
+	/// The string is not a `base58btc` encoded CIDv1 nor a CIDv0
+	InvalidMultibase,
+}
+
+/// **Struct** of a **decoded CID**
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cid {
+	/// The CID version (0 or 1)
+	pub version: u64,
+	/// The Multicodec code of the content (always `dag-pb` for a CIDv0)
+	pub codec: u64,
+	/// The Multihash code of the hash function that was used to hash the content
+	pub hash_code: u64,
+	/// The digest of the content
+	pub digest: Vec<u8>,
+}
+
+impl Cid {
+	/// Get the **hash of the data indexed on the Fragnova Blockchain** that this CID points to,
+	/// if this CID is a CIDv1 whose codec is `raw` and whose multihash is `blake2b-256`
+	pub fn get_fragnova_hash(&self) -> Option<Hash256> {
+		if self.version == CID_V1 && self.codec == RAW_CODEC && self.hash_code == BLAKE2B_256 {
+			self.digest.clone().try_into().ok()
+		} else {
+			None
+		}
+	}
+}
+
+/// Read an unsigned varint (https://github.com/multiformats/unsigned-varint) from the beginning of `bytes`,
+/// and advance `bytes` past it
+pub fn read_varint(bytes: &mut &[u8]) -> Result<u64, CidError> {
+	let mut value = 0u64;
+	// the unsigned varints used in multiformats are at most 9 bytes long
+	for (i, byte) in bytes.iter().enumerate().take(9) {
+		value |= ((byte & 0x7f) as u64) << (7 * i);
+		if byte & 0x80 == 0 {
+			*bytes = &bytes[i + 1..];
+			return Ok(value)
+		}
+	}
+	Err(CidError::InvalidVarint)
+}
+
+/// **Get** the **binary CIDv1** of the data whose `blake2b-256` hash is `hash`.
+///
+/// Note: This is the CID with which the data can be fetched via bitswap (IPFS) from a Fragnova node
+pub fn encode_cid(hash: &Hash256) -> Vec<u8> {
+	[&CID_PREFIX[..], &hash[..]].concat()
+}
+
+/// **Decode** and **validate** the **binary CID** `cid` (either a CIDv0 or a CIDv1)
+pub fn decode_cid(cid: &[u8]) -> Result<Cid, CidError> {
+	if cid.is_empty() || cid.len() > MAX_CID_LENGTH {
+		return Err(CidError::InvalidLength)
+	}
+
+	// A CIDv0 is a bare `sha2-256` multihash
+	if cid.len() == 34 && cid[0] == SHA2_256 as u8 && cid[1] == 32 {
+		return Ok(Cid { version: 0, codec: 0x70, hash_code: SHA2_256, digest: cid[2..].to_vec() })
+	}
+
+	let mut bytes = cid;
+	let version = read_varint(&mut bytes)?;
+	if version != CID_V1 {
+		return Err(CidError::UnsupportedVersion)
+	}
+	let codec = read_varint(&mut bytes)?;
+	let hash_code = read_varint(&mut bytes)?;
+	let digest_length = read_varint(&mut bytes)?;
+	if digest_length == 0 || bytes.len() as u64 != digest_length {
+		return Err(CidError::InvalidDigestLength)
+	}
+
+	Ok(Cid { version, codec, hash_code, digest: bytes.to_vec() })
+}
+
+/// **Convert** the **binary CID** `cid` into its **string representation**
+/// (i.e `base58btc` for a CIDv0 and `"z" + base58btc` for a CIDv1)
+pub fn cid_to_string(cid: &[u8]) -> Result<String, CidError> {
+	let decoded = decode_cid(cid)?;
+	let mut string = String::new();
+	if decoded.version == CID_V1 {
+		string.push(BASE58BTC_PREFIX);
+	}
+	string.push_str(&cid.to_base58());
+	Ok(string)
+}
+
+/// **Convert** the **string representation of a CID** (see `cid_to_string()`) into a **binary CID**
+pub fn cid_from_string(cid: &str) -> Result<Vec<u8>, CidError> {
+	let bytes = if let Some(cid_v1) = cid.strip_prefix(BASE58BTC_PREFIX) {
+		cid_v1.from_base58()
+	} else if cid.starts_with("Qm") {
+		cid.from_base58()
+	} else {
+		return Err(CidError::InvalidMultibase)
+	}
+	.map_err(|_| CidError::InvalidMultibase)?;
+
+	decode_cid(&bytes)?;
+
+	Ok(bytes)
+}
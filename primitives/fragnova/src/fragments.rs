@@ -6,7 +6,7 @@
 use crate::Hash256;
 use codec::{Compact, Decode, Encode};
 use protos::permissions::FragmentPerms;
-use sp_std::collections::btree_map::BTreeMap;
+use sp_std::{collections::btree_map::BTreeMap, vec::Vec};
 
 /// Type used to represent an Instance's Edition ID and an Instance's Copy ID
 pub type InstanceUnit = u64;
@@ -69,6 +69,8 @@ pub struct FragmentDefinition<TU8Array, TFungibleAsset, TAccountId, TBlockNum> {
 	pub max_supply: Option<Compact<InstanceUnit>>,
 	/// The creator of this class
 	pub creator: TAccountId,
+	/// The **current owner** of the **Fragment Definition** (initially its creator), i.e the account that is allowed to manage it
+	pub owner: TAccountId,
 	/// The block number when the item was created
 	pub created_at: TBlockNum,
 	/// **Map** that maps the **Key of a Proto-Fragment's Custom Metadata Object** to the **Hash of the aforementioned Custom Metadata Object**
@@ -118,3 +120,46 @@ pub struct FragmentInstance<TBlockNum> {
 	pub _reserved3: Option<()>,
 	// Total 16 bytes	should be!
 }
+
+/// **Enum** that represents the **type** of an **attribute** of the **Fragment Instances** of a **Fragment Definition**
+#[derive(Encode, Decode, Copy, Clone, scale_info::TypeInfo, Debug, PartialEq, Eq)]
+pub enum AttributeType {
+	/// Signed integer
+	Number,
+	/// UTF-8 string
+	String,
+	/// 32-byte hash
+	Hash,
+}
+
+/// **Enum** that represents the **value** of an **attribute** of a **Fragment Instance**
+#[derive(Encode, Decode, Clone, scale_info::TypeInfo, Debug, PartialEq, Eq)]
+pub enum AttributeValue {
+	/// Signed integer
+	Number(i64),
+	/// UTF-8 string
+	String(Vec<u8>),
+	/// 32-byte hash
+	Hash(Hash256),
+}
+
+impl AttributeValue {
+	/// Get the **type** of the **attribute value**
+	pub fn attribute_type(&self) -> AttributeType {
+		match self {
+			AttributeValue::Number(_) => AttributeType::Number,
+			AttributeValue::String(_) => AttributeType::String,
+			AttributeValue::Hash(_) => AttributeType::Hash,
+		}
+	}
+}
+
+/// **Struct** of the **attribute schema** of a **Fragment Definition**,
+/// i.e the **typed attributes** that its **Fragment Instances** can have
+#[derive(Encode, Decode, Clone, scale_info::TypeInfo, Debug, PartialEq)]
+pub struct AttributeSchema {
+	/// **Trait Proto-Fragment** whose **records** declare the **attributes**
+	pub trait_proto: Hash256,
+	/// **Name** and **type** of **every attribute** (every name is the name of a record of the Trait)
+	pub attributes: Vec<(Vec<u8>, AttributeType)>,
+}
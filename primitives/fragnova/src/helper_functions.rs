@@ -1,7 +1,7 @@
 //! Helper Functions that can be used in other packages of this workspace
 
 use codec::{Decode, Encode, Error as CodecError};
-use sp_core::offchain::{HttpRequestStatus, Timestamp};
+use sp_core::offchain::{HttpRequestId, HttpRequestStatus, Timestamp};
 use sp_io::{hashing::blake2_256, offchain};
 use sp_std::vec::Vec;
 
@@ -24,6 +24,27 @@ pub fn http_json_post(
 	// send off the request
 	offchain::http_request_write_body(request, &[], None).unwrap();
 
+	http_response_read(request, wait)
+}
+
+/// Make an HTTP GET Request to the URL `url`
+pub fn http_get(url: &str, wait: Option<Timestamp>) -> Result<Vec<u8>, &'static str> {
+	log::debug!("sp_fragnova http_get called...");
+
+	let request =
+		offchain::http_request_start("GET", url, &[]).map_err(|_| "Failed to start request")?;
+
+	// send off the request
+	offchain::http_request_write_body(request, &[], None).map_err(|_| "Failed to send request")?;
+
+	http_response_read(request, wait)
+}
+
+/// Wait for the response of the HTTP Request `request` and return its body (if the response status is 200)
+fn http_response_read(
+	request: HttpRequestId,
+	wait: Option<Timestamp>,
+) -> Result<Vec<u8>, &'static str> {
 	let results = offchain::http_response_wait(&[request], wait);
 	let status = results[0];
 
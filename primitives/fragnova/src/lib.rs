@@ -6,18 +6,22 @@
 // #[cfg(feature = "std")]
 // extern crate chainblocks;
 
+/// Helper Functions to encode and decode Content Identifiers (CIDs)
+pub mod cid;
 /// Types that will be used by the Fragments pallet
 pub mod fragments;
 /// Helper Functions that can be used in other packages of this workspace
 mod helper_functions;
+/// A Binary Merkle Tree whose nodes are hashed with `blake2_256`
+pub mod merkle;
 /// Types that will be used by the Protos pallet
 pub mod protos;
-pub use helper_functions::{get_account_id, get_locked_frag_account, http_json_post};
+pub use helper_functions::{get_account_id, get_locked_frag_account, http_get, http_json_post};
 /// TODO - Documentation
 mod runtime_interface;
 pub use runtime_interface::fragnova;
 #[cfg(feature = "std")]
-pub use runtime_interface::init;
+pub use runtime_interface::{init, init_data_gateways};
 
 /// 64 bytes u8-Array
 pub type Hash64 = [u8; 8];
@@ -0,0 +1,118 @@
+//! A Binary Merkle Tree whose nodes are hashed with `blake2_256`
+
+use crate::Hash256;
+use codec::{Decode, Encode};
+use sp_io::hashing::blake2_256;
+use sp_std::vec::Vec;
+
+/// Prefix of the preimage of every **leaf** of the Merkle Tree
+pub const LEAF_PREFIX: u8 = 0x00;
+/// Prefix of the preimage of every **inner node** of the Merkle Tree
+pub const NODE_PREFIX: u8 = 0x01;
+
+/// Struct **representing** a **Merkle Proof** that a leaf is in a Merkle Tree (see `verify_proof()`)
+#[derive(Encode, Decode, Clone, scale_info::TypeInfo, Debug, PartialEq, Eq)]
+pub struct MerkleProof {
+	/// **Index** of the **leaf** in the leaves of the Merkle Tree
+	pub leaf_index: u32,
+	/// **Number of leaves** of the Merkle Tree
+	pub leaf_count: u32,
+	/// The **sibling nodes** from the leaf to the root (the rows in which the node has no sibling are skipped)
+	pub proof: Vec<Hash256>,
+}
+
+/// Hash the leaf `leaf` into the first row of the Merkle Tree
+fn hash_leaf(leaf: &Hash256) -> Hash256 {
+	blake2_256(&[&[LEAF_PREFIX][..], &leaf[..]].concat())
+}
+
+/// Hash the nodes `left` and `right` (or the last node `left` of a row alone) into the next row of the Merkle Tree
+fn hash_node(left: &Hash256, right: Option<&Hash256>) -> Hash256 {
+	match right {
+		Some(right) => blake2_256(&[&[NODE_PREFIX][..], &left[..], &right[..]].concat()),
+		None => blake2_256(&[&[NODE_PREFIX][..], &left[..]].concat()),
+	}
+}
+
+/// Hash the row `row` of the Merkle Tree into the next row
+fn next_row(row: &[Hash256]) -> Vec<Hash256> {
+	row.chunks(2)
+		.map(|pair| match pair {
+			[left, right] => hash_node(left, Some(right)),
+			[single] => hash_node(single, None),
+			_ => unreachable!("`chunks(2)` never yields an empty or a bigger slice; qed"),
+		})
+		.collect::<Vec<Hash256>>()
+}
+
+/// Compute the **Merkle Root** of a **Binary Merkle Tree** whose **leaves** are `leaves`.
+///
+/// Every leaf is `blake2_256(0x00 ++ leaf)` and every inner node is `blake2_256(0x01 ++ left ++ right)`.
+/// If a row has an odd number of nodes, its last node is hashed alone (i.e `blake2_256(0x01 ++ node)`) into the next row.
+///
+/// The prefixes ensure that a leaf can never be mistaken for an inner node (and vice versa),
+/// and that the root is never the plain `blake2_256` hash of the concatenated leaves.
+///
+/// Returns a 0-filled hash if `leaves` is empty.
+pub fn merkle_root(leaves: &[Hash256]) -> Hash256 {
+	if leaves.is_empty() {
+		return [0u8; 32]
+	}
+
+	let mut row = leaves.iter().map(hash_leaf).collect::<Vec<Hash256>>();
+	while row.len() > 1 {
+		row = next_row(&row);
+	}
+
+	row[0]
+}
+
+/// Compute the **Merkle Proof** of the leaf at index `leaf_index` of the **Binary Merkle Tree** whose **leaves** are `leaves`
+/// (see `merkle_root()`).
+///
+/// Returns `None` if `leaf_index` is out of bounds.
+pub fn merkle_proof(leaves: &[Hash256], leaf_index: u32) -> Option<MerkleProof> {
+	if leaf_index as usize >= leaves.len() {
+		return None
+	}
+
+	let mut proof = Vec::new();
+	let mut index = leaf_index as usize;
+	let mut row = leaves.iter().map(hash_leaf).collect::<Vec<Hash256>>();
+	while row.len() > 1 {
+		if let Some(sibling) = row.get(index ^ 1) {
+			proof.push(*sibling);
+		}
+		row = next_row(&row);
+		index /= 2;
+	}
+
+	Some(MerkleProof { leaf_index, leaf_count: leaves.len() as u32, proof })
+}
+
+/// Whether `proof` proves that `leaf` is a leaf of the **Binary Merkle Tree** whose **Merkle Root** is `root` (see `merkle_root()`).
+pub fn verify_proof(root: &Hash256, leaf: &Hash256, proof: &MerkleProof) -> bool {
+	if proof.leaf_index >= proof.leaf_count {
+		return false
+	}
+
+	let mut siblings = proof.proof.iter();
+	let mut index = proof.leaf_index;
+	let mut width = proof.leaf_count;
+	let mut node = hash_leaf(leaf);
+	while width > 1 {
+		node = if index % 2 == 1 {
+			let Some(sibling) = siblings.next() else { return false };
+			hash_node(sibling, Some(&node))
+		} else if index + 1 < width {
+			let Some(sibling) = siblings.next() else { return false };
+			hash_node(&node, Some(sibling))
+		} else {
+			hash_node(&node, None)
+		};
+		index /= 2;
+		width = width / 2 + width % 2;
+	}
+
+	siblings.next().is_none() && node == *root
+}
@@ -73,7 +73,14 @@ pub enum ProtoData {
 	Arweave(Hash256),
 	/// Data is maybe somewhere on the IPFS network, this is unsafe cos the IPFS network is all about caching and content delivery, offering no guarantee of permanent storage
 	/// With that said there are some ways to guarantee the data is stored on IPFS via stuff like FileCoin + Lighthouse but they are relatively not as popular as Arweave so it's not recommended.
+	///
+	/// **Deprecated** (use `IpfsCid` instead): its data is not validated, and it will no longer be accepted by `upload()` and `patch()` in the next release.
+	/// The Proto-Fragments that were stored with it can still be decoded (and their data can be replaced by an `IpfsCid` with `patch()`).
 	Ipfs([u8; 64]),
+	/// Data is stored on the IPFS network (see `Ipfs`).
+	///
+	/// The data is the binary CID (CIDv0 or CIDv1) of the data, it is validated when uploaded (see `crate::cid::decode_cid()`).
+	IpfsCid(Vec<u8>),
 }
 
 /// **Struct** of a **Proto-Fragment**
@@ -109,3 +116,30 @@ pub struct Proto<TAccountId, TBlockNumber> {
 	/// Reserved for future use
 	pub _reserved3: Option<()>,
 }
+
+/// **Struct** of a **Multi-Part Upload Session** of a **Proto-Fragment's data**
+///
+/// The data is split into chunks of a fixed size (the last chunk can be smaller) that are uploaded in separate extrinsics.
+#[derive(Encode, Decode, Clone, scale_info::TypeInfo, Debug, PartialEq, Eq)]
+pub struct UploadSession<TAccountId, TBlockNumber, TBalance> {
+	/// **Account** that **began the upload session** (and that will own the Proto-Fragment)
+	pub owner: TAccountId,
+	/// **Category** of the **Proto-Fragment** that will be created
+	pub category: Categories,
+	/// **Total size** (in bytes) of the **Proto-Fragment's data**
+	pub total_size: u64,
+	/// **Merkle Root** of the **Binary Merkle Tree** whose leaves are the **blake2_256 hashes of the chunks** (in order)
+	pub merkle_root: Hash256,
+	/// **Size** (in bytes) of **every chunk** (except the last one, which can be smaller)
+	pub chunk_size: u32,
+	/// **Number of chunks** that the **data** is **split into**
+	pub chunk_count: u32,
+	/// **Number of chunks** that have **already been uploaded**
+	pub uploaded_chunks: u32,
+	/// **Block Number** in which the **upload session began** (the session expires a fixed number of blocks after it)
+	pub created_at: TBlockNumber,
+	/// **Amount** that is **reserved** from the **owner** until the **upload session** is **finalized or cancelled**
+	pub deposit: TBalance,
+	/// The **first bytes** of the **Proto-Fragment's data** (filled in as the chunks are uploaded), which are validated when the upload is finalized
+	pub prefix: Vec<u8>,
+}
@@ -38,6 +38,16 @@ pub trait Fragnova {
 	fn get_geth_url() -> Option<Vec<u8>> {
 		details::_get_geth_url()
 	}
+
+	/// Get the URL of the Arweave Gateway that is used to fetch the data of Proto-Fragments stored on Arweave
+	fn get_arweave_gateway_url() -> Option<Vec<u8>> {
+		details::_get_arweave_gateway_url()
+	}
+
+	/// Get the URL of the IPFS Gateway that is used to fetch the data of Proto-Fragments stored on IPFS
+	fn get_ipfs_gateway_url() -> Option<Vec<u8>> {
+		details::_get_ipfs_gateway_url()
+	}
 }
 
 #[cfg(feature = "std")]
@@ -47,6 +57,8 @@ mod details {
 
 	lazy_static! {
 		pub static ref GETH_URL: Mutex<Option<Vec<u8>>> = Mutex::new(None);
+		pub static ref ARWEAVE_GATEWAY_URL: Mutex<Option<Vec<u8>>> = Mutex::new(None);
+		pub static ref IPFS_GATEWAY_URL: Mutex<Option<Vec<u8>>> = Mutex::new(None);
 	}
 
 	// lazy_static! {
@@ -89,6 +101,16 @@ mod details {
 			None
 		}
 	}
+
+	/// Get the URL of the Arweave Gateway
+	pub fn _get_arweave_gateway_url() -> Option<Vec<u8>> {
+		ARWEAVE_GATEWAY_URL.lock().unwrap().clone()
+	}
+
+	/// Get the URL of the IPFS Gateway
+	pub fn _get_ipfs_gateway_url() -> Option<Vec<u8>> {
+		IPFS_GATEWAY_URL.lock().unwrap().clone()
+	}
 }
 
 #[cfg(not(feature = "std"))]
@@ -107,6 +129,18 @@ mod details {
 	pub fn _get_geth_url() -> Option<Vec<u8>> {
 		None
 	}
+
+	/// Unreachable placeholder: the Wasm runtime calls `get_arweave_gateway_url()` on the native node
+	/// (see `#[runtime_interface]`), which returns the URL set by `init_data_gateways()`
+	pub fn _get_arweave_gateway_url() -> Option<Vec<u8>> {
+		None
+	}
+
+	/// Unreachable placeholder: the Wasm runtime calls `get_ipfs_gateway_url()` on the native node
+	/// (see `#[runtime_interface]`), which returns the URL set by `init_data_gateways()`
+	pub fn _get_ipfs_gateway_url() -> Option<Vec<u8>> {
+		None
+	}
 }
 
 /// Set the Fragnova-owned Geth Node's URL
@@ -130,3 +164,11 @@ pub fn init(geth_url: Option<String>) {
 
 	// shlog!("Chainblocks initialized!");
 }
+
+/// Set the URLs of the Arweave Gateway and of the IPFS Gateway that are used to fetch the data of Proto-Fragments
+/// that is not stored on the Fragnova Blockchain
+#[cfg(feature = "std")]
+pub fn init_data_gateways(arweave_gateway_url: Option<String>, ipfs_gateway_url: Option<String>) {
+	*details::ARWEAVE_GATEWAY_URL.lock().unwrap() = arweave_gateway_url.map(String::into_bytes);
+	*details::IPFS_GATEWAY_URL.lock().unwrap() = ipfs_gateway_url.map(String::into_bytes);
+}
@@ -15,6 +15,30 @@ const connectToLocalNode = async () => {
             { name: "at", type: "BlockHash", isOptional: true }
           ]
         },
+        getChunkedData: {
+          description: "Query and Return the data of a Proto-Fragment that was uploaded in multiple parts, by reassembling its chunks. The **return type** is base64 encoded bytes.",
+          type: "String",
+          params: [
+            { name: "proto_hash", type: "BlockHash" },
+            { name: "at", type: "BlockHash", isOptional: true }
+          ]
+        },
+        getCid: {
+          description: "Query and Return the CID of the current data of a Proto-Fragment. The **return type** is the string representation of the CID.",
+          type: "String",
+          params: [
+            { name: "proto_hash", type: "BlockHash" },
+            { name: "at", type: "BlockHash", isOptional: true }
+          ]
+        },
+        getDataByCid: {
+          description: "Query and Return the data indexed on Fragnova based on its CID. The **return type** is base64 encoded bytes.",
+          type: "String",
+          params: [
+            { name: "cid", type: "String" },
+            { name: "at", type: "BlockHash", isOptional: true }
+          ]
+        },
         getProtos: {
           description: "Query and Return Proto-Fragment(s) based on `params`. The return type is a JSON string",
           type: "String",
@@ -24,7 +48,7 @@ const connectToLocalNode = async () => {
           ]
         },
         getGenealogy: {
-          description: "Query the Genealogy of a Proto-Fragment based on `params`. The return type is a JSON string that represents an Adjacency List.",
+          description: "Query the Genealogy of a Proto-Fragment based on `params`. The return type is a string in the format `params.format` (a JSON Adjacency List, a DOT digraph or a GraphML document).",
           type: "String",
           params: [
             { name: "param", type: "GetGenealogyParams" },
@@ -57,6 +81,30 @@ const connectToLocalNode = async () => {
             { name: "at", type: "BlockHash", isOptional: true }
           ]
         },
+        getVaultBalance: {
+          description: "Query the balance of the vault of a Fragment Definition (i.e its sale proceeds). The return type is a JSON string",
+          type: "String",
+          params: [
+            { name: "definition_hash", type: "String" },
+            { name: "at", type: "BlockHash", isOptional: true }
+          ]
+        },
+        getSalePhase: {
+          description: "Query the current Sale Phase of a Fragment Definition that is on sale. The return type is a JSON string",
+          type: "String",
+          params: [
+            { name: "param", type: "GetSalePhaseParams" },
+            { name: "at", type: "BlockHash", isOptional: true }
+          ]
+        },
+        getRecipes: {
+          description: "Query the crafting recipes. The return type is a JSON string",
+          type: "String",
+          params: [
+            { name: "param", type: "GetRecipesParams" },
+            { name: "at", type: "BlockHash", isOptional: true }
+          ]
+        },
       },
     },
 
@@ -155,6 +203,15 @@ const connectToLocalNode = async () => {
         _reserved3: "Option<()>",
       },
 
+      ProtoData: {
+        _enum: {
+          "Local": "Vec<u8>",
+          "Arweave": "[u8; 32]",
+          "Ipfs": "[u8; 64]",
+          "IpfsCid": "Vec<u8>",
+        }
+      },
+
       GetProtosParams: {
         desc: 'bool',
         from: 'u32',
@@ -166,10 +223,30 @@ const connectToLocalNode = async () => {
         tags: 'Vec<String>',
         exclude_tags: 'Vec<String>',
         available: 'Option<bool>',
+        data_availability: 'Option<DataAvailability>',
+      },
+      DataAvailability: {
+        _enum: [
+          "Available",
+          "Missing",
+          "Invalid",
+        ]
       },
       GetGenealogyParams: {
         proto_hash: "String",
         get_ancestors: "bool",
+        max_depth: "Option<u32>",
+        max_nodes: "Option<u32>",
+        include_patches: "Option<bool>",
+        annotate_edges: "bool",
+        format: "GenealogyFormat",
+      },
+      GenealogyFormat: {
+        _enum: [
+          "Json",
+          "Dot",
+          "GraphMl",
+        ]
       },
 
       GetDefinitionsParams: {
@@ -179,6 +256,13 @@ const connectToLocalNode = async () => {
         metadata_keys: "Vec<String>",
         owner: "Option<AccountId>",
         return_owners: "bool",
+        categories: "Vec<Categories>",
+        tags: "Vec<String>",
+        currency: "Option<String>",
+        published: "Option<bool>",
+        min_price: "Option<u128>",
+        max_price: "Option<u128>",
+        sold_out: "Option<bool>",
       },
       GetInstancesParams: {
         desc: "bool",
@@ -188,12 +272,24 @@ const connectToLocalNode = async () => {
         metadata_keys: "Vec<String>",
         owner: "Option<AccountId>",
         only_return_first_copies: "bool",
+        return_nested: "bool",
       },
       GetInstanceOwnerParams: {
         definition_hash: 'String', // "[u8; 16]", // using `String` because Polkadot-JS has a problem fixed-sized arrays: https://github.com/encointer/pallets/pull/86
         edition_id: "InstanceUnit",
         copy_id: "InstanceUnit",
       },
+      GetSalePhaseParams: {
+        definition_hash: 'String', // "[u8; 16]", // using `String` because Polkadot-JS has a problem fixed-sized arrays: https://github.com/encointer/pallets/pull/86
+        account: "Option<AccountId>",
+        allowance: "Option<InstanceUnit>",
+      },
+      GetRecipesParams: {
+        from: "u64",
+        limit: "u64",
+        owner: "Option<AccountId>",
+        only_enabled: "bool",
+      },
       "InstanceUnit": "u64",
 
     }
@@ -193,6 +193,19 @@ describe("RPCs", () => {
     });
   })
 
+  describe("protos.getCid()", () => {
+    it("should return the CIDv1 of the proto data", async () => {
+      const result = await api.rpc.protos.getCid("0x" + protoHash);
+      assert(result.toString().startsWith("z"));
+    });
+
+    it("should return the proto data when querying by CID", async () => {
+      const cid = await api.rpc.protos.getCid("0x" + protoHash);
+      const result = await api.rpc.protos.getDataByCid(cid);
+      assert.equal(Buffer.from(result.toString(), "base64").toString(), "Proto-Indo-European");
+    });
+  })
+
   describe("fragments.getDefinitions()", () => {
     it("should return FD", async () => {
       const params = api.createType("GetDefinitionsParams", {desc: true, from: 0, limit: 10});
@@ -235,6 +248,22 @@ describe("RPCs", () => {
     });
   });
 
+  describe("fragments.getVaultBalance()", () => {
+    it("should return the vault balance of the FD", async () => {
+      const result = JSON.parse(await api.rpc.fragments.getVaultBalance(definitionHash));
+      assert.equal(result.currency, "native");
+      assert.ok(BigInt(result.balance) >= BigInt(result.withdrawable));
+    });
+  });
+
+  describe("fragments.getRecipes()", () => {
+    it("should return a JSON object of the crafting recipes", async () => {
+      const params = api.createType("GetRecipesParams", {from: 0, limit: 10, only_enabled: true});
+      const result = JSON.parse(await api.rpc.fragments.getRecipes(params));
+      assert.equal(typeof result, "object");
+    });
+  });
+
   describe("protos.getData()", () => {
 
     it("should work", async function () {
@@ -5,7 +5,7 @@ use fragnova_extensions::FragnovaEnvironment;
 #[ink::contract(env = crate::FragnovaEnvironment)]
 mod dummy_contract {
     use scale::Compact;
-    use ink::prelude::vec::Vec;
+    use ink::prelude::{collections::BTreeMap, vec::Vec};
     use fragnova_extensions::{AssetId, MyChainExtensionError};
     use sp_fragnova::{
         Hash128,
@@ -14,6 +14,8 @@ mod dummy_contract {
             Proto
         },
         fragments::{
+            AttributeSchema,
+            AttributeValue,
             FragmentDefinition,
             FragmentInstance,
             InstanceUnit
@@ -64,6 +66,22 @@ mod dummy_contract {
         pub fn give_instance(&mut self, definition_hash: Hash128, edition_id: InstanceUnit, copy_id: InstanceUnit, to: AccountId, new_permissions: Option<FragmentPerms>, expirations: Option<BlockNumber>) -> Result<(), MyChainExtensionError> {
             self.env().extension().give_instance(definition_hash, edition_id, copy_id, to, new_permissions, expirations)
         }
+        #[ink(message)]
+        pub fn consume_instance(&mut self, definition_hash: Hash128, edition_id: InstanceUnit, copy_id: InstanceUnit, amount: InstanceUnit) -> Result<(), MyChainExtensionError> {
+            self.env().extension().consume_instance(definition_hash, edition_id, copy_id, amount)
+        }
+        #[ink(message)]
+        pub fn get_instance_user(&self, definition_hash: Hash128, edition_id: InstanceUnit, copy_id: InstanceUnit) -> Option<AccountId> {
+            self.env().extension().get_instance_user(definition_hash, edition_id, copy_id)
+        }
+        #[ink(message)]
+        pub fn get_attribute_schema(&self, definition_hash: Hash128) -> Option<AttributeSchema> {
+            self.env().extension().get_attribute_schema(definition_hash)
+        }
+        #[ink(message)]
+        pub fn get_instance_attributes(&self, definition_hash: Hash128, edition_id: InstanceUnit, copy_id: InstanceUnit) -> BTreeMap<Vec<u8>, AttributeValue> {
+            self.env().extension().get_instance_attributes(definition_hash, edition_id, copy_id)
+        }
 
     }
 }
@@ -283,6 +283,11 @@ impl pallet_protos::Config for Test {
 	type StringLimit = StringLimit;
 	type DetachAccountLimit = ConstU32<20>;
 	type MaxTags = ConstU32<10>;
+	type UploadChunkSize = ConstU32<4>;
+	type MaxUploadChunks = ConstU32<16>;
+	type UploadChunkDeposit = ConstU128<1>;
+	type UploadSessionTimeout = ConstU64<100>;
+	type DataValidator = ();
 }
 
 impl pallet_fragments::Config for Test {
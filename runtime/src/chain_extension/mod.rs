@@ -10,12 +10,14 @@ use pallet_contracts::chain_extension::{
 };
 use pallet_fragments::WeightInfo;
 use sp_fragnova::{
-	fragments::{FragmentDefinition, FragmentInstance, InstanceUnit},
+	fragments::{
+		AttributeSchema, AttributeValue, FragmentDefinition, FragmentInstance, InstanceUnit,
+	},
 	protos::{Proto, ProtoOwner},
 	Hash128, Hash256,
 };
 use sp_runtime::{traits::StaticLookup, DispatchError};
-use sp_std::vec::Vec; // this is a trait
+use sp_std::{collections::btree_map::BTreeMap, vec::Vec}; // this is a trait
 
 use protos::permissions::FragmentPerms;
 
@@ -35,15 +37,24 @@ enum FuncId {
 	/// Get the list of Proto-Fragments that are owned by `owner`
 	GetProtoIds,
 
-	/// Get the `FragmentDefinition` struct of the Fragment Definition which has the ID of `definition_hash`
+	/// Get the `FragmentDefinition` struct of the Fragment Definition which has the ID of `definition_hash` (including its current `owner`)
 	GetDefinition,
 	/// Get the `FragmentInstance` struct of the Fragment Instance whose Fragment Definition ID is `definition_hash`,
 	/// whose Edition ID is `edition_id` and whose Copy ID is `copy_id`
 	GetInstance,
 	/// Get the list of Fragment Instances of the Fragment Definition `definition_hash` that are owned by `owner`
 	GetInstanceIds,
-	/// Give a Fragment Instance (that is owned by the smart contract) to `to`.
+	/// Give a Fragment Instance (that is owned by the smart contract, or that the smart contract is approved to transfer) to `to`.
 	GiveInstance,
+	/// Consume `amount` items of the stack of a Fragment Instance (that is owned by the smart contract).
+	ConsumeInstance,
+	/// Get the Account ID that has the use rights of a Fragment Instance (i.e its renter if it is rented out, otherwise its owner)
+	GetInstanceUser,
+	/// Get the attribute schema of the Fragment Definition `definition_hash`
+	GetAttributeSchema,
+	/// Get the attribute values of the Fragment Instance whose Fragment Definition ID is `definition_hash`,
+	/// whose Edition ID is `edition_id` and whose Copy ID is `copy_id`
+	GetInstanceAttributes,
 }
 
 impl TryFrom<u16> for FuncId {
@@ -58,6 +69,10 @@ impl TryFrom<u16> for FuncId {
 			0x0c01 => Self::GetInstance,
 			0x0c02 => Self::GetInstanceIds,
 			0x0c03 => Self::GiveInstance,
+			0x0c04 => Self::ConsumeInstance,
+			0x0c05 => Self::GetInstanceUser,
+			0x0c06 => Self::GetAttributeSchema,
+			0x0c07 => Self::GetInstanceAttributes,
 			_ => {
 				log::error!("Called an unregistered `func_id`: {:}", func_id);
 				return Err(DispatchError::Other("Unimplemented func_id"))
@@ -151,9 +166,17 @@ where
 			FuncId::GetInstance => {
 				let (definition_hash, edition_id, copy_id): (Hash128, InstanceUnit, InstanceUnit) =
 					env.read_as()?;
-				env.charge_weight(<T as SysConfig>::DbWeight::get().reads(1))?;
+				env.charge_weight(<T as SysConfig>::DbWeight::get().reads(2))?;
+				let current_block_number = frame_system::Pallet::<T>::block_number();
+				// an expired Fragment Instance that has not been swept yet is treated as non-existent
 				let output: Option<FragmentInstance<T::BlockNumber>> =
-					pallet_fragments::Fragments::<T>::get((definition_hash, edition_id, copy_id));
+					pallet_fragments::Fragments::<T>::get((definition_hash, edition_id, copy_id))
+						.filter(|instance| {
+							!pallet_fragments::Pallet::<T>::is_expired(
+								instance,
+								current_block_number,
+							)
+						});
 				// TODO Review - Should `weights_per_byte` be `None`? In the examples (https://github.com/paritytech/ink/blob/master/examples/rand-extension/runtime/chain-extension-example.rs and https://github.com/paritytech/ink/blob/master/examples/psp22-extension/runtime/psp22-extension-example.rs) and in https://github.com/AstarNetwork/astar-frame/search?q=env.write,
 				// I only see `None` - but in our case we are outputting a a struct that has a `Vec` field!
 				env.write(&output.encode(), false, None).map_err(|_| {
@@ -162,10 +185,29 @@ where
 			},
 			FuncId::GetInstanceIds => {
 				let (definition_hash, owner): (Hash128, T::AccountId) = env.read_as()?;
-				env.charge_weight(<T as SysConfig>::DbWeight::get().reads(1))?;
-				let output: Vec<(Compact<InstanceUnit>, Compact<InstanceUnit>)> =
+				env.charge_weight(<T as SysConfig>::DbWeight::get().reads(2))?;
+				let ids: Vec<(Compact<InstanceUnit>, Compact<InstanceUnit>)> =
 					pallet_fragments::Inventory::<T>::get(owner, definition_hash)
 						.unwrap_or_default();
+				// every Fragment Instance is read to filter out the expired Fragment Instances that have not been swept yet
+				env.charge_weight(<T as SysConfig>::DbWeight::get().reads(ids.len() as u64))?;
+				let current_block_number = frame_system::Pallet::<T>::block_number();
+				let output: Vec<(Compact<InstanceUnit>, Compact<InstanceUnit>)> = ids
+					.into_iter()
+					.filter(|(edition_id, copy_id)| {
+						pallet_fragments::Fragments::<T>::get((
+							definition_hash,
+							edition_id.0,
+							copy_id.0,
+						))
+						.map_or(false, |instance| {
+							!pallet_fragments::Pallet::<T>::is_expired(
+								&instance,
+								current_block_number,
+							)
+						})
+					})
+					.collect();
 				// TODO Review - Should `weights_per_byte` be `None`? In the examples (https://github.com/paritytech/ink/blob/master/examples/rand-extension/runtime/chain-extension-example.rs and https://github.com/paritytech/ink/blob/master/examples/psp22-extension/runtime/psp22-extension-example.rs) and in https://github.com/AstarNetwork/astar-frame/search?q=env.write,
 				// I only see `None` - but in our case we are outputting a `Vec`!
 				env.write(&output.encode(), false, None).map_err(|_| {
@@ -235,6 +277,65 @@ where
 					expiration,
 				)?;
 			},
+			FuncId::ConsumeInstance => {
+				let (definition_hash, edition_id, copy_id, amount): (
+					Hash128,
+					InstanceUnit,
+					InstanceUnit,
+					InstanceUnit,
+				) = env.read_as()?;
+
+				// This is the exact same expression that is in the weight macro of `fragments.consume()`
+				env.charge_weight(<T as pallet_fragments::Config>::WeightInfo::consume())?;
+
+				pallet_fragments::Pallet::<T>::consume(
+					RawOrigin::Signed(env.ext().address().clone()).into(),
+					definition_hash,
+					edition_id,
+					copy_id,
+					amount,
+				)?;
+			},
+			FuncId::GetInstanceUser => {
+				let (definition_hash, edition_id, copy_id): (Hash128, InstanceUnit, InstanceUnit) =
+					env.read_as()?;
+				// `Fragments`, `Rentals` and `InstanceOwner` are read
+				env.charge_weight(<T as SysConfig>::DbWeight::get().reads(3))?;
+				let output: Option<T::AccountId> = pallet_fragments::Pallet::<T>::get_instance_user(
+					&definition_hash,
+					edition_id,
+					copy_id,
+				);
+				env.write(&output.encode(), false, None).map_err(|_| {
+					DispatchError::Other("ChainExtension failed to get the fragment instance user")
+				})?;
+			},
+			FuncId::GetAttributeSchema => {
+				let definition_hash: Hash128 = env.read_as()?;
+				env.charge_weight(<T as SysConfig>::DbWeight::get().reads(1))?;
+				let output: Option<AttributeSchema> =
+					pallet_fragments::AttributeSchemas::<T>::get(&definition_hash);
+				env.write(&output.encode(), false, None).map_err(|_| {
+					DispatchError::Other("ChainExtension failed to get the attribute schema")
+				})?;
+			},
+			FuncId::GetInstanceAttributes => {
+				let (definition_hash, edition_id, copy_id): (Hash128, InstanceUnit, InstanceUnit) =
+					env.read_as()?;
+				env.charge_weight(<T as SysConfig>::DbWeight::get().reads(1))?;
+				let output: BTreeMap<Vec<u8>, AttributeValue> =
+					pallet_fragments::InstanceAttributes::<T>::get((
+						definition_hash,
+						edition_id,
+						copy_id,
+					))
+					.unwrap_or_default();
+				env.write(&output.encode(), false, None).map_err(|_| {
+					DispatchError::Other(
+						"ChainExtension failed to get the fragment instance attributes",
+					)
+				})?;
+			},
 		};
 
 		Ok(RetVal::Converging(0))
@@ -14,8 +14,10 @@ use frame_support::traits::Currency;
 use protos::{
 	categories::{Categories, TextCategories},
 	permissions::FragmentPerms,
+	traits::{Trait, VariableType, VariableTypeInfo},
 };
 use sp_fragnova::Hash256;
+use sp_std::collections::btree_map::BTreeMap;
 
 use pallet_contracts::Determinism;
 
@@ -78,6 +80,10 @@ fn are_all_chain_extension_methods_tested() {
 		FuncId::GetInstance => fragments_tests::get_instance_should_work,
 		FuncId::GetInstanceIds => fragments_tests::get_instance_ids_should_work,
 		FuncId::GiveInstance => fragments_tests::give_instance_should_work,
+		FuncId::ConsumeInstance => fragments_tests::consume_instance_should_work,
+		FuncId::GetInstanceUser => fragments_tests::get_instance_user_should_work,
+		FuncId::GetAttributeSchema => fragments_tests::get_attribute_schema_should_work,
+		FuncId::GetInstanceAttributes => fragments_tests::get_instance_attributes_should_work,
 	};
 }
 
@@ -235,6 +241,57 @@ mod fragments_tests {
 		});
 	}
 
+	#[test]
+	pub fn get_definition_should_return_the_current_owner_of_the_definition() {
+		new_test_ext().execute_with(|| {
+			let code_hash = upload_dummy_contract(ALICE);
+			let contract_address =
+				Contracts::contract_address(&ALICE, &code_hash, &blake2_256(b"new")[0..4], &[]);
+
+			let proto_data = b"Je suis Data".to_vec();
+			assert_ok!(upload(ALICE, &proto_data));
+			let proto_hash = blake2_256(&proto_data);
+
+			let definition_name = b"Je suis un Nom".to_vec();
+			assert_ok!(create(ALICE, &proto_hash, &definition_name));
+			let definition_hash = blake2_128(
+				&[
+					&proto_hash[..],
+					&definition_name.encode(),
+					&pallet_fragments::Currency::<<Test as pallet_assets::Config>::AssetId>::Native
+						.encode(),
+				]
+				.concat(),
+			);
+			assert_ok!(Fragments::transfer_definition(
+				RuntimeOrigin::signed(ALICE),
+				definition_hash,
+				BOB
+			));
+
+			let contract_result = Contracts::bare_call(
+				ALICE,
+				contract_address,
+				0,
+				GAS_LIMIT,
+				None,
+				vec![&blake2_256(b"get_definition")[0..4], &definition_hash.encode()[..]].concat(),
+				false,
+				Determinism::Deterministic,
+			);
+
+			let expected_definition =
+				pallet_fragments::Definitions::<Test>::get(&definition_hash).unwrap();
+			assert_eq!(expected_definition.owner, BOB);
+			assert_eq!(expected_definition.creator, ALICE);
+			assert_eq!(contract_result.result.as_ref().unwrap().flags.bits(), 0);
+			assert_eq!(
+				contract_result.result.unwrap().data,
+				Ok::<_, ()>(Some(expected_definition)).encode()
+			);
+		});
+	}
+
 	#[test]
 	pub fn get_instance_should_work() {
 		new_test_ext().execute_with(|| {
@@ -479,4 +536,357 @@ mod fragments_tests {
 			assert_eq!(pallet_fragments::Inventory::<Test>::get(BOB, definition_hash), None);
 		});
 	}
+
+	#[test]
+	pub fn consume_instance_should_work() {
+		new_test_ext().execute_with(|| {
+			let code_hash = upload_dummy_contract(ALICE);
+			let contract_address =
+				Contracts::contract_address(&ALICE, &code_hash, &blake2_256(b"new")[0..4], &[]);
+
+			let proto_data = b"Je suis Data".to_vec();
+			assert_ok!(upload(ALICE, &proto_data));
+			let proto_hash = blake2_256(&proto_data);
+
+			let definition_name = b"Je suis un Nom".to_vec();
+			assert_ok!(create(ALICE, &proto_hash, &definition_name));
+			let definition_hash = blake2_128(
+				&[
+					&proto_hash[..],
+					&definition_name.encode(),
+					&pallet_fragments::Currency::<<Test as pallet_assets::Config>::AssetId>::Native
+						.encode(),
+				]
+				.concat(),
+			);
+
+			assert_ok!(Fragments::mint(
+				RuntimeOrigin::signed(ALICE),
+				definition_hash,
+				pallet_fragments::FragmentBuyOptions::Quantity(1),
+				Some(10), // stack amount
+			));
+			assert_ok!(Fragments::give(
+				RuntimeOrigin::signed(ALICE),
+				definition_hash,  // definition_hash
+				1,                // edition_id
+				1,                // copy_id
+				contract_address, // to
+				None,             // new_permissions
+				None,             // expiration
+			));
+
+			let contract_result = Contracts::bare_call(
+				ALICE,
+				contract_address, // Address of the contract to call.
+				0,                // The balance to transfer from the origin to dest.
+				GAS_LIMIT,        // The gas limit enforced when executing the constructor.
+				None, // The maximum amount of balance that can be charged from the caller to pay for the storage consumed.
+				vec![
+					&blake2_256(b"consume_instance")[0..4],
+					&(definition_hash, 1u64, 1u64, 3u64).encode()[..],
+				]
+				.concat(), // The input data to pass to the contract.
+				false, // `debug` should only ever be set to true when executing as an RPC because it adds allocations and could be abused to drive the runtime into an OOM panic.
+				Determinism::Deterministic, // The execution should be deterministic and hence no indeterministic instructions are allowed.
+			);
+
+			assert_eq!(contract_result.result.as_ref().unwrap().flags.bits(), 0);
+
+			assert_eq!(
+				pallet_fragments::Fragments::<Test>::get((definition_hash, 1, 1))
+					.unwrap()
+					.stack_amount,
+				Some(Compact(7))
+			);
+		});
+	}
+
+	#[test]
+	pub fn give_instance_should_work_if_the_smart_contract_is_approved() {
+		new_test_ext().execute_with(|| {
+			let code_hash = upload_dummy_contract(ALICE);
+			let contract_address =
+				Contracts::contract_address(&ALICE, &code_hash, &blake2_256(b"new")[0..4], &[]);
+
+			let proto_data = b"Je suis Data".to_vec();
+			assert_ok!(upload(ALICE, &proto_data));
+			let proto_hash = blake2_256(&proto_data);
+
+			let definition_name = b"Je suis un Nom".to_vec();
+			assert_ok!(create(ALICE, &proto_hash, &definition_name));
+			let definition_hash = blake2_128(
+				&[
+					&proto_hash[..],
+					&definition_name.encode(),
+					&pallet_fragments::Currency::<<Test as pallet_assets::Config>::AssetId>::Native
+						.encode(),
+				]
+				.concat(),
+			);
+
+			assert_ok!(Fragments::mint(
+				RuntimeOrigin::signed(ALICE),
+				definition_hash,
+				pallet_fragments::FragmentBuyOptions::Quantity(1),
+				None,
+			));
+			assert_ok!(Fragments::approve(
+				RuntimeOrigin::signed(ALICE),
+				definition_hash,        // definition_hash
+				1,                      // edition_id
+				1,                      // copy_id
+				Some(contract_address), // operator
+			));
+
+			let contract_result = Contracts::bare_call(
+				ALICE,
+				contract_address, // Address of the contract to call.
+				0,                // The balance to transfer from the origin to dest.
+				GAS_LIMIT,        // The gas limit enforced when executing the constructor.
+				None, // The maximum amount of balance that can be charged from the caller to pay for the storage consumed.
+				vec![
+					&blake2_256(b"give_instance")[0..4],
+					&(
+						definition_hash,
+						1u64,
+						1u64,
+						BOB,
+						None::<Option<FragmentPerms>>,
+						None::<Option<<Test as frame_system::Config>::BlockNumber>>,
+					)
+						.encode()[..],
+				]
+				.concat(), // The input data to pass to the contract.
+				false, // `debug` should only ever be set to true when executing as an RPC because it adds allocations and could be abused to drive the runtime into an OOM panic.
+				Determinism::Deterministic, // The execution should be deterministic and hence no indeterministic instructions are allowed.
+			);
+
+			assert_eq!(contract_result.result.as_ref().unwrap().flags.bits(), 0);
+			// assert_eq!(
+			// 	contract_result.result.unwrap().data,
+			// 	().encode()
+			// );
+
+			assert_eq!(
+				pallet_fragments::Inventory::<Test>::get(BOB, definition_hash)
+					.unwrap()
+					.contains(&(Compact(1), Compact(1))),
+				true
+			);
+		});
+	}
+
+	#[test]
+	pub fn get_instance_user_should_work() {
+		new_test_ext().execute_with(|| {
+			let code_hash = upload_dummy_contract(ALICE);
+			let contract_address =
+				Contracts::contract_address(&ALICE, &code_hash, &blake2_256(b"new")[0..4], &[]);
+
+			let proto_data = b"Je suis Data".to_vec();
+			assert_ok!(upload(ALICE, &proto_data));
+			let proto_hash = blake2_256(&proto_data);
+
+			let definition_name = b"Je suis un Nom".to_vec();
+			assert_ok!(create(ALICE, &proto_hash, &definition_name));
+			let definition_hash = blake2_128(
+				&[
+					&proto_hash[..],
+					&definition_name.encode(),
+					&pallet_fragments::Currency::<<Test as pallet_assets::Config>::AssetId>::Native
+						.encode(),
+				]
+				.concat(),
+			);
+
+			assert_ok!(Fragments::mint(
+				RuntimeOrigin::signed(ALICE),
+				definition_hash,
+				pallet_fragments::FragmentBuyOptions::Quantity(1),
+				None,
+			));
+			assert_ok!(Fragments::rent(
+				RuntimeOrigin::signed(ALICE),
+				definition_hash,             // definition_hash
+				1,                           // edition_id
+				1,                           // copy_id
+				BOB,                         // to
+				System::block_number() + 10, // until
+			));
+
+			let contract_result = Contracts::bare_call(
+				ALICE,
+				contract_address, // Address of the contract to call.
+				0,                // The balance to transfer from the origin to dest.
+				GAS_LIMIT,        // The gas limit enforced when executing the constructor.
+				None, // The maximum amount of balance that can be charged from the caller to pay for the storage consumed.
+				vec![
+					&blake2_256(b"get_instance_user")[0..4],
+					&(definition_hash, 1u64, 1u64).encode()[..],
+				]
+				.concat(), // The input data to pass to the contract.
+				false, // `debug` should only ever be set to true when executing as an RPC because it adds allocations and could be abused to drive the runtime into an OOM panic.
+				Determinism::Deterministic, // The execution should be deterministic and hence no indeterministic instructions are allowed.
+			);
+
+			assert_eq!(contract_result.result.as_ref().unwrap().flags.bits(), 0);
+			assert_eq!(contract_result.result.unwrap().data, Ok::<_, ()>(Some(BOB)).encode());
+		});
+	}
+
+	/// Upload a Trait Proto-Fragment that has the records "level" and "title", and return its data
+	fn upload_trait(signer: <Test as frame_system::Config>::AccountId) -> Vec<u8> {
+		let records = vec![
+			(
+				"level".to_string(),
+				vec![VariableTypeInfo { type_: VariableType::Int(None), default: None }],
+			)
+				.into(),
+			(
+				"title".to_string(),
+				vec![VariableTypeInfo { type_: VariableType::Int(None), default: None }],
+			)
+				.into(),
+		];
+		let trait_data = Trait { name: "Je suis un Trait".to_string(), records }.encode();
+		assert_ok!(Protos::upload(
+			RuntimeOrigin::signed(signer),
+			Vec::<Hash256>::new(),
+			Categories::Trait(None),
+			Vec::<BoundedVec<u8, _>>::new().try_into().unwrap(),
+			None,
+			pallet_protos::UsageLicense::Closed,
+			None,
+			pallet_protos::ProtoData::Local(trait_data.clone()),
+		));
+		trait_data
+	}
+
+	#[test]
+	pub fn get_attribute_schema_should_work() {
+		new_test_ext().execute_with(|| {
+			let code_hash = upload_dummy_contract(ALICE);
+			let contract_address =
+				Contracts::contract_address(&ALICE, &code_hash, &blake2_256(b"new")[0..4], &[]);
+
+			let proto_data = b"Je suis Data".to_vec();
+			assert_ok!(upload(ALICE, &proto_data));
+			let proto_hash = blake2_256(&proto_data);
+
+			let definition_name = b"Je suis un Nom".to_vec();
+			assert_ok!(create(ALICE, &proto_hash, &definition_name));
+			let definition_hash = blake2_128(
+				&[
+					&proto_hash[..],
+					&definition_name.encode(),
+					&pallet_fragments::Currency::<<Test as pallet_assets::Config>::AssetId>::Native
+						.encode(),
+				]
+				.concat(),
+			);
+
+			let trait_data = upload_trait(ALICE);
+			assert_ok!(Fragments::set_attribute_schema(
+				RuntimeOrigin::signed(ALICE),
+				definition_hash,
+				trait_data.clone(),
+				vec![(b"level".to_vec(), pallet_fragments::AttributeType::Number)],
+			));
+
+			let contract_result = Contracts::bare_call(
+				ALICE,
+				contract_address, // Address of the contract to call.
+				0,                // The balance to transfer from the origin to dest.
+				GAS_LIMIT,        // The gas limit enforced when executing the constructor.
+				None, // The maximum amount of balance that can be charged from the caller to pay for the storage consumed.
+				vec![&blake2_256(b"get_attribute_schema")[0..4], &definition_hash.encode()[..]]
+					.concat(), // The input data to pass to the contract.
+				false, // `debug` should only ever be set to true when executing as an RPC because it adds allocations and could be abused to drive the runtime into an OOM panic.
+				Determinism::Deterministic, // The execution should be deterministic and hence no indeterministic instructions are allowed.
+			);
+
+			assert_eq!(contract_result.result.as_ref().unwrap().flags.bits(), 0);
+			assert_eq!(
+				contract_result.result.unwrap().data,
+				Ok::<_, ()>(Some(pallet_fragments::AttributeSchema {
+					trait_proto: blake2_256(&trait_data),
+					attributes: vec![(b"level".to_vec(), pallet_fragments::AttributeType::Number)],
+				}))
+				.encode()
+			);
+		});
+	}
+
+	#[test]
+	pub fn get_instance_attributes_should_work() {
+		new_test_ext().execute_with(|| {
+			let code_hash = upload_dummy_contract(ALICE);
+			let contract_address =
+				Contracts::contract_address(&ALICE, &code_hash, &blake2_256(b"new")[0..4], &[]);
+
+			let proto_data = b"Je suis Data".to_vec();
+			assert_ok!(upload(ALICE, &proto_data));
+			let proto_hash = blake2_256(&proto_data);
+
+			let definition_name = b"Je suis un Nom".to_vec();
+			assert_ok!(create(ALICE, &proto_hash, &definition_name));
+			let definition_hash = blake2_128(
+				&[
+					&proto_hash[..],
+					&definition_name.encode(),
+					&pallet_fragments::Currency::<<Test as pallet_assets::Config>::AssetId>::Native
+						.encode(),
+				]
+				.concat(),
+			);
+
+			let trait_data = upload_trait(ALICE);
+			assert_ok!(Fragments::set_attribute_schema(
+				RuntimeOrigin::signed(ALICE),
+				definition_hash,
+				trait_data,
+				vec![(b"level".to_vec(), pallet_fragments::AttributeType::Number)],
+			));
+
+			assert_ok!(Fragments::mint(
+				RuntimeOrigin::signed(ALICE),
+				definition_hash,
+				pallet_fragments::FragmentBuyOptions::Quantity(1),
+				None,
+			));
+			assert_ok!(Fragments::set_instance_attributes(
+				RuntimeOrigin::signed(ALICE),
+				definition_hash,
+				1, // edition_id
+				1, // copy_id
+				vec![(b"level".to_vec(), Some(pallet_fragments::AttributeValue::Number(7)))],
+			));
+
+			let contract_result = Contracts::bare_call(
+				ALICE,
+				contract_address, // Address of the contract to call.
+				0,                // The balance to transfer from the origin to dest.
+				GAS_LIMIT,        // The gas limit enforced when executing the constructor.
+				None, // The maximum amount of balance that can be charged from the caller to pay for the storage consumed.
+				vec![
+					&blake2_256(b"get_instance_attributes")[0..4],
+					&(definition_hash, 1u64, 1u64).encode()[..],
+				]
+				.concat(), // The input data to pass to the contract.
+				false, // `debug` should only ever be set to true when executing as an RPC because it adds allocations and could be abused to drive the runtime into an OOM panic.
+				Determinism::Deterministic, // The execution should be deterministic and hence no indeterministic instructions are allowed.
+			);
+
+			assert_eq!(contract_result.result.as_ref().unwrap().flags.bits(), 0);
+			assert_eq!(
+				contract_result.result.unwrap().data,
+				Ok::<_, ()>(BTreeMap::from([(
+					b"level".to_vec(),
+					pallet_fragments::AttributeValue::Number(7)
+				)]))
+				.encode()
+			);
+		});
+	}
 }
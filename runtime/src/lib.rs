@@ -113,7 +113,10 @@ use codec::{Decode, Encode};
 use frame_support::traits::AsEnsureOriginWithArg;
 use sp_runtime::traits::{ConstU8, SaturatedConversion, StaticLookup};
 
-use pallet_fragments::{GetDefinitionsParams, GetInstanceOwnerParams, GetInstancesParams};
+use pallet_fragments::{
+	GetDefinitionsParams, GetInstanceOwnerParams, GetInstancesParams, GetRecipesParams,
+	GetSalePhaseParams,
+};
 use pallet_protos::{GetGenealogyParams, GetProtosParams};
 
 use pallet_oracle::OracleProvider;
@@ -428,12 +431,14 @@ mod validation_logic {
 			RuntimeCall::Protos(pallet_protos::Call::upload { .. }) | // https://fragcolor-xyz.github.io/fragnova/doc/pallet_protos/pallet/enum.Call.html#
 		RuntimeCall::Protos(pallet_protos::Call::patch { .. }) |
 		RuntimeCall::Protos(pallet_protos::Call::set_metadata { .. }) |
+		RuntimeCall::Protos(pallet_protos::Call::upload_chunk { .. }) |
 		RuntimeCall::Fragments(pallet_fragments::Call::set_definition_metadata { .. }) | // https://fragcolor-xyz.github.io/fragnova/doc/pallet_fragments/pallet/enum.Call.html#
-		RuntimeCall::Fragments(pallet_fragments::Call::set_instance_metadata { .. })
+		RuntimeCall::Fragments(pallet_fragments::Call::set_instance_metadata { .. }) |
+		RuntimeCall::Fragments(pallet_fragments::Call::update_unique_data { .. })
 		)
 	}
 
-	fn is_valid(category: &Categories, data: &Vec<u8>) -> bool {
+	pub fn is_valid(category: &Categories, data: &[u8]) -> bool {
 		match category {
 			Categories::Text(sub_categories) => match sub_categories {
 				TextCategories::Plain | TextCategories::Wgsl | TextCategories::Markdown =>
@@ -543,6 +548,9 @@ mod validation_logic {
 					_ => true,
 				}
 			},
+			RuntimeCall::Protos(ProtosCall::begin_upload{ref category, ..}) => {
+				pallet_protos::can_upload_in_chunks(category)
+			},
 			RuntimeCall::Protos(ProtosCall::set_metadata{ref data, ref metadata_key, ..}) |
 			RuntimeCall::Fragments(FragmentsCall::set_definition_metadata{ref data, ref metadata_key, ..}) |
 			RuntimeCall::Fragments(FragmentsCall::set_instance_metadata{ref data, ref metadata_key, ..}) => {
@@ -1125,6 +1133,17 @@ impl pallet_protos::Config for Runtime {
 	type StringLimit = StringLimit;
 	type DetachAccountLimit = ConstU32<20>; // An ethereum public account address has a length of 20.
 	type MaxTags = ConstU32<10>;
+	type UploadChunkSize = ConstU32<{ 1024 * 1024 }>; // 1 MiB, so that several chunks fit in a block
+	type MaxUploadChunks = ConstU32<1024>; // up to 1 GiB of data
+	type UploadChunkDeposit = ConstU128<{ 100 * MILLICENTS }>;
+	type UploadSessionTimeout = ConstU64<{ 7 * DAYS }>;
+	type DataValidator = Runtime;
+}
+
+impl pallet_protos::ProtoDataValidator for Runtime {
+	fn is_valid(category: &Categories, data: &[u8]) -> bool {
+		validation_logic::is_valid(category, data)
+	}
 }
 
 impl pallet_detach::Config for Runtime {
@@ -1575,6 +1594,12 @@ pub type UncheckedExtrinsic =
 /// Note: This type is only needed if you want to enable an off-chain worker for the runtime,
 /// since it is only used when implementing the trait `frame_system::offchain::CreateSignedTransaction` for `Runtime`.
 pub type SignedPayload = generic::SignedPayload<RuntimeCall, SignedExtra>;
+/// Storage migrations that are executed when the runtime is upgraded
+pub type Migrations = (
+	pallet_fragments::migration::v1::MigrateToV1<Runtime>,
+	pallet_fragments::migration::v2::MigrateToV2<Runtime>,
+	pallet_fragments::migration::v3::MigrateToV3<Runtime>,
+);
 /// Executive: handles dispatch to the various modules.
 pub type Executive = frame_executive::Executive<
 	Runtime,
@@ -1582,6 +1607,7 @@ pub type Executive = frame_executive::Executive<
 	frame_system::ChainContext<Runtime>,
 	Runtime,
 	AllPalletsWithSystem,
+	Migrations,
 >;
 
 #[cfg(feature = "std")]
@@ -1966,6 +1992,14 @@ impl_runtime_apis! {
 		fn get_genealogy(params: GetGenealogyParams<Vec<u8>>) -> Result<Vec<u8>, Vec<u8>> {
 			Protos::get_genealogy(params)
 		}
+		/// Get the hashes of the chunks of a Proto-Fragment created by a Multi-Part Upload
+		fn get_data_chunks(proto_hash: [u8; 32]) -> Option<Vec<[u8; 32]>> {
+			Protos::get_data_chunks(proto_hash)
+		}
+
+		fn get_cid(proto_hash: [u8; 32]) -> Option<Vec<u8>> {
+			Protos::get_cid(proto_hash)
+		}
 	}
 
 	/// Runtime API that allows the Outer Node to communicate with the Runtime's Pallet-Fragments
@@ -1982,6 +2016,18 @@ impl_runtime_apis! {
 		fn get_instance_owner(params: GetInstanceOwnerParams<Vec<u8>>) -> Result<Vec<u8>, Vec<u8>> {
 			Fragments::get_instance_owner(params)
 		}
+
+		fn get_vault_balance(definition_hash: Vec<u8>) -> Result<Vec<u8>, Vec<u8>> {
+			Fragments::get_vault_balance(definition_hash)
+		}
+
+		fn get_sale_phase(params: GetSalePhaseParams<AccountId, Vec<u8>>) -> Result<Vec<u8>, Vec<u8>> {
+			Fragments::get_sale_phase(params)
+		}
+
+		fn get_recipes(params: GetRecipesParams<AccountId>) -> Result<Vec<u8>, Vec<u8>> {
+			Fragments::get_recipes(params)
+		}
 	}
 
 	/// Runtime api for benchmarking a FRAME runtime.